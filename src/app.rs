@@ -1,33 +1,49 @@
 // use std::io::Write;
 use std::{collections::HashMap, path::PathBuf, time::Instant};
 
-use unicode_segmentation::GraphemeCursor;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use typed_builder::*;
 
 use data_farmer::*;
-use data_harvester::{processes, temperature};
+use data_harvester::{process_details, processes, temperature};
+pub use event_bus::{AppAction, EventBus};
 use layout_manager::*;
 pub use states::*;
 
 use crate::{
     canvas, constants,
+    data_conversion::ConvertedProcessData,
     options::Config,
     options::ConfigFlags,
     options::WidgetIdEnabled,
+    query,
     utils::error::{BottomError, Result},
     Pid,
 };
 
+pub mod capabilities;
+pub mod compare;
+pub mod control;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod data_source;
+pub mod event_bus;
+pub mod exporters;
+pub mod graph_export;
+pub mod hook;
 pub mod layout_manager;
-mod process_killer;
-pub mod query;
+pub mod metrics;
+pub mod plugins;
+pub mod process_killer;
+pub mod search_history;
+pub mod session_cache;
 pub mod states;
+pub mod summary;
 
 const MAX_SEARCH_LENGTH: usize = 200;
+const COMMAND_SCROLL_STEP: usize = 4;
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
@@ -49,12 +65,43 @@ pub struct AppConfigFields {
     pub disable_click: bool,
     pub no_write: bool,
     pub show_table_scroll_position: bool,
+    pub show_graph_stats: bool,
+    pub show_process_share: bool,
+    pub link_widgets: bool,
+    pub hide_removable_disks: bool,
+    pub synchronized_output: bool,
+    pub max_fps: Option<u64>,
+    pub window_titles: bool,
+    pub stacked_cpu: bool,
+    pub persist_data: bool,
+    pub warm_start: bool,
+    pub snapshot_path: Option<PathBuf>,
+    pub hook_command: Option<String>,
+    pub hook_interval: u64,
+    pub control_path: Option<PathBuf>,
+    pub process_name_source: processes::ProcessNameSource,
+    pub gpu_memory_rate_in_milliseconds: u64,
+    pub graph_thresholds: GraphThresholds,
+}
+
+/// Where to draw guide lines on graphs.
+#[derive(Debug, Default)]
+pub struct GraphThresholds {
+    /// Percentages.
+    pub cpu: Vec<f64>,
+    /// Percentages.
+    pub mem: Vec<f64>,
+    /// Bytes per second.
+    pub network: Vec<f64>,
 }
 
 /// For filtering out information
 pub struct DataFilters {
     pub disk_filter: Option<Filter>,
     pub temp_filter: Option<Filter>,
+    pub process_excludes: Vec<query::Query>,
+    /// Queries for processes to pin above the others in every process widget.
+    pub process_pins: Vec<query::Query>,
 }
 
 #[derive(Debug)]
@@ -80,6 +127,11 @@ pub struct App {
     #[builder(default = false, setter(skip))]
     pub is_frozen: bool,
 
+    /// A message about the result of the last action, such as a graph export, and when it
+    /// happened, to show it for a moment.
+    #[builder(default, setter(skip))]
+    pub status_message: Option<(Instant, String)>,
+
     #[builder(default = Instant::now(), setter(skip))]
     last_key_press: Instant,
 
@@ -95,6 +147,15 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
+    #[builder(default, setter(skip))]
+    pub renice_dialog_state: AppReniceDialogState,
+
+    #[builder(default, setter(skip))]
+    pub column_picker_state: AppColumnPickerState,
+
+    #[builder(default, setter(skip))]
+    pub process_details_state: AppProcessDetailsState,
+
     #[builder(default = false, setter(skip))]
     pub is_expanded: bool,
 
@@ -113,6 +174,18 @@ pub struct App {
     #[builder(default = false, setter(skip))]
     pub did_config_fail_to_save: bool,
 
+    #[builder(default = EventBus::init(), setter(skip))]
+    pub event_bus: EventBus,
+
+    /// The selected process, if widgets are linked and a process widget is selected.
+    #[builder(default, setter(skip))]
+    pub linked_process: Option<LinkedProcess>,
+
+    /// A mount point to find the processes using, which is left to the collection thread so a
+    /// hung filesystem can't freeze the interface.
+    #[builder(default, setter(skip))]
+    pub pending_mount_scan: Option<String>,
+
     pub cpu_state: CpuState,
     pub mem_state: MemState,
     pub net_state: NetState,
@@ -120,6 +193,8 @@ pub struct App {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: BatteryState,
+    pub custom_state: CustomState,
+    pub plugin_host: plugins::PluginHost,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
@@ -138,6 +213,9 @@ impl App {
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
         self.delete_dialog_state.is_showing_dd = false;
+        self.close_renice_dialog();
+        self.close_column_picker();
+        self.close_process_details();
 
         // Close all searches and reset it
         self.proc_state
@@ -168,9 +246,41 @@ impl App {
         self.is_force_redraw || self.is_determining_widget_boundary
     }
 
+    /// Returns whether something on screen goes away after a while, like a status message or the
+    /// time scale of a graph with `autohide_time`, so it has to be redrawn even if nothing changed.
+    pub fn has_expiring_elements(&self) -> bool {
+        let has_status_message = self.status_message.as_ref().is_some_and(|(time, _)| {
+            // Give it an extra tick, so the draw after it expires still happens.
+            time.elapsed().as_millis()
+                <= u128::from(
+                    constants::STATUS_MESSAGE_TIMEOUT_MILLISECONDS
+                        + constants::TICK_RATE_IN_MILLISECONDS,
+                )
+        });
+
+        has_status_message
+            || self
+                .cpu_state
+                .widget_states
+                .values()
+                .any(|state| state.autohide_timer.is_some())
+            || self
+                .mem_state
+                .widget_states
+                .values()
+                .any(|state| state.autohide_timer.is_some())
+            || self
+                .net_state
+                .widget_states
+                .values()
+                .any(|state| state.autohide_timer.is_some())
+    }
+
     fn close_dd(&mut self) {
         self.delete_dialog_state.is_showing_dd = false;
         self.delete_dialog_state.is_on_yes = false;
+        self.delete_dialog_state.selected_signal = 0;
+        self.delete_dialog_state.typed_signal.clear();
         self.to_delete_process_list = None;
         self.dd_err = None;
     }
@@ -181,6 +291,12 @@ impl App {
             if self.help_dialog_state.is_showing_help {
                 self.help_dialog_state.is_showing_help = false;
                 self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            } else if self.renice_dialog_state.is_showing {
+                self.close_renice_dialog();
+            } else if self.column_picker_state.is_showing {
+                self.close_column_picker();
+            } else if self.process_details_state.is_showing {
+                self.close_process_details();
             } else {
                 self.close_dd();
             }
@@ -188,6 +304,8 @@ impl App {
             self.is_force_redraw = true;
         } else if self.is_config_open {
             self.close_config_screen();
+        } else if self.is_typing_table_search() {
+            self.update_table_search(|search_state| search_state.is_typing = false);
         } else {
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
@@ -208,6 +326,7 @@ impl App {
                     }
                 }
                 BottomWidgetType::ProcSearch => {
+                    self.add_to_search_history(self.current_widget.widget_id - 1);
                     if let Some(current_proc_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
@@ -255,13 +374,58 @@ impl App {
         )
     }
 
+    /// Returns whether a search is being typed into the selected disk or temperature table.
+    pub fn is_typing_table_search(&self) -> bool {
+        match self.current_widget.widget_type {
+            BottomWidgetType::Disk => self
+                .disk_state
+                .widget_states
+                .get(&self.current_widget.widget_id)
+                .is_some_and(|disk_widget_state| disk_widget_state.search_state.is_typing),
+            BottomWidgetType::Temp => self
+                .temp_state
+                .widget_states
+                .get(&self.current_widget.widget_id)
+                .is_some_and(|temp_widget_state| temp_widget_state.search_state.is_typing),
+            _ => false,
+        }
+    }
+
+    /// Changes the search of the selected disk or temperature table, and scrolls back to the top
+    /// as the rows shown may have changed.
+    fn update_table_search(&mut self, update: impl FnOnce(&mut TableSearchState)) {
+        let widget_id = self.current_widget.widget_id;
+        let states = match self.current_widget.widget_type {
+            BottomWidgetType::Disk => self
+                .disk_state
+                .get_mut_widget_state(widget_id)
+                .map(|state| (&mut state.search_state, &mut state.scroll_state)),
+            BottomWidgetType::Temp => self
+                .temp_state
+                .get_mut_widget_state(widget_id)
+                .map(|state| (&mut state.search_state, &mut state.scroll_state)),
+            _ => None,
+        };
+
+        if let Some((search_state, scroll_state)) = states {
+            update(search_state);
+            scroll_state.previous_scroll_position = 0;
+            scroll_state.current_scroll_position = 0;
+            self.is_force_redraw = true;
+        }
+    }
+
     fn reset_multi_tap_keys(&mut self) {
         self.awaiting_second_char = false;
         self.second_char = None;
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.renice_dialog_state.is_showing
+            || self.column_picker_state.is_showing
+            || self.process_details_state.is_showing
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -290,40 +454,28 @@ impl App {
                         // Do NOT allow when in tree mode!
                         if !proc_widget_state.is_tree_mode {
                             // Toggles process widget grouping state
-                            proc_widget_state.is_grouped = !(proc_widget_state.is_grouped);
-
-                            // Forcefully switch off column if we were on it...
-                            if (proc_widget_state.is_grouped
-                                && proc_widget_state.process_sorting_type
-                                    == data_harvester::processes::ProcessSorting::Pid)
-                                || (!proc_widget_state.is_grouped
-                                    && proc_widget_state.process_sorting_type
-                                        == data_harvester::processes::ProcessSorting::Count)
-                            {
-                                proc_widget_state.process_sorting_type =
-                                    data_harvester::processes::ProcessSorting::CpuPercent; // Go back to default, negate PID for group
-                                proc_widget_state.is_process_sort_descending = true;
-                            }
-
-                            proc_widget_state
-                                .columns
-                                .column_mapping
-                                .get_mut(&processes::ProcessSorting::State)
-                                .unwrap()
-                                .enabled = !(proc_widget_state.is_grouped);
-
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Count);
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Pid);
-
-                            proc_widget_state.requires_redraw = true;
+                            proc_widget_state.set_is_grouped(!proc_widget_state.is_grouped);
                             self.proc_state.force_update = Some(self.current_widget.widget_id);
                         }
                     }
                 }
+                BottomWidgetType::ProcSearch => {
+                    // Accept the suggested completion, if there is one, ready for what comes next.
+                    if let Some(proc_widget_state) = self
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id - 1)
+                    {
+                        if let Some(completion) = proc_widget_state.get_search_completion() {
+                            let completed_query = format!(
+                                "{}{} ",
+                                proc_widget_state.get_current_search_query(),
+                                completion
+                            );
+                            proc_widget_state.set_search_query(&completed_query);
+                            self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -359,6 +511,9 @@ impl App {
                         self.is_force_redraw = true;
                     }
                 }
+                BottomWidgetType::Disk | BottomWidgetType::Temp => {
+                    self.update_table_search(|search_state| search_state.is_typing = true);
+                }
                 _ => {}
             }
         }
@@ -489,7 +644,7 @@ impl App {
                     *mapping = is_ignoring_case;
 
                     flags.search_case_enabled_widgets =
-                        Some(WidgetIdEnabled::create_from_hashmap(&map));
+                        Some(WidgetIdEnabled::create_from_hashmap(map));
                 } else {
                     // Map doesn't exist yet... initialize ourselves.
                     let mut map = HashMap::default();
@@ -547,7 +702,7 @@ impl App {
                     *mapping = is_searching_whole_word;
 
                     flags.search_whole_word_enabled_widgets =
-                        Some(WidgetIdEnabled::create_from_hashmap(&map));
+                        Some(WidgetIdEnabled::create_from_hashmap(map));
                 } else {
                     // Map doesn't exist yet... initialize ourselves.
                     let mut map = HashMap::default();
@@ -605,7 +760,7 @@ impl App {
                     *mapping = is_searching_whole_word;
 
                     flags.search_regex_enabled_widgets =
-                        Some(WidgetIdEnabled::create_from_hashmap(&map));
+                        Some(WidgetIdEnabled::create_from_hashmap(map));
                 } else {
                     // Map doesn't exist yet... initialize ourselves.
                     let mut map = HashMap::default();
@@ -631,6 +786,62 @@ impl App {
         }
     }
 
+    pub fn toggle_search_glob(&mut self) {
+        let is_in_search_widget = self.is_in_search_widget();
+        let mut is_searching_with_glob: Option<bool> = None;
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            if is_in_search_widget && proc_widget_state.is_search_enabled() {
+                proc_widget_state.process_search_state.search_toggle_glob();
+                proc_widget_state.update_query();
+                self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+
+                is_searching_with_glob = Some(
+                    proc_widget_state
+                        .process_search_state
+                        .is_searching_with_glob,
+                );
+            }
+        }
+
+        // Also toggle it in the config file if we actually changed it.
+        if let Some(is_searching_with_glob) = is_searching_with_glob {
+            if let Some(flags) = &mut self.config.flags {
+                if let Some(map) = &mut flags.search_glob_enabled_widgets_map {
+                    // Just update the map.
+                    let mapping = map.entry(self.current_widget.widget_id - 1).or_default();
+                    *mapping = is_searching_with_glob;
+
+                    flags.search_glob_enabled_widgets =
+                        Some(WidgetIdEnabled::create_from_hashmap(map));
+                } else {
+                    // Map doesn't exist yet... initialize ourselves.
+                    let mut map = HashMap::default();
+                    map.insert(self.current_widget.widget_id - 1, is_searching_with_glob);
+                    flags.search_glob_enabled_widgets =
+                        Some(WidgetIdEnabled::create_from_hashmap(&map));
+                    flags.search_glob_enabled_widgets_map = Some(map);
+                }
+            } else {
+                // Must initialize it ourselves...
+                let mut map = HashMap::default();
+                map.insert(self.current_widget.widget_id - 1, is_searching_with_glob);
+
+                self.config.flags = Some(
+                    ConfigFlags::builder()
+                        .search_glob_enabled_widgets(WidgetIdEnabled::create_from_hashmap(&map))
+                        .search_glob_enabled_widgets_map(map)
+                        .build(),
+                );
+            }
+
+            self.did_config_fail_to_save = self.update_config_file().is_err();
+        }
+    }
+
     pub fn toggle_tree_mode(&mut self) {
         if let Some(proc_widget_state) = self
             .proc_state
@@ -643,6 +854,218 @@ impl App {
                 // We enabled... set PID sort type to ascending.
                 proc_widget_state.process_sorting_type = processes::ProcessSorting::Pid;
                 proc_widget_state.is_process_sort_descending = false;
+                proc_widget_state.is_sectioned_by_state = false;
+            }
+
+            self.proc_state.force_update = Some(self.current_widget.widget_id);
+            proc_widget_state.requires_redraw = true;
+        }
+    }
+
+    /// Toggles grouping processes by the app they belong to, rather than by name.
+    pub fn toggle_apps_view(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                // Like grouping, this doesn't work in tree mode.
+                if !proc_widget_state.is_tree_mode {
+                    let is_grouped_by_app = !proc_widget_state.is_grouped_by_app;
+                    proc_widget_state.set_is_grouped(is_grouped_by_app);
+                    proc_widget_state.is_grouped_by_app = is_grouped_by_app;
+                    proc_widget_state.is_grouped_by_user = false;
+                    self.proc_state.force_update = Some(self.current_widget.widget_id);
+                }
+            }
+        }
+    }
+
+    /// Toggles grouping processes by the user they belong to, rather than by name.
+    pub fn toggle_users_view(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                // Like grouping, this doesn't work in tree mode.
+                if !proc_widget_state.is_tree_mode {
+                    let is_grouped_by_user = !proc_widget_state.is_grouped_by_user;
+                    proc_widget_state.set_is_grouped(is_grouped_by_user);
+                    proc_widget_state.is_grouped_by_user = is_grouped_by_user;
+                    proc_widget_state.is_grouped_by_app = false;
+                    self.proc_state.force_update = Some(self.current_widget.widget_id);
+                }
+            }
+        }
+    }
+
+    fn toggle_quick_filter(&mut self, quick_filter: query::QuickFilter) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                proc_widget_state.toggle_quick_filter(quick_filter);
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+            }
+        }
+    }
+
+    /// Returns the states of the process widgets that are drawn, which is all of them unless another
+    /// widget is expanded.  Expensive data is only collected for these.
+    fn get_visible_proc_widget_states(&self) -> impl Iterator<Item = (&u64, &ProcWidgetState)> {
+        let expanded_widget_id = if self.is_expanded {
+            let widget_id = self.current_widget.widget_id;
+            Some(match self.current_widget.widget_type {
+                BottomWidgetType::Proc => Some(widget_id),
+                BottomWidgetType::ProcSearch => Some(widget_id - 1),
+                BottomWidgetType::ProcSort => Some(widget_id - 2),
+                _ => None,
+            })
+        } else {
+            None
+        };
+
+        self.proc_state
+            .widget_states
+            .iter()
+            .filter(move |(widget_id, _)| {
+                expanded_widget_id
+                    .is_none_or(|expanded_widget_id| expanded_widget_id == Some(**widget_id))
+            })
+    }
+
+    /// Returns whether any process widget needs the number of network connections of each
+    /// process, which is only collected if needed.
+    pub fn is_using_connections(&self) -> bool {
+        self.get_visible_proc_widget_states().any(|(widget_id, _)| {
+            self.get_query_set(*widget_id)
+                .has_prefix(&query::PrefixType::Connections)
+        })
+    }
+
+    /// Returns whether any process widget shows the app of each process, which is only
+    /// collected if needed.
+    pub fn is_using_app_names(&self) -> bool {
+        self.get_visible_proc_widget_states()
+            .any(|(_, proc_widget_state)| {
+                proc_widget_state.is_grouped_by_app
+                    || proc_widget_state.is_column_needed(&processes::ProcessSorting::App)
+            })
+    }
+
+    /// Returns whether any process widget needs the GPU usage or GPU memory usage of each
+    /// process, which are only collected if needed.
+    pub fn is_using_gpu_stats(&self) -> bool {
+        self.get_visible_proc_widget_states()
+            .any(|(widget_id, proc_widget_state)| {
+                proc_widget_state.is_column_needed(&processes::ProcessSorting::GpuPercent)
+                    || proc_widget_state.is_column_needed(&processes::ProcessSorting::GpuMem)
+                    || self
+                        .get_query_set(*widget_id)
+                        .has_prefix(&query::PrefixType::GpuMem)
+                    || self
+                        .get_query_set(*widget_id)
+                        .has_prefix(&query::PrefixType::GpuUsage)
+            })
+    }
+
+    /// Returns whether any process widget needs the security context of each process, which is
+    /// only collected if needed.
+    pub fn is_using_security_contexts(&self) -> bool {
+        self.get_visible_proc_widget_states()
+            .any(|(widget_id, proc_widget_state)| {
+                proc_widget_state.is_column_needed(&processes::ProcessSorting::SecurityContext)
+                    || self
+                        .get_query_set(*widget_id)
+                        .has_prefix(&query::PrefixType::SecurityContext)
+            })
+    }
+
+    /// Returns whether any process widget needs the namespaces of each process, which are only
+    /// collected if needed.
+    pub fn is_using_namespaces(&self) -> bool {
+        self.get_visible_proc_widget_states()
+            .any(|(widget_id, proc_widget_state)| {
+                proc_widget_state.is_column_needed(&processes::ProcessSorting::Namespaces)
+                    || self
+                        .get_query_set(*widget_id)
+                        .has_prefix(&query::PrefixType::Namespace)
+            })
+    }
+
+    /// Returns whether any process widget needs the number of open file descriptors of each
+    /// process, which is only collected if needed.
+    pub fn is_using_fds(&self) -> bool {
+        self.get_visible_proc_widget_states().any(|(widget_id, _)| {
+            self.get_query_set(*widget_id)
+                .has_prefix(&query::PrefixType::Fds)
+        })
+    }
+
+    /// Returns whether any process widget needs the environment of each process, which is only
+    /// collected if needed.
+    pub fn is_using_env(&self) -> bool {
+        self.get_visible_proc_widget_states().any(|(widget_id, _)| {
+            self.get_query_set(*widget_id)
+                .has_prefix(&query::PrefixType::Env(None))
+        })
+    }
+
+    /// Returns whether any process widget needs the cgroup of each process, which is only
+    /// collected if needed.
+    pub fn is_using_cgroups(&self) -> bool {
+        self.get_visible_proc_widget_states().any(|(widget_id, _)| {
+            self.get_query_set(*widget_id)
+                .has_prefix(&query::PrefixType::Cgroup)
+        })
+    }
+
+    /// Returns whether any process widget needs to know which processes use the camera or
+    /// microphone, or play audio, which is only collected if needed.
+    pub fn is_using_media(&self) -> bool {
+        self.get_visible_proc_widget_states()
+            .any(|(widget_id, proc_widget_state)| {
+                proc_widget_state.is_column_needed(&processes::ProcessSorting::Media)
+                    || self
+                        .get_query_set(*widget_id)
+                        .has_prefix(&query::PrefixType::Media)
+            })
+    }
+
+    /// Returns whether any process widget shows the energy impact of each process, which is only
+    /// estimated if needed.
+    pub fn is_using_energy(&self) -> bool {
+        self.get_visible_proc_widget_states()
+            .any(|(_, proc_widget_state)| {
+                proc_widget_state.is_column_needed(&processes::ProcessSorting::Energy)
+            })
+    }
+
+    /// Returns whether the titles of the windows of each process are needed, which are only
+    /// collected if searches match names against them, a search uses them, or the process
+    /// details popup is open.
+    pub fn is_using_window_titles(&self) -> bool {
+        (self.app_config_fields.window_titles
+            && self.get_visible_proc_widget_states().next().is_some())
+            || self.process_details_state.is_showing
+            || self.get_visible_proc_widget_states().any(|(widget_id, _)| {
+                self.get_query_set(*widget_id)
+                    .has_prefix(&query::PrefixType::WindowTitle)
+            })
+    }
+
+    pub fn toggle_state_sections(&mut self) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id))
+        {
+            proc_widget_state.is_sectioned_by_state = !proc_widget_state.is_sectioned_by_state;
+
+            if proc_widget_state.is_sectioned_by_state {
+                proc_widget_state.is_tree_mode = false;
             }
 
             self.proc_state.force_update = Some(self.current_widget.widget_id);
@@ -650,9 +1073,37 @@ impl App {
         }
     }
 
+    pub fn toggle_summary_row(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                proc_widget_state.is_showing_summary_row =
+                    !proc_widget_state.is_showing_summary_row;
+                proc_widget_state.requires_redraw = true;
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.is_typing_table_search() {
+            self.update_table_search(|search_state| search_state.is_typing = false);
+        } else if self.renice_dialog_state.is_showing {
+            if self.renice_dialog_state.error.is_some() {
+                self.close_renice_dialog();
+            } else {
+                self.renice_processes();
+            }
+            self.is_force_redraw = true;
+        } else if self.column_picker_state.is_showing {
+            self.toggle_picked_column();
+        } else if self.process_details_state.is_showing {
+            self.close_process_details();
+            self.is_force_redraw = true;
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
                 self.close_dd();
             } else if self.delete_dialog_state.is_on_yes {
@@ -683,6 +1134,32 @@ impl App {
                 proc_widget_state.update_sorting_with_columns();
                 self.toggle_sort();
             }
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+            // Keep the search going, but go back to the processes it matches.
+            self.add_to_search_history(self.current_widget.widget_id - 1);
+            self.move_widget_selection(&WidgetDirection::Up);
+        } else if let BottomWidgetType::Disk = self.current_widget.widget_type {
+            self.show_processes_on_selected_disk();
+        } else if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.start_process_details();
+        }
+    }
+
+    /// Jumps to the processes using the filesystem of the selected disk.
+    fn show_processes_on_selected_disk(&mut self) {
+        if let Some(disk_widget_state) = self
+            .disk_state
+            .widget_states
+            .get(&self.current_widget.widget_id)
+        {
+            let mount_point = disk_widget_state
+                .search_state
+                .filter(&self.canvas_data.disk_data)
+                .get(disk_widget_state.scroll_state.current_scroll_position)
+                .map(|disk| disk.mount_point.clone());
+            if let Some(mount_point) = mount_point {
+                self.emit(AppAction::ShowProcessesOnMount { mount_point });
+            }
         }
     }
 
@@ -736,7 +1213,13 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.renice_dialog_state.is_showing {
+            self.renice_dialog_state.typed_nice.pop();
+        } else if self.delete_dialog_state.is_showing_dd {
+            self.delete_dialog_state.typed_signal.pop();
+        } else if self.is_typing_table_search() {
+            self.update_table_search(TableSearchState::pop_char);
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -796,12 +1279,46 @@ impl App {
         }
     }
 
+    /// Returns every query the processes of a widget have to match: its search query if it is
+    /// valid, its quick filters, and the process excludes from the config file.
+    pub fn get_query_set(&self, widget_id: u64) -> query::QuerySet<'_> {
+        let mut query_set = query::QuerySet::default();
+
+        for exclude in &self.filters.process_excludes {
+            query_set.exclude(exclude);
+        }
+
+        if let Some(process_widget_state) = self.proc_state.widget_states.get(&widget_id) {
+            for quick_filter_query in &process_widget_state.quick_filter_queries {
+                query_set.require(quick_filter_query);
+            }
+
+            let search_state = &process_widget_state.process_search_state.search_state;
+            if !search_state.is_invalid_or_blank_search() {
+                if let Some(query) = &search_state.query {
+                    query_set.require(query);
+                }
+            }
+        }
+
+        query_set
+    }
+
     pub fn on_up_key(&mut self) {
         if self.is_config_open {
         } else if !self.is_in_dialog() {
+            if self.is_in_search_widget() {
+                self.recall_search_query(true);
+            }
             self.decrement_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.column_picker_state.is_showing {
+            self.pick_column(false);
+        } else if self.process_details_state.is_showing {
+            self.process_details_scroll_up();
+        } else if self.delete_dialog_state.is_showing_dd && self.dd_err.is_none() {
+            self.delete_dialog_state.pick_signal(false);
         }
         self.reset_multi_tap_keys();
     }
@@ -809,9 +1326,18 @@ impl App {
     pub fn on_down_key(&mut self) {
         if self.is_config_open {
         } else if !self.is_in_dialog() {
+            if self.is_in_search_widget() {
+                self.recall_search_query(false);
+            }
             self.increment_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.column_picker_state.is_showing {
+            self.pick_column(true);
+        } else if self.process_details_state.is_showing {
+            self.process_details_scroll_down();
+        } else if self.delete_dialog_state.is_showing_dd && self.dd_err.is_none() {
+            self.delete_dialog_state.pick_signal(true);
         }
         self.reset_multi_tap_keys();
     }
@@ -821,6 +1347,7 @@ impl App {
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
+                    self.scroll_commands(false);
                     // if let Some(proc_widget_state) = self
                     //     .proc_state
                     //     .get_mut_widget_state(self.current_widget.widget_id)
@@ -862,15 +1389,13 @@ impl App {
                         }
                     }
                 }
-                BottomWidgetType::Battery => {
-                    if !self.canvas_data.battery_data.is_empty() {
-                        if let Some(battery_widget_state) = self
-                            .battery_state
-                            .get_mut_widget_state(self.current_widget.widget_id)
-                        {
-                            if battery_widget_state.currently_selected_battery_index > 0 {
-                                battery_widget_state.currently_selected_battery_index -= 1;
-                            }
+                BottomWidgetType::Battery if !self.canvas_data.battery_data.is_empty() => {
+                    if let Some(battery_widget_state) = self
+                        .battery_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if battery_widget_state.currently_selected_battery_index > 0 {
+                            battery_widget_state.currently_selected_battery_index -= 1;
                         }
                     }
                 }
@@ -886,6 +1411,7 @@ impl App {
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
+                    self.scroll_commands(true);
                     // if let Some(proc_widget_state) = self
                     //     .proc_state
                     //     .get_mut_widget_state(self.current_widget.widget_id)
@@ -930,18 +1456,15 @@ impl App {
                         }
                     }
                 }
-                BottomWidgetType::Battery => {
-                    if !self.canvas_data.battery_data.is_empty() {
-                        let battery_count = self.canvas_data.battery_data.len();
-                        if let Some(battery_widget_state) = self
-                            .battery_state
-                            .get_mut_widget_state(self.current_widget.widget_id)
+                BottomWidgetType::Battery if !self.canvas_data.battery_data.is_empty() => {
+                    let battery_count = self.canvas_data.battery_data.len();
+                    if let Some(battery_widget_state) = self
+                        .battery_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if battery_widget_state.currently_selected_battery_index < battery_count - 1
                         {
-                            if battery_widget_state.currently_selected_battery_index
-                                < battery_count - 1
-                            {
-                                battery_widget_state.currently_selected_battery_index += 1;
-                            }
+                            battery_widget_state.currently_selected_battery_index += 1;
                         }
                     }
                 }
@@ -1037,7 +1560,9 @@ impl App {
     }
 
     pub fn clear_search(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.is_typing_table_search() {
+            self.update_table_search(TableSearchState::clear);
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .proc_state
                 .widget_states
@@ -1049,46 +1574,443 @@ impl App {
         }
     }
 
+    /// Replaces the search query with an older or newer one from the search history.
+    fn recall_search_query(&mut self, is_older: bool) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            let current_query = proc_widget_state.get_current_search_query().clone();
+            let history = &mut proc_widget_state.process_search_state.history;
+            let recalled_query = if is_older {
+                history.recall_older(&current_query)
+            } else {
+                history.recall_newer(&current_query)
+            };
+
+            if let Some(recalled_query) = recalled_query {
+                proc_widget_state.set_search_query(&recalled_query);
+                self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+            }
+        }
+    }
+
+    /// Adds the search query of a process widget to the search history if it's valid.  The
+    /// history is shared by every process widget.
+    fn add_to_search_history(&mut self, widget_id: u64) {
+        if let Some(proc_widget_state) = self.proc_state.widget_states.get(&widget_id) {
+            let search_state = &proc_widget_state.process_search_state.search_state;
+            if search_state.is_invalid_or_blank_search() {
+                return;
+            }
+
+            let query = search_state.current_search_query.clone();
+            for proc_widget_state in self.proc_state.widget_states.values_mut() {
+                proc_widget_state.process_search_state.history.push(&query);
+            }
+        }
+    }
+
+    /// Replaces the search history of every process widget, such as with the one from previous
+    /// sessions.
+    pub fn set_search_history(&mut self, search_history: &search_history::SearchHistory) {
+        for proc_widget_state in self.proc_state.widget_states.values_mut() {
+            proc_widget_state.process_search_state.history = search_history.clone();
+        }
+    }
+
+    /// Returns the search history, if there are any process widgets.
+    pub fn get_search_history(&self) -> Option<&search_history::SearchHistory> {
+        self.proc_state
+            .widget_states
+            .values()
+            .next()
+            .map(|proc_widget_state| &proc_widget_state.process_search_state.history)
+    }
+
+    /// Returns the process selected in the current process widget, along with its name and PIDs,
+    /// which are those of the whole group if processes are grouped.
+    fn get_selected_processes(&self) -> Option<(&ConvertedProcessData, (String, Vec<Pid>))> {
+        let proc_widget_state = self
+            .proc_state
+            .widget_states
+            .get(&self.current_widget.widget_id)?;
+        let process = self
+            .canvas_data
+            .finalized_process_data_map
+            .get(&self.current_widget.widget_id)?
+            .get(proc_widget_state.scroll_state.current_scroll_position)?;
+
+        if self.is_grouped(self.current_widget.widget_id) {
+            // Don't offer to act on everything that isn't part of an app, or whose owner isn't
+            // known, at once!
+            if (proc_widget_state.is_grouped_by_app && process.app.is_none())
+                || (proc_widget_state.is_grouped_by_user && process.uid.is_none())
+            {
+                return None;
+            }
+            Some((
+                process,
+                (process.name.to_string(), process.group_pids.clone()),
+            ))
+        } else {
+            if process.section_header.is_some() {
+                return None;
+            }
+            Some((process, (process.name.clone(), vec![process.pid])))
+        }
+    }
+
     pub fn start_dd(&mut self) {
         self.reset_multi_tap_keys();
 
+        if let Some((_, current_process)) = self.get_selected_processes() {
+            self.to_delete_process_list = Some(current_process);
+            self.delete_dialog_state.selected_signal = 0;
+            self.delete_dialog_state.typed_signal.clear();
+            self.delete_dialog_state.is_showing_dd = true;
+            self.is_determining_widget_boundary = true;
+        }
+    }
+
+    /// Opens a prompt for a new nice value for the selected process.
+    pub fn start_renice(&mut self) {
+        if let Some((process, current_process)) = self.get_selected_processes() {
+            self.renice_dialog_state = AppReniceDialogState {
+                is_showing: true,
+                current_nice: process.nice,
+                processes: Some(current_process),
+                ..AppReniceDialogState::default()
+            };
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Stops the selected processes with `SIGSTOP`, or lets them continue with `SIGCONT` if
+    /// `is_stopping` is false.
+    fn stop_processes(&mut self, is_stopping: bool) {
+        let (name, pids) = match self.get_selected_processes() {
+            Some((_, current_process)) => current_process,
+            None => return,
+        };
+
+        let message = match pids
+            .iter()
+            .try_for_each(|pid| process_killer::stop_process_given_pid(*pid, is_stopping))
+        {
+            Ok(()) => {
+                let signal_instant = Instant::now();
+                for pid in pids {
+                    self.proc_state
+                        .stopped_pids
+                        .insert(pid, (is_stopping, signal_instant));
+                }
+                self.proc_state.force_update_all = true;
+                format!(
+                    "{} \"{}\"",
+                    if is_stopping { "Stopped" } else { "Continued" },
+                    name
+                )
+            }
+            Err(err) => format!(
+                "Unable to {} \"{}\": {}",
+                if is_stopping { "stop" } else { "continue" },
+                name,
+                err
+            ),
+        };
+        debug!("{}", message);
+        self.status_message = Some((Instant::now(), message));
+    }
+
+    /// Pins the selected process above the others, or unpins it if it already is.
+    fn toggle_pinned_processes(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        let (name, pids) = match self.get_selected_processes() {
+            Some((_, current_process)) => current_process,
+            None => return,
+        };
+
+        if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+            let is_pinned = pids
+                .iter()
+                .all(|pid| proc_widget_state.pinned_pids.contains(pid));
+            for pid in pids {
+                if is_pinned {
+                    proc_widget_state.pinned_pids.remove(&pid);
+                } else {
+                    proc_widget_state.pinned_pids.insert(pid);
+                }
+            }
+            self.proc_state.force_update = Some(widget_id);
+            self.status_message = Some((
+                Instant::now(),
+                format!(
+                    "{} \"{}\"",
+                    if is_pinned { "Unpinned" } else { "Pinned" },
+                    name
+                ),
+            ));
+        }
+    }
+
+    /// Locks the selection onto the selected process, so it stays selected and in the same place on
+    /// screen as the list changes, or unlocks it.
+    fn toggle_locked_follow(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        let selected_process = self
+            .get_selected_processes()
+            .map(|(process, (name, _))| (process.pid, name));
+
+        if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+            let message = if let Some(locked_pid) = proc_widget_state.locked_pid.take() {
+                format!("Stopped following {}", locked_pid)
+            } else if let Some((pid, name)) = selected_process {
+                proc_widget_state.locked_pid = Some(pid);
+                format!("Following \"{}\" ({})", name, pid)
+            } else {
+                return;
+            };
+            proc_widget_state.requires_redraw = true;
+            self.status_message = Some((Instant::now(), message));
+        }
+    }
+
+    fn close_renice_dialog(&mut self) {
+        self.renice_dialog_state = AppReniceDialogState::default();
+    }
+
+    /// Sets the nice value typed into the renice prompt, or shows why it couldn't be.
+    fn renice_processes(&mut self) {
+        let (name, pids) = match &self.renice_dialog_state.processes {
+            Some(processes) => processes,
+            None => return,
+        };
+
+        let result = match self.renice_dialog_state.typed_nice.parse::<i32>() {
+            Ok(nice) if process_killer::NICE_RANGE.contains(&nice) => pids
+                .iter()
+                .try_for_each(|pid| process_killer::renice_process_given_pid(*pid, nice))
+                .map(|_| nice),
+            _ => Err(BottomError::GenericError(format!(
+                "\"{}\" is not a nice value from {} to {}.",
+                self.renice_dialog_state.typed_nice,
+                process_killer::NICE_RANGE.start(),
+                process_killer::NICE_RANGE.end()
+            ))),
+        };
+
+        match result {
+            Ok(nice) => {
+                self.status_message = Some((
+                    Instant::now(),
+                    format!("Set the nice value of \"{}\" to {}", name, nice),
+                ));
+                self.close_renice_dialog();
+            }
+            Err(err) => self.renice_dialog_state.error = Some(err.to_string()),
+        }
+    }
+
+    /// Scrolls the commands in the selected process widget left, or right if `is_right` is true,
+    /// so that the rest of long commands can be read.  Does nothing while showing names.
+    fn scroll_commands(&mut self, is_right: bool) {
+        let widget_id = self.current_widget.widget_id;
+        let longest_command_len = self
+            .canvas_data
+            .finalized_process_data_map
+            .get(&widget_id)
+            .and_then(|process_data| {
+                process_data
+                    .iter()
+                    .map(|process| {
+                        UnicodeSegmentation::graphemes(process.command.as_str(), true).count()
+                    })
+                    .max()
+            })
+            .unwrap_or(0);
+
+        if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+            if !proc_widget_state.is_using_command {
+                return;
+            }
+
+            let command_scroll_offset = if is_right {
+                std::cmp::min(
+                    proc_widget_state.command_scroll_offset + COMMAND_SCROLL_STEP,
+                    longest_command_len.saturating_sub(1),
+                )
+            } else {
+                proc_widget_state
+                    .command_scroll_offset
+                    .saturating_sub(COMMAND_SCROLL_STEP)
+            };
+            if command_scroll_offset != proc_widget_state.command_scroll_offset {
+                proc_widget_state.command_scroll_offset = command_scroll_offset;
+                proc_widget_state.requires_redraw = true;
+                self.proc_state.force_update = Some(widget_id);
+            }
+        }
+    }
+
+    /// Opens a popup with details about the selected process.
+    pub fn start_process_details(&mut self) {
+        if let Some((process, (name, pids))) = self.get_selected_processes() {
+            let pid = pids.first().copied().unwrap_or(process.pid);
+            self.process_details_state = AppProcessDetailsState {
+                is_showing: true,
+                name,
+                pid,
+                num_processes: pids.len(),
+                user: process.user.clone(),
+                details: process_details::get_process_details(pid),
+                window_titles: process.window_titles.clone(),
+                group_pids: pids,
+                ..AppProcessDetailsState::default()
+            };
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_process_details(&mut self) {
+        self.process_details_state = AppProcessDetailsState::default();
+    }
+
+    /// Reads the details shown in the process details popup again, if it's open.
+    pub fn refresh_process_details(&mut self) {
+        if self.process_details_state.is_showing {
+            self.process_details_state.details =
+                process_details::get_process_details(self.process_details_state.pid);
+
+            let group_pids = &self.process_details_state.group_pids;
+            self.process_details_state.window_titles = self
+                .data_collection
+                .process_harvest
+                .iter()
+                .filter(|process| group_pids.contains(&process.pid))
+                .flat_map(|process| process.window_titles.iter().cloned())
+                .collect();
+        }
+    }
+
+    fn process_details_scroll_up(&mut self) {
+        let scroll_state = &mut self.process_details_state.scroll_state;
+        scroll_state.current_scroll_index = scroll_state.current_scroll_index.saturating_sub(1);
+    }
+
+    fn process_details_scroll_down(&mut self) {
+        let scroll_state = &mut self.process_details_state.scroll_state;
+        if scroll_state.current_scroll_index < scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index += 1;
+        }
+    }
+
+    /// Opens the column picker for the selected process widget.
+    pub fn start_column_picker(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.column_picker_state = AppColumnPickerState {
+                is_showing: true,
+                widget_id: self.current_widget.widget_id,
+                selected_index: 0,
+            };
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_column_picker(&mut self) {
+        self.column_picker_state = AppColumnPickerState::default();
+    }
+
+    /// Selects the previous column in the column picker, or the next one if `is_next` is true.
+    fn pick_column(&mut self, is_next: bool) {
         if let Some(proc_widget_state) = self
             .proc_state
             .widget_states
-            .get(&self.current_widget.widget_id)
+            .get(&self.column_picker_state.widget_id)
         {
-            if let Some(corresponding_filtered_process_list) = self
-                .canvas_data
-                .finalized_process_data_map
-                .get(&self.current_widget.widget_id)
-            {
-                if proc_widget_state.scroll_state.current_scroll_position
-                    < corresponding_filtered_process_list.len()
-                {
-                    let current_process: (String, Vec<Pid>);
-                    if self.is_grouped(self.current_widget.widget_id) {
-                        if let Some(process) = &corresponding_filtered_process_list
-                            .get(proc_widget_state.scroll_state.current_scroll_position)
-                        {
-                            current_process = (process.name.to_string(), process.group_pids.clone())
-                        } else {
-                            return;
-                        }
-                    } else {
-                        let process = corresponding_filtered_process_list
-                            [proc_widget_state.scroll_state.current_scroll_position]
-                            .clone();
-                        current_process = (process.name.clone(), vec![process.pid])
-                    };
-
-                    self.to_delete_process_list = Some(current_process);
-                    self.delete_dialog_state.is_showing_dd = true;
-                    self.is_determining_widget_boundary = true;
+            let num_column_groups = proc_widget_state.columns.get_column_groups().len();
+            let selected_index = &mut self.column_picker_state.selected_index;
+            if is_next {
+                if *selected_index + 1 < num_column_groups {
+                    *selected_index += 1;
                 }
+            } else {
+                *selected_index = selected_index.saturating_sub(1);
             }
         }
     }
 
+    /// Shows or hides the column selected in the column picker.
+    fn toggle_picked_column(&mut self) {
+        self.update_picked_column(|columns, column_group| {
+            columns
+                .set_column_group_shown(column_group, !columns.is_column_group_shown(column_group))
+        });
+    }
+
+    /// Moves the column selected in the column picker before the previous one, or after the next
+    /// one if `is_later` is true.  The moved column stays selected.
+    fn move_picked_column(&mut self, is_later: bool) {
+        self.update_picked_column(|columns, column_group| {
+            columns.move_column_group(column_group, is_later)
+        });
+        self.pick_column(is_later);
+    }
+
+    /// Changes the columns of the process widget that the column picker is for, given the
+    /// selected group of columns.
+    fn update_picked_column(
+        &mut self, update: impl FnOnce(&mut ProcColumn, &processes::ProcessSorting),
+    ) {
+        let widget_id = self.column_picker_state.widget_id;
+        if let Some(proc_widget_state) = self.proc_state.widget_states.get_mut(&widget_id) {
+            let column_groups = proc_widget_state.columns.get_column_groups();
+            if let Some(column_group) = column_groups.get(self.column_picker_state.selected_index) {
+                update(&mut proc_widget_state.columns, column_group);
+
+                // Column indices may have changed, so point the sort widget at the sorted column again.
+                proc_widget_state
+                    .columns
+                    .set_to_sorted_index(&proc_widget_state.process_sorting_type);
+                proc_widget_state.requires_redraw = true;
+                self.proc_state.force_update = Some(widget_id);
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    /// If widgets are linked, emits an action to link them to the process selected in the
+    /// current process widget whenever it changes, or to unlink them if none is selected.
+    pub fn update_widget_links(&mut self) {
+        if !self.app_config_fields.link_widgets {
+            return;
+        }
+
+        let selected_pid = match self.current_widget.widget_type {
+            BottomWidgetType::Proc if !self.is_grouped(self.current_widget.widget_id) => self
+                .proc_state
+                .widget_states
+                .get(&self.current_widget.widget_id)
+                .and_then(|proc_widget_state| {
+                    self.canvas_data
+                        .finalized_process_data_map
+                        .get(&self.current_widget.widget_id)
+                        .and_then(|processes| {
+                            processes.get(proc_widget_state.scroll_state.current_scroll_position)
+                        })
+                })
+                .filter(|process| process.section_header.is_none())
+                .map(|process| process.pid),
+            _ => None,
+        };
+
+        let linked_pid = self.linked_process.as_ref().map(|process| process.pid);
+        if selected_pid != linked_pid {
+            self.emit(AppAction::LinkProcess(selected_pid));
+        }
+    }
+
     pub fn on_char_key(&mut self, caught_char: char) {
         // Skip control code chars
         if caught_char.is_control() {
@@ -1107,6 +2029,17 @@ impl App {
             }
             self.last_key_press = current_key_press_inst;
 
+            if self.is_typing_table_search() {
+                self.update_table_search(|search_state| {
+                    if UnicodeWidthStr::width(search_state.query_string.as_str())
+                        <= MAX_SEARCH_LENGTH
+                    {
+                        search_state.push_char(caught_char);
+                    }
+                });
+                return;
+            }
+
             if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
                 let is_in_search_widget = self.is_in_search_widget();
                 if let Some(proc_widget_state) = self
@@ -1179,16 +2112,61 @@ impl App {
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                 _ => {}
             }
+        } else if self.renice_dialog_state.is_showing {
+            let typed_nice = &mut self.renice_dialog_state.typed_nice;
+            match caught_char {
+                '-' if typed_nice.is_empty() => typed_nice.push(caught_char),
+                '0'..='9' if typed_nice.trim_start_matches('-').len() < 2 => {
+                    typed_nice.push(caught_char)
+                }
+                _ => {}
+            }
+        } else if self.column_picker_state.is_showing {
+            match caught_char {
+                'k' => self.pick_column(false),
+                'j' => self.pick_column(true),
+                'K' => self.move_picked_column(false),
+                'J' => self.move_picked_column(true),
+                ' ' => self.toggle_picked_column(),
+                _ => {}
+            }
+        } else if self.process_details_state.is_showing {
+            match caught_char {
+                'k' => self.process_details_scroll_up(),
+                'j' => self.process_details_scroll_down(),
+                'g' => self.process_details_state.scroll_state.current_scroll_index = 0,
+                'G' => {
+                    self.process_details_state.scroll_state.current_scroll_index =
+                        self.process_details_state.scroll_state.max_scroll_index
+                }
+                _ => {}
+            }
         } else if self.delete_dialog_state.is_showing_dd {
             match caught_char {
                 'h' | 'j' => self.on_left_key(),
                 'k' | 'l' => self.on_right_key(),
+                '0'..='9'
+                    if !process_killer::SIGNALS.is_empty()
+                        && self.delete_dialog_state.typed_signal.len() < 3 =>
+                {
+                    self.delete_dialog_state.typed_signal.push(caught_char);
+                }
                 _ => {}
             }
         } else if self.is_config_open {
         }
     }
 
+    /// Emits a sort action for the currently selected process widget, if any.
+    fn emit_process_sort(&mut self, sort_type: processes::ProcessSorting) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.emit(AppAction::SortProcesses {
+                widget_id: self.current_widget.widget_id,
+                sort_type,
+            });
+        }
+    }
+
     fn handle_char(&mut self, caught_char: char) {
         match caught_char {
             '/' => {
@@ -1232,85 +2210,13 @@ impl App {
             'G' => self.skip_to_last(),
             'k' => self.on_up_key(),
             'j' => self.on_down_key(),
-            'f' => {
-                self.is_frozen = !self.is_frozen;
-                if self.is_frozen {
-                    self.data_collection.set_frozen_time();
-                }
-            }
+            'f' => self.emit(AppAction::ToggleFreeze),
             'C' => {
                 // self.open_config(),
             }
-            'c' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        match proc_widget_state.process_sorting_type {
-                            processes::ProcessSorting::CpuPercent => {
-                                proc_widget_state.is_process_sort_descending =
-                                    !proc_widget_state.is_process_sort_descending
-                            }
-                            _ => {
-                                proc_widget_state.process_sorting_type =
-                                    processes::ProcessSorting::CpuPercent;
-                                proc_widget_state.is_process_sort_descending = true;
-                            }
-                        }
-                        self.proc_state.force_update = Some(self.current_widget.widget_id);
-
-                        self.skip_to_first();
-                    }
-                }
-            }
-            'm' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        match proc_widget_state.process_sorting_type {
-                            processes::ProcessSorting::MemPercent => {
-                                proc_widget_state.is_process_sort_descending =
-                                    !proc_widget_state.is_process_sort_descending
-                            }
-                            _ => {
-                                proc_widget_state.process_sorting_type =
-                                    processes::ProcessSorting::MemPercent;
-                                proc_widget_state.is_process_sort_descending = true;
-                            }
-                        }
-                        self.proc_state.force_update = Some(self.current_widget.widget_id);
-                        self.skip_to_first();
-                    }
-                }
-            }
-            'p' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        // Skip if grouped
-                        if !proc_widget_state.is_grouped {
-                            match proc_widget_state.process_sorting_type {
-                                processes::ProcessSorting::Pid => {
-                                    proc_widget_state.is_process_sort_descending =
-                                        !proc_widget_state.is_process_sort_descending
-                                }
-                                _ => {
-                                    proc_widget_state.process_sorting_type =
-                                        processes::ProcessSorting::Pid;
-                                    proc_widget_state.is_process_sort_descending = false;
-                                }
-                            }
-                            self.proc_state.force_update = Some(self.current_widget.widget_id);
-                            self.skip_to_first();
-                        }
-                    }
-                }
-            }
+            'c' => self.emit_process_sort(processes::ProcessSorting::CpuPercent),
+            'm' => self.emit_process_sort(processes::ProcessSorting::MemPercent),
+            'p' => self.emit_process_sort(processes::ProcessSorting::Pid),
             'P' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
@@ -1318,6 +2224,7 @@ impl App {
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
                         proc_widget_state.is_using_command = !proc_widget_state.is_using_command;
+                        proc_widget_state.command_scroll_offset = 0;
                         proc_widget_state
                             .toggle_command_and_name(proc_widget_state.is_using_command);
 
@@ -1339,48 +2246,51 @@ impl App {
                     }
                 }
             }
-            'n' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        match proc_widget_state.process_sorting_type {
-                            processes::ProcessSorting::ProcessName
-                            | processes::ProcessSorting::Command => {
-                                proc_widget_state.is_process_sort_descending =
-                                    !proc_widget_state.is_process_sort_descending
-                            }
-                            _ => {
-                                proc_widget_state.process_sorting_type =
-                                    if proc_widget_state.is_using_command {
-                                        processes::ProcessSorting::Command
-                                    } else {
-                                        processes::ProcessSorting::ProcessName
-                                    };
-                                proc_widget_state.is_process_sort_descending = false;
-                            }
-                        }
-                        self.proc_state.force_update = Some(self.current_widget.widget_id);
-                        self.skip_to_first();
-                    }
-                }
-            }
-            '?' => {
-                self.help_dialog_state.is_showing_help = true;
-                self.is_force_redraw = true;
-            }
+            'n' => self.emit_process_sort(processes::ProcessSorting::ProcessName),
+            '?' => self.emit(AppAction::ShowHelp),
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
             'J' | 'S' => self.move_widget_selection(&WidgetDirection::Down),
             't' => self.toggle_tree_mode(),
+            'T' => self.toggle_state_sections(),
+            'v' => self.toggle_apps_view(),
+            'U' => self.toggle_users_view(),
+            '#' => self.toggle_summary_row(),
+            'u' => self.toggle_quick_filter(query::QuickFilter::OwnProcesses),
+            'a' => self.toggle_quick_filter(query::QuickFilter::Active),
+            'o' => self.toggle_quick_filter(query::QuickFilter::Connected),
+            '!' => self.toggle_quick_filter(query::QuickFilter::Suspicious),
+            'M' => self.toggle_quick_filter(query::QuickFilter::Media),
             '+' => self.on_plus(),
             '-' => self.on_minus(),
             '=' => self.reset_zoom(),
+            'x' => self.export_graph_range(false),
+            'X' => self.export_graph_range(true),
             'e' => self.toggle_expand_widget(),
             's' => self.toggle_sort(),
             'I' => self.invert_sort(),
+            'r' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.start_renice();
+                }
+            }
+            'z' | 'Z' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.stop_processes(caught_char == 'z');
+                }
+            }
+            'l' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.toggle_locked_follow();
+                }
+            }
+            '*' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.toggle_pinned_processes();
+                }
+            }
+            'F' => self.start_column_picker(),
             '%' => self.toggle_percentages(),
             ' ' => self.on_space(),
             _ => {}
@@ -1431,8 +2341,9 @@ impl App {
     pub fn kill_highlighted_process(&mut self) -> Result<()> {
         if let BottomWidgetType::Proc = self.current_widget.widget_type {
             if let Some(current_selected_processes) = &self.to_delete_process_list {
+                let signal = self.delete_dialog_state.get_signal();
                 for pid in &current_selected_processes.1 {
-                    process_killer::kill_process_given_pid(*pid)?;
+                    process_killer::kill_process_given_pid(*pid, signal)?;
                 }
             }
             self.to_delete_process_list = None;
@@ -1499,7 +2410,7 @@ impl App {
                 WidgetDirection::Up => self.current_widget.up_neighbour,
                 WidgetDirection::Down => self.current_widget.down_neighbour,
             }) {
-                if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
+                if let Some(new_widget) = self.widget_map.get(new_widget_id) {
                     match &new_widget.widget_type {
                         BottomWidgetType::Temp
                         | BottomWidgetType::Proc
@@ -1628,42 +2539,35 @@ impl App {
                                                 .get(&(new_widget_id - *offset))
                                             {
                                                 match &new_widget.widget_type {
-                                                    BottomWidgetType::ProcSearch => {
-                                                        if !proc_widget_state.is_search_enabled() {
-                                                            if let Some(next_neighbour_id) =
-                                                                option_next_neighbour_id
+                                                    BottomWidgetType::ProcSearch
+                                                        if !proc_widget_state
+                                                            .is_search_enabled() =>
+                                                    {
+                                                        if let Some(next_neighbour_id) =
+                                                            option_next_neighbour_id
+                                                        {
+                                                            if let Some(next_neighbour_widget) =
+                                                                self.widget_map
+                                                                    .get(&next_neighbour_id)
                                                             {
-                                                                if let Some(next_neighbour_widget) =
-                                                                    self.widget_map
-                                                                        .get(&next_neighbour_id)
-                                                                {
-                                                                    self.current_widget =
-                                                                        next_neighbour_widget
-                                                                            .clone();
-                                                                }
+                                                                self.current_widget =
+                                                                    next_neighbour_widget.clone();
                                                             }
-                                                        } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
                                                         }
                                                     }
-                                                    BottomWidgetType::ProcSort => {
-                                                        if !proc_widget_state.is_sort_open {
-                                                            if let Some(next_neighbour_id) =
-                                                                option_next_neighbour_id
+                                                    BottomWidgetType::ProcSort
+                                                        if !proc_widget_state.is_sort_open =>
+                                                    {
+                                                        if let Some(next_neighbour_id) =
+                                                            option_next_neighbour_id
+                                                        {
+                                                            if let Some(next_neighbour_widget) =
+                                                                self.widget_map
+                                                                    .get(&next_neighbour_id)
                                                             {
-                                                                if let Some(next_neighbour_widget) =
-                                                                    self.widget_map
-                                                                        .get(&next_neighbour_id)
-                                                                {
-                                                                    self.current_widget =
-                                                                        next_neighbour_widget
-                                                                            .clone();
-                                                                }
+                                                                self.current_widget =
+                                                                    next_neighbour_widget.clone();
                                                             }
-                                                        } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
                                                         }
                                                     }
                                                     _ => {
@@ -1706,32 +2610,27 @@ impl App {
                                                 .get(&(new_widget_id - *offset))
                                             {
                                                 match &new_widget.widget_type {
-                                                    BottomWidgetType::ProcSearch => {
-                                                        if !proc_widget_state.is_search_enabled() {
-                                                            if let Some(parent_proc_widget) = self
-                                                                .widget_map
-                                                                .get(&(new_widget_id - *offset))
-                                                            {
-                                                                self.current_widget =
-                                                                    parent_proc_widget.clone();
-                                                            }
-                                                        } else {
+                                                    BottomWidgetType::ProcSearch
+                                                        if !proc_widget_state
+                                                            .is_search_enabled() =>
+                                                    {
+                                                        if let Some(parent_proc_widget) = self
+                                                            .widget_map
+                                                            .get(&(new_widget_id - *offset))
+                                                        {
                                                             self.current_widget =
-                                                                new_widget.clone();
+                                                                parent_proc_widget.clone();
                                                         }
                                                     }
-                                                    BottomWidgetType::ProcSort => {
-                                                        if !proc_widget_state.is_sort_open {
-                                                            if let Some(parent_proc_widget) = self
-                                                                .widget_map
-                                                                .get(&(new_widget_id - *offset))
-                                                            {
-                                                                self.current_widget =
-                                                                    parent_proc_widget.clone();
-                                                            }
-                                                        } else {
+                                                    BottomWidgetType::ProcSort
+                                                        if !proc_widget_state.is_sort_open =>
+                                                    {
+                                                        if let Some(parent_proc_widget) = self
+                                                            .widget_map
+                                                            .get(&(new_widget_id - *offset))
+                                                        {
                                                             self.current_widget =
-                                                                new_widget.clone();
+                                                                parent_proc_widget.clone();
                                                         }
                                                     }
                                                     _ => {
@@ -1775,15 +2674,15 @@ impl App {
                                     .get(&(self.current_widget.widget_id - *offset))
                                 {
                                     match &self.current_widget.widget_type {
-                                        BottomWidgetType::ProcSearch => {
-                                            if !proc_widget_state.is_search_enabled() {
-                                                reflection_dir = Some(parent_direction.clone());
-                                            }
+                                        BottomWidgetType::ProcSearch
+                                            if !proc_widget_state.is_search_enabled() =>
+                                        {
+                                            reflection_dir = Some(parent_direction.clone());
                                         }
-                                        BottomWidgetType::ProcSort => {
-                                            if !proc_widget_state.is_sort_open {
-                                                reflection_dir = Some(parent_direction.clone());
-                                            }
+                                        BottomWidgetType::ProcSort
+                                            if !proc_widget_state.is_sort_open =>
+                                        {
+                                            reflection_dir = Some(parent_direction.clone());
                                         }
                                         _ => {}
                                     }
@@ -2017,9 +2916,13 @@ impl App {
                         .temp_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if !self.canvas_data.temp_sensor_data.is_empty() {
+                        let num_sensors = temp_widget_state
+                            .search_state
+                            .filter(&self.canvas_data.temp_sensor_data)
+                            .len();
+                        if num_sensors > 0 {
                             temp_widget_state.scroll_state.current_scroll_position =
-                                self.canvas_data.temp_sensor_data.len() - 1;
+                                num_sensors - 1;
                             temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
                         }
                     }
@@ -2029,9 +2932,12 @@ impl App {
                         .disk_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if !self.canvas_data.disk_data.is_empty() {
-                            disk_widget_state.scroll_state.current_scroll_position =
-                                self.canvas_data.disk_data.len() - 1;
+                        let num_disks = disk_widget_state
+                            .search_state
+                            .filter(&self.canvas_data.disk_data)
+                            .len();
+                        if num_disks > 0 {
+                            disk_widget_state.scroll_state.current_scroll_position = num_disks - 1;
                             disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
                         }
                     }
@@ -2179,10 +3085,13 @@ impl App {
             .get_mut(&self.current_widget.widget_id)
         {
             let current_posn = temp_widget_state.scroll_state.current_scroll_position;
+            let num_sensors = temp_widget_state
+                .search_state
+                .filter(&self.canvas_data.temp_sensor_data)
+                .len();
 
             if current_posn as i64 + num_to_change_by >= 0
-                && current_posn as i64 + num_to_change_by
-                    < self.canvas_data.temp_sensor_data.len() as i64
+                && current_posn as i64 + num_to_change_by < num_sensors as i64
             {
                 temp_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
@@ -2203,9 +3112,13 @@ impl App {
             .get_mut(&self.current_widget.widget_id)
         {
             let current_posn = disk_widget_state.scroll_state.current_scroll_position;
+            let num_disks = disk_widget_state
+                .search_state
+                .filter(&self.canvas_data.disk_data)
+                .len();
 
             if current_posn as i64 + num_to_change_by >= 0
-                && current_posn as i64 + num_to_change_by < self.canvas_data.disk_data.len() as i64
+                && current_posn as i64 + num_to_change_by < num_disks as i64
             {
                 disk_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
@@ -2245,6 +3158,8 @@ impl App {
     pub fn handle_scroll_up(&mut self) {
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.process_details_state.is_showing {
+            self.process_details_scroll_up();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_in();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2255,6 +3170,8 @@ impl App {
     pub fn handle_scroll_down(&mut self) {
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.process_details_state.is_showing {
+            self.process_details_scroll_down();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_out();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2294,6 +3211,14 @@ impl App {
                 .get(&self.current_widget.widget_id)
             {
                 if let Some(corresponding_process) = displayed_process_list.get(current_posn) {
+                    if let Some(section) = corresponding_process.section_header {
+                        if !proc_widget_state.collapsed_state_sections.remove(&section) {
+                            proc_widget_state.collapsed_state_sections.insert(section);
+                        }
+                        self.proc_state.force_update = Some(self.current_widget.widget_id);
+                        return;
+                    }
+
                     let corresponding_pid = corresponding_process.pid;
 
                     if let Some(process_data) = self
@@ -2309,6 +3234,61 @@ impl App {
         }
     }
 
+    /// Exports the samples in the visible time range of the selected graph to CSV, either of just
+    /// that graph or of every graph.
+    fn export_graph_range(&mut self, is_exporting_all: bool) {
+        let widget_id = self.current_widget.widget_id;
+        let (graphs, display_time) = match self.current_widget.widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => {
+                let widget_id = match self.current_widget.widget_type {
+                    BottomWidgetType::CpuLegend => widget_id - 1,
+                    _ => widget_id,
+                };
+                match self.cpu_state.get_widget_state(widget_id) {
+                    Some(state) => (
+                        graph_export::ExportedGraphs::Cpu,
+                        state.current_display_time,
+                    ),
+                    None => return,
+                }
+            }
+            BottomWidgetType::Mem => match self.mem_state.get_widget_state(widget_id) {
+                Some(state) => (
+                    graph_export::ExportedGraphs::Mem,
+                    state.current_display_time,
+                ),
+                None => return,
+            },
+            BottomWidgetType::Net => match self.net_state.get_widget_state(widget_id) {
+                Some(state) => (
+                    graph_export::ExportedGraphs::Net,
+                    state.current_display_time,
+                ),
+                None => return,
+            },
+            _ => return,
+        };
+        let graphs = if is_exporting_all {
+            graph_export::ExportedGraphs::All
+        } else {
+            graphs
+        };
+
+        let path = graph_export::get_export_path(graphs);
+        let message = match graph_export::export_graph_range(
+            &self.data_collection,
+            self.is_frozen,
+            display_time,
+            graphs,
+            &path,
+        ) {
+            Ok(rows) => format!("Exported {} samples to {}", rows, path.display()),
+            Err(err) => format!("Unable to export to {}: {}", path.display(), err),
+        };
+        debug!("{}", message);
+        self.status_message = Some((Instant::now(), message));
+    }
+
     fn zoom_out(&mut self) {
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => {
@@ -2624,7 +3604,7 @@ impl App {
                 (widget.top_left_corner, widget.bottom_right_corner)
             {
                 if (x >= tlc_x && y >= tlc_y) && (x <= brc_x && y <= brc_y) {
-                    if let Some(new_widget) = self.widget_map.get(&new_widget_id) {
+                    if let Some(new_widget) = self.widget_map.get(new_widget_id) {
                         self.current_widget = new_widget.clone();
 
                         match &self.current_widget.widget_type {
@@ -2682,22 +3662,29 @@ impl App {
                                     .proc_state
                                     .get_widget_state(self.current_widget.widget_id)
                                 {
-                                    if let Some(visual_index) =
-                                        proc_widget_state.scroll_state.table_state.selected()
-                                    {
-                                        // If in tree mode, also check to see if this click is on
-                                        // the same entry as the already selected one - if it is,
-                                        // then we minimize.
+                                    // Clicking the summary row doesn't select anything.
+                                    let is_on_summary_row = proc_widget_state
+                                        .is_showing_summary_row
+                                        && offset_clicked_entry == 0;
+
+                                    if let (Some(visual_index), false) = (
+                                        proc_widget_state.scroll_state.table_state.selected(),
+                                        is_on_summary_row,
+                                    ) {
+                                        // If in tree mode or sectioned by state, also check to see
+                                        // if this click is on the same entry as the already selected
+                                        // one - if it is, then we minimize.
 
                                         let previous_scroll_position =
                                             proc_widget_state.scroll_state.current_scroll_position;
-                                        let is_tree_mode = proc_widget_state.is_tree_mode;
+                                        let is_collapsible = proc_widget_state.is_tree_mode
+                                            || proc_widget_state.is_sectioned_by_state;
 
                                         let new_position = self.increment_process_position(
                                             offset_clicked_entry as i64 - visual_index as i64,
                                         );
 
-                                        if is_tree_mode {
+                                        if is_collapsible {
                                             if let Some(new_position) = new_position {
                                                 if previous_scroll_position == new_position {
                                                     self.toggle_collapsing_process_branch();
@@ -0,0 +1,209 @@
+//! A machine-readable report of what this build of bottom can do on this system, for scripts
+//! and bug reports.
+
+use serde::Serialize;
+
+use super::data_harvester::temperature::{self, TemperatureType};
+use crate::utils::error::{BottomError, Result};
+
+/// Bumped whenever a field is removed or changes meaning, so scripts can tell if they can still
+/// read the report.  Adding fields doesn't change it.
+const CAPABILITIES_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub format_version: u32,
+    pub version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    /// The cargo features this build was made with.
+    pub features: Vec<&'static str>,
+    pub harvesters: Harvesters,
+    pub detected: Detected,
+}
+
+/// Which kinds of data can be collected on this platform, with this build.
+#[derive(Debug, Serialize)]
+pub struct Harvesters {
+    pub cpu: bool,
+    pub memory: bool,
+    pub network: bool,
+    pub disks: bool,
+    pub disk_io: bool,
+    pub temperature: bool,
+    pub battery: bool,
+    pub load_average: bool,
+    pub processes: bool,
+    pub process_gpu_usage: bool,
+    pub process_gpu_memory: bool,
+    pub process_real_time: bool,
+    pub process_security_context: bool,
+    pub process_namespaces: bool,
+    pub hypervisor: bool,
+    pub steal_time: bool,
+    pub balloon_memory: bool,
+    pub usage_snapshot: bool,
+    pub control_fifo: bool,
+}
+
+impl Default for Harvesters {
+    fn default() -> Self {
+        Harvesters {
+            cpu: true,
+            memory: true,
+            network: true,
+            disks: true,
+            disk_io: cfg!(not(any(target_arch = "aarch64", target_arch = "arm"))),
+            temperature: true,
+            battery: true,
+            load_average: cfg!(not(target_os = "windows")),
+            processes: true,
+            process_gpu_usage: cfg!(target_os = "linux"),
+            process_gpu_memory: cfg!(any(target_os = "linux", feature = "nvidia")),
+            process_real_time: cfg!(target_os = "linux"),
+            process_security_context: cfg!(target_os = "linux"),
+            process_namespaces: cfg!(target_os = "linux"),
+            hypervisor: cfg!(target_os = "linux"),
+            steal_time: cfg!(target_os = "linux"),
+            balloon_memory: cfg!(target_os = "linux"),
+            usage_snapshot: cfg!(target_family = "unix"),
+            control_fifo: cfg!(target_family = "unix"),
+        }
+    }
+}
+
+/// What was found on this system when the report was made.
+#[derive(Debug, Serialize)]
+pub struct Detected {
+    pub cpu_cores: usize,
+    pub temperature_sensors: Vec<String>,
+    pub batteries: usize,
+    pub gpus: Vec<String>,
+    /// The hypervisor bottom is running under, if it's in a virtual machine.
+    pub hypervisor: Option<String>,
+    /// The version of WSL bottom is running under, if it is.
+    pub wsl: Option<String>,
+}
+
+impl Capabilities {
+    /// Checks what this system has, which takes about as long as the collector's first run.
+    pub fn detect() -> Self {
+        let mut features = Vec::new();
+        if cfg!(feature = "otlp") {
+            features.push("otlp");
+        }
+        if cfg!(feature = "nvidia") {
+            features.push("nvidia");
+        }
+
+        Capabilities {
+            format_version: CAPABILITIES_FORMAT_VERSION,
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            features,
+            harvesters: Harvesters::default(),
+            detected: detect_hardware(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| BottomError::ConversionError(err.to_string()))
+    }
+}
+
+fn detect_hardware() -> Detected {
+    use sysinfo::{RefreshKind, System, SystemExt};
+
+    Detected {
+        cpu_cores: System::new_with_specifics(RefreshKind::new().with_cpu())
+            .get_processors()
+            .len(),
+        temperature_sensors: detect_temperature_sensors(),
+        batteries: detect_batteries(),
+        gpus: get_gpus(),
+        hypervisor: super::data_harvester::guest::detect_hypervisor(),
+        wsl: super::data_harvester::guest::detect_wsl().map(|wsl_version| wsl_version.to_string()),
+    }
+}
+
+/// Returns the names of the temperature sensors that can be read, found the same way the
+/// temperature widget finds them.
+pub fn detect_temperature_sensors() -> Vec<String> {
+    #[cfg(any(not(target_os = "linux"), target_arch = "aarch64", target_arch = "arm"))]
+    let sensors = {
+        use sysinfo::{RefreshKind, System, SystemExt};
+
+        let sys =
+            System::new_with_specifics(RefreshKind::new().with_components_list().with_components());
+        futures::executor::block_on(temperature::arm_and_non_linux_temperature_data(
+            &sys,
+            &TemperatureType::Celsius,
+            true,
+        ))
+    };
+    #[cfg(not(any(not(target_os = "linux"), target_arch = "aarch64", target_arch = "arm")))]
+    let sensors = futures::executor::block_on(temperature::linux_temperature_data(
+        &TemperatureType::Celsius,
+        true,
+    ));
+
+    sensors
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|sensor| sensor.component_label.or(sensor.component_name))
+        .collect()
+}
+
+/// Returns how many batteries can be read.
+pub fn detect_batteries() -> usize {
+    battery::Manager::new()
+        .and_then(|battery_manager| battery_manager.batteries())
+        .map(|batteries| batteries.filter(|battery| battery.is_ok()).count())
+        .unwrap_or_default()
+}
+
+/// Returns the names of the GPUs that per-process GPU stats can come from.
+fn get_gpus() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut gpus = Vec::new();
+
+    // DRM devices, along with the driver behind each of them, ie: "card0 (amdgpu)".
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(entries) = std::fs::read_dir("/sys/class/drm") {
+            let mut cards = entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let number = name.strip_prefix("card")?;
+                    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                        return None;
+                    }
+
+                    let driver = std::fs::read_link(entry.path().join("device/driver"))
+                        .ok()
+                        .and_then(|driver| {
+                            driver
+                                .file_name()
+                                .map(|driver| driver.to_string_lossy().into_owned())
+                        });
+                    Some(match driver {
+                        Some(driver) => format!("{} ({})", name, driver),
+                        None => name,
+                    })
+                })
+                .collect::<Vec<_>>();
+            cards.sort();
+            gpus.append(&mut cards);
+        }
+    }
+
+    #[cfg(feature = "nvidia")]
+    gpus.append(&mut super::data_harvester::nvidia::get_gpu_names());
+
+    gpus
+}
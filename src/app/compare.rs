@@ -0,0 +1,303 @@
+//! Compares two saved sessions (see [`super::session_cache`]) by overlaying their graphs, for
+//! example to see how a system behaves before and after a kernel upgrade.
+
+use std::{
+    io::{stdout, Stdout, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    terminal::Frame,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
+    Terminal,
+};
+
+use super::session_cache::SessionSnapshot;
+use crate::utils::{
+    error::{BottomError, Result},
+    gen_util::{get_byte_values, ByteUnits},
+    number_format::format_number,
+};
+
+type Point = (f64, f64);
+
+const BEFORE_COLOUR: Color = Color::LightBlue;
+const AFTER_COLOUR: Color = Color::LightRed;
+
+/// The graphs of one saved session, with times in seconds since the start of the session so
+/// that both sessions line up.
+#[derive(Debug, Default)]
+pub struct ComparedSession {
+    pub name: String,
+    /// The average usage of all CPUs, as a percentage.
+    pub cpu: Vec<Point>,
+    pub mem: Vec<Point>,
+    /// In bytes per second.
+    pub rx: Vec<Point>,
+    /// In bytes per second.
+    pub tx: Vec<Point>,
+}
+
+impl ComparedSession {
+    pub fn load(path: &Path) -> Result<Self> {
+        let snapshot = SessionSnapshot::from_bytes(&std::fs::read(path)?)?;
+        if snapshot.timed_data.is_empty() {
+            return Err(BottomError::GenericError(format!(
+                "the session in {} has no graph data to compare.",
+                path.display()
+            )));
+        }
+
+        // Offsets count back from when the session was saved, so the largest is its start.
+        let start_offset = snapshot
+            .timed_data
+            .iter()
+            .map(|(offset, _)| *offset)
+            .max()
+            .unwrap_or(0);
+        let mut session = ComparedSession {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            ..ComparedSession::default()
+        };
+        for (offset, entry) in &snapshot.timed_data {
+            let time = (start_offset - offset) as f64 / 1000.0;
+            if !entry.cpu_data.is_empty() {
+                let cpu = entry.cpu_data.iter().sum::<f64>() / entry.cpu_data.len() as f64;
+                session.cpu.push((time, cpu));
+            }
+            session.mem.push((time, entry.mem_data));
+            // Network data is stored as the log2 of the rate.
+            session.rx.push((time, from_log2(entry.rx_data)));
+            session.tx.push((time, from_log2(entry.tx_data)));
+        }
+        session.cpu.sort_by(|a, b| a.0.total_cmp(&b.0));
+        session.mem.sort_by(|a, b| a.0.total_cmp(&b.0));
+        session.rx.sort_by(|a, b| a.0.total_cmp(&b.0));
+        session.tx.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        Ok(session)
+    }
+
+    /// How long the session is, in seconds.
+    pub fn duration(&self) -> f64 {
+        self.mem.last().map(|(time, _)| *time).unwrap_or(0.0)
+    }
+}
+
+fn from_log2(value: f64) -> f64 {
+    if value > 0.0 {
+        value.exp2()
+    } else {
+        0.0
+    }
+}
+
+/// Returns the average of the values of some points, or [`None`] if there aren't any.
+pub fn get_average(points: &[Point]) -> Option<f64> {
+    if points.is_empty() {
+        None
+    } else {
+        Some(points.iter().map(|(_, value)| value).sum::<f64>() / points.len() as f64)
+    }
+}
+
+/// Shows the two sessions overlaid until the user quits with `q`, `Esc`, or `Ctrl-c`.
+pub fn run_compare(before_path: &Path, after_path: &Path) -> Result<()> {
+    let before = ComparedSession::load(before_path)?;
+    let after = ComparedSession::load(after_path)?;
+
+    let mut stdout_val = stdout();
+    execute!(stdout_val, EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout_val))?;
+    terminal.clear()?;
+    terminal.hide_cursor()?;
+
+    let result = compare_loop(&mut terminal, &before, &after);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+fn compare_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>, before: &ComparedSession,
+    after: &ComparedSession,
+) -> Result<()> {
+    loop {
+        terminal.draw(|f| draw_comparison(f, before, after))?;
+
+        if event::poll(Duration::from_millis(250))? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn draw_comparison(
+    f: &mut Frame<'_, CrosstermBackend<Stdout>>, before: &ComparedSession, after: &ComparedSession,
+) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+        .split(f.size());
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+        .split(rows[0]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+        .split(rows[1]);
+
+    let duration = before.duration().max(after.duration()).max(1.0);
+    let format_percent = |value: f64| format!("{}%", format_number(value, 0));
+    let format_rate = |value: f64| {
+        let (value, unit) = get_byte_values(value.max(0.0) as u64, false, ByteUnits::Binary);
+        format!("{}{}/s", format_number(value, 1), unit)
+    };
+
+    draw_compared_graph(
+        f,
+        top[0],
+        " CPU ",
+        (&before.name, &before.cpu),
+        (&after.name, &after.cpu),
+        duration,
+        100.0,
+        &format_percent,
+    );
+    draw_compared_graph(
+        f,
+        top[1],
+        " Memory ",
+        (&before.name, &before.mem),
+        (&after.name, &after.mem),
+        duration,
+        100.0,
+        &format_percent,
+    );
+
+    let rx_max = before
+        .rx
+        .iter()
+        .chain(after.rx.iter())
+        .map(|(_, value)| *value)
+        .fold(1.0, f64::max);
+    draw_compared_graph(
+        f,
+        bottom[0],
+        " Network RX ",
+        (&before.name, &before.rx),
+        (&after.name, &after.rx),
+        duration,
+        rx_max,
+        &format_rate,
+    );
+    let tx_max = before
+        .tx
+        .iter()
+        .chain(after.tx.iter())
+        .map(|(_, value)| *value)
+        .fold(1.0, f64::max);
+    draw_compared_graph(
+        f,
+        bottom[1],
+        " Network TX ",
+        (&before.name, &before.tx),
+        (&after.name, &after.tx),
+        duration,
+        tx_max,
+        &format_rate,
+    );
+}
+
+/// Draws one graph of both sessions, with the average of each and the difference between them
+/// in the legend.
+#[allow(clippy::too_many_arguments)]
+fn draw_compared_graph(
+    f: &mut Frame<'_, CrosstermBackend<Stdout>>, draw_loc: Rect, title: &str,
+    before: (&str, &[Point]), after: (&str, &[Point]), duration: f64, max_value: f64,
+    format_value: &dyn Fn(f64) -> String,
+) {
+    let (before_name, before_points) = before;
+    let (after_name, after_points) = after;
+    let before_average = get_average(before_points);
+    let after_average = get_average(after_points);
+
+    let before_label = format!(
+        "{} avg {}",
+        before_name,
+        before_average.map_or_else(|| "N/A".to_string(), format_value)
+    );
+    let after_label = match (before_average, after_average) {
+        (Some(before_average), Some(after_average)) => {
+            let delta = after_average - before_average;
+            format!(
+                "{} avg {} (Δ {}{})",
+                after_name,
+                format_value(after_average),
+                if delta < 0.0 { "-" } else { "+" },
+                format_value(delta.abs())
+            )
+        }
+        (_, after_average) => format!(
+            "{} avg {}",
+            after_name,
+            after_average.map_or_else(|| "N/A".to_string(), format_value)
+        ),
+    };
+
+    let x_axis = Axis::default().bounds([0.0, duration]).labels(vec![
+        Span::raw("0s"),
+        Span::raw(format!("{}s", format_number(duration, 0))),
+    ]);
+    let y_axis = Axis::default().bounds([0.0, max_value]).labels(vec![
+        Span::raw(format_value(0.0)),
+        Span::raw(format_value(max_value)),
+    ]);
+
+    let chart = Chart::new(vec![
+        Dataset::default()
+            .name(before_label)
+            .marker(Marker::Braille)
+            .style(Style::default().fg(BEFORE_COLOUR))
+            .data(before_points)
+            .graph_type(GraphType::Line),
+        Dataset::default()
+            .name(after_label)
+            .marker(Marker::Braille)
+            .style(Style::default().fg(AFTER_COLOUR))
+            .data(after_points)
+            .graph_type(GraphType::Line),
+    ])
+    .block(Block::default().title(title).borders(Borders::ALL))
+    .x_axis(x_axis)
+    .y_axis(y_axis)
+    .hidden_legend_constraints((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)));
+
+    f.render_widget(chart, draw_loc);
+}
@@ -0,0 +1,80 @@
+//! A small line-based control protocol, so scripts and key bindings outside of bottom can
+//! drive the UI.  Commands are read from a FIFO, one per line, and turned into
+//! [`AppAction`]s on the event bus.
+//!
+//! Supported commands are:
+//! - `freeze`, `unfreeze`, and `toggle-freeze`
+//! - `set-filter <query>` and `clear-filter`, which set the process search query
+//! - `switch-tab <n>`, which selects the nth widget, counting from 1
+//! - `help`
+
+use std::path::Path;
+
+use super::AppAction;
+use crate::utils::error::{BottomError, Result};
+
+/// Parses a single line of the control protocol.
+pub fn parse_control_command(line: &str) -> Result<AppAction> {
+    let line = line.trim();
+    let (command, argument) = match line.find(char::is_whitespace) {
+        Some(index) => (&line[..index], line[index..].trim()),
+        None => (line, ""),
+    };
+
+    match command {
+        "freeze" => Ok(AppAction::SetFrozen(true)),
+        "unfreeze" => Ok(AppAction::SetFrozen(false)),
+        "toggle-freeze" => Ok(AppAction::ToggleFreeze),
+        "set-filter" => Ok(AppAction::SetProcessFilter {
+            query: argument.to_string(),
+        }),
+        "clear-filter" => Ok(AppAction::SetProcessFilter {
+            query: String::new(),
+        }),
+        "switch-tab" => match argument.parse::<usize>() {
+            Ok(index) if index > 0 => Ok(AppAction::SelectWidget(index - 1)),
+            _ => Err(BottomError::GenericError(format!(
+                "\"{}\" is not a valid widget number.",
+                argument
+            ))),
+        },
+        "help" => Ok(AppAction::ShowHelp),
+        _ => Err(BottomError::GenericError(format!(
+            "\"{}\" is not a known control command.",
+            command
+        ))),
+    }
+}
+
+/// Creates a FIFO at the given path if nothing exists there yet.  An existing FIFO is reused,
+/// but any other existing file is treated as an error, so we never clobber it.
+#[cfg(target_family = "unix")]
+pub fn create_control_fifo(path: &Path) -> Result<()> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt, os::unix::fs::FileTypeExt};
+
+    if let Ok(metadata) = std::fs::metadata(path) {
+        return if metadata.file_type().is_fifo() {
+            Ok(())
+        } else {
+            Err(BottomError::ConfigError(format!(
+                "{:?} already exists and is not a FIFO.",
+                path
+            )))
+        };
+    }
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| BottomError::ConfigError(err.to_string()))?;
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_family = "unix"))]
+pub fn create_control_fifo(_path: &Path) -> Result<()> {
+    Err(BottomError::ConfigError(
+        "control_path is only supported on Unix-like systems.".to_string(),
+    ))
+}
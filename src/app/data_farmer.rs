@@ -14,11 +14,21 @@
 /// more points as this is used!
 use once_cell::sync::Lazy;
 
-use std::{time::Instant, vec::Vec};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Instant,
+    vec::Vec,
+};
 
 use crate::{
-    data_harvester::{battery_harvester, cpu, disks, mem, network, processes, temperature, Data},
-    utils::gen_util::get_simple_byte_values,
+    app::GraphThresholds,
+    data_harvester::{
+        battery_harvester, cpu, disks, guest, mem, network, processes, temperature, Data,
+    },
+    utils::{
+        gen_util::{get_byte_values, ByteUnits},
+        number_format::format_number,
+    },
 };
 use regex::Regex;
 
@@ -34,6 +44,41 @@ pub struct TimedData {
     pub swap_data: Value,
 }
 
+/// The highest values seen since bottom started, which unlike [`TimedData`] are never cleaned.
+#[derive(Debug, Default)]
+pub struct DataPeaks {
+    pub cpu: Vec<Value>,
+    pub mem: Value,
+    pub swap: Value,
+    pub rx: u64,
+    pub tx: u64,
+}
+
+/// The recent CPU and memory usage of a process, which is kept for as long as other data is.
+#[derive(Debug, Default)]
+pub struct ProcessHistory {
+    /// When the process started, to tell if its PID was reused.
+    pub start_time: u64,
+    /// The time, CPU usage percentage, and memory usage in bytes of each sample, oldest first.
+    pub samples: VecDeque<(Instant, Value, u64)>,
+}
+
+/// Which graph an alert is shown on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertGraph {
+    Cpu,
+    Mem,
+    Net,
+}
+
+/// A moment a value on a graph crossed above one of its thresholds, like `CPU > 80%`.
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub instant: Instant,
+    pub graph: AlertGraph,
+    pub name: String,
+}
+
 /// AppCollection represents the pooled data stored within the main app
 /// thread.  Basically stores a (occasionally cleaned) record of the data
 /// collected, and what is needed to convert into a displayable form.
@@ -53,12 +98,23 @@ pub struct DataCollection {
     pub swap_harvest: mem::MemHarvest,
     pub cpu_harvest: cpu::CpuHarvest,
     pub process_harvest: Vec<processes::ProcessHarvest>,
+    /// The history of each running process, by PID.
+    pub process_history: HashMap<crate::Pid, ProcessHistory>,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IOHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
+    /// The read and write labels of the members of composite disks, by member name.
+    pub io_member_labels: HashMap<String, (String, String)>,
+    /// The last-known disk counters from the previous session, by device, along with when
+    /// they were taken.
+    pub io_counter_seeds: HashMap<String, (Instant, u64, u64)>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
     pub battery_harvest: Vec<battery_harvester::BatteryHarvest>,
+    pub guest_harvest: Option<guest::GuestHarvest>,
+    pub previous_session_instant: Option<Instant>,
+    pub peaks: DataPeaks,
+    pub alert_events: Vec<AlertEvent>,
 }
 
 impl Default for DataCollection {
@@ -72,12 +128,19 @@ impl Default for DataCollection {
             swap_harvest: mem::MemHarvest::default(),
             cpu_harvest: cpu::CpuHarvest::default(),
             process_harvest: Vec::default(),
+            process_history: HashMap::default(),
             disk_harvest: Vec::default(),
             io_harvest: disks::IOHarvest::default(),
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
+            io_member_labels: HashMap::default(),
+            io_counter_seeds: HashMap::default(),
             temp_harvest: Vec::default(),
             battery_harvest: Vec::default(),
+            guest_harvest: None,
+            previous_session_instant: None,
+            peaks: DataPeaks::default(),
+            alert_events: Vec::default(),
         }
     }
 }
@@ -90,11 +153,18 @@ impl DataCollection {
         self.swap_harvest = mem::MemHarvest::default();
         self.cpu_harvest = cpu::CpuHarvest::default();
         self.process_harvest = Vec::default();
+        self.process_history = HashMap::default();
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IOHarvest::default();
         self.io_labels_and_prev = Vec::default();
+        self.io_member_labels = HashMap::default();
+        self.io_counter_seeds = HashMap::default();
         self.temp_harvest = Vec::default();
         self.battery_harvest = Vec::default();
+        self.guest_harvest = None;
+        self.previous_session_instant = None;
+        self.peaks = DataPeaks::default();
+        self.alert_events = Vec::default();
     }
 
     pub fn set_frozen_time(&mut self) {
@@ -115,6 +185,96 @@ impl DataCollection {
         }
 
         self.timed_data_vec.drain(0..remove_index);
+        self.alert_events.retain(|event| {
+            current_time.duration_since(event.instant).as_millis() < max_time_millis as u128
+        });
+        for history in self.process_history.values_mut() {
+            while let Some((instant, _, _)) = history.samples.front() {
+                if current_time.duration_since(*instant).as_millis() >= max_time_millis as u128 {
+                    history.samples.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Records an alert for every threshold that the newest data crossed above since the previous
+    /// update.  CPU thresholds are checked against the average, or the busiest CPU if the average
+    /// isn't shown, and network thresholds against both RX and TX.
+    pub fn record_threshold_alerts(&mut self, thresholds: &GraphThresholds) {
+        let (previous, instant, current) = match self.timed_data_vec.as_slice() {
+            [.., (_, previous), (instant, current)] => (previous, *instant, current),
+            _ => return,
+        };
+
+        let avg_position = self
+            .cpu_harvest
+            .iter()
+            .position(|cpu| cpu.cpu_count.is_none());
+        let get_cpu = |data: &TimedData| match avg_position {
+            Some(avg_position) => data.cpu_data.get(avg_position).copied().unwrap_or(0.0),
+            None => data.cpu_data.iter().copied().fold(0.0, f64::max),
+        };
+        let from_log2 = |value: f64| if value > 0.0 { value.exp2() } else { 0.0 };
+        let format_rate = |bytes: f64| {
+            let (value, unit) = get_byte_values(bytes as u64, false, ByteUnits::Binary);
+            format!("{}{}/s", format_number(value, 1), unit)
+        };
+
+        let mut alert_events = Vec::new();
+        let mut check = |graph: AlertGraph,
+                         label: &str,
+                         threshold_values: &[f64],
+                         previous: f64,
+                         current: f64,
+                         format_value: &dyn Fn(f64) -> String| {
+            for threshold in threshold_values {
+                if previous <= *threshold && current > *threshold {
+                    alert_events.push(AlertEvent {
+                        instant,
+                        graph,
+                        name: format!("{} > {}", label, format_value(*threshold)),
+                    });
+                }
+            }
+        };
+        let format_percent = |value: f64| format!("{}%", format_number(value, 0));
+
+        check(
+            AlertGraph::Cpu,
+            "CPU",
+            &thresholds.cpu,
+            get_cpu(previous),
+            get_cpu(current),
+            &format_percent,
+        );
+        check(
+            AlertGraph::Mem,
+            "RAM",
+            &thresholds.mem,
+            previous.mem_data,
+            current.mem_data,
+            &format_percent,
+        );
+        check(
+            AlertGraph::Net,
+            "RX",
+            &thresholds.network,
+            from_log2(previous.rx_data),
+            from_log2(current.rx_data),
+            &format_rate,
+        );
+        check(
+            AlertGraph::Net,
+            "TX",
+            &thresholds.network,
+            from_log2(previous.tx_data),
+            from_log2(current.tx_data),
+            &format_rate,
+        );
+
+        self.alert_events.extend(alert_events);
     }
 
     pub fn eat_data(&mut self, harvested_data: &Data) {
@@ -155,7 +315,7 @@ impl DataCollection {
 
         // Processes
         if let Some(list_of_processes) = &harvested_data.list_of_processes {
-            self.eat_proc(list_of_processes);
+            self.eat_proc(list_of_processes, harvested_time);
         }
 
         // Battery
@@ -163,6 +323,11 @@ impl DataCollection {
             self.eat_battery(list_of_batteries);
         }
 
+        // Guest
+        if let Some(guest) = &harvested_data.guest {
+            self.guest_harvest = Some(guest.clone());
+        }
+
         // And we're done eating.  Update time and push the new entry!
         self.current_instant = harvested_time;
         self.timed_data_vec.push((harvested_time, new_entry));
@@ -178,6 +343,7 @@ impl DataCollection {
             total => (memory.mem_used_in_mb as f64) / (total as f64) * 100.0,
         };
         new_entry.mem_data = mem_percent;
+        self.peaks.mem = self.peaks.mem.max(mem_percent);
 
         // Swap
         if swap.mem_total_in_mb > 0 {
@@ -186,6 +352,7 @@ impl DataCollection {
                 total => (swap.mem_used_in_mb as f64) / (total as f64) * 100.0,
             };
             new_entry.swap_data = swap_percent;
+            self.peaks.swap = self.peaks.swap.max(swap_percent);
         }
 
         // In addition copy over latest data for easy reference
@@ -211,6 +378,9 @@ impl DataCollection {
             0.0
         };
 
+        self.peaks.rx = self.peaks.rx.max(network.rx);
+        self.peaks.tx = self.peaks.tx.max(network.tx);
+
         // In addition copy over latest data for easy reference
         self.network_harvest = network.clone();
     }
@@ -223,6 +393,11 @@ impl DataCollection {
         cpu.iter()
             .for_each(|cpu| new_entry.cpu_data.push(cpu.cpu_usage));
 
+        self.peaks.cpu.resize(cpu.len(), 0.0);
+        for (peak, cpu) in self.peaks.cpu.iter_mut().zip(cpu) {
+            *peak = peak.max(cpu.cpu_usage);
+        }
+
         self.cpu_harvest = cpu.to_vec();
     }
 
@@ -243,18 +418,17 @@ impl DataCollection {
             .as_secs_f64();
 
         for (itx, device) in disks.iter().enumerate() {
-            if let Some(trim) = device.name.split('/').last() {
-                let io_device = if cfg!(target_os = "macos") {
+            if let Some(trim) = device.name.split('/').next_back() {
+                let io_key = if cfg!(target_os = "macos") {
                     // Must trim one level further!
                     static DISK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"disk\d+").unwrap());
-                    if let Some(disk_trim) = DISK_REGEX.find(trim) {
-                        io.get(disk_trim.as_str())
-                    } else {
-                        None
-                    }
+                    DISK_REGEX
+                        .find(trim)
+                        .map(|disk_trim| disk_trim.as_str().to_string())
                 } else {
-                    io.get(trim)
+                    Some(disks::get_io_key(device, io))
                 };
+                let io_device = io_key.as_ref().and_then(|io_key| io.get(io_key));
 
                 if let Some(io_device) = io_device {
                     let (io_r_pt, io_w_pt) = if let Some(io) = io_device {
@@ -267,8 +441,20 @@ impl DataCollection {
                         self.io_labels.push((String::default(), String::default()));
                     }
 
+                    let mut time_since_last_harvest = time_since_last_harvest;
                     if self.io_labels_and_prev.len() <= itx {
-                        self.io_labels_and_prev.push(((0, 0), (io_r_pt, io_w_pt)));
+                        // If we have counters from a previous session, start from those so the
+                        // first rate is actually meaningful rather than just zero.
+                        let seed = io_key
+                            .as_ref()
+                            .and_then(|io_key| self.io_counter_seeds.remove(io_key));
+                        if let Some((seed_instant, seed_r, seed_w)) = seed {
+                            time_since_last_harvest =
+                                harvested_time.duration_since(seed_instant).as_secs_f64();
+                            self.io_labels_and_prev.push(((0, 0), (seed_r, seed_w)));
+                        } else {
+                            self.io_labels_and_prev.push(((0, 0), (io_r_pt, io_w_pt)));
+                        }
                     }
 
                     if let Some((io_curr, io_prev)) = self.io_labels_and_prev.get_mut(itx) {
@@ -283,12 +469,7 @@ impl DataCollection {
                         *io_prev = (io_r_pt, io_w_pt);
 
                         if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                            let converted_read = get_simple_byte_values(r_rate, false);
-                            let converted_write = get_simple_byte_values(w_rate, false);
-                            *io_labels = (
-                                format!("{:.*}{}/s", 0, converted_read.0, converted_read.1),
-                                format!("{:.*}{}/s", 0, converted_write.0, converted_write.1),
-                            );
+                            *io_labels = (get_io_rate_label(r_rate), get_io_rate_label(w_rate));
                         }
                     }
                 } else {
@@ -303,13 +484,78 @@ impl DataCollection {
             }
         }
 
+        // Members of composite disks are rated against the counters of the last harvest.
+        let mut member_rates = HashMap::new();
+        for member in disks
+            .iter()
+            .filter_map(|disk| disk.composite.as_ref())
+            .flat_map(|composite| &composite.members)
+        {
+            if let (Some(Some(curr)), Some(Some(prev))) =
+                (io.get(&member.name), self.io_harvest.get(&member.name))
+            {
+                let r_rate = (curr.read_bytes.saturating_sub(prev.read_bytes) as f64
+                    / time_since_last_harvest)
+                    .round() as u64;
+                let w_rate = (curr.write_bytes.saturating_sub(prev.write_bytes) as f64
+                    / time_since_last_harvest)
+                    .round() as u64;
+                member_rates.insert(member.name.clone(), (r_rate, w_rate));
+            }
+        }
+
+        // A btrfs filesystem has no device of its own, so its I/O is that of all of its devices.
+        for (itx, disk) in disks.iter().enumerate() {
+            if let Some(composite) = &disk.composite {
+                if composite.kind == "btrfs" {
+                    let (r_rate, w_rate) = composite
+                        .members
+                        .iter()
+                        .filter_map(|member| member_rates.get(&member.name))
+                        .fold((0, 0), |(r_total, w_total), (r_rate, w_rate)| {
+                            (r_total + r_rate, w_total + w_rate)
+                        });
+                    if let Some(io_labels) = self.io_labels.get_mut(itx) {
+                        *io_labels = (get_io_rate_label(r_rate), get_io_rate_label(w_rate));
+                    }
+                }
+            }
+        }
+
+        self.io_member_labels = member_rates
+            .into_iter()
+            .map(|(name, (r_rate, w_rate))| {
+                (name, (get_io_rate_label(r_rate), get_io_rate_label(w_rate)))
+            })
+            .collect();
         self.disk_harvest = disks.to_vec();
         self.io_harvest = io.clone();
     }
 
-    fn eat_proc(&mut self, list_of_processes: &[processes::ProcessHarvest]) {
+    fn eat_proc(
+        &mut self, list_of_processes: &[processes::ProcessHarvest], harvested_time: Instant,
+    ) {
         trace!("Eating proc.");
         self.process_harvest = list_of_processes.to_vec();
+
+        // Forget processes that are gone, and start over for any whose PID was reused.
+        let pids = list_of_processes
+            .iter()
+            .map(|process| process.pid)
+            .collect::<HashSet<_>>();
+        self.process_history.retain(|pid, _| pids.contains(pid));
+        for process in list_of_processes {
+            let history = self.process_history.entry(process.pid).or_default();
+            if history.start_time != process.start_time {
+                history.start_time = process.start_time;
+                history.samples.clear();
+            }
+            history.samples.push_back((
+                harvested_time,
+                process.cpu_usage_percent,
+                process.mem_usage_bytes,
+            ));
+        }
     }
 
     fn eat_battery(&mut self, list_of_batteries: &[battery_harvester::BatteryHarvest]) {
@@ -317,3 +563,9 @@ impl DataCollection {
         self.battery_harvest = list_of_batteries.to_vec();
     }
 }
+
+/// Formats a rate in bytes per second, as shown in the disk table.
+fn get_io_rate_label(rate: u64) -> String {
+    let (value, unit) = get_byte_values(rate, false, ByteUnits::Si);
+    format!("{}{}/s", format_number(value, 0), unit)
+}
@@ -16,10 +16,17 @@ use futures::join;
 pub mod battery_harvester;
 pub mod cpu;
 pub mod disks;
+pub mod energy;
+pub mod guest;
+pub mod media;
 pub mod mem;
 pub mod network;
+#[cfg(feature = "nvidia")]
+pub mod nvidia;
+pub mod process_details;
 pub mod processes;
 pub mod temperature;
+pub mod window_titles;
 
 #[derive(Clone, Debug)]
 pub struct Data {
@@ -33,6 +40,7 @@ pub struct Data {
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IOHarvest>,
     pub list_of_batteries: Option<Vec<battery_harvester::BatteryHarvest>>,
+    pub guest: Option<guest::GuestHarvest>,
 }
 
 impl Default for Data {
@@ -48,6 +56,7 @@ impl Default for Data {
             io: None,
             network: None,
             list_of_batteries: None,
+            guest: None,
         }
     }
 }
@@ -61,6 +70,7 @@ impl Data {
         self.memory = None;
         self.swap = None;
         self.cpu = None;
+        self.guest = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -74,10 +84,41 @@ pub struct DataCollector {
     sys: System,
     #[cfg(target_os = "linux")]
     pid_mapping: HashMap<crate::Pid, processes::PrevProcDetails>,
+    user_table: processes::UserTable,
+    #[cfg(not(target_os = "linux"))]
+    exe_table: processes::ExeTable,
+    collect_connections: bool,
+    #[cfg(target_os = "linux")]
+    process_name_source: processes::ProcessNameSource,
+    #[cfg(target_os = "linux")]
+    app_table: processes::AppTable,
+    collect_app_names: bool,
+    collect_security_contexts: bool,
+    collect_namespaces: bool,
+    collect_fds: bool,
+    collect_env: bool,
+    collect_cgroups: bool,
+    collect_window_titles: bool,
+    collect_media: bool,
+    collect_energy: bool,
+    energy_collector: energy::EnergyCollector,
+    window_title_collector: window_titles::WindowTitleCollector,
+    #[cfg(feature = "nvidia")]
+    gpu_mem_collector: nvidia::GpuMemCollector,
+    #[cfg(any(target_os = "linux", feature = "nvidia"))]
+    collect_gpu_stats: bool,
+    #[cfg(feature = "nvidia")]
+    gpu_mem_rate_in_milliseconds: u64,
     #[cfg(target_os = "linux")]
     prev_idle: f64,
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
+    hypervisor: Option<String>,
+    wsl: Option<guest::WslVersion>,
+    #[cfg(target_os = "linux")]
+    prev_steal: f64,
+    #[cfg(target_os = "linux")]
+    prev_total: f64,
     mem_total_kb: u64,
     temperature_type: temperature::TemperatureType,
     use_current_cpu_total: bool,
@@ -100,10 +141,41 @@ impl Default for DataCollector {
             sys: System::new_all(),
             #[cfg(target_os = "linux")]
             pid_mapping: HashMap::new(),
+            user_table: processes::UserTable::default(),
+            #[cfg(not(target_os = "linux"))]
+            exe_table: processes::ExeTable::default(),
+            collect_connections: false,
+            #[cfg(target_os = "linux")]
+            process_name_source: processes::ProcessNameSource::default(),
+            #[cfg(target_os = "linux")]
+            app_table: processes::AppTable::default(),
+            collect_app_names: false,
+            collect_security_contexts: false,
+            collect_namespaces: false,
+            collect_fds: false,
+            collect_env: false,
+            collect_cgroups: false,
+            collect_window_titles: false,
+            collect_media: false,
+            collect_energy: false,
+            energy_collector: energy::EnergyCollector::default(),
+            window_title_collector: window_titles::WindowTitleCollector::default(),
+            #[cfg(feature = "nvidia")]
+            gpu_mem_collector: nvidia::GpuMemCollector::default(),
+            #[cfg(any(target_os = "linux", feature = "nvidia"))]
+            collect_gpu_stats: false,
+            #[cfg(feature = "nvidia")]
+            gpu_mem_rate_in_milliseconds: crate::constants::DEFAULT_GPU_MEMORY_RATE_IN_MILLISECONDS,
             #[cfg(target_os = "linux")]
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
+            hypervisor: None,
+            wsl: None,
+            #[cfg(target_os = "linux")]
+            prev_steal: 0_f64,
+            #[cfg(target_os = "linux")]
+            prev_total: 0_f64,
             mem_total_kb: 0,
             temperature_type: temperature::TemperatureType::Celsius,
             use_current_cpu_total: false,
@@ -130,6 +202,10 @@ impl DataCollector {
         self.mem_total_kb = self.sys.get_total_memory();
         trace!("Total memory in KB: {}", self.mem_total_kb);
 
+        self.wsl = guest::detect_wsl();
+        self.hypervisor = guest::detect_hypervisor();
+        trace!("Hypervisor: {:?}", self.hypervisor);
+
         if self.widgets_to_harvest.use_battery {
             trace!("First run battery vec creation.");
             if let Ok(battery_manager) = Manager::new() {
@@ -166,6 +242,79 @@ impl DataCollector {
         self.use_current_cpu_total = use_current_cpu_total;
     }
 
+    pub fn set_collect_connections(&mut self, collect_connections: bool) {
+        self.collect_connections = collect_connections;
+    }
+
+    pub fn set_collect_app_names(&mut self, collect_app_names: bool) {
+        self.collect_app_names = collect_app_names;
+    }
+
+    pub fn set_collect_security_contexts(&mut self, collect_security_contexts: bool) {
+        self.collect_security_contexts = collect_security_contexts;
+    }
+
+    pub fn set_collect_namespaces(&mut self, collect_namespaces: bool) {
+        self.collect_namespaces = collect_namespaces;
+    }
+
+    pub fn set_collect_fds(&mut self, collect_fds: bool) {
+        self.collect_fds = collect_fds;
+    }
+
+    pub fn set_collect_env(&mut self, collect_env: bool) {
+        self.collect_env = collect_env;
+    }
+
+    pub fn set_collect_cgroups(&mut self, collect_cgroups: bool) {
+        self.collect_cgroups = collect_cgroups;
+    }
+
+    pub fn set_collect_window_titles(&mut self, collect_window_titles: bool) {
+        self.collect_window_titles = collect_window_titles;
+    }
+
+    pub fn set_collect_media(&mut self, collect_media: bool) {
+        self.collect_media = collect_media;
+    }
+
+    pub fn set_collect_energy(&mut self, collect_energy: bool) {
+        self.collect_energy = collect_energy;
+    }
+
+    /// Only used on Linux, or if built with the "nvidia" feature.
+    #[allow(unused_variables)]
+    pub fn set_collect_gpu_stats(&mut self, collect_gpu_stats: bool) {
+        #[cfg(any(target_os = "linux", feature = "nvidia"))]
+        {
+            self.collect_gpu_stats = collect_gpu_stats;
+        }
+        #[cfg(feature = "nvidia")]
+        {
+            if !collect_gpu_stats {
+                self.gpu_mem_collector.clear();
+            }
+        }
+    }
+
+    /// Only used if built with the "nvidia" feature.
+    #[allow(unused_variables)]
+    pub fn set_gpu_mem_rate(&mut self, gpu_mem_rate_in_milliseconds: u64) {
+        #[cfg(feature = "nvidia")]
+        {
+            self.gpu_mem_rate_in_milliseconds = gpu_mem_rate_in_milliseconds;
+        }
+    }
+
+    /// Only used on Linux, as that's the only place process names get truncated.
+    #[allow(unused_variables)]
+    pub fn set_process_name_source(&mut self, process_name_source: processes::ProcessNameSource) {
+        #[cfg(target_os = "linux")]
+        {
+            self.process_name_source = process_name_source;
+        }
+    }
+
     pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
         self.show_average_cpu = show_average_cpu;
     }
@@ -221,7 +370,7 @@ impl DataCollector {
         if let Some(battery_manager) = &self.battery_manager {
             if let Some(battery_list) = &mut self.battery_list {
                 self.data.list_of_batteries = Some(battery_harvester::refresh_batteries(
-                    &battery_manager,
+                    battery_manager,
                     battery_list,
                 ));
             }
@@ -235,6 +384,25 @@ impl DataCollector {
             }
         }
 
+        // Guest
+        if let Some(hypervisor) = &self.hypervisor {
+            if self.widgets_to_harvest.use_cpu || self.widgets_to_harvest.use_mem {
+                #[allow(unused_mut)]
+                let mut guest_harvest = guest::GuestHarvest {
+                    hypervisor: hypervisor.clone(),
+                    ..guest::GuestHarvest::default()
+                };
+                // WSL1 has no VM, so there's no steal time or balloon to speak of.
+                #[cfg(target_os = "linux")]
+                if self.wsl != Some(guest::WslVersion::Wsl1) {
+                    guest_harvest.steal_percent =
+                        guest::get_steal_percent(&mut self.prev_steal, &mut self.prev_total);
+                    guest_harvest.balloon_bytes = guest::get_balloon_bytes(self.page_file_size_kb);
+                }
+                self.data.guest = Some(guest_harvest);
+            }
+        }
+
         if self.widgets_to_harvest.use_proc {
             // Processes.  This is the longest part of the harvesting process... changing this might be
             // good in the future.  What was tried already:
@@ -252,6 +420,20 @@ impl DataCollector {
                             .as_secs(),
                         self.mem_total_kb,
                         self.page_file_size_kb,
+                        &mut self.user_table,
+                        self.collect_connections,
+                        self.process_name_source,
+                        if self.collect_app_names {
+                            Some(&mut self.app_table)
+                        } else {
+                            None
+                        },
+                        self.collect_gpu_stats,
+                        self.collect_security_contexts,
+                        self.collect_namespaces,
+                        self.collect_fds,
+                        self.collect_env,
+                        self.collect_cgroups,
                     )
                 }
                 #[cfg(not(target_os = "linux"))]
@@ -265,6 +447,8 @@ impl DataCollector {
                         &self.sys,
                         self.use_current_cpu_total,
                         self.mem_total_kb,
+                        &mut self.user_table,
+                        &mut self.exe_table,
                     )
                 }
                 #[cfg(target_os = "linux")]
@@ -275,6 +459,58 @@ impl DataCollector {
                 self.data.list_of_processes = Some(process_list);
             }
 
+            #[cfg(feature = "nvidia")]
+            {
+                if self.collect_gpu_stats {
+                    if let Some(processes) = &mut self.data.list_of_processes {
+                        let gpu_mem_by_pid = self.gpu_mem_collector.get_gpu_mem_by_pid(
+                            std::time::Duration::from_millis(self.gpu_mem_rate_in_milliseconds),
+                        );
+                        for process in processes {
+                            if let Some(gpu_mem_bytes) = gpu_mem_by_pid.get(&process.pid) {
+                                process.gpu_mem_bytes += gpu_mem_bytes;
+                            }
+                        }
+                    }
+                }
+            }
+
+            if self.collect_window_titles {
+                if let Some(processes) = &mut self.data.list_of_processes {
+                    let mut window_titles = self.window_title_collector.get_window_titles();
+                    for process in processes {
+                        if let Some(titles) = window_titles.remove(&process.pid) {
+                            process.window_titles = titles;
+                        }
+                    }
+                }
+            }
+
+            if self.collect_media {
+                if let Some(processes) = &mut self.data.list_of_processes {
+                    let mut media_usage =
+                        media::get_media_usage(processes.iter().map(|process| process.pid));
+                    for process in processes {
+                        if let Some(media_usage) = media_usage.remove(&process.pid) {
+                            process.media_usage = media_usage;
+                        }
+                    }
+                }
+            }
+
+            if self.collect_energy {
+                if let Some(processes) = &mut self.data.list_of_processes {
+                    let cpu_usages = processes
+                        .iter()
+                        .map(|process| process.cpu_usage_percent)
+                        .collect::<Vec<_>>();
+                    let energy_impacts = self.energy_collector.get_energy_impacts(&cpu_usages);
+                    for (process, energy_impact) in processes.iter_mut().zip(energy_impacts) {
+                        process.energy_impact = energy_impact;
+                    }
+                }
+            }
+
             if log_enabled!(log::Level::Trace) {
                 if let Some(processes) = &self.data.list_of_processes {
                     trace!("processes: {:#?} results", processes.len());
@@ -400,6 +636,13 @@ impl DataCollector {
             }
         }
 
+        #[cfg(target_os = "linux")]
+        let mem_res = match mem_res {
+            Err(_) if self.wsl.is_some() && self.widgets_to_harvest.use_mem => {
+                Ok(mem::get_meminfo_mem_data())
+            }
+            mem_res => mem_res,
+        };
         if let Ok(memory) = mem_res {
             self.data.memory = memory;
             if log_enabled!(log::Level::Trace) {
@@ -414,7 +657,12 @@ impl DataCollector {
             }
         }
 
-        if let Ok(disks) = disk_res {
+        if let Ok(mut disks) = disk_res {
+            if self.wsl.is_some() {
+                if let Some(disks) = &mut disks {
+                    disks.retain(|disk| !guest::is_wsl_internal_mount(&disk.mount_point));
+                }
+            }
             self.data.disks = disks;
             if log_enabled!(log::Level::Trace) {
                 if let Some(disks) = &self.data.disks {
@@ -13,11 +13,7 @@ pub struct BatteryHarvest {
 }
 
 fn convert_optional_time_to_optional_seconds(optional_time: Option<Time>) -> Option<i64> {
-    if let Some(time) = optional_time {
-        Some(f64::from(time.get::<second>()) as i64)
-    } else {
-        None
-    }
+    optional_time.map(|time| f64::from(time.get::<second>()) as i64)
 }
 
 pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<BatteryHarvest> {
@@ -8,6 +8,35 @@ pub struct DiskHarvest {
     pub free_space: u64,
     pub used_space: u64,
     pub total_space: u64,
+    /// What the disk is made up of, if it is made up of other block devices.
+    pub composite: Option<CompositeDisk>,
+    /// The notable options the disk is mounted with, like `ro` or `nosuid`.
+    pub mount_flags: Vec<String>,
+    /// Whether the mount point is listed in `/etc/fstab`, if that is known.
+    pub is_in_fstab: Option<bool>,
+    /// Whether the disk is mounted read-only even though `/etc/fstab` says it shouldn't be, which
+    /// usually means the kernel remounted it after errors.
+    pub is_unexpectedly_read_only: bool,
+    /// Whether the disk is removable media, like a USB stick or an SD card.
+    pub is_removable: bool,
+}
+
+/// A disk made up of other block devices, like a RAID array, an LVM volume, or a btrfs
+/// filesystem spanning several devices.
+#[derive(Debug, Clone, Default)]
+pub struct CompositeDisk {
+    /// What kind of disk this is, like "raid1", "lvm", "crypt", or "btrfs".
+    pub kind: String,
+    pub members: Vec<DiskMember>,
+}
+
+/// A block device that is part of a [`CompositeDisk`].
+#[derive(Debug, Clone, Default)]
+pub struct DiskMember {
+    /// The kernel's name for the device, like "sda1", which is also what its I/O counters are
+    /// listed under.
+    pub name: String,
+    pub total_space: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -18,6 +47,272 @@ pub struct IOData {
 
 pub type IOHarvest = std::collections::HashMap<String, Option<IOData>>;
 
+/// Returns which I/O counters in `io` belong to a disk.  Counters are listed under the kernel's
+/// name for a device, so on Linux, the device a disk is mounted from is found through its mount
+/// point, which also works for LVM and encrypted volumes (like `dm-0`) and for `/dev/root`.  If
+/// only whole disks are counted, partitions use the counters of the disk they are on.
+pub fn get_io_key(disk: &DiskHarvest, io: &IOHarvest) -> String {
+    #[cfg(target_os = "linux")]
+    {
+        let kernel_name = get_kernel_device_name(disk);
+        if !io.contains_key(&kernel_name) {
+            if let Some(parent_name) = get_parent_device_name(&kernel_name) {
+                if io.contains_key(&parent_name) {
+                    return parent_name;
+                }
+            }
+        }
+        kernel_name
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = io;
+        disk.name
+            .split('/')
+            .next_back()
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// Returns the kernel's name for the block device a disk is mounted from.
+#[cfg(target_os = "linux")]
+fn get_kernel_device_name(disk: &DiskHarvest) -> String {
+    get_mounted_device_name(&disk.mount_point)
+        .or_else(|| {
+            std::fs::canonicalize(&disk.name)
+                .ok()
+                .and_then(|path| Some(path.file_name()?.to_str()?.to_string()))
+        })
+        .unwrap_or_else(|| {
+            disk.name
+                .split('/')
+                .next_back()
+                .unwrap_or_default()
+                .to_string()
+        })
+}
+
+/// Finds what a disk is made up of, if it is a RAID array, a device-mapper device like an LVM
+/// volume, or a btrfs filesystem on more than one device.
+#[cfg(target_os = "linux")]
+pub fn get_composite_disk(disk: &DiskHarvest) -> Option<CompositeDisk> {
+    let device_name = get_kernel_device_name(disk);
+    let device_path = std::path::PathBuf::from(format!("/sys/class/block/{}", device_name));
+
+    let (kind, member_names) =
+        if let Ok(level) = std::fs::read_to_string(device_path.join("md/level")) {
+            (
+                level.trim().to_string(),
+                get_dir_names(&device_path.join("slaves")),
+            )
+        } else if let Ok(uuid) = std::fs::read_to_string(device_path.join("dm/uuid")) {
+            let kind = if uuid.starts_with("LVM-") {
+                "lvm"
+            } else if uuid.starts_with("CRYPT-") {
+                "crypt"
+            } else {
+                "dm"
+            };
+            (kind.to_string(), get_dir_names(&device_path.join("slaves")))
+        } else {
+            // A btrfs filesystem has no block device of its own, so look for the one that the
+            // disk's device is part of.
+            let member_names = get_dir_names(std::path::Path::new("/sys/fs/btrfs"))
+                .into_iter()
+                .map(|uuid| {
+                    get_dir_names(
+                        &std::path::Path::new("/sys/fs/btrfs/")
+                            .join(uuid)
+                            .join("devices"),
+                    )
+                })
+                .find(|member_names| member_names.contains(&device_name))?;
+            if member_names.len() < 2 {
+                return None;
+            }
+            ("btrfs".to_string(), member_names)
+        };
+
+    if member_names.is_empty() {
+        return None;
+    }
+    Some(CompositeDisk {
+        kind,
+        members: member_names
+            .into_iter()
+            .map(|name| DiskMember {
+                total_space: std::fs::read_to_string(format!("/sys/class/block/{}/size", name))
+                    .ok()
+                    .and_then(|sectors| sectors.trim().parse::<u64>().ok())
+                    .map(|sectors| sectors * 512),
+                name,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_composite_disk(_disk: &DiskHarvest) -> Option<CompositeDisk> {
+    None
+}
+
+/// Returns whether a disk is removable media.  Devices the kernel marks as removable count, as
+/// do any on USB, since USB drives often aren't marked.
+#[cfg(target_os = "linux")]
+pub fn is_removable_disk(disk: &DiskHarvest) -> bool {
+    let device_name = get_kernel_device_name(disk);
+    let disk_name = get_parent_device_name(&device_name).unwrap_or(device_name);
+
+    let is_marked_removable =
+        std::fs::read_to_string(format!("/sys/class/block/{}/removable", disk_name))
+            .is_ok_and(|removable| removable.trim() == "1");
+    let is_on_usb =
+        std::fs::canonicalize(format!("/sys/class/block/{}", disk_name)).is_ok_and(|path| {
+            path.components().any(|part| {
+                part.as_os_str()
+                    .to_str()
+                    .is_some_and(|part| part.starts_with("usb"))
+            })
+        });
+
+    is_marked_removable || is_on_usb
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_removable_disk(_disk: &DiskHarvest) -> bool {
+    false
+}
+
+/// The mount options that are worth showing, as they restrict what can be done on a disk.
+#[cfg(target_os = "linux")]
+const NOTABLE_MOUNT_FLAGS: [&str; 4] = ["ro", "nosuid", "nodev", "noexec"];
+
+/// Fills in how each disk is mounted, from `/proc/self/mounts`, and compares that with
+/// `/etc/fstab`.
+#[cfg(target_os = "linux")]
+pub fn set_mount_flags(disks: &mut [DiskHarvest]) {
+    let mounted_options = read_mount_options("/proc/self/mounts").unwrap_or_default();
+    // An empty fstab says nothing about what should be mounted, like in most containers.
+    let fstab_options =
+        read_mount_options("/etc/fstab").filter(|fstab_options| !fstab_options.is_empty());
+
+    for disk in disks {
+        if let Some(options) = mounted_options.get(&disk.mount_point) {
+            disk.mount_flags = options
+                .iter()
+                .filter(|option| NOTABLE_MOUNT_FLAGS.contains(&option.as_str()))
+                .cloned()
+                .collect();
+        }
+
+        if let Some(fstab_options) = &fstab_options {
+            let expected_options = fstab_options.get(&disk.mount_point);
+            disk.is_in_fstab = Some(expected_options.is_some());
+            disk.is_unexpectedly_read_only = disk.mount_flags.iter().any(|flag| flag == "ro")
+                && expected_options.is_some_and(|options| !options.iter().any(|o| o == "ro"));
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_mount_flags(_disks: &mut [DiskHarvest]) {}
+
+/// Returns the mount points of everything that is mounted, or none if they can't be read.
+#[cfg(target_os = "linux")]
+pub fn get_mount_points() -> Option<Vec<String>> {
+    read_mount_options("/proc/self/mounts").map(|options| options.into_keys().collect())
+}
+
+/// Reads a file in the format of `/etc/fstab` into the options of each mount point, or none if it
+/// can't be read.
+#[cfg(target_os = "linux")]
+fn read_mount_options(path: &str) -> Option<std::collections::HashMap<String, Vec<String>>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    Some(
+        contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let mount_point = fields.nth(1)?;
+                let options = fields.nth(1)?;
+                Some((
+                    unescape_mount_field(mount_point),
+                    options
+                        .split(',')
+                        .map(|option| option.to_string())
+                        .collect(),
+                ))
+            })
+            .collect(),
+    )
+}
+
+/// Undoes the octal escapes, like `\040` for a space, used in mount tables.
+#[cfg(target_os = "linux")]
+fn unescape_mount_field(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+        let escape = rest.get(index + 1..index + 4);
+        match escape.and_then(|escape| u8::from_str_radix(escape, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Returns the sorted names of the entries of a directory, or none if it can't be read.
+#[cfg(target_os = "linux")]
+fn get_dir_names(path: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Returns the kernel's name for the block device a mount point is on, like `sda1` or `dm-0`.
+/// Filesystems that aren't on a single block device, like btrfs or network filesystems, have
+/// none.
+#[cfg(target_os = "linux")]
+fn get_mounted_device_name(mount_point: &str) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let dev = std::fs::metadata(mount_point).ok()?.dev();
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+    let path = std::fs::canonicalize(format!("/sys/dev/block/{}:{}", major, minor)).ok()?;
+    Some(path.file_name()?.to_str()?.to_string())
+}
+
+/// Returns the name of the disk a partition is on, like `sda` for `sda1`, or [`None`] if the
+/// device isn't a partition.
+#[cfg(target_os = "linux")]
+fn get_parent_device_name(device_name: &str) -> Option<String> {
+    let path = std::fs::canonicalize(format!("/sys/class/block/{}", device_name)).ok()?;
+    if !path.join("partition").exists() {
+        return None;
+    }
+    Some(path.parent()?.file_name()?.to_str()?.to_string())
+}
+
 /// Meant for ARM use.
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 pub async fn arm_io_usage(
@@ -53,8 +348,16 @@ pub async fn arm_disk_usage(
                 .get_total_space()
                 .saturating_sub(disk.get_available_space()),
             total_space: disk.get_total_space(),
+            composite: None,
+            ..DiskHarvest::default()
+        })
+        .map(|mut disk| {
+            disk.composite = get_composite_disk(&disk);
+            disk.is_removable = is_removable_disk(&disk);
+            disk
         })
         .collect::<Vec<DiskHarvest>>();
+    set_mount_flags(&mut vec_disks);
     vec_disks.sort_by(|a, b| a.name.cmp(&b.name));
 
     Ok(Some(vec_disks))
@@ -119,7 +422,7 @@ pub async fn non_arm_disk_usage(
             let partition = part;
             let usage = heim::disk::usage(partition.mount_point().to_path_buf()).await?;
 
-            vec_disks.push(DiskHarvest {
+            let mut disk = DiskHarvest {
                 free_space: usage.free().get::<heim::units::information::byte>(),
                 used_space: usage.used().get::<heim::units::information::byte>(),
                 total_space: usage.total().get::<heim::units::information::byte>(),
@@ -134,10 +437,15 @@ pub async fn non_arm_disk_usage(
                     .to_str()
                     .unwrap_or("Name Unavailable"))
                 .to_string(),
-            });
+                ..DiskHarvest::default()
+            };
+            disk.composite = get_composite_disk(&disk);
+            disk.is_removable = is_removable_disk(&disk);
+            vec_disks.push(disk);
         }
     }
 
+    set_mount_flags(&mut vec_disks);
     vec_disks.sort_by(|a, b| a.name.cmp(&b.name));
 
     Ok(Some(vec_disks))
@@ -0,0 +1,146 @@
+//! Estimates how much each process drains the battery.  Only collected while used.
+//!
+//! If the CPU's energy counters can be read through RAPL on Linux, the power drawn by the CPU
+//! packages is split between processes by how much of the CPU they use.  Otherwise, each process
+//! gets an impact score, which is its CPU usage scaled by how fast the CPU is clocked compared to
+//! its maximum, as higher clocks cost disproportionately more power.  Clock speeds are only read
+//! on Linux, so elsewhere the score is just the CPU usage.
+
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+
+/// The estimated energy impact of a process.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct EnergyImpact {
+    /// The power the process draws, in watts, if it could be measured.
+    pub watts: Option<f64>,
+    /// The CPU usage of the process in percent, scaled by the clock speed of the CPU.
+    pub score: f64,
+}
+
+impl EnergyImpact {
+    pub fn add(&mut self, other: EnergyImpact) {
+        self.watts = match (self.watts, other.watts) {
+            (Some(watts), Some(other_watts)) => Some(watts + other_watts),
+            (watts, other_watts) => watts.or(other_watts),
+        };
+        self.score += other.score;
+    }
+}
+
+/// Holds on to the last reading of the energy counters, to measure power between collections.
+#[derive(Debug, Default)]
+pub struct EnergyCollector {
+    #[cfg(target_os = "linux")]
+    prev_energy: Option<(Instant, u64)>,
+}
+
+impl EnergyCollector {
+    /// Returns the energy impact of each process, given the CPU usage of each in percent.
+    pub fn get_energy_impacts(&mut self, cpu_usages: &[f64]) -> Vec<EnergyImpact> {
+        let package_watts = self.get_package_watts();
+        let frequency_ratio = get_frequency_ratio().unwrap_or(1.0);
+        let total_cpu_usage: f64 = cpu_usages.iter().sum();
+
+        cpu_usages
+            .iter()
+            .map(|cpu_usage| EnergyImpact {
+                watts: package_watts.map(|package_watts| {
+                    if total_cpu_usage > 0.0 {
+                        package_watts * cpu_usage / total_cpu_usage
+                    } else {
+                        0.0
+                    }
+                }),
+                score: cpu_usage * frequency_ratio,
+            })
+            .collect()
+    }
+
+    /// Returns the power drawn by all CPU packages since the last call, in watts.
+    #[cfg(target_os = "linux")]
+    fn get_package_watts(&mut self) -> Option<f64> {
+        let now = Instant::now();
+        let energy = get_rapl_package_energy();
+        let prev_energy =
+            std::mem::replace(&mut self.prev_energy, energy.map(|energy| (now, energy)));
+
+        match (prev_energy, energy) {
+            // The counters wrap around, so a drop means a wrap rather than negative energy.
+            (Some((prev_time, prev_energy)), Some(energy)) if energy >= prev_energy => {
+                let elapsed_secs = now.duration_since(prev_time).as_secs_f64();
+                if elapsed_secs > 0.0 {
+                    Some((energy - prev_energy) as f64 / 1_000_000.0 / elapsed_secs)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn get_package_watts(&mut self) -> Option<f64> {
+        None
+    }
+}
+
+/// Returns the energy used by all CPU packages, in microjoules.  Reading these usually requires
+/// root, since they can leak what the CPU is doing.
+#[cfg(target_os = "linux")]
+fn get_rapl_package_energy() -> Option<u64> {
+    let domains = std::fs::read_dir("/sys/class/powercap").ok()?;
+    let mut total_energy = None;
+    for domain in domains.flatten() {
+        // Packages are `intel-rapl:<N>`, and the parts inside them are `intel-rapl:<N>:<M>`.
+        let name = domain.file_name().to_string_lossy().to_string();
+        let is_package = name
+            .strip_prefix("intel-rapl:")
+            .is_some_and(|index| index.parse::<u32>().is_ok());
+        if is_package {
+            let energy = std::fs::read_to_string(domain.path().join("energy_uj"))
+                .ok()?
+                .trim()
+                .parse::<u64>()
+                .ok()?;
+            total_energy = Some(total_energy.unwrap_or(0) + energy);
+        }
+    }
+    total_energy
+}
+
+/// Returns how fast the CPU is clocked on average, compared to its maximum.
+#[cfg(target_os = "linux")]
+fn get_frequency_ratio() -> Option<f64> {
+    let read_khz = |path: std::path::PathBuf| {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|khz| khz.trim().parse::<f64>().ok())
+    };
+
+    let ratios = std::fs::read_dir("/sys/devices/system/cpu/cpufreq")
+        .ok()?
+        .flatten()
+        .filter(|policy| policy.file_name().to_string_lossy().starts_with("policy"))
+        .filter_map(|policy| {
+            let current_khz = read_khz(policy.path().join("scaling_cur_freq"))?;
+            let max_khz = read_khz(policy.path().join("cpuinfo_max_freq"))?;
+            if max_khz > 0.0 {
+                Some(current_khz / max_khz)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if ratios.is_empty() {
+        None
+    } else {
+        Some(ratios.iter().sum::<f64>() / ratios.len() as f64)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_frequency_ratio() -> Option<f64> {
+    None
+}
@@ -0,0 +1,169 @@
+//! Data about the virtual machine bottom runs in, if it runs in one.
+
+#[derive(Debug, Clone, Default)]
+pub struct GuestHarvest {
+    /// The name of the hypervisor, like "KVM".
+    pub hypervisor: String,
+    /// How much of the time the vCPUs were ready to run but the hypervisor ran something else, as
+    /// a percentage.  Only collected on Linux.
+    pub steal_percent: Option<f64>,
+    /// How much memory the balloon driver has handed back to the host, in bytes, if there is one.
+    /// Only collected on Linux.
+    pub balloon_bytes: Option<u64>,
+}
+
+/// Which version of the Windows Subsystem for Linux bottom is running under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WslVersion {
+    /// Linux system calls translated by the Windows kernel, without a VM.
+    Wsl1,
+    /// A real Linux kernel in a lightweight Hyper-V VM.
+    Wsl2,
+}
+
+impl std::fmt::Display for WslVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WslVersion::Wsl1 => write!(f, "WSL1"),
+            WslVersion::Wsl2 => write!(f, "WSL2"),
+        }
+    }
+}
+
+/// Returns which version of WSL this is, or [`None`] if it isn't WSL.  WSL2 kernels are named
+/// like `5.15.90.1-microsoft-standard-WSL2`, while WSL1 reports a fake `4.4.0-19041-Microsoft`.
+pub fn detect_wsl() -> Option<WslVersion> {
+    #[cfg(target_os = "linux")]
+    {
+        let os_release = std::fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+        if os_release.contains("WSL2") || os_release.contains("microsoft-standard") {
+            return Some(WslVersion::Wsl2);
+        } else if os_release.contains("Microsoft") {
+            return Some(WslVersion::Wsl1);
+        }
+    }
+
+    None
+}
+
+/// Returns whether a mount point is one WSL uses for its own plumbing, like the WSLg and GPU
+/// driver mounts, which aren't worth showing as disks.
+pub fn is_wsl_internal_mount(mount_point: &str) -> bool {
+    mount_point == "/init"
+        || ["/mnt/wsl", "/mnt/wslg", "/usr/lib/wsl"]
+            .iter()
+            .any(|prefix| {
+                mount_point == *prefix || mount_point.starts_with(&format!("{}/", prefix))
+            })
+}
+
+/// Returns the name of the hypervisor this system runs under, or [`None`] if it doesn't seem to
+/// be a virtual machine.  WSL is reported as `WSL1` or `WSL2` rather than as Hyper-V, as that's
+/// what matters to whoever is using it.  Only detected on Linux.
+pub fn detect_hypervisor() -> Option<String> {
+    if let Some(wsl_version) = detect_wsl() {
+        return Some(wsl_version.to_string());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let read_trimmed = |path: &str| {
+            std::fs::read_to_string(path)
+                .map(|contents| contents.trim().to_string())
+                .unwrap_or_default()
+        };
+        let sys_vendor = read_trimmed("/sys/class/dmi/id/sys_vendor");
+        let product_name = read_trimmed("/sys/class/dmi/id/product_name");
+
+        let hypervisor = if read_trimmed("/sys/hypervisor/type") == "xen" || sys_vendor == "Xen" {
+            Some("Xen")
+        } else if product_name.contains("KVM") {
+            Some("KVM")
+        } else if sys_vendor == "QEMU" {
+            Some("QEMU")
+        } else if sys_vendor == "Microsoft Corporation" && product_name == "Virtual Machine" {
+            Some("Hyper-V")
+        } else if sys_vendor.starts_with("VMware") {
+            Some("VMware")
+        } else if product_name == "VirtualBox" {
+            Some("VirtualBox")
+        } else if sys_vendor.starts_with("Parallels") {
+            Some("Parallels")
+        } else {
+            None
+        };
+
+        if let Some(hypervisor) = hypervisor {
+            return Some(hypervisor.to_string());
+        }
+
+        // CPUs report this flag under any hypervisor, so it still catches ones we can't name.
+        let has_hypervisor_flag = std::fs::read_to_string("/proc/cpuinfo")
+            .map(|cpuinfo| {
+                cpuinfo
+                    .lines()
+                    .filter(|line| line.starts_with("flags"))
+                    .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"))
+            })
+            .unwrap_or(false);
+        if has_hypervisor_flag {
+            return Some("Unknown hypervisor".to_string());
+        }
+    }
+
+    None
+}
+
+/// Returns the CPU steal time since the last call as a percentage of all CPU time, from the
+/// aggregate line of `/proc/stat`.
+#[cfg(target_os = "linux")]
+pub fn get_steal_percent(prev_steal: &mut f64, prev_total: &mut f64) -> Option<f64> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let values = stat
+        .lines()
+        .next()?
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|val| val.parse::<f64>().unwrap_or(0_f64))
+        .collect::<Vec<_>>();
+    if values.len() < 8 {
+        return None;
+    }
+
+    // Guest time is already counted in user time, so only sum up to steal.
+    let steal = values[7];
+    let total: f64 = values.iter().sum();
+
+    let steal_delta = steal - *prev_steal;
+    let total_delta = total - *prev_total;
+    *prev_steal = steal;
+    *prev_total = total;
+
+    if total_delta > 0.0 {
+        Some((steal_delta / total_delta * 100.0).max(0.0))
+    } else {
+        Some(0.0)
+    }
+}
+
+/// Returns how much memory has been inflated into the balloon, from the balloon counters in
+/// `/proc/vmstat`, or [`None`] if the kernel doesn't keep them.
+#[cfg(target_os = "linux")]
+pub fn get_balloon_bytes(page_file_size_kb: u64) -> Option<u64> {
+    let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+    let get_counter = |name: &str| {
+        vmstat.lines().find_map(|line| {
+            let mut split = line.split_whitespace();
+            if split.next()? == name {
+                split.next()?.parse::<u64>().ok()
+            } else {
+                None
+            }
+        })
+    };
+
+    let inflated = get_counter("balloon_inflate")?;
+    let deflated = get_counter("balloon_deflate")?;
+    Some(inflated.saturating_sub(deflated) * page_file_size_kb * 1024)
+}
@@ -0,0 +1,191 @@
+//! Which processes are using the camera or microphone, or playing audio.  Only collected on
+//! Linux, and only while used.
+//!
+//! Devices opened directly are found through the file descriptors of each process.  Apps going
+//! through PulseAudio or PipeWire only talk to the sound server, so their streams are read from
+//! it with `pactl`, which works with both.
+
+use std::collections::HashMap;
+
+use crate::Pid;
+
+/// What media devices a process is using.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MediaUsage {
+    pub camera: bool,
+    pub microphone: bool,
+    pub audio: bool,
+}
+
+impl MediaUsage {
+    pub fn is_empty(&self) -> bool {
+        !(self.camera || self.microphone || self.audio)
+    }
+
+    /// Returns the names of what is being used, like `cam` or `audio`.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        IntoIterator::into_iter([
+            (self.camera, "cam"),
+            (self.microphone, "mic"),
+            (self.audio, "audio"),
+        ])
+        .filter_map(|(is_used, name)| if is_used { Some(name) } else { None })
+    }
+
+    pub fn merge(&mut self, other: MediaUsage) {
+        self.camera |= other.camera;
+        self.microphone |= other.microphone;
+        self.audio |= other.audio;
+    }
+}
+
+impl std::fmt::Display for MediaUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.names().collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// Returns what media devices a process has opened directly, like `/dev/video0` for a camera, or
+/// ALSA's `/dev/snd/pcmC0D0c` for a microphone and `/dev/snd/pcmC0D0p` for audio.
+#[cfg(target_os = "linux")]
+fn get_linux_process_media_usage(pid: Pid) -> MediaUsage {
+    let mut media_usage = MediaUsage::default();
+
+    if let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) {
+        for fd in fds.flatten() {
+            if let Ok(target) = std::fs::read_link(fd.path()) {
+                let target = target.to_string_lossy();
+                if target.starts_with("/dev/video") {
+                    media_usage.camera = true;
+                } else if let Some(pcm) = target.strip_prefix("/dev/snd/pcm") {
+                    if pcm.ends_with('c') {
+                        media_usage.microphone = true;
+                    } else if pcm.ends_with('p') {
+                        media_usage.audio = true;
+                    }
+                }
+            }
+        }
+    }
+
+    media_usage
+}
+
+/// Returns what each process is recording or playing through PulseAudio or PipeWire.  Paused
+/// streams, and recordings of what is being played rather than of a microphone, are left out.
+#[cfg(target_os = "linux")]
+fn get_sound_server_media_usage() -> HashMap<Pid, MediaUsage> {
+    let mut media_usage_by_pid: HashMap<Pid, MediaUsage> = HashMap::new();
+
+    let monitor_sources = match run_pactl(&["list", "short", "sources"]) {
+        Ok(sources) => sources
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let index = fields.next()?;
+                let name = fields.next()?;
+                if name.ends_with(".monitor") {
+                    Some(index.to_string())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            debug!("Could not list the sound server's streams: {}", err);
+            return media_usage_by_pid;
+        }
+    };
+
+    for (kind, is_recording) in &[("sink-inputs", false), ("source-outputs", true)] {
+        let streams = run_pactl(&["list", kind]).unwrap_or_default();
+        for stream in get_pactl_blocks(&streams) {
+            let field = |key: &str| {
+                stream
+                    .iter()
+                    .find_map(|line| line.trim().strip_prefix(key))
+                    .map(|value| value.trim().trim_matches('"'))
+            };
+
+            if field("Corked:") == Some("yes") {
+                continue;
+            }
+            if *is_recording
+                && field("Source:")
+                    .is_some_and(|source| monitor_sources.iter().any(|monitor| monitor == source))
+            {
+                continue;
+            }
+            if let Some(pid) =
+                field("application.process.id =").and_then(|pid| pid.parse::<Pid>().ok())
+            {
+                let media_usage = media_usage_by_pid.entry(pid).or_default();
+                if *is_recording {
+                    media_usage.microphone = true;
+                } else {
+                    media_usage.audio = true;
+                }
+            }
+        }
+    }
+
+    media_usage_by_pid
+}
+
+/// Splits the output of `pactl list` into one block of lines per stream, each of which starts
+/// with an unindented header like `Sink Input #42`.
+#[cfg(target_os = "linux")]
+fn get_pactl_blocks(output: &str) -> Vec<Vec<&str>> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    for line in output.lines() {
+        if !line.is_empty() && !line.starts_with(char::is_whitespace) {
+            blocks.push(Vec::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.push(line);
+        }
+    }
+    blocks
+}
+
+#[cfg(target_os = "linux")]
+fn run_pactl(args: &[&str]) -> std::io::Result<String> {
+    // The output is translated, so ask for it in English to be able to read it.
+    let output = std::process::Command::new("pactl")
+        .args(args)
+        .env("LC_ALL", "C")
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(std::io::Error::other(format!(
+            "pactl exited with {}",
+            output.status
+        )))
+    }
+}
+
+/// Returns what media devices each process is using.
+#[cfg(target_os = "linux")]
+pub fn get_media_usage(pids: impl Iterator<Item = Pid>) -> HashMap<Pid, MediaUsage> {
+    let mut media_usage_by_pid = get_sound_server_media_usage();
+    for pid in pids {
+        let media_usage = get_linux_process_media_usage(pid);
+        if !media_usage.is_empty() {
+            media_usage_by_pid
+                .entry(pid)
+                .or_default()
+                .merge(media_usage);
+        }
+    }
+    media_usage_by_pid
+}
+
+/// Returns what media devices each process is using.
+#[cfg(not(target_os = "linux"))]
+pub fn get_media_usage(_pids: impl Iterator<Item = Pid>) -> HashMap<Pid, MediaUsage> {
+    HashMap::new()
+}
@@ -1,18 +1,9 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct MemHarvest {
     pub mem_total_in_mb: u64,
     pub mem_used_in_mb: u64,
 }
 
-impl Default for MemHarvest {
-    fn default() -> Self {
-        MemHarvest {
-            mem_total_in_mb: 0,
-            mem_used_in_mb: 0,
-        }
-    }
-}
-
 /// Meant for ARM use.
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 pub async fn arm_mem_data(
@@ -45,6 +36,36 @@ pub async fn arm_swap_data(
     }))
 }
 
+/// Reads memory usage straight from `/proc/meminfo`, only needing `MemTotal`.  WSL1 leaves out
+/// some of the fields that heim expects, which would otherwise leave the memory widget empty.
+#[cfg(target_os = "linux")]
+pub fn get_meminfo_mem_data() -> Option<MemHarvest> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let get_kb = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let (field, value) = line.split_once(':')?;
+            if field == name {
+                value.split_whitespace().next()?.parse::<u64>().ok()
+            } else {
+                None
+            }
+        })
+    };
+
+    let total_kb = get_kb("MemTotal")?;
+    let available_kb = get_kb("MemAvailable").unwrap_or_else(|| {
+        get_kb("MemFree").unwrap_or(0)
+            + get_kb("Buffers").unwrap_or(0)
+            + get_kb("Cached").unwrap_or(0)
+    });
+
+    // Matches heim, which uses megabytes of 1000 * 1000 bytes.
+    Some(MemHarvest {
+        mem_total_in_mb: total_kb * 1024 / 1_000_000,
+        mem_used_in_mb: total_kb.saturating_sub(available_kb) * 1024 / 1_000_000,
+    })
+}
+
 #[cfg(not(any(target_arch = "aarch64", target_arch = "arm")))]
 pub async fn non_arm_mem_data(
     actually_get: bool,
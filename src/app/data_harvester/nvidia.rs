@@ -0,0 +1,95 @@
+//! Per-process GPU memory usage of NVIDIA GPUs, using NVML.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use nvml_wrapper::{enums::device::UsedGpuMemory, Nvml};
+
+use crate::Pid;
+
+/// Holds on to NVML between collections, as initializing it is slow.  Results are cached so
+/// they can be refreshed less often than the rest of the process data.
+#[derive(Debug, Default)]
+pub struct GpuMemCollector {
+    nvml: Option<Nvml>,
+    has_tried_init: bool,
+    last_refresh_time: Option<Instant>,
+    gpu_mem_by_pid: HashMap<Pid, u64>,
+}
+
+impl GpuMemCollector {
+    /// Returns how many bytes of GPU memory each process uses across all GPUs.  This is only
+    /// refreshed if at least `refresh_rate` has passed since the last refresh.
+    pub fn get_gpu_mem_by_pid(&mut self, refresh_rate: Duration) -> &HashMap<Pid, u64> {
+        if !self.has_tried_init {
+            self.has_tried_init = true;
+            match Nvml::init() {
+                Ok(nvml) => self.nvml = Some(nvml),
+                Err(err) => debug!("Could not initialize NVML: {}", err),
+            }
+        }
+
+        if let Some(nvml) = &self.nvml {
+            if self
+                .last_refresh_time
+                .is_none_or(|last_refresh_time| last_refresh_time.elapsed() >= refresh_rate)
+            {
+                self.last_refresh_time = Some(Instant::now());
+                self.gpu_mem_by_pid = get_gpu_mem_by_pid(nvml);
+            }
+        }
+
+        &self.gpu_mem_by_pid
+    }
+
+    /// Forgets the last results, so the next call refreshes them no matter when they were from.
+    pub fn clear(&mut self) {
+        self.last_refresh_time = None;
+        self.gpu_mem_by_pid.clear();
+    }
+}
+
+fn get_gpu_mem_by_pid(nvml: &Nvml) -> HashMap<Pid, u64> {
+    let mut gpu_mem_by_pid = HashMap::new();
+
+    if let Ok(device_count) = nvml.device_count() {
+        for index in 0..device_count {
+            if let Ok(device) = nvml.device_by_index(index) {
+                // A process can be both a compute and a graphics process on the same GPU, but
+                // it's still the same memory, so only count it once per GPU.
+                let mut device_gpu_mem_by_pid = HashMap::new();
+                for process in device
+                    .running_compute_processes()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .chain(device.running_graphics_processes().unwrap_or_default())
+                {
+                    if let UsedGpuMemory::Used(bytes) = process.used_gpu_memory {
+                        device_gpu_mem_by_pid.insert(process.pid as Pid, bytes);
+                    }
+                }
+
+                for (pid, bytes) in device_gpu_mem_by_pid {
+                    *gpu_mem_by_pid.entry(pid).or_insert(0) += bytes;
+                }
+            }
+        }
+    }
+
+    gpu_mem_by_pid
+}
+
+/// Returns the names of the NVIDIA GPUs that NVML can see, if it can be initialized.
+pub fn get_gpu_names() -> Vec<String> {
+    match Nvml::init() {
+        Ok(nvml) => (0..nvml.device_count().unwrap_or_default())
+            .filter_map(|index| nvml.device_by_index(index).ok()?.name().ok())
+            .collect(),
+        Err(err) => {
+            debug!("Could not initialize NVML: {}", err);
+            Vec::new()
+        }
+    }
+}
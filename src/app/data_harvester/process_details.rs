@@ -0,0 +1,126 @@
+//! Details about a single process, which are only collected while they're shown, as they're more
+//! expensive to read than what the process widget needs.
+
+use crate::Pid;
+
+#[cfg(not(target_os = "linux"))]
+use sysinfo::{ProcessExt, System, SystemExt};
+
+/// Details about a process.  Anything that can't be read on this platform, or without more
+/// permissions, is [`None`].
+#[derive(Debug, Clone, Default)]
+pub struct ProcessDetails {
+    /// The full command line, with arguments separated by spaces.
+    pub command: String,
+    pub exe: Option<String>,
+    /// The working directory of the process.
+    pub cwd: Option<String>,
+    pub num_threads: Option<u64>,
+    pub num_fds: Option<u64>,
+    /// The resident set size, in bytes.
+    pub rss_bytes: Option<u64>,
+    /// The size of the virtual address space, in bytes.
+    pub virtual_bytes: Option<u64>,
+    /// How much of the resident set is shared with other processes, in bytes.
+    pub shared_bytes: Option<u64>,
+    /// How many bytes the process has read from disk since it started.
+    pub total_read_bytes: Option<u64>,
+    /// How many bytes the process has written to disk since it started.
+    pub total_write_bytes: Option<u64>,
+}
+
+/// Reads the details of the process with the given PID, or returns [`None`] if it's gone.
+#[cfg(target_os = "linux")]
+pub fn get_process_details(pid: Pid) -> Option<ProcessDetails> {
+    use std::fs;
+
+    let proc_path = std::path::PathBuf::from(format!("/proc/{}", pid));
+    let status = fs::read_to_string(proc_path.join("status")).ok()?;
+
+    let command = fs::read(proc_path.join("cmdline"))
+        .map(|cmdline| {
+            cmdline
+                .split(|byte| *byte == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(String::from_utf8_lossy)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    let read_link = |name: &str| {
+        fs::read_link(proc_path.join(name))
+            .ok()
+            .map(|path| path.to_string_lossy().to_string())
+    };
+    let num_threads = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|threads| threads.trim().parse::<u64>().ok());
+    let num_fds = fs::read_dir(proc_path.join("fd"))
+        .ok()
+        .map(|fds| fds.count() as u64);
+
+    // These are all in pages.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+    let statm = fs::read_to_string(proc_path.join("statm")).unwrap_or_default();
+    let statm_bytes = |index: usize| {
+        statm
+            .split_whitespace()
+            .nth(index)
+            .and_then(|pages| pages.parse::<u64>().ok())
+            .map(|pages| pages * page_size)
+    };
+
+    let io = fs::read_to_string(proc_path.join("io")).unwrap_or_default();
+    let io_bytes = |key: &str| {
+        io.lines()
+            .find_map(|line| line.strip_prefix(key))
+            .and_then(|bytes| bytes.trim().parse::<u64>().ok())
+    };
+
+    Some(ProcessDetails {
+        command,
+        exe: read_link("exe"),
+        cwd: read_link("cwd"),
+        num_threads,
+        num_fds,
+        rss_bytes: statm_bytes(1),
+        virtual_bytes: statm_bytes(0),
+        shared_bytes: statm_bytes(2),
+        total_read_bytes: io_bytes("read_bytes:"),
+        total_write_bytes: io_bytes("write_bytes:"),
+    })
+}
+
+/// Reads the details of the process with the given PID, or returns [`None`] if it's gone.
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_details(pid: Pid) -> Option<ProcessDetails> {
+    let mut sys = System::new();
+    if !sys.refresh_process(pid) {
+        return None;
+    }
+    let process = sys.get_process(pid)?;
+
+    let path_to_string = |path: &std::path::Path| {
+        if path.as_os_str().is_empty() {
+            None
+        } else {
+            Some(path.to_string_lossy().to_string())
+        }
+    };
+    let disk_usage = process.disk_usage();
+
+    Some(ProcessDetails {
+        command: process.cmd().join(" "),
+        exe: path_to_string(process.exe()),
+        cwd: path_to_string(process.cwd()),
+        num_threads: None,
+        num_fds: None,
+        // These are in kilobytes.
+        rss_bytes: Some(process.memory() * 1024),
+        virtual_bytes: Some(process.virtual_memory() * 1024),
+        shared_bytes: None,
+        total_read_bytes: Some(disk_usage.total_read_bytes),
+        total_write_bytes: Some(disk_usage.total_written_bytes),
+    })
+}
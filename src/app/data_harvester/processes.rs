@@ -1,12 +1,13 @@
+use super::{energy::EnergyImpact, media::MediaUsage};
 use crate::Pid;
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 use sysinfo::ProcessStatus;
 
 #[cfg(target_os = "linux")]
 use crate::utils::error::{self, BottomError};
 
 #[cfg(target_os = "linux")]
-use std::collections::{hash_map::RandomState, HashMap};
+use std::collections::{hash_map::RandomState, HashMap, HashSet};
 
 #[cfg(not(target_os = "linux"))]
 use sysinfo::{ProcessExt, ProcessorExt, System, SystemExt};
@@ -16,8 +17,9 @@ use sysinfo::{ProcessExt, ProcessorExt, System, SystemExt};
 const MAX_STAT_NAME_LEN: usize = 15;
 
 // TODO: Add value so we know if it's sorted ascending or descending by default?
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
 pub enum ProcessSorting {
+    #[default]
     CpuPercent,
     Mem,
     MemPercent,
@@ -30,6 +32,18 @@ pub enum ProcessSorting {
     TotalWrite,
     State,
     Count,
+    App,
+    GpuPercent,
+    GpuMem,
+    RtPriority,
+    Nice,
+    SecurityContext,
+    Namespaces,
+    StartTime,
+    RunTime,
+    Media,
+    Energy,
+    User,
 }
 
 impl std::fmt::Display for ProcessSorting {
@@ -51,14 +65,226 @@ impl std::fmt::Display for ProcessSorting {
                 Command => "Command",
                 Pid => "PID",
                 Count => "Count",
+                App => "App",
+                GpuPercent => "GPU%",
+                GpuMem => "GMem",
+                RtPriority => "RT",
+                Nice => "Nice",
+                SecurityContext => "Security",
+                Namespaces => "NS",
+                StartTime => "Started",
+                RunTime => "Time",
+                Media => "Media",
+                Energy => "Energy",
+                User => "User",
             }
         )
     }
 }
 
-impl Default for ProcessSorting {
-    fn default() -> Self {
-        ProcessSorting::CpuPercent
+impl ProcessSorting {
+    /// Returns the search prefix that matches what the column shows, if there is one.
+    pub fn get_query_prefix(&self) -> Option<&'static str> {
+        use ProcessSorting::*;
+        match self {
+            CpuPercent => Some("cpu"),
+            Mem => Some("memb"),
+            MemPercent => Some("mem"),
+            Pid => Some("pid"),
+            ProcessName => Some("name"),
+            Command => Some("cmd"),
+            ReadPerSecond => Some("read"),
+            WritePerSecond => Some("write"),
+            TotalRead => Some("tread"),
+            TotalWrite => Some("twrite"),
+            State => Some("state"),
+            GpuPercent => Some("gpu"),
+            GpuMem => Some("gmem"),
+            Nice => Some("nice"),
+            SecurityContext => Some("context"),
+            Namespaces => Some("ns"),
+            RunTime => Some("time"),
+            Media => Some("media"),
+            User => Some("user"),
+            Count | App | RtPriority | StartTime | Energy => None,
+        }
+    }
+}
+
+impl std::str::FromStr for ProcessSorting {
+    type Err = crate::utils::error::BottomError;
+
+    /// Parses a column name from the config.  Columns that take each other's place, like PID and
+    /// Count, share a name.
+    fn from_str(s: &str) -> crate::utils::error::Result<Self> {
+        use ProcessSorting::*;
+        match s.to_lowercase().as_str() {
+            "pid" | "count" => Ok(Pid),
+            "name" | "command" => Ok(ProcessName),
+            "cpu" | "cpu%" => Ok(CpuPercent),
+            "mem" | "mem%" => Ok(MemPercent),
+            "r/s" | "read" => Ok(ReadPerSecond),
+            "w/s" | "write" => Ok(WritePerSecond),
+            "t.read" | "total_read" => Ok(TotalRead),
+            "t.write" | "total_write" => Ok(TotalWrite),
+            "state" => Ok(State),
+            "app" => Ok(App),
+            "gpu%" | "gpu" => Ok(GpuPercent),
+            "gmem" | "gpu_mem" => Ok(GpuMem),
+            "rt" => Ok(RtPriority),
+            "nice" => Ok(Nice),
+            "security" => Ok(SecurityContext),
+            "ns" | "namespaces" => Ok(Namespaces),
+            "started" | "start_time" => Ok(StartTime),
+            "time" | "run_time" => Ok(RunTime),
+            "media" => Ok(Media),
+            "energy" | "power" => Ok(Energy),
+            "user" => Ok(User),
+            _ => Err(crate::utils::error::BottomError::ConfigError(format!(
+                "\"{}\" is an invalid process column, use \"<pid|name|cpu|mem|r/s|w/s|t.read|t.write|state|app|gpu%|gmem|rt|nice|security|ns|started|time|media|energy|user>\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where to look for a process' full name on Linux, as the name in /proc/<PID>/stat is cut off
+/// at 15 characters.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ProcessNameSource {
+    /// The executable that /proc/<PID>/exe links to, then the command line.
+    #[default]
+    Exe,
+    /// The first argument of the command line, then the executable.
+    Cmdline,
+    /// Always use the (possibly truncated) name from /proc/<PID>/stat.
+    Comm,
+}
+
+impl std::str::FromStr for ProcessNameSource {
+    type Err = crate::utils::error::BottomError;
+
+    fn from_str(s: &str) -> crate::utils::error::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "exe" => Ok(ProcessNameSource::Exe),
+            "cmdline" => Ok(ProcessNameSource::Cmdline),
+            "comm" => Ok(ProcessNameSource::Comm),
+            _ => Err(crate::utils::error::BottomError::ConfigError(format!(
+                "\"{}\" is an invalid process name source, use \"<exe|cmdline|comm>\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// The sections the process table is split into when sectioning processes by state.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum StateSection {
+    Running,
+    Sleeping,
+    Stopped,
+    Zombie,
+    Other,
+}
+
+impl StateSection {
+    pub fn from_state_char(state_char: char) -> Self {
+        match state_char {
+            'R' => StateSection::Running,
+            'S' | 'D' | 'I' => StateSection::Sleeping,
+            'T' | 't' => StateSection::Stopped,
+            'Z' => StateSection::Zombie,
+            _ => StateSection::Other,
+        }
+    }
+}
+
+impl std::fmt::Display for StateSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use StateSection::*;
+        write!(
+            f,
+            "{}",
+            match &self {
+                Running => "Running",
+                Sleeping => "Sleeping",
+                Stopped => "Stopped",
+                Zombie => "Zombie",
+                Other => "Other",
+            }
+        )
+    }
+}
+
+/// A real-time scheduling policy.  Processes using these always run before normal ones, so a
+/// busy one can starve everything else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RealtimePolicy {
+    Fifo,
+    RoundRobin,
+    Deadline,
+}
+
+impl std::fmt::Display for RealtimePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RealtimePolicy::Fifo => "FIFO",
+                RealtimePolicy::RoundRobin => "RR",
+                RealtimePolicy::Deadline => "DL",
+            }
+        )
+    }
+}
+
+/// Why a process looks out of place, as a quick aid when checking a system for anything unusual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuspiciousReason {
+    /// The executable of the process has been deleted since it started.
+    DeletedExe,
+    /// The executable of the process is in a temporary directory, like `/tmp` or `/dev/shm`.
+    TempExe,
+}
+
+impl std::fmt::Display for SuspiciousReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SuspiciousReason::DeletedExe => "deleted",
+                SuspiciousReason::TempExe => "temp",
+            }
+        )
+    }
+}
+
+/// Returns why a process with the given executable looks suspicious, if it does.
+fn get_suspicious_reason(
+    exe_path: &std::path::Path, is_exe_deleted: bool,
+) -> Option<SuspiciousReason> {
+    const TEMP_DIRS: [&str; 5] = [
+        "/tmp",
+        "/var/tmp",
+        "/dev/shm",
+        "/private/tmp",
+        "/private/var/tmp",
+    ];
+
+    if exe_path.as_os_str().is_empty() {
+        None
+    } else if is_exe_deleted {
+        Some(SuspiciousReason::DeletedExe)
+    } else if TEMP_DIRS
+        .iter()
+        .any(|temp_dir| exe_path.starts_with(temp_dir))
+        || (cfg!(target_os = "windows") && exe_path.starts_with(std::env::temp_dir()))
+    {
+        Some(SuspiciousReason::TempExe)
+    } else {
+        None
     }
 }
 
@@ -79,6 +305,56 @@ pub struct ProcessHarvest {
     pub total_write_bytes: u64,
     pub process_state: String,
     pub process_state_char: char,
+    pub uid: Option<u32>,
+    pub user: Option<String>,
+    pub num_connections: u64,
+    /// How many threads the process has.  Only collected on Linux.
+    pub num_threads: u64,
+    /// How many file descriptors the process has open.  Only collected on Linux, and only while
+    /// used.
+    pub num_fds: u64,
+    /// How long the process has been running for, in seconds.
+    pub run_time: u64,
+    /// When the process started, in seconds since the Unix epoch.
+    pub start_time: u64,
+    /// The Flatpak, Snap, or AppImage the process belongs to, if any.
+    pub app: Option<String>,
+    /// How much GPU memory the process uses, in bytes.
+    pub gpu_mem_bytes: u64,
+    /// How busy the process keeps the busiest GPU engine it uses.  Only collected on Linux.
+    pub gpu_usage_percent: f64,
+    /// The real-time scheduling policy of the process, if it has one.  Only collected on Linux.
+    pub rt_policy: Option<RealtimePolicy>,
+    /// The real-time priority of the process, from 1 to 99, or 0 if it doesn't have one.
+    pub rt_priority: u32,
+    /// The nice value of the process, from -20 (most favourable) to 19.  Only collected on Linux.
+    pub nice: i64,
+    /// The scheduling priority of the process as the kernel sees it, where lower is more
+    /// favourable.  Real-time processes go below 0.  Only collected on Linux.
+    pub priority: i64,
+    /// The SELinux context or AppArmor profile of the process, if it has one.  Only collected on
+    /// Linux.
+    pub security_context: Option<String>,
+    /// Why the process looks suspicious, if it does.
+    pub suspicious_reason: Option<SuspiciousReason>,
+    /// The path of the executable of the process, if it can be read.
+    pub exe: Option<String>,
+    /// Which of its namespaces the process doesn't share with the host, like `pid,net,mnt`, or
+    /// [`None`] if they can't be read.  Only collected on Linux.
+    pub namespaces: Option<String>,
+    /// The path of the cgroup of the process, like `/system.slice/docker-<ID>.scope`, or
+    /// [`None`] if it can't be read.  Only collected on Linux.
+    pub cgroup: Option<String>,
+    /// The environment of the process as `KEY=value` entries, or [`None`] if it can't be read.
+    /// Only collected on Linux, and only while used.
+    pub environ: Option<Arc<Vec<String>>>,
+    /// The titles of the windows of the process.  Only collected on Linux, and only while used.
+    pub window_titles: Vec<String>,
+    /// Whether the process is using the camera or microphone, or playing audio.  Only collected
+    /// on Linux, and only while used.
+    pub media_usage: MediaUsage,
+    /// How much the process drains the battery.  Only collected while used.
+    pub energy_impact: EnergyImpact,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -92,6 +368,15 @@ pub struct PrevProcDetails {
     pub proc_io_path: PathBuf,
     pub proc_cmdline_path: PathBuf,
     pub just_read: bool,
+    /// The start time of the process in clock ticks since boot, to tell if the PID was reused.
+    pub start_time: u64,
+    /// The sandboxed app of the process, if it's been looked up yet.
+    pub app: Option<Option<String>>,
+    /// The environment of the process, if it's been read yet.
+    pub environ: Option<Option<Arc<Vec<String>>>>,
+    /// The last GPU usage read from the fdinfo of the process, and when it was read.
+    #[cfg(target_os = "linux")]
+    pub drm_usage: Option<(std::time::Instant, DrmUsage)>,
 }
 
 impl PrevProcDetails {
@@ -107,6 +392,445 @@ impl PrevProcDetails {
     }
 }
 
+/// Caches user names by UID, so we don't have to look through the user database for every
+/// process on every update.
+#[derive(Debug, Default)]
+pub struct UserTable {
+    uid_user_mapping: std::collections::HashMap<u32, Option<String>>,
+}
+
+impl UserTable {
+    pub fn get_uid_to_username_mapping(&mut self, uid: u32) -> Option<String> {
+        self.uid_user_mapping
+            .entry(uid)
+            .or_insert_with(|| get_username(uid))
+            .clone()
+    }
+}
+
+/// Caches whether the executable of each process has been deleted, by PID and start time, so we
+/// don't have to check the file system for every process on every update.
+#[cfg(not(target_os = "linux"))]
+#[derive(Debug, Default)]
+pub struct ExeTable {
+    exe_deleted_mapping: std::collections::HashMap<(Pid, u64), bool>,
+}
+
+#[cfg(not(target_os = "linux"))]
+impl ExeTable {
+    fn is_exe_deleted(&mut self, pid: Pid, start_time: u64, exe_path: &std::path::Path) -> bool {
+        *self
+            .exe_deleted_mapping
+            .entry((pid, start_time))
+            .or_insert_with(|| {
+                // Only count a missing file, as we may just not be allowed to look at it.
+                !exe_path.as_os_str().is_empty()
+                    && matches!(
+                        std::fs::metadata(exe_path),
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound
+                    )
+            })
+    }
+}
+
+/// Caches the display names of sandboxed apps by their ID, so we don't have to read their
+/// desktop entries for every process on every update.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default)]
+pub struct AppTable {
+    app_name_mapping: HashMap<(AppSource, String), String>,
+}
+
+#[cfg(target_os = "linux")]
+impl AppTable {
+    fn get_app_name(&mut self, source: AppSource, app_id: &str) -> String {
+        self.app_name_mapping
+            .entry((source, app_id.to_string()))
+            .or_insert_with(|| {
+                get_desktop_entry_name(source, app_id).unwrap_or_else(|| app_id.to_string())
+            })
+            .clone()
+    }
+}
+
+/// How an app was packaged or started.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum AppSource {
+    Flatpak,
+    Snap,
+    AppImage,
+    /// Launched from a desktop entry by the desktop environment.
+    Desktop,
+}
+
+/// Returns the name in the desktop entry of an app, which is what desktops show to users.
+#[cfg(target_os = "linux")]
+fn get_desktop_entry_name(source: AppSource, app_id: &str) -> Option<String> {
+    let desktop_entry_paths = match source {
+        AppSource::Flatpak => {
+            let mut export_dirs =
+                vec![PathBuf::from("/var/lib/flatpak/exports/share/applications")];
+            if let Some(home_dir) = dirs_next::home_dir() {
+                export_dirs.push(home_dir.join(".local/share/flatpak/exports/share/applications"));
+            }
+            export_dirs
+                .into_iter()
+                .map(|dir| dir.join(format!("{}.desktop", app_id)))
+                .collect()
+        }
+        AppSource::Snap => {
+            // Entries are named "<snap>_<app>.desktop", and the main app usually shares the snap's name.
+            vec![PathBuf::from(format!(
+                "/var/lib/snapd/desktop/applications/{}_{}.desktop",
+                app_id, app_id
+            ))]
+        }
+        AppSource::AppImage => Vec::new(),
+        AppSource::Desktop => {
+            let mut data_dirs = vec![dirs_next::data_dir().unwrap_or_default()];
+            data_dirs.extend(std::env::split_paths(
+                &std::env::var_os("XDG_DATA_DIRS")
+                    .unwrap_or_else(|| "/usr/local/share:/usr/share".into()),
+            ));
+            data_dirs
+                .into_iter()
+                .map(|dir| dir.join("applications").join(format!("{}.desktop", app_id)))
+                .collect()
+        }
+    };
+
+    desktop_entry_paths.iter().find_map(|path| {
+        let desktop_entry = std::fs::read_to_string(path).ok()?;
+        get_ini_value(&desktop_entry, "Desktop Entry", "Name")
+    })
+}
+
+/// Reads a value out of a section of an INI-like file, such as a desktop entry.
+#[cfg(target_os = "linux")]
+fn get_ini_value(contents: &str, section: &str, key: &str) -> Option<String> {
+    let section_header = format!("[{}]", section);
+    contents
+        .lines()
+        .skip_while(|line| line.trim() != section_header)
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| {
+            let (line_key, value) = line.split_once('=')?;
+            if line_key.trim() == key {
+                Some(value.trim().to_string())
+            } else {
+                None
+            }
+        })
+}
+
+/// Parses the launcher and app ID out of a systemd unit for a desktop app, which are named like
+/// `app[-<launcher>]-<app ID>-<random>.scope` or `app[-<launcher>]-<app ID>[@<random>].service`.
+/// Dashes inside of the launcher and app ID are escaped as `\x2d`.
+#[cfg(target_os = "linux")]
+fn parse_app_unit(unit: &str) -> Option<(Option<String>, String)> {
+    let unit = unit.strip_prefix("app-")?;
+    let mut parts = if let Some(scope) = unit.strip_suffix(".scope") {
+        // The last part is random, to tell apart multiple instances of the app.
+        let (name, _random) = scope.rsplit_once('-')?;
+        name.split('-').collect::<Vec<_>>()
+    } else if let Some(service) = unit.strip_suffix(".service") {
+        let name = service.split('@').next()?;
+        name.split('-').collect::<Vec<_>>()
+    } else {
+        return None;
+    };
+
+    let app_id = parts.pop().filter(|app_id| !app_id.is_empty())?;
+    let launcher = match parts.as_slice() {
+        [] => None,
+        [launcher] => Some(launcher.replace("\\x2d", "-")),
+        _ => return None,
+    };
+
+    Some((launcher, app_id.replace("\\x2d", "-")))
+}
+
+/// Returns the SELinux context or AppArmor profile of a process, such as
+/// `system_u:system_r:httpd_t:s0` or `/usr/sbin/cupsd (enforce)`.  Returns [`None`] if no security
+/// module labels processes.
+#[cfg(target_os = "linux")]
+fn get_linux_security_context(pid: Pid) -> Option<String> {
+    // With more than one security module loaded, `attr/current` is only for one of them, so try
+    // the AppArmor specific one too.
+    ["attr/current", "attr/apparmor/current"]
+        .iter()
+        .filter_map(|attr| std::fs::read_to_string(format!("/proc/{}/{}", pid, attr)).ok())
+        .map(|context| context.trim_end_matches(['\0', '\n']).to_string())
+        .find(|context| !context.is_empty())
+}
+
+/// The namespaces checked for whether a process is isolated from the host, like in a container.
+#[cfg(target_os = "linux")]
+const LINUX_NAMESPACES: [&str; 3] = ["pid", "net", "mnt"];
+
+/// Returns the namespaces of a process (or `self`), in the same order as [`LINUX_NAMESPACES`].
+/// Reading these for other users' processes requires root.
+#[cfg(target_os = "linux")]
+fn get_linux_namespace_ids(pid: &str) -> Vec<Option<PathBuf>> {
+    LINUX_NAMESPACES
+        .iter()
+        .map(|namespace| std::fs::read_link(format!("/proc/{}/ns/{}", pid, namespace)).ok())
+        .collect()
+}
+
+/// Returns the namespaces of the host, which are those of init.  If those can't be read, ours are
+/// used instead, which are the same unless bottom itself is running in a container.
+#[cfg(target_os = "linux")]
+fn get_linux_host_namespace_ids() -> Vec<Option<PathBuf>> {
+    let init_namespace_ids = get_linux_namespace_ids("1");
+    if init_namespace_ids.iter().all(Option::is_some) {
+        init_namespace_ids
+    } else {
+        get_linux_namespace_ids("self")
+    }
+}
+
+/// Returns which namespaces a process doesn't share with the host, like `pid,net`.
+#[cfg(target_os = "linux")]
+fn get_linux_process_namespaces(
+    pid: Pid, host_namespace_ids: &[Option<PathBuf>],
+) -> Option<String> {
+    let namespace_ids = get_linux_namespace_ids(&pid.to_string());
+    if namespace_ids.iter().all(Option::is_none) {
+        return None;
+    }
+
+    Some(
+        LINUX_NAMESPACES
+            .iter()
+            .zip(namespace_ids.iter().zip(host_namespace_ids))
+            .filter_map(|(namespace, ids)| match ids {
+                (Some(namespace_id), Some(host_namespace_id))
+                    if namespace_id != host_namespace_id =>
+                {
+                    Some(*namespace)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+/// Returns the path of the cgroup a process is in.  With cgroup v2 there's only the one; with v1
+/// the first hierarchy that puts the process somewhere other than the root is used.
+#[cfg(target_os = "linux")]
+fn get_linux_process_cgroup(pid: Pid) -> Option<String> {
+    let cgroups = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    let paths = cgroups
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2).map(|path| (line, path)));
+
+    let mut fallback = None;
+    for (line, path) in paths {
+        if line.starts_with("0::") {
+            return Some(path.to_string());
+        } else if fallback.is_none() && path != "/" {
+            fallback = Some(path.to_string());
+        }
+    }
+    fallback.or_else(|| Some("/".to_string()))
+}
+
+/// Returns the environment a process was started with, as `KEY=value` entries.  This can fail
+/// if permission is denied, which is the case for processes of other users.
+#[cfg(target_os = "linux")]
+fn get_linux_process_environ(pid: Pid) -> Option<Arc<Vec<String>>> {
+    let environ = std::fs::read(format!("/proc/{}/environ", pid)).ok()?;
+    Some(Arc::new(
+        environ
+            .split(|byte| *byte == 0)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| String::from_utf8_lossy(entry).into_owned())
+            .collect(),
+    ))
+}
+
+/// Finds the app a process belongs to, if any: a Flatpak, Snap, AppImage, or an app started by
+/// the desktop.
+#[cfg(target_os = "linux")]
+fn get_linux_process_app_id(pid: Pid) -> Option<(AppSource, String)> {
+    // systemd puts apps in units named after them, like
+    // "app-flatpak-org.mozilla.firefox-1234.scope", "app-gnome-firefox-1234.scope", or
+    // "snap.firefox.firefox-<UUID>.scope".  Everything started by the app stays in its unit.
+    let mut desktop_app_id = None;
+    if let Ok(cgroups) = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)) {
+        for unit in cgroups.lines().filter_map(|line| line.rsplit('/').next()) {
+            if let Some((launcher, app_id)) = parse_app_unit(unit) {
+                if launcher.as_deref() == Some("flatpak") {
+                    return Some((AppSource::Flatpak, app_id));
+                }
+                desktop_app_id = Some(app_id);
+            } else if let Some(snap_unit) = unit.strip_prefix("snap.") {
+                if let Some(snap_name) = snap_unit.split('.').next() {
+                    return Some((AppSource::Snap, snap_name.to_string()));
+                }
+            }
+        }
+    }
+
+    // Without systemd, Flatpaks still have their metadata at the root of their sandbox.
+    if let Ok(flatpak_info) = std::fs::read_to_string(format!("/proc/{}/root/.flatpak-info", pid)) {
+        if let Some(app_id) = get_ini_value(&flatpak_info, "Application", "name") {
+            return Some((AppSource::Flatpak, app_id));
+        }
+    }
+
+    // AppImages pass the path of the image to everything they run.  This can fail if permission
+    // is denied!
+    if let Ok(environ) = std::fs::read(format!("/proc/{}/environ", pid)) {
+        if let Some(image_path) = environ
+            .split(|byte| *byte == 0)
+            .find_map(|variable| variable.strip_prefix(b"APPIMAGE="))
+        {
+            let image_path = PathBuf::from(String::from_utf8_lossy(image_path).into_owned());
+            if let Some(image_name) = image_path.file_stem() {
+                return Some((
+                    AppSource::AppImage,
+                    image_name.to_string_lossy().into_owned(),
+                ));
+            }
+        }
+    }
+
+    // Only fall back to the desktop's unit last, as a sandboxed app may have been started by the
+    // desktop too.
+    desktop_app_id.map(|app_id| (AppSource::Desktop, app_id))
+}
+
+/// Returns the name of the app bundle an executable is in, if any.  Helpers are often nested in
+/// the bundles of their app, so this uses the outermost bundle.
+#[cfg(target_os = "macos")]
+fn get_bundle_app_name(exe_path: &std::path::Path) -> Option<String> {
+    exe_path.components().find_map(|component| {
+        component
+            .as_os_str()
+            .to_str()
+            .and_then(|component| component.strip_suffix(".app"))
+            .map(|app_name| app_name.to_string())
+    })
+}
+
+#[cfg(target_family = "unix")]
+fn get_username(uid: u32) -> Option<String> {
+    let mut passwd = std::mem::MaybeUninit::<libc::passwd>::uninit();
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    let mut result = std::ptr::null_mut();
+
+    let rc = unsafe {
+        libc::getpwuid_r(
+            uid,
+            passwd.as_mut_ptr(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        )
+    };
+
+    if rc == 0 && !result.is_null() {
+        let name = unsafe { std::ffi::CStr::from_ptr((*result).pw_name) };
+        Some(name.to_string_lossy().into_owned())
+    } else {
+        None
+    }
+}
+
+#[cfg(not(target_family = "unix"))]
+fn get_username(_uid: u32) -> Option<String> {
+    None
+}
+
+/// Returns the CPUs a process may run on, as the kernel lists them (like "0-3,8") and as
+/// indices.
+#[cfg(target_os = "linux")]
+pub fn get_process_cpu_affinity(pid: Pid) -> Option<(String, Vec<usize>)> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let list = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))?
+        .trim();
+
+    // Don't trust the list to only have CPUs that exist, so a bogus range can't be huge.
+    let num_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) }.max(1) as usize;
+    let mut cpus = Vec::new();
+    for range in list.split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let start = start.parse::<usize>().ok()?;
+                let end = end.parse::<usize>().ok()?.min(num_cpus - 1);
+                // A reversed range, or one starting past the last CPU, is just empty.
+                cpus.extend(start..=end);
+            }
+            None => {
+                let cpu = range.parse::<usize>().ok()?;
+                if cpu < num_cpus {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    Some((list.to_string(), cpus))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_cpu_affinity(_pid: Pid) -> Option<(String, Vec<usize>)> {
+    None
+}
+
+/// Finds the processes that have a file open on the filesystem mounted at a mount point, or
+/// whose working directory is on it, like `fuser -m` does.  Processes we aren't permitted to
+/// look into, like those of other users, are skipped.
+///
+/// Only the links to open files are read, and what they point to is never looked at, as that
+/// can hang on a stale NFS or FUSE mount.  A file is on the mount if its path is under the
+/// mount point, and not under another mount point inside of it.
+#[cfg(target_os = "linux")]
+pub fn get_pids_using_mount(mount_point: &str) -> Option<Vec<Pid>> {
+    use std::path::Path;
+
+    let mount_points = super::disks::get_mount_points()?;
+    let mount_point = Path::new(mount_point);
+    let is_on_mount = |path: PathBuf| {
+        std::fs::read_link(path).is_ok_and(|target| {
+            mount_points
+                .iter()
+                .map(Path::new)
+                .filter(|candidate| target.starts_with(candidate))
+                .max_by_key(|candidate| candidate.as_os_str().len())
+                .is_some_and(|deepest| deepest == mount_point)
+        })
+    };
+
+    let mut pids = Vec::new();
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let pid = match entry.file_name().to_str().map(str::parse::<Pid>) {
+            Some(Ok(pid)) => pid,
+            _ => continue,
+        };
+        let path = entry.path();
+
+        let has_open_file = std::fs::read_dir(path.join("fd"))
+            .map(|fds| fds.flatten().any(|fd| is_on_mount(fd.path())))
+            .unwrap_or(false);
+        if has_open_file || is_on_mount(path.join("cwd")) {
+            pids.push(pid);
+        }
+    }
+    Some(pids)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_pids_using_mount(_mount_point: &str) -> Option<Vec<Pid>> {
+    None
+}
+
 #[cfg(target_os = "linux")]
 fn cpu_usage_calculation(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
@@ -117,17 +841,16 @@ fn cpu_usage_calculation(
     path.push("stat");
 
     let stat_results = std::fs::read_to_string(path)?;
-    let first_line: &str;
 
     let split_results = stat_results.split('\n').collect::<Vec<&str>>();
-    if split_results.is_empty() {
+    let first_line: &str = if split_results.is_empty() {
         return Err(error::BottomError::InvalidIO(format!(
             "Unable to properly split the stat results; saw {} values, expected at least 1 value.",
             split_results.len()
         )));
     } else {
-        first_line = split_results[0];
-    }
+        split_results[0]
+    };
 
     let val = first_line.split_whitespace().collect::<Vec<&str>>();
 
@@ -178,7 +901,7 @@ fn cpu_usage_calculation(
 
 #[cfg(target_os = "linux")]
 fn get_process_io(path: &PathBuf) -> std::io::Result<String> {
-    Ok(std::fs::read_to_string(path)?)
+    std::fs::read_to_string(path)
 }
 
 #[cfg(target_os = "linux")]
@@ -199,9 +922,68 @@ fn get_linux_process_vsize_rss(stat: &[&str]) -> (u64, u64) {
     )
 }
 
+/// Returns how long a process has been running for, and when it started in seconds since the Unix
+/// epoch.
+#[cfg(target_os = "linux")]
+fn get_linux_process_run_and_start_time(
+    stat: &[&str], uptime_secs: f64, boot_time: u64,
+) -> (u64, u64) {
+    // The start time is in clock ticks since boot.
+    let start_time_ticks = stat[19].parse::<u64>().unwrap_or(0);
+    let clock_ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if clock_ticks_per_sec > 0 {
+        let start_time_secs = start_time_ticks as f64 / clock_ticks_per_sec as f64;
+        (
+            (uptime_secs - start_time_secs).max(0.0) as u64,
+            boot_time + start_time_secs as u64,
+        )
+    } else {
+        (0, 0)
+    }
+}
+
+/// Returns the real-time scheduling policy and priority of a process, if it has one.
+#[cfg(target_os = "linux")]
+fn get_linux_process_rt_scheduling(stat: &[&str]) -> (Option<RealtimePolicy>, u32) {
+    let rt_priority = stat
+        .get(37)
+        .and_then(|val| val.parse::<u32>().ok())
+        .unwrap_or(0);
+    let rt_policy = match stat.get(38).and_then(|val| val.parse::<i32>().ok()) {
+        Some(libc::SCHED_FIFO) => Some(RealtimePolicy::Fifo),
+        Some(libc::SCHED_RR) => Some(RealtimePolicy::RoundRobin),
+        // Not in libc yet; see sched(7).
+        Some(6) => Some(RealtimePolicy::Deadline),
+        _ => None,
+    };
+
+    (rt_policy, rt_priority)
+}
+
+/// Returns how long the system has been running for, in seconds.
+#[cfg(target_os = "linux")]
+fn get_linux_uptime() -> error::Result<f64> {
+    let uptime = read_path_contents(&PathBuf::from("/proc/uptime"))?;
+    uptime
+        .split_whitespace()
+        .next()
+        .and_then(|uptime| uptime.parse::<f64>().ok())
+        .ok_or(BottomError::MinorError)
+}
+
+/// Returns when the system booted, in seconds since the Unix epoch.
+#[cfg(target_os = "linux")]
+fn get_linux_boot_time() -> error::Result<u64> {
+    let stat = read_path_contents(&PathBuf::from("/proc/stat"))?;
+    stat.lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|boot_time| boot_time.trim().parse::<u64>().ok())
+        .ok_or(BottomError::MinorError)
+}
+
 #[cfg(target_os = "linux")]
 fn read_path_contents(path: &PathBuf) -> std::io::Result<String> {
-    Ok(std::fs::read_to_string(path)?)
+    std::fs::read_to_string(path)
 }
 
 #[cfg(target_os = "linux")]
@@ -229,7 +1011,7 @@ fn get_linux_cpu_usage(
     }
 
     // Based heavily on https://stackoverflow.com/a/23376195 and https://stackoverflow.com/a/1424556
-    let new_proc_val = get_process_cpu_stats(&proc_stats);
+    let new_proc_val = get_process_cpu_stats(proc_stats);
 
     if cpu_usage == 0.0 {
         Ok(0_f64)
@@ -244,12 +1026,52 @@ fn get_linux_cpu_usage(
     }
 }
 
+/// Tries to find the full name of a process whose /proc/<PID>/stat name was cut off, using the
+/// sources in the order given by `name_source`.  A candidate is only accepted if it starts with the
+/// truncated name, so processes that rewrite their command line don't get a misleading name.
+#[cfg(target_os = "linux")]
+fn resolve_linux_process_name(
+    truncated_name: &str, cmdline: &str, exe_path: &std::path::Path, name_source: ProcessNameSource,
+) -> Option<String> {
+    let from_exe = || {
+        std::fs::read_link(exe_path).ok().and_then(|path| {
+            path.file_name().map(|file_name| {
+                let file_name = file_name.to_string_lossy();
+                file_name
+                    .strip_suffix(" (deleted)")
+                    .unwrap_or(&file_name)
+                    .to_string()
+            })
+        })
+    };
+    let from_cmdline = || {
+        // Only look at the first argument, without the path; the rest is what the command is for.
+        cmdline
+            .split('\0')
+            .next()
+            .and_then(|first_arg| first_arg.rsplit('/').next())
+            .map(|name| name.to_string())
+    };
+    let is_full_name = |name: &String| name.starts_with(truncated_name);
+
+    match name_source {
+        ProcessNameSource::Exe => from_exe()
+            .filter(is_full_name)
+            .or_else(|| from_cmdline().filter(is_full_name)),
+        ProcessNameSource::Cmdline => from_cmdline()
+            .filter(is_full_name)
+            .or_else(|| from_exe().filter(is_full_name)),
+        ProcessNameSource::Comm => None,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 #[cfg(target_os = "linux")]
 fn read_proc<S: core::hash::BuildHasher>(
     pid: Pid, cpu_usage: f64, cpu_fraction: f64,
     pid_mapping: &mut HashMap<Pid, PrevProcDetails, S>, use_current_cpu_total: bool,
-    time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64,
+    time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64, user_table: &mut UserTable,
+    uptime_secs: f64, boot_time: u64, name_source: ProcessNameSource,
 ) -> error::Result<ProcessHarvest> {
     let pid_stat = pid_mapping
         .entry(pid)
@@ -273,30 +1095,24 @@ fn read_proc<S: core::hash::BuildHasher>(
         if trimmed_cmd.is_empty() {
             (format!("[{}]", truncated_name), truncated_name)
         } else {
+            let name = if truncated_name.len() >= MAX_STAT_NAME_LEN {
+                resolve_linux_process_name(
+                    &truncated_name,
+                    trimmed_cmd,
+                    &pid_stat.proc_exe_path,
+                    name_source,
+                )
+                .unwrap_or(truncated_name)
+            } else {
+                truncated_name
+            };
+
             // We split by spaces and null terminators.
             let separated_strings = trimmed_cmd
-                .split_terminator(|c| c == '\0' || c == ' ')
+                .split_terminator(['\0', ' '])
                 .collect::<Vec<&str>>();
 
-            (
-                separated_strings.join(" "),
-                if truncated_name.len() >= MAX_STAT_NAME_LEN {
-                    if let Some(first_part) = separated_strings.first() {
-                        // We're only interested in the executable part... not the file path.
-                        // That's for command.
-                        first_part
-                            .split('/')
-                            .collect::<Vec<_>>()
-                            .last()
-                            .unwrap_or(&truncated_name.as_str())
-                            .to_string()
-                    } else {
-                        truncated_name
-                    }
-                } else {
-                    truncated_name
-                },
-            )
+            (separated_strings.join(" "), name)
         }
     };
     let stat = stat_results
@@ -315,7 +1131,47 @@ fn read_proc<S: core::hash::BuildHasher>(
         use_current_cpu_total,
     )?;
     let parent_pid = stat[1].parse::<Pid>().ok();
+    // The kernel marks executables that have been deleted (or replaced) by adding " (deleted)".
+    let (exe, suspicious_reason) = match std::fs::read_link(&pid_stat.proc_exe_path) {
+        Ok(exe_path) => {
+            let exe_path = exe_path.to_string_lossy();
+            match exe_path.strip_suffix(" (deleted)") {
+                Some(exe_path) => (
+                    Some(exe_path.to_string()),
+                    get_suspicious_reason(std::path::Path::new(exe_path), true),
+                ),
+                None => (
+                    Some(exe_path.to_string()),
+                    get_suspicious_reason(std::path::Path::new(exe_path.as_ref()), false),
+                ),
+            }
+        }
+        Err(_) => (None, None),
+    };
     let (_vsize, rss) = get_linux_process_vsize_rss(&stat);
+    let (run_time, start_time) =
+        get_linux_process_run_and_start_time(&stat, uptime_secs, boot_time);
+    let (rt_policy, rt_priority) = get_linux_process_rt_scheduling(&stat);
+    let priority = stat
+        .get(15)
+        .and_then(|val| val.parse::<i64>().ok())
+        .unwrap_or(0);
+    let nice = stat
+        .get(16)
+        .and_then(|val| val.parse::<i64>().ok())
+        .unwrap_or(0);
+    let num_threads = stat
+        .get(17)
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(0);
+    let start_time_ticks = stat[19].parse::<u64>().unwrap_or(0);
+    if start_time_ticks != pid_stat.start_time {
+        // This is a new process with the same PID, so forget what we knew about the old one.
+        pid_stat.start_time = start_time_ticks;
+        pid_stat.app = None;
+        pid_stat.environ = None;
+        pid_stat.drm_usage = None;
+    }
     let mem_usage_kb = rss * page_file_kb;
     let mem_usage_percent = mem_usage_kb as f64 / mem_total_kb as f64 * 100.0;
     let mem_usage_bytes = mem_usage_kb * 1024;
@@ -326,17 +1182,14 @@ fn read_proc<S: core::hash::BuildHasher>(
             let io_stats = io_results.split_whitespace().collect::<Vec<&str>>();
 
             let (total_read_bytes, total_write_bytes) = get_linux_process_io_usage(&io_stats);
-            let read_bytes_per_sec = if time_difference_in_secs == 0 {
-                0
-            } else {
-                total_read_bytes.saturating_sub(pid_stat.total_read_bytes) / time_difference_in_secs
-            };
-            let write_bytes_per_sec = if time_difference_in_secs == 0 {
-                0
-            } else {
-                total_write_bytes.saturating_sub(pid_stat.total_write_bytes)
-                    / time_difference_in_secs
-            };
+            let read_bytes_per_sec = total_read_bytes
+                .saturating_sub(pid_stat.total_read_bytes)
+                .checked_div(time_difference_in_secs)
+                .unwrap_or(0);
+            let write_bytes_per_sec = total_write_bytes
+                .saturating_sub(pid_stat.total_write_bytes)
+                .checked_div(time_difference_in_secs)
+                .unwrap_or(0);
 
             pid_stat.total_read_bytes = total_read_bytes;
             pid_stat.total_write_bytes = total_write_bytes;
@@ -351,6 +1204,17 @@ fn read_proc<S: core::hash::BuildHasher>(
             (0, 0, 0, 0)
         };
 
+    // The owner of the /proc/<PID> directory is the process' effective UID.
+    let uid = pid_stat
+        .proc_stat_path
+        .parent()
+        .and_then(|proc_path| std::fs::metadata(proc_path).ok())
+        .map(|metadata| {
+            use std::os::unix::fs::MetadataExt;
+            metadata.uid()
+        });
+    let user = uid.and_then(|uid| user_table.get_uid_to_username_mapping(uid));
+
     Ok(ProcessHarvest {
         pid,
         parent_pid,
@@ -365,18 +1229,279 @@ fn read_proc<S: core::hash::BuildHasher>(
         write_bytes_per_sec,
         process_state,
         process_state_char,
+        uid,
+        user,
+        num_connections: 0,
+        num_threads,
+        num_fds: 0,
+        run_time,
+        start_time,
+        app: None,
+        gpu_mem_bytes: 0,
+        gpu_usage_percent: 0.0,
+        rt_policy,
+        rt_priority,
+        nice,
+        priority,
+        security_context: None,
+        suspicious_reason,
+        exe,
+        namespaces: None,
+        cgroup: None,
+        environ: None,
+        window_titles: Vec::new(),
+        media_usage: MediaUsage::default(),
+        energy_impact: EnergyImpact::default(),
     })
 }
 
+/// Returns the inodes of all TCP and UDP sockets, so they can be matched against the file
+/// descriptors of a process.
+#[cfg(target_os = "linux")]
+fn get_socket_inodes() -> HashSet<u64> {
+    let mut socket_inodes = HashSet::new();
+
+    for path in &[
+        "/proc/net/tcp",
+        "/proc/net/tcp6",
+        "/proc/net/udp",
+        "/proc/net/udp6",
+    ] {
+        if let Ok(contents) = read_path_contents(&PathBuf::from(path)) {
+            for line in contents.lines().skip(1) {
+                if let Some(inode) = line
+                    .split_whitespace()
+                    .nth(9)
+                    .and_then(|inode| inode.parse::<u64>().ok())
+                {
+                    if inode != 0 {
+                        socket_inodes.insert(inode);
+                    }
+                }
+            }
+        }
+    }
+
+    socket_inodes
+}
+
+/// Counts the open file descriptors of a process.  This can fail if permission is denied, in
+/// which case we just report none.
+#[cfg(target_os = "linux")]
+fn get_num_fds(pid: Pid) -> u64 {
+    std::fs::read_dir(format!("/proc/{}/fd", pid))
+        .map(|fds| fds.count() as u64)
+        .unwrap_or(0)
+}
+
+/// Counts how many of the file descriptors of a process are network sockets.  This can fail if
+/// permission is denied, in which case we just report none.
+#[cfg(target_os = "linux")]
+fn get_num_connections(pid: Pid, socket_inodes: &HashSet<u64>) -> u64 {
+    if let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) {
+        fds.filter_map(|fd| std::fs::read_link(fd.ok()?.path()).ok())
+            .filter(|link| {
+                link.to_str()
+                    .and_then(|link| link.strip_prefix("socket:["))
+                    .and_then(|link| link.strip_suffix(']'))
+                    .and_then(|inode| inode.parse::<u64>().ok())
+                    .is_some_and(|inode| socket_inodes.contains(&inode))
+            })
+            .count() as u64
+    } else {
+        0
+    }
+}
+
+/// The GPU usage of a process, from the DRM entries in the fdinfo of its GPU file descriptors.
+/// Kernels older than 5.19 don't have these.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default, Clone)]
+pub struct DrmUsage {
+    /// How long each engine has been busy for in nanoseconds, keyed by DRM client and engine.
+    engine_times: HashMap<(String, String), u64>,
+    /// How many cycles each engine has been busy for, and how many cycles have passed in total,
+    /// keyed by DRM client and engine.  Used by drivers that don't report times, like xe.
+    engine_cycles: HashMap<(String, String), (u64, u64)>,
+    /// How many engines of each kind there are, if there's more than one.
+    engine_capacities: HashMap<String, u64>,
+    vram_bytes: u64,
+}
+
+/// Reads the GPU usage of a process from its DRM file descriptors, returning [`None`] if it
+/// doesn't have any, or if permission is denied.
+#[cfg(target_os = "linux")]
+fn get_linux_drm_usage(pid: Pid) -> Option<DrmUsage> {
+    /// Parses values like `1234 ns` or `1234 KiB` from fdinfo.
+    fn parse_drm_value(value: &str) -> Option<u64> {
+        let mut parts = value.split_whitespace();
+        let value = parts.next()?.parse::<u64>().ok()?;
+        let multiplier = match parts.next() {
+            Some("KiB") => 1024,
+            Some("MiB") => 1024 * 1024,
+            Some("GiB") => 1024 * 1024 * 1024,
+            _ => 1,
+        };
+
+        Some(value * multiplier)
+    }
+
+    let mut drm_usage = DrmUsage::default();
+    let mut seen_clients = HashSet::new();
+
+    for fd in std::fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()?
+        .flatten()
+    {
+        if !std::fs::read_link(fd.path()).is_ok_and(|link| link.starts_with("/dev/dri/")) {
+            continue;
+        }
+
+        let fdinfo = match std::fs::read_to_string(format!(
+            "/proc/{}/fdinfo/{}",
+            pid,
+            fd.file_name().to_string_lossy()
+        )) {
+            Ok(fdinfo) => fdinfo,
+            Err(_) => continue,
+        };
+        let fields = fdinfo
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect::<HashMap<_, _>>();
+
+        // The same client can be open through more than one file descriptor, so only count each
+        // one once.
+        let client = match fields.get("drm-client-id") {
+            Some(client_id) => format!("{}/{}", fields.get("drm-pdev").unwrap_or(&""), client_id),
+            None => continue,
+        };
+        if !seen_clients.insert(client.clone()) {
+            continue;
+        }
+
+        let mut resident_vram_bytes = None;
+        for (key, value) in &fields {
+            if let Some(engine) = key.strip_prefix("drm-engine-capacity-") {
+                if let Some(capacity) = parse_drm_value(value) {
+                    drm_usage
+                        .engine_capacities
+                        .insert(engine.to_string(), capacity);
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+                if let Some(time) = parse_drm_value(value) {
+                    drm_usage
+                        .engine_times
+                        .insert((client.clone(), engine.to_string()), time);
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-cycles-") {
+                if let (Some(cycles), Some(total_cycles)) = (
+                    parse_drm_value(value),
+                    fields
+                        .get(format!("drm-total-cycles-{}", engine).as_str())
+                        .and_then(|total_cycles| parse_drm_value(total_cycles)),
+                ) {
+                    drm_usage
+                        .engine_cycles
+                        .insert((client.clone(), engine.to_string()), (cycles, total_cycles));
+                }
+            } else if key.starts_with("drm-resident-vram") || key.starts_with("drm-resident-local")
+            {
+                if let Some(bytes) = parse_drm_value(value) {
+                    *resident_vram_bytes.get_or_insert(0) += bytes;
+                }
+            }
+        }
+
+        // Older kernels only have drm-memory-vram, newer ones have both it and drm-resident-vram0.
+        drm_usage.vram_bytes += resident_vram_bytes
+            .or_else(|| {
+                fields
+                    .get("drm-memory-vram")
+                    .and_then(|value| parse_drm_value(value))
+            })
+            .unwrap_or(0);
+    }
+
+    if seen_clients.is_empty() {
+        None
+    } else {
+        Some(drm_usage)
+    }
+}
+
+/// Returns how busy the busiest engine used by a process was between two readings of its DRM
+/// usage, as a percentage.
+#[cfg(target_os = "linux")]
+fn get_linux_gpu_usage_percent(
+    prev_drm_usage: &DrmUsage, drm_usage: &DrmUsage, time_difference: std::time::Duration,
+) -> f64 {
+    let time_difference_ns = time_difference.as_nanos() as f64;
+    let mut busy_fraction_by_engine: HashMap<&str, f64> = HashMap::new();
+
+    if time_difference_ns > 0.0 {
+        for (key, time) in &drm_usage.engine_times {
+            if let Some(prev_time) = prev_drm_usage.engine_times.get(key) {
+                *busy_fraction_by_engine.entry(&key.1).or_insert(0.0) +=
+                    time.saturating_sub(*prev_time) as f64 / time_difference_ns;
+            }
+        }
+    }
+    for (key, (cycles, total_cycles)) in &drm_usage.engine_cycles {
+        if let Some((prev_cycles, prev_total_cycles)) = prev_drm_usage.engine_cycles.get(key) {
+            let total_cycles = total_cycles.saturating_sub(*prev_total_cycles);
+            if total_cycles > 0 {
+                *busy_fraction_by_engine.entry(&key.1).or_insert(0.0) +=
+                    cycles.saturating_sub(*prev_cycles) as f64 / total_cycles as f64;
+            }
+        }
+    }
+
+    busy_fraction_by_engine
+        .into_iter()
+        .map(|(engine, busy_fraction)| {
+            let capacity = drm_usage
+                .engine_capacities
+                .get(engine)
+                .copied()
+                .unwrap_or(1)
+                .max(1);
+            busy_fraction / capacity as f64
+        })
+        .fold(0.0, f64::max)
+        .min(1.0)
+        * 100.0
+}
+
+#[allow(clippy::too_many_arguments)]
 #[cfg(target_os = "linux")]
 pub fn linux_processes(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
     pid_mapping: &mut HashMap<Pid, PrevProcDetails, RandomState>, use_current_cpu_total: bool,
-    time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64,
+    time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64, user_table: &mut UserTable,
+    collect_connections: bool, name_source: ProcessNameSource,
+    mut app_table: Option<&mut AppTable>, collect_gpu_stats: bool, collect_security_contexts: bool,
+    collect_namespaces: bool, collect_fds: bool, collect_env: bool, collect_cgroups: bool,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     // TODO: [PROC THREADS] Add threads
 
     if let Ok((cpu_usage, cpu_fraction)) = cpu_usage_calculation(prev_idle, prev_non_idle) {
+        let uptime_secs = get_linux_uptime().unwrap_or(0.0);
+        let boot_time = get_linux_boot_time().unwrap_or(0);
+
+        // Looking through every file descriptor is expensive, so only do so if asked to.
+        let socket_inodes = if collect_connections {
+            Some(get_socket_inodes())
+        } else {
+            None
+        };
+        let host_namespace_ids = if collect_namespaces {
+            Some(get_linux_host_namespace_ids())
+        } else {
+            None
+        };
+
         let process_vector: Vec<ProcessHarvest> = std::fs::read_dir("/proc")?
             .filter_map(|dir| {
                 if let Ok(dir) = dir {
@@ -392,7 +1517,72 @@ pub fn linux_processes(
                             time_difference_in_secs,
                             mem_total_kb,
                             page_file_kb,
+                            user_table,
+                            uptime_secs,
+                            boot_time,
+                            name_source,
                         ) {
+                            let mut process_object = process_object;
+                            if let Some(socket_inodes) = &socket_inodes {
+                                process_object.num_connections =
+                                    get_num_connections(pid, socket_inodes);
+                            }
+                            if collect_fds {
+                                process_object.num_fds = get_num_fds(pid);
+                            }
+                            if let (Some(app_table), Some(pid_stat)) =
+                                (app_table.as_deref_mut(), pid_mapping.get_mut(&pid))
+                            {
+                                // A process can't leave its sandbox, so only look this up once.
+                                process_object.app = pid_stat
+                                    .app
+                                    .get_or_insert_with(|| {
+                                        get_linux_process_app_id(pid).map(|(source, app_id)| {
+                                            app_table.get_app_name(source, &app_id)
+                                        })
+                                    })
+                                    .clone();
+                            }
+                            if let (true, Some(pid_stat)) = (collect_env, pid_mapping.get_mut(&pid))
+                            {
+                                // Reading the environment is expensive and it rarely changes after
+                                // a process starts, so only read it once.
+                                process_object.environ = pid_stat
+                                    .environ
+                                    .get_or_insert_with(|| get_linux_process_environ(pid))
+                                    .clone();
+                            }
+                            if collect_security_contexts {
+                                process_object.security_context = get_linux_security_context(pid);
+                            }
+                            if let Some(host_namespace_ids) = &host_namespace_ids {
+                                process_object.namespaces =
+                                    get_linux_process_namespaces(pid, host_namespace_ids);
+                            }
+                            if collect_cgroups {
+                                process_object.cgroup = get_linux_process_cgroup(pid);
+                            }
+                            if collect_gpu_stats {
+                                if let Some(pid_stat) = pid_mapping.get_mut(&pid) {
+                                    let drm_usage = get_linux_drm_usage(pid);
+                                    let now = std::time::Instant::now();
+                                    if let (Some(drm_usage), Some((prev_time, prev_drm_usage))) =
+                                        (&drm_usage, &pid_stat.drm_usage)
+                                    {
+                                        process_object.gpu_usage_percent =
+                                            get_linux_gpu_usage_percent(
+                                                prev_drm_usage,
+                                                drm_usage,
+                                                now.duration_since(*prev_time),
+                                            );
+                                    }
+                                    if let Some(drm_usage) = &drm_usage {
+                                        process_object.gpu_mem_bytes = drm_usage.vram_bytes;
+                                    }
+                                    pid_stat.drm_usage =
+                                        drm_usage.map(|drm_usage| (now, drm_usage));
+                                }
+                            }
                             return Some(process_object);
                         }
                     }
@@ -410,7 +1600,8 @@ pub fn linux_processes(
 
 #[cfg(not(target_os = "linux"))]
 pub fn windows_macos_processes(
-    sys: &System, use_current_cpu_total: bool, mem_total_kb: u64,
+    sys: &System, use_current_cpu_total: bool, mem_total_kb: u64, user_table: &mut UserTable,
+    exe_table: &mut ExeTable,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     let mut process_vector: Vec<ProcessHarvest> = Vec::new();
     let process_hashmap = sys.get_processes();
@@ -458,6 +1649,31 @@ pub fn windows_macos_processes(
         };
 
         let disk_usage = process_val.disk_usage();
+        let run_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|now| now.as_secs().saturating_sub(process_val.start_time()))
+            .unwrap_or(0);
+
+        #[cfg(target_family = "unix")]
+        let uid = Some(process_val.uid);
+        #[cfg(not(target_family = "unix"))]
+        let uid = None;
+
+        let exe_path = process_val.exe();
+        let suspicious_reason = get_suspicious_reason(
+            exe_path,
+            exe_table.is_exe_deleted(process_val.pid(), process_val.start_time(), exe_path),
+        );
+        let exe = if exe_path.as_os_str().is_empty() {
+            None
+        } else {
+            Some(exe_path.to_string_lossy().into_owned())
+        };
+
+        #[cfg(target_os = "macos")]
+        let app = get_bundle_app_name(process_val.exe());
+        #[cfg(not(target_os = "macos"))]
+        let app = None;
 
         process_vector.push(ProcessHarvest {
             pid: process_val.pid(),
@@ -477,9 +1693,37 @@ pub fn windows_macos_processes(
             total_write_bytes: disk_usage.total_written_bytes,
             process_state: process_val.status().to_string().to_string(),
             process_state_char: convert_process_status_to_char(process_val.status()),
+            uid,
+            user: uid.and_then(|uid| user_table.get_uid_to_username_mapping(uid)),
+            num_connections: 0,
+            num_threads: 0,
+            num_fds: 0,
+            run_time,
+            start_time: process_val.start_time(),
+            app,
+            gpu_mem_bytes: 0,
+            gpu_usage_percent: 0.0,
+            rt_policy: None,
+            rt_priority: 0,
+            nice: 0,
+            priority: 0,
+            security_context: None,
+            suspicious_reason,
+            exe,
+            namespaces: None,
+            cgroup: None,
+            environ: None,
+            window_titles: Vec::new(),
+            media_usage: MediaUsage::default(),
+            energy_impact: EnergyImpact::default(),
         });
     }
 
+    // Forget about processes that have exited.
+    exe_table
+        .exe_deleted_mapping
+        .retain(|(pid, _), _| process_hashmap.contains_key(pid));
+
     Ok(process_vector)
 }
 
@@ -493,6 +1737,7 @@ fn convert_process_status_to_char(status: ProcessStatus) -> char {
                 ProcessStatus::Run => 'R',
                 ProcessStatus::Sleep => 'S',
                 ProcessStatus::Idle => 'D',
+                ProcessStatus::Stop => 'T',
                 ProcessStatus::Zombie => 'Z',
                 _ => '?',
             }
@@ -7,19 +7,14 @@ pub struct TempHarvest {
     pub temperature: f32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub enum TemperatureType {
+    #[default]
     Celsius,
     Kelvin,
     Fahrenheit,
 }
 
-impl Default for TemperatureType {
-    fn default() -> Self {
-        TemperatureType::Celsius
-    }
-}
-
 /// Meant for ARM and non-Linux usage.
 #[cfg(any(not(target_os = "linux"), target_arch = "aarch64", target_arch = "arm"))]
 pub async fn arm_and_non_linux_temperature_data(
@@ -78,11 +73,7 @@ pub async fn linux_temperature_data(
         if let Ok(sensor) = sensor {
             temperature_vec.push(TempHarvest {
                 component_name: Some(sensor.unit().to_string()),
-                component_label: if let Some(label) = sensor.label() {
-                    Some(label.to_string())
-                } else {
-                    None
-                },
+                component_label: sensor.label().map(|label| label.to_string()),
                 temperature: match temp_type {
                     TemperatureType::Celsius => sensor
                         .current()
@@ -103,7 +94,7 @@ pub async fn linux_temperature_data(
     Ok(Some(temperature_vec))
 }
 
-fn temp_vec_sort(temperature_vec: &mut Vec<TempHarvest>) {
+fn temp_vec_sort(temperature_vec: &mut [TempHarvest]) {
     // By default, sort temperature, then by alphabetically!
     // TODO: [TEMPS] Allow users to control this.
 
@@ -0,0 +1,378 @@
+//! The titles of the windows of each process, so GUI apps can be searched for by what they show,
+//! like the page a browser is on.  Only collected on Linux, and only while used.
+//!
+//! Windows are read from the X server through EWMH's `_NET_CLIENT_LIST` and `_NET_WM_PID`,
+//! which also covers apps running through XWayland.  Wayland has no common way to list the
+//! windows of other apps, so native Wayland windows are only read from sway, through its IPC.
+
+use std::collections::HashMap;
+
+use crate::Pid;
+
+/// Holds on to the connection to the X server between collections.
+#[derive(Debug, Default)]
+pub struct WindowTitleCollector {
+    #[cfg(target_os = "linux")]
+    x11_connection: Option<x11::X11Connection>,
+}
+
+impl WindowTitleCollector {
+    /// Returns the titles of the windows of each process that has any.
+    #[cfg(target_os = "linux")]
+    pub fn get_window_titles(&mut self) -> HashMap<Pid, Vec<String>> {
+        let mut window_titles: HashMap<Pid, Vec<String>> = HashMap::new();
+        let mut add_window_title = |pid: Pid, title: String| {
+            let titles = window_titles.entry(pid).or_default();
+            if !title.is_empty() && !titles.contains(&title) {
+                titles.push(title);
+            }
+        };
+
+        if self.x11_connection.is_none() {
+            self.x11_connection = x11::X11Connection::connect()
+                .map_err(|err| debug!("Could not connect to the X server: {}", err))
+                .ok();
+        }
+        if let Some(x11_connection) = &mut self.x11_connection {
+            match x11_connection.get_window_titles() {
+                Ok(titles) => titles
+                    .into_iter()
+                    .for_each(|(pid, title)| add_window_title(pid, title)),
+                Err(err) => {
+                    // Try again with a new connection next time, in case the server restarted.
+                    debug!("Could not read windows from the X server: {}", err);
+                    self.x11_connection = None;
+                }
+            }
+        }
+
+        match sway::get_window_titles() {
+            Ok(titles) => titles
+                .into_iter()
+                .for_each(|(pid, title)| add_window_title(pid, title)),
+            Err(err) => debug!("Could not read windows from sway: {}", err),
+        }
+
+        window_titles.retain(|_pid, titles| !titles.is_empty());
+        window_titles
+    }
+
+    /// Returns the titles of the windows of each process that has any.
+    #[cfg(not(target_os = "linux"))]
+    pub fn get_window_titles(&mut self) -> HashMap<Pid, Vec<String>> {
+        HashMap::new()
+    }
+}
+
+/// A minimal client of the X11 protocol, which only reads the properties of windows.
+#[cfg(target_os = "linux")]
+mod x11 {
+    use std::{
+        io::{self, Read, Write},
+        os::unix::net::UnixStream,
+        time::Duration,
+    };
+
+    use crate::Pid;
+
+    /// The predefined atoms used, from the core protocol.
+    const ATOM_WM_NAME: u32 = 39;
+    const ANY_PROPERTY_TYPE: u32 = 0;
+
+    const OPCODE_INTERN_ATOM: u8 = 16;
+    const OPCODE_GET_PROPERTY: u8 = 20;
+
+    /// How many 4-byte units of a property are read at most.
+    const MAX_PROPERTY_LENGTH: u32 = 4096;
+
+    fn pad(length: usize) -> usize {
+        (4 - length % 4) % 4
+    }
+
+    fn get_u16(bytes: &[u8], offset: usize) -> io::Result<u16> {
+        bytes
+            .get(offset..offset + 2)
+            .map(|value| u16::from_le_bytes([value[0], value[1]]))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "reply is too short"))
+    }
+
+    fn get_u32(bytes: &[u8], offset: usize) -> io::Result<u32> {
+        bytes
+            .get(offset..offset + 4)
+            .map(|value| u32::from_le_bytes([value[0], value[1], value[2], value[3]]))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "reply is too short"))
+    }
+
+    #[derive(Debug)]
+    pub struct X11Connection {
+        stream: UnixStream,
+        root: u32,
+        atom_net_client_list: u32,
+        atom_net_wm_pid: u32,
+        atom_net_wm_name: u32,
+    }
+
+    impl X11Connection {
+        /// Connects to the local X server of `DISPLAY`, authenticating with the cookie from the
+        /// Xauthority file if there is one.
+        pub fn connect() -> io::Result<Self> {
+            let display = std::env::var("DISPLAY")
+                .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "DISPLAY is not set"))?;
+            let (host, display_number) = display.rsplit_once(':').ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "DISPLAY is not valid")
+            })?;
+            if !host.is_empty() && host != "unix" {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "only local X servers are supported",
+                ));
+            }
+            let display_number = display_number.split('.').next().unwrap_or_default();
+
+            let mut stream = UnixStream::connect(format!("/tmp/.X11-unix/X{}", display_number))?;
+            stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+            stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+
+            let (auth_name, auth_data) = get_auth_cookie(display_number).unwrap_or_default();
+            let mut setup_request = vec![b'l', 0];
+            setup_request.extend_from_slice(&11_u16.to_le_bytes());
+            setup_request.extend_from_slice(&0_u16.to_le_bytes());
+            setup_request.extend_from_slice(&(auth_name.len() as u16).to_le_bytes());
+            setup_request.extend_from_slice(&(auth_data.len() as u16).to_le_bytes());
+            setup_request.extend_from_slice(&[0, 0]);
+            for field in &[&auth_name, &auth_data] {
+                setup_request.extend_from_slice(field);
+                setup_request.extend(std::iter::repeat_n(0, pad(field.len())));
+            }
+            stream.write_all(&setup_request)?;
+
+            let mut setup_header = [0; 8];
+            stream.read_exact(&mut setup_header)?;
+            let mut setup = vec![0; usize::from(get_u16(&setup_header, 6)?) * 4];
+            stream.read_exact(&mut setup)?;
+            if setup_header[0] != 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "the X server refused the connection",
+                ));
+            }
+
+            // The first screen comes after the vendor name and the pixmap formats.
+            let vendor_length = usize::from(get_u16(&setup, 16)?);
+            let num_formats = usize::from(*setup.get(21).unwrap_or(&0));
+            let root = get_u32(
+                &setup,
+                32 + vendor_length + pad(vendor_length) + 8 * num_formats,
+            )?;
+
+            let mut connection = X11Connection {
+                stream,
+                root,
+                atom_net_client_list: 0,
+                atom_net_wm_pid: 0,
+                atom_net_wm_name: 0,
+            };
+            connection.atom_net_client_list = connection.intern_atom("_NET_CLIENT_LIST")?;
+            connection.atom_net_wm_pid = connection.intern_atom("_NET_WM_PID")?;
+            connection.atom_net_wm_name = connection.intern_atom("_NET_WM_NAME")?;
+
+            Ok(connection)
+        }
+
+        /// Returns the PID and title of each window the window manager manages.
+        pub fn get_window_titles(&mut self) -> io::Result<Vec<(Pid, String)>> {
+            let windows = self
+                .get_property(self.root, self.atom_net_client_list)?
+                .unwrap_or_default();
+
+            let mut window_titles = Vec::new();
+            for window in windows.chunks_exact(4) {
+                let window = u32::from_le_bytes([window[0], window[1], window[2], window[3]]);
+                let pid = match self.get_property(window, self.atom_net_wm_pid)? {
+                    Some(pid) if pid.len() >= 4 => {
+                        u32::from_le_bytes([pid[0], pid[1], pid[2], pid[3]]) as Pid
+                    }
+                    _ => continue,
+                };
+
+                // Prefer the UTF-8 title, as the older one is in Latin-1.
+                let title = match self.get_property(window, self.atom_net_wm_name)? {
+                    Some(title) if !title.is_empty() => String::from_utf8_lossy(&title).to_string(),
+                    _ => match self.get_property(window, ATOM_WM_NAME)? {
+                        Some(title) => title.iter().map(|byte| char::from(*byte)).collect(),
+                        None => continue,
+                    },
+                };
+                window_titles.push((pid, title));
+            }
+
+            Ok(window_titles)
+        }
+
+        fn intern_atom(&mut self, name: &str) -> io::Result<u32> {
+            let mut request = vec![OPCODE_INTERN_ATOM, 0];
+            request.extend_from_slice(
+                &((2 + (name.len() + pad(name.len())) / 4) as u16).to_le_bytes(),
+            );
+            request.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            request.extend_from_slice(&[0, 0]);
+            request.extend_from_slice(name.as_bytes());
+            request.extend(std::iter::repeat_n(0, pad(name.len())));
+
+            match self.send_request(&request)? {
+                Some(reply) => get_u32(&reply, 8),
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "could not intern an atom",
+                )),
+            }
+        }
+
+        /// Returns the value of a property of a window, or [`None`] if it doesn't have it or the
+        /// window is gone.
+        fn get_property(&mut self, window: u32, property: u32) -> io::Result<Option<Vec<u8>>> {
+            let mut request = vec![OPCODE_GET_PROPERTY, 0];
+            request.extend_from_slice(&6_u16.to_le_bytes());
+            for value in &[window, property, ANY_PROPERTY_TYPE, 0, MAX_PROPERTY_LENGTH] {
+                request.extend_from_slice(&value.to_le_bytes());
+            }
+
+            match self.send_request(&request)? {
+                Some(reply) => {
+                    let property_type = get_u32(&reply, 8)?;
+                    if property_type == 0 {
+                        return Ok(None);
+                    }
+                    let format = usize::from(reply[1]);
+                    let value_length = get_u32(&reply, 16)? as usize * format / 8;
+                    Ok(reply.get(32..32 + value_length).map(<[u8]>::to_vec))
+                }
+                None => Ok(None),
+            }
+        }
+
+        /// Sends a request and waits for its reply, or returns [`None`] if the server replied
+        /// with an error.
+        fn send_request(&mut self, request: &[u8]) -> io::Result<Option<Vec<u8>>> {
+            self.stream.write_all(request)?;
+
+            loop {
+                let mut reply = vec![0; 32];
+                self.stream.read_exact(&mut reply)?;
+                match reply[0] {
+                    0 => return Ok(None),
+                    1 => {
+                        let extra_length = get_u32(&reply, 4)? as usize * 4;
+                        reply.resize(32 + extra_length, 0);
+                        self.stream.read_exact(&mut reply[32..])?;
+                        return Ok(Some(reply));
+                    }
+                    // No events were asked for, but skip any that still arrive.
+                    _ => continue,
+                }
+            }
+        }
+    }
+
+    /// Returns the name and data of the MIT-MAGIC-COOKIE-1 for the display from the Xauthority
+    /// file, if there is one.
+    fn get_auth_cookie(display_number: &str) -> Option<(Vec<u8>, Vec<u8>)> {
+        const FAMILY_LOCAL: u16 = 256;
+        const FAMILY_WILD: u16 = 65535;
+
+        let path = std::env::var_os("XAUTHORITY")
+            .map(std::path::PathBuf::from)
+            .or_else(|| dirs_next::home_dir().map(|home| home.join(".Xauthority")))?;
+        let contents = std::fs::read(path).ok()?;
+
+        // Each entry is a family followed by the address, display number, name, and data, each
+        // of which is prefixed by its length.  All of them are big-endian.
+        let mut offset = 0;
+        let mut read_field = |contents: &[u8]| {
+            let length = usize::from(u16::from_be_bytes([
+                *contents.get(offset)?,
+                *contents.get(offset + 1)?,
+            ]));
+            let field = contents.get(offset + 2..offset + 2 + length)?.to_vec();
+            offset += 2 + length;
+            Some(field)
+        };
+        loop {
+            let family = read_field(&contents)?;
+            let family = u16::from_be_bytes([*family.first()?, *family.get(1)?]);
+            let _address = read_field(&contents)?;
+            let number = read_field(&contents)?;
+            let name = read_field(&contents)?;
+            let data = read_field(&contents)?;
+
+            if (family == FAMILY_LOCAL || family == FAMILY_WILD)
+                && (number.is_empty() || number == display_number.as_bytes())
+                && name == b"MIT-MAGIC-COOKIE-1"
+            {
+                return Some((name, data));
+            }
+        }
+    }
+}
+
+/// Reads windows from sway, which has them as part of the tree of containers it lays out.
+#[cfg(target_os = "linux")]
+mod sway {
+    use std::{
+        io::{self, Read, Write},
+        os::unix::net::UnixStream,
+        time::Duration,
+    };
+
+    use crate::Pid;
+
+    const IPC_MAGIC: &[u8] = b"i3-ipc";
+    const IPC_GET_TREE: u32 = 4;
+
+    /// Returns the PID and title of each window sway shows, or nothing if sway isn't running.
+    pub fn get_window_titles() -> io::Result<Vec<(Pid, String)>> {
+        let socket_path = match std::env::var_os("SWAYSOCK") {
+            Some(socket_path) => socket_path,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut stream = UnixStream::connect(socket_path)?;
+        stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+
+        let mut request = IPC_MAGIC.to_vec();
+        request.extend_from_slice(&0_u32.to_ne_bytes());
+        request.extend_from_slice(&IPC_GET_TREE.to_ne_bytes());
+        stream.write_all(&request)?;
+
+        let mut header = [0; 14];
+        stream.read_exact(&mut header)?;
+        let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]);
+        let mut payload = vec![0; length as usize];
+        stream.read_exact(&mut payload)?;
+
+        let tree: serde_json::Value = serde_json::from_slice(&payload)?;
+        let mut window_titles = Vec::new();
+        add_window_titles(&tree, &mut window_titles);
+
+        Ok(window_titles)
+    }
+
+    /// Adds the windows in a container and everything inside it.
+    fn add_window_titles(node: &serde_json::Value, window_titles: &mut Vec<(Pid, String)>) {
+        if let (Some(pid), Some(title)) = (
+            node.get("pid").and_then(serde_json::Value::as_i64),
+            node.get("name").and_then(serde_json::Value::as_str),
+        ) {
+            window_titles.push((pid as Pid, title.to_string()));
+        }
+
+        for children in &["nodes", "floating_nodes"] {
+            if let Some(children) = node.get(children).and_then(serde_json::Value::as_array) {
+                for child in children {
+                    add_window_titles(child, window_titles);
+                }
+            }
+        }
+    }
+}
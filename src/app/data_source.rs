@@ -0,0 +1,233 @@
+//! Data sources that generic, config-defined widgets can be bound to.
+//!
+//! A binding is a string like `cpu.avg` or `temp.k10temp`, which is parsed into some
+//! [`DataSource`] that is sampled once per collection tick.
+
+use super::{data_farmer::DataCollection, layout_manager::UsedWidgets, plugins::PluginHost};
+use crate::utils::{
+    error::{BottomError, Result},
+    number_format::format_number,
+};
+
+/// A single sample produced by a [`DataSource`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataValue {
+    Scalar(f64),
+    Text(String),
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// A batch of samples from a distribution, such as latencies.
+    Samples(Vec<f64>),
+}
+
+impl DataValue {
+    pub fn as_scalar(&self) -> Option<f64> {
+        match self {
+            DataValue::Scalar(val) => Some(*val),
+            DataValue::Text(text) => text.trim().parse().ok(),
+            DataValue::Table { .. } | DataValue::Samples(_) => None,
+        }
+    }
+}
+
+/// Something that can produce data for a generic widget.
+pub trait DataSource: Send {
+    /// Returns a new sample, or `None` if there is no data available right now.
+    fn sample(&mut self, data_collection: &DataCollection) -> Option<DataValue>;
+
+    /// Marks what harvested data this source needs, so it gets collected even if no
+    /// built-in widget uses it.
+    fn mark_used_widgets(&self, _used_widgets: &mut UsedWidgets) {}
+}
+
+/// The sources built into bottom, which are read from the already harvested data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuiltinSource {
+    CpuAverage,
+    Cpu(usize),
+    MemPercent,
+    MemUsed,
+    SwapPercent,
+    SwapUsed,
+    NetRx,
+    NetTx,
+    NetTotalRx,
+    NetTotalTx,
+    Temp(String),
+    Temps,
+    Disks,
+    Battery(usize),
+    ProcessCount,
+}
+
+impl std::str::FromStr for BuiltinSource {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        use BuiltinSource::*;
+
+        let lower_case = s.trim().to_lowercase();
+        let (category, rest) = match lower_case.find('.') {
+            Some(index) => (&lower_case[..index], Some(&lower_case[index + 1..])),
+            None => (lower_case.as_str(), None),
+        };
+
+        let source = match (category, rest) {
+            ("cpu", Some("avg")) => Some(CpuAverage),
+            ("cpu", Some(core)) => core.parse().ok().map(Cpu),
+            ("mem", Some("percent")) | ("mem", None) => Some(MemPercent),
+            ("mem", Some("used")) => Some(MemUsed),
+            ("swap", Some("percent")) | ("swap", None) => Some(SwapPercent),
+            ("swap", Some("used")) => Some(SwapUsed),
+            ("net", Some("rx")) => Some(NetRx),
+            ("net", Some("tx")) => Some(NetTx),
+            ("net", Some("total_rx")) => Some(NetTotalRx),
+            ("net", Some("total_tx")) => Some(NetTotalTx),
+            ("temp", Some(sensor)) => Some(Temp(sensor.to_string())),
+            ("temp", None) | ("temps", None) => Some(Temps),
+            ("disk", None) | ("disks", None) => Some(Disks),
+            ("battery", None) => Some(Battery(0)),
+            ("battery", Some(index)) => index.parse().ok().map(Battery),
+            ("proc", Some("count")) => Some(ProcessCount),
+            _ => None,
+        };
+
+        source.ok_or_else(|| {
+            BottomError::ConfigError(format!(
+                "\"{}\" is an invalid data source.  Supported sources are cpu.avg, cpu.<core>, mem.percent, mem.used, swap.percent, swap.used, net.rx, net.tx, net.total_rx, net.total_tx, temp.<sensor>, temps, disks, battery.<index>, proc.count, and plugin.<name>.",
+                s
+            ))
+        })
+    }
+}
+
+impl DataSource for BuiltinSource {
+    fn mark_used_widgets(&self, used_widgets: &mut UsedWidgets) {
+        use BuiltinSource::*;
+
+        match self {
+            CpuAverage | Cpu(_) => used_widgets.use_cpu = true,
+            MemPercent | MemUsed | SwapPercent | SwapUsed => used_widgets.use_mem = true,
+            NetRx | NetTx | NetTotalRx | NetTotalTx => used_widgets.use_net = true,
+            Temp(_) | Temps => used_widgets.use_temp = true,
+            Disks => used_widgets.use_disk = true,
+            Battery(_) => used_widgets.use_battery = true,
+            ProcessCount => used_widgets.use_proc = true,
+        }
+    }
+
+    fn sample(&mut self, data_collection: &DataCollection) -> Option<DataValue> {
+        use BuiltinSource::*;
+
+        fn percent(used: u64, total: u64) -> Option<f64> {
+            if total == 0 {
+                None
+            } else {
+                Some(used as f64 / total as f64 * 100.0)
+            }
+        }
+
+        match self {
+            CpuAverage => {
+                let cpu_harvest = &data_collection.cpu_harvest;
+                if let Some(avg) = cpu_harvest.iter().find(|cpu| cpu.cpu_count.is_none()) {
+                    Some(avg.cpu_usage)
+                } else if cpu_harvest.is_empty() {
+                    None
+                } else {
+                    Some(
+                        cpu_harvest.iter().map(|cpu| cpu.cpu_usage).sum::<f64>()
+                            / cpu_harvest.len() as f64,
+                    )
+                }
+            }
+            Cpu(core) => data_collection
+                .cpu_harvest
+                .iter()
+                .find(|cpu| cpu.cpu_count == Some(*core))
+                .map(|cpu| cpu.cpu_usage),
+            MemPercent => percent(
+                data_collection.memory_harvest.mem_used_in_mb,
+                data_collection.memory_harvest.mem_total_in_mb,
+            ),
+            MemUsed => Some(data_collection.memory_harvest.mem_used_in_mb as f64),
+            SwapPercent => percent(
+                data_collection.swap_harvest.mem_used_in_mb,
+                data_collection.swap_harvest.mem_total_in_mb,
+            ),
+            SwapUsed => Some(data_collection.swap_harvest.mem_used_in_mb as f64),
+            NetRx => Some(data_collection.network_harvest.rx as f64),
+            NetTx => Some(data_collection.network_harvest.tx as f64),
+            NetTotalRx => Some(data_collection.network_harvest.total_rx as f64),
+            NetTotalTx => Some(data_collection.network_harvest.total_tx as f64),
+            Temp(sensor) => data_collection
+                .temp_harvest
+                .iter()
+                .find(|temp| {
+                    let matches = |name: &Option<String>| {
+                        name.as_ref()
+                            .map(|name| name.eq_ignore_ascii_case(sensor))
+                            .unwrap_or(false)
+                    };
+                    matches(&temp.component_name) || matches(&temp.component_label)
+                })
+                .map(|temp| temp.temperature as f64),
+            Battery(index) => data_collection
+                .battery_harvest
+                .get(*index)
+                .map(|battery| battery.charge_percent),
+            ProcessCount => Some(data_collection.process_harvest.len() as f64),
+            Temps => {
+                return Some(DataValue::Table {
+                    headers: vec!["Sensor".to_string(), "Temp".to_string()],
+                    rows: data_collection
+                        .temp_harvest
+                        .iter()
+                        .map(|temp| {
+                            vec![
+                                temp.component_label
+                                    .clone()
+                                    .or_else(|| temp.component_name.clone())
+                                    .unwrap_or_default(),
+                                format_number(temp.temperature.into(), 0),
+                            ]
+                        })
+                        .collect(),
+                })
+            }
+            Disks => {
+                return Some(DataValue::Table {
+                    headers: vec!["Disk".to_string(), "Mount".to_string(), "Used%".to_string()],
+                    rows: data_collection
+                        .disk_harvest
+                        .iter()
+                        .map(|disk| {
+                            vec![
+                                disk.name.clone(),
+                                disk.mount_point.clone(),
+                                percent(disk.used_space, disk.total_space)
+                                    .map(|percent| format!("{}%", format_number(percent, 0)))
+                                    .unwrap_or_else(|| "N/A".to_string()),
+                            ]
+                        })
+                        .collect(),
+                })
+            }
+        }
+        .map(DataValue::Scalar)
+    }
+}
+
+/// Creates a data source from a binding string, as written in the config file.  Bindings of
+/// the form `plugin.<name>` start (or reuse) the plugin of that name.
+pub fn create_data_source(
+    binding: &str, plugin_host: &mut PluginHost,
+) -> Result<Box<dyn DataSource>> {
+    if let Some(plugin_name) = binding.trim().strip_prefix("plugin.") {
+        Ok(Box::new(plugin_host.get_source(plugin_name)?))
+    } else {
+        Ok(Box::new(binding.parse::<BuiltinSource>()?))
+    }
+}
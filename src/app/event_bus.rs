@@ -0,0 +1,337 @@
+//! A small internal event bus.  Rather than having every key or mouse handler
+//! directly reach into every piece of state it may affect, handlers can emit an
+//! [`AppAction`], which is then delivered to every registered [`ActionHandler`].
+//! This lets widgets react to each other without having to know about one
+//! another.
+
+use std::{collections::VecDeque, time::Instant};
+
+use super::{
+    data_harvester::processes::{self, ProcessSorting},
+    App, BottomWidgetType, LinkedProcess,
+};
+use crate::Pid;
+
+/// The maximum number of actions processed in one dispatch.  Handlers may emit further
+/// actions while handling one, so this guards against two handlers ping-ponging forever.
+const MAX_ACTIONS_PER_DISPATCH: usize = 64;
+
+/// An action emitted by some part of the app, to be handled by any interested handlers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppAction {
+    /// Toggle whether data is frozen.
+    ToggleFreeze,
+    /// Freeze or unfreeze data.
+    SetFrozen(bool),
+    /// Select the nth selectable widget, in layout order, starting from 0.
+    SelectWidget(usize),
+    /// Show the help dialog.
+    ShowHelp,
+    /// Sort the given process widget by a column.  If it is already sorted by that column,
+    /// the sort direction is flipped instead.
+    SortProcesses {
+        widget_id: u64,
+        sort_type: ProcessSorting,
+    },
+    /// Set the search query of the selected process widget, or the first one if no process
+    /// widget is selected.  An empty query clears the search.
+    SetProcessFilter { query: String },
+    /// Link other widgets to the given process, or unlink them if there is none.
+    LinkProcess(Option<Pid>),
+    /// Select the first process widget, filtered to the processes using the filesystem mounted
+    /// at the given mount point.  These are found in the background, after which
+    /// [`AppAction::ShowProcessesFoundOnMount`] is emitted.
+    ShowProcessesOnMount { mount_point: String },
+    /// Select the first process widget, filtered to the given processes found using a mount
+    /// point, or [`None`] if they couldn't be found.
+    ShowProcessesFoundOnMount {
+        mount_point: String,
+        pids: Option<Vec<Pid>>,
+    },
+}
+
+/// A function that is called for every emitted action.  Handlers should ignore any
+/// actions they are not interested in.
+pub type ActionHandler = fn(&mut App, &AppAction);
+
+pub struct EventBus {
+    queue: VecDeque<AppAction>,
+    handlers: Vec<ActionHandler>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        EventBus::init()
+    }
+}
+
+impl EventBus {
+    /// Creates a new bus with all built-in handlers registered.
+    pub fn init() -> Self {
+        let mut bus = EventBus {
+            queue: VecDeque::new(),
+            handlers: Vec::new(),
+        };
+        bus.register(handle_global_action);
+        bus.register(handle_process_action);
+        bus.register(handle_link_action);
+        bus
+    }
+
+    /// Registers a new handler.  Handlers are called in the order they were registered.
+    pub fn register(&mut self, handler: ActionHandler) {
+        self.handlers.push(handler);
+    }
+
+    /// Queues an action to be handled on the next dispatch.
+    pub fn emit(&mut self, action: AppAction) {
+        self.queue.push_back(action);
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    fn pop(&mut self) -> Option<AppAction> {
+        self.queue.pop_front()
+    }
+}
+
+impl App {
+    /// Emits an action onto the app's event bus.
+    pub fn emit(&mut self, action: AppAction) {
+        self.event_bus.emit(action);
+    }
+
+    /// Delivers all pending actions to every registered handler.
+    pub fn dispatch_actions(&mut self) {
+        let mut num_handled = 0;
+        while num_handled < MAX_ACTIONS_PER_DISPATCH {
+            let action = match self.event_bus.pop() {
+                Some(action) => action,
+                None => return,
+            };
+
+            // Handlers may register other handlers, so iterate over a copy.
+            let handlers = self.event_bus.handlers.clone();
+            for handler in handlers {
+                handler(self, &action);
+            }
+            num_handled += 1;
+        }
+
+        if self.event_bus.has_pending() {
+            debug!(
+                "Dropping {} actions that were still pending after dispatch.",
+                self.event_bus.queue.len()
+            );
+            self.event_bus.queue.clear();
+        }
+    }
+}
+
+/// Handles actions that affect the entire app.
+fn handle_global_action(app: &mut App, action: &AppAction) {
+    match action {
+        AppAction::ToggleFreeze => {
+            app.is_frozen = !app.is_frozen;
+            if app.is_frozen {
+                app.data_collection.set_frozen_time();
+            }
+        }
+        AppAction::SetFrozen(is_frozen) if app.is_frozen != *is_frozen => {
+            app.is_frozen = *is_frozen;
+            if app.is_frozen {
+                app.data_collection.set_frozen_time();
+            }
+        }
+        AppAction::SelectWidget(index) => {
+            let mut selectable_widgets: Vec<_> = app
+                .widget_map
+                .values()
+                .filter(|widget| {
+                    !matches!(
+                        widget.widget_type,
+                        BottomWidgetType::Empty
+                            | BottomWidgetType::CpuLegend
+                            | BottomWidgetType::ProcSearch
+                            | BottomWidgetType::ProcSort
+                            | BottomWidgetType::BasicTables
+                    )
+                })
+                .collect();
+            // Widget IDs are handed out in layout order.
+            selectable_widgets.sort_by_key(|widget| widget.widget_id);
+
+            if let Some(widget) = selectable_widgets.get(*index) {
+                app.current_widget = (*widget).clone();
+                if let Some(basic_table_widget_state) = &mut app.basic_table_widget_state {
+                    if matches!(
+                        app.current_widget.widget_type,
+                        BottomWidgetType::Temp
+                            | BottomWidgetType::Proc
+                            | BottomWidgetType::Disk
+                            | BottomWidgetType::Battery
+                    ) {
+                        basic_table_widget_state.currently_displayed_widget_id =
+                            app.current_widget.widget_id;
+                        basic_table_widget_state.currently_displayed_widget_type =
+                            app.current_widget.widget_type.clone();
+                    }
+                }
+                app.is_force_redraw = true;
+            }
+        }
+        AppAction::ShowHelp => {
+            app.help_dialog_state.is_showing_help = true;
+            app.is_force_redraw = true;
+        }
+        _ => {}
+    }
+}
+
+/// Handles actions that affect process widgets.
+fn handle_process_action(app: &mut App, action: &AppAction) {
+    if let AppAction::SetProcessFilter { query } = action {
+        let widget_id = match app.current_widget.widget_type {
+            BottomWidgetType::Proc => Some(app.current_widget.widget_id),
+            BottomWidgetType::ProcSearch => Some(app.current_widget.widget_id - 1),
+            BottomWidgetType::ProcSort => Some(app.current_widget.widget_id - 2),
+            _ => app.proc_state.widget_states.keys().min().copied(),
+        };
+
+        if let Some(widget_id) = widget_id {
+            if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+                proc_widget_state.set_search_query(query);
+                app.proc_state.force_update = Some(widget_id);
+                app.is_force_redraw = true;
+            }
+        }
+    } else if let AppAction::ShowProcessesOnMount { mount_point } = action {
+        // Looking through every open file can be slow, so leave it to the collection thread.
+        app.pending_mount_scan = Some(mount_point.clone());
+        app.status_message = Some((
+            Instant::now(),
+            format!("Finding the processes using {}...", mount_point),
+        ));
+    } else if let AppAction::ShowProcessesFoundOnMount { mount_point, pids } = action {
+        show_processes_on_mount(app, mount_point, pids.as_deref());
+    } else if let AppAction::SortProcesses {
+        widget_id,
+        sort_type,
+    } = action
+    {
+        if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(*widget_id) {
+            // PIDs are meaningless when grouped, so skip.
+            if proc_widget_state.is_grouped && *sort_type == ProcessSorting::Pid {
+                return;
+            }
+
+            let is_same_type = match (&proc_widget_state.process_sorting_type, sort_type) {
+                (ProcessSorting::ProcessName, ProcessSorting::ProcessName)
+                | (ProcessSorting::ProcessName, ProcessSorting::Command)
+                | (ProcessSorting::Command, ProcessSorting::ProcessName)
+                | (ProcessSorting::Command, ProcessSorting::Command) => true,
+                (current, new) => current == new,
+            };
+
+            if is_same_type {
+                proc_widget_state.is_process_sort_descending =
+                    !proc_widget_state.is_process_sort_descending;
+            } else {
+                proc_widget_state.process_sorting_type = match sort_type {
+                    ProcessSorting::ProcessName | ProcessSorting::Command => {
+                        if proc_widget_state.is_using_command {
+                            ProcessSorting::Command
+                        } else {
+                            ProcessSorting::ProcessName
+                        }
+                    }
+                    _ => sort_type.clone(),
+                };
+                // Textual and ID columns default to ascending, everything else to descending.
+                proc_widget_state.is_process_sort_descending = !matches!(
+                    sort_type,
+                    ProcessSorting::Pid
+                        | ProcessSorting::ProcessName
+                        | ProcessSorting::Command
+                        | ProcessSorting::App
+                );
+            }
+
+            app.proc_state.force_update = Some(*widget_id);
+
+            if app.current_widget.widget_id == *widget_id {
+                if let BottomWidgetType::Proc = app.current_widget.widget_type {
+                    app.skip_to_first();
+                }
+            }
+        }
+    }
+}
+
+fn show_processes_on_mount(app: &mut App, mount_point: &str, pids: Option<&[Pid]>) {
+    let widget_id = match app.proc_state.widget_states.keys().min().copied() {
+        Some(widget_id) => widget_id,
+        None => {
+            app.status_message = Some((
+                Instant::now(),
+                "There is no process widget to show processes in".to_string(),
+            ));
+            return;
+        }
+    };
+    let pids = match pids {
+        Some(pids) if !pids.is_empty() => pids,
+        Some(_) => {
+            app.status_message = Some((
+                Instant::now(),
+                format!("No processes are using {}", mount_point),
+            ));
+            return;
+        }
+        None => {
+            app.status_message = Some((
+                Instant::now(),
+                format!("Unable to find the processes using {}", mount_point),
+            ));
+            return;
+        }
+    };
+
+    let query = pids
+        .iter()
+        .map(|pid| format!("pid = {}", pid))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    app.status_message = None;
+    if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+        proc_widget_state.set_search_query(&query);
+        app.proc_state.force_update = Some(widget_id);
+    }
+    if let Some(widget) = app.widget_map.get(&widget_id) {
+        app.current_widget = widget.clone();
+        if let Some(basic_table_widget_state) = &mut app.basic_table_widget_state {
+            basic_table_widget_state.currently_displayed_widget_id = widget_id;
+            basic_table_widget_state.currently_displayed_widget_type = BottomWidgetType::Proc;
+        }
+    }
+    app.is_force_redraw = true;
+}
+
+/// Handles linking other widgets to the selected process.
+fn handle_link_action(app: &mut App, action: &AppAction) {
+    if let AppAction::LinkProcess(pid) = action {
+        app.linked_process = pid.map(|pid| LinkedProcess {
+            pid,
+            name: app
+                .canvas_data
+                .single_process_data
+                .get(&pid)
+                .map(|process| process.name.clone())
+                .unwrap_or_default(),
+            cpu_affinity: processes::get_process_cpu_affinity(pid),
+        });
+        app.is_force_redraw = true;
+    }
+}
@@ -0,0 +1,181 @@
+//! Exporters push a [`MetricSnapshot`] to some external destination on every collection tick.
+//! All exporters run on a single background thread, so a slow or unreachable destination
+//! never blocks drawing.
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    sync::mpsc::{self, SyncSender},
+    thread,
+    time::Duration,
+};
+
+use super::{layout_manager::UsedWidgets, metrics::MetricSnapshot};
+use crate::{
+    options::exporter_options::ExporterConfig,
+    utils::error::{BottomError, Result},
+};
+
+pub mod influxdb;
+pub mod mqtt;
+#[cfg(feature = "otlp")]
+pub mod otlp;
+pub mod record;
+pub mod statsd;
+
+/// The default prefix for metric names and topics.
+pub const DEFAULT_EXPORTER_PREFIX: &str = "bottom";
+
+/// How long to wait on a network destination before giving up.
+pub const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Something that metrics can be pushed to.
+pub trait Exporter: Send {
+    fn export(&mut self, snapshot: &MetricSnapshot) -> Result<()>;
+}
+
+/// Creates an exporter from its config.
+pub fn create_exporter(config: &ExporterConfig) -> Result<Box<dyn Exporter>> {
+    match config.kind.to_lowercase().as_str() {
+        "statsd" => Ok(Box::new(statsd::StatsdExporter::new(config)?)),
+        "mqtt" => Ok(Box::new(mqtt::MqttExporter::new(config))),
+        "influxdb" | "influx" => Ok(Box::new(influxdb::InfluxDbExporter::new(config)?)),
+        "record" => Ok(Box::new(record::RecordExporter::new(config)?)),
+        #[cfg(feature = "otlp")]
+        "otlp" => Ok(Box::new(otlp::OtlpExporter::new(config)?)),
+        #[cfg(not(feature = "otlp"))]
+        "otlp" => Err(BottomError::ConfigError(
+            "the otlp exporter requires bottom to be built with the \"otlp\" feature.".to_string(),
+        )),
+        _ => Err(BottomError::ConfigError(format!(
+            "\"{}\" is an invalid exporter kind.  Supported kinds are statsd, mqtt, influxdb, otlp, and record.",
+            config.kind
+        ))),
+    }
+}
+
+/// A handle to the exporter thread.  The thread stops once this is dropped.
+pub struct ExporterHandle {
+    sender: SyncSender<MetricSnapshot>,
+    thread: thread::JoinHandle<()>,
+}
+
+impl ExporterHandle {
+    /// Queues a snapshot to be exported.  If the exporters are still busy with the previous
+    /// snapshot, this one is dropped.
+    pub fn publish(&self, snapshot: MetricSnapshot) {
+        if self.sender.try_send(snapshot).is_err() {
+            debug!("Exporters are busy, skipping a snapshot.");
+        }
+    }
+
+    /// Stops the exporter thread once it's done with any snapshot it was given, so a recording
+    /// isn't cut off partway through a line when bottom exits.
+    pub fn finish(self) {
+        drop(self.sender);
+        if self.thread.join().is_err() {
+            debug!("The exporter thread panicked.");
+        }
+    }
+}
+
+/// Marks what data must be collected to export the metrics requested by the given config.
+fn mark_used_widgets(config: &ExporterConfig, used_widgets: &mut UsedWidgets) {
+    let is_wanted = |category: &str| match &config.metrics {
+        Some(metrics) => metrics
+            .iter()
+            .any(|metric| metric.split('.').next() == Some(category)),
+        None => true,
+    };
+
+    used_widgets.use_cpu |= is_wanted("cpu");
+    used_widgets.use_mem |= is_wanted("mem") || is_wanted("swap");
+    used_widgets.use_net |= is_wanted("net");
+    used_widgets.use_temp |= is_wanted("temp");
+    used_widgets.use_disk |= is_wanted("disk");
+    used_widgets.use_battery |= is_wanted("battery");
+
+    // Process harvesting is expensive, so only do it if explicitly requested.
+    if let Some(metrics) = &config.metrics {
+        used_widgets.use_proc |= metrics
+            .iter()
+            .any(|metric| metric.split('.').next() == Some("proc"));
+    }
+}
+
+/// Starts all configured exporters on a background thread.  Returns `None` if there are none.
+pub fn start_exporters(
+    configs: &[ExporterConfig], used_widgets: &mut UsedWidgets,
+) -> Result<Option<ExporterHandle>> {
+    if configs.is_empty() {
+        return Ok(None);
+    }
+
+    let mut exporters = configs
+        .iter()
+        .map(|config| {
+            mark_used_widgets(config, used_widgets);
+            Ok((create_exporter(config)?, config.metrics.clone()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (sender, receiver) = mpsc::sync_channel::<MetricSnapshot>(1);
+    let thread = thread::spawn(move || {
+        for snapshot in receiver {
+            for (exporter, metrics) in &mut exporters {
+                let filtered = MetricSnapshot {
+                    timestamp: snapshot.timestamp,
+                    metrics: snapshot
+                        .filtered(metrics.as_deref())
+                        .map(|(name, value)| (name.clone(), *value))
+                        .collect(),
+                };
+                if let Err(err) = exporter.export(&filtered) {
+                    debug!("Unable to export metrics: {}", err);
+                }
+            }
+        }
+        trace!("Exporter thread has closed.");
+    });
+
+    Ok(Some(ExporterHandle { sender, thread }))
+}
+
+/// Sends a body to a plain HTTP endpoint with a POST request, and checks that the response
+/// was successful.
+pub fn http_post(
+    host: &str, path: &str, headers: &[(&str, String)], content_type: &str, body: &str,
+) -> Result<()> {
+    let mut stream = TcpStream::connect(host)?;
+    stream.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+    stream.set_write_timeout(Some(NETWORK_TIMEOUT))?;
+
+    let extra_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\n{}Content-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        extra_headers,
+        content_type,
+        body.len(),
+        body
+    )?;
+
+    // We only care about the status code, ie: "HTTP/1.1 204".
+    let mut status_line = [0; 12];
+    stream.read_exact(&mut status_line)?;
+    let status = String::from_utf8_lossy(&status_line);
+    if !status.starts_with("HTTP/1.") || !status[9..].starts_with('2') {
+        return Err(BottomError::GenericError(format!(
+            "{} responded with status {}.",
+            host,
+            status[9..].trim()
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! Writes metrics in the InfluxDB line protocol to a file, a UDP or TCP socket, or an HTTP
+//! write endpoint.
+
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::Write,
+    net::{TcpStream, UdpSocket},
+    path::PathBuf,
+};
+
+use super::{http_post, Exporter, DEFAULT_EXPORTER_PREFIX, NETWORK_TIMEOUT};
+use crate::{
+    app::metrics::MetricSnapshot,
+    options::exporter_options::ExporterConfig,
+    utils::error::{BottomError, Result},
+};
+
+const DEFAULT_BATCH_SIZE: usize = 1;
+const DEFAULT_RETRIES: u32 = 3;
+
+/// The most lines held while a destination is failing, so memory use stays bounded.
+const MAX_PENDING_LINES: usize = 10_000;
+
+enum Destination {
+    File(PathBuf),
+    Udp(String),
+    Tcp(String),
+    Http {
+        host: String,
+        path: String,
+        token: Option<String>,
+    },
+}
+
+impl Destination {
+    fn parse(address: &str, token: Option<String>) -> Result<Self> {
+        if let Some(path) = address.strip_prefix("file://") {
+            Ok(Destination::File(PathBuf::from(path)))
+        } else if let Some(host) = address.strip_prefix("udp://") {
+            Ok(Destination::Udp(host.to_string()))
+        } else if let Some(host) = address.strip_prefix("tcp://") {
+            Ok(Destination::Tcp(host.to_string()))
+        } else if let Some(rest) = address.strip_prefix("http://") {
+            let (host, path) = match rest.find('/') {
+                Some(index) => (&rest[..index], &rest[index..]),
+                None => (rest, "/write"),
+            };
+            Ok(Destination::Http {
+                host: host.to_string(),
+                path: path.to_string(),
+                token,
+            })
+        } else {
+            Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid InfluxDB address.  It must start with file://, udp://, tcp://, or http://.",
+                address
+            )))
+        }
+    }
+
+    fn write(&self, lines: &str) -> Result<()> {
+        match self {
+            Destination::File(path) => {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                file.write_all(lines.as_bytes())?;
+            }
+            Destination::Udp(host) => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(host)?;
+                socket.send(lines.as_bytes())?;
+            }
+            Destination::Tcp(host) => {
+                let mut stream = TcpStream::connect(host)?;
+                stream.set_write_timeout(Some(NETWORK_TIMEOUT))?;
+                stream.write_all(lines.as_bytes())?;
+            }
+            Destination::Http { host, path, token } => {
+                let headers = match token {
+                    Some(token) => vec![("Authorization", format!("Token {}", token))],
+                    None => vec![],
+                };
+                http_post(host, path, &headers, "text/plain; charset=utf-8", lines)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Batches snapshots as line protocol, and retries failed writes on later ticks.
+pub struct InfluxDbExporter {
+    destination: Destination,
+    prefix: String,
+    batch_size: usize,
+    retries: u32,
+    pending_lines: Vec<String>,
+    pending_snapshots: usize,
+    failures: u32,
+}
+
+impl InfluxDbExporter {
+    pub fn new(config: &ExporterConfig) -> Result<Self> {
+        Ok(InfluxDbExporter {
+            destination: Destination::parse(&config.address, config.token.clone())?,
+            prefix: config
+                .prefix
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EXPORTER_PREFIX.to_string()),
+            batch_size: config.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+            retries: config.retries.unwrap_or(DEFAULT_RETRIES),
+            pending_lines: Vec::new(),
+            pending_snapshots: 0,
+            failures: 0,
+        })
+    }
+}
+
+impl Exporter for InfluxDbExporter {
+    fn export(&mut self, snapshot: &MetricSnapshot) -> Result<()> {
+        self.pending_lines
+            .extend(to_line_protocol(snapshot, &self.prefix));
+        self.pending_snapshots += 1;
+
+        if self.pending_lines.len() > MAX_PENDING_LINES {
+            let excess = self.pending_lines.len() - MAX_PENDING_LINES;
+            self.pending_lines.drain(..excess);
+        }
+
+        if self.pending_snapshots < self.batch_size {
+            return Ok(());
+        }
+
+        let mut lines = self.pending_lines.join("\n");
+        lines.push('\n');
+        match self.destination.write(&lines) {
+            Ok(()) => {
+                self.pending_lines.clear();
+                self.pending_snapshots = 0;
+                self.failures = 0;
+                Ok(())
+            }
+            Err(err) => {
+                self.failures += 1;
+                if self.failures > self.retries {
+                    // Give up on this batch.
+                    self.pending_lines.clear();
+                    self.pending_snapshots = 0;
+                    self.failures = 0;
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Converts a snapshot into line protocol.  The first segment of a metric's name is used as
+/// the measurement, and the last as the field.  Any segment in between is used as the value of
+/// the `instance` tag; for example, with a prefix of `bottom`, `disk.sda1.used_percent` becomes
+/// `bottom_disk,instance=sda1 used_percent=...`.
+pub fn to_line_protocol(snapshot: &MetricSnapshot, prefix: &str) -> Vec<String> {
+    let mut points: BTreeMap<(&str, Option<&str>), Vec<(&str, f64)>> = BTreeMap::new();
+    for (name, value) in &snapshot.metrics {
+        if !value.is_finite() {
+            continue;
+        }
+        let segments: Vec<&str> = name.split('.').collect();
+        let (measurement, instance, field) = match segments.as_slice() {
+            [measurement, field] => (*measurement, None, *field),
+            [measurement, instance, field] => (*measurement, Some(*instance), *field),
+            _ => continue,
+        };
+        points
+            .entry((measurement, instance))
+            .or_default()
+            .push((field, *value));
+    }
+
+    let timestamp = u128::from(snapshot.timestamp) * 1_000_000;
+    points
+        .into_iter()
+        .map(|((measurement, instance), fields)| {
+            let measurement = if prefix.is_empty() {
+                escape(measurement)
+            } else {
+                format!("{}_{}", escape(prefix), escape(measurement))
+            };
+            let tags = match instance {
+                Some(instance) => format!(",instance={}", escape(instance)),
+                None => String::new(),
+            };
+            let fields = fields
+                .iter()
+                .map(|(field, value)| format!("{}={}", escape(field), value))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{}{} {} {}", measurement, tags, fields, timestamp)
+        })
+        .collect()
+}
+
+fn escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        if c == ',' || c == '=' || c == ' ' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,149 @@
+//! A minimal MQTT 3.1.1 client, which only supports publishing at QoS 0.
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+};
+
+use super::{Exporter, DEFAULT_EXPORTER_PREFIX, NETWORK_TIMEOUT};
+use crate::{
+    app::metrics::MetricSnapshot,
+    options::exporter_options::ExporterConfig,
+    utils::error::{BottomError, Result},
+};
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const DISCONNECT: u8 = 0xe0;
+
+/// Publishes every metric to its own topic, under the configured prefix.  For example,
+/// `cpu.avg` is published to `bottom/cpu/avg`.
+pub struct MqttExporter {
+    address: String,
+    prefix: String,
+    client_id: String,
+    username: Option<String>,
+    password: Option<String>,
+    stream: Option<TcpStream>,
+}
+
+impl MqttExporter {
+    pub fn new(config: &ExporterConfig) -> Self {
+        MqttExporter {
+            address: config.address.clone(),
+            prefix: config
+                .prefix
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EXPORTER_PREFIX.to_string()),
+            client_id: config
+                .client_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EXPORTER_PREFIX.to_string()),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            stream: None,
+        }
+    }
+
+    fn connect(&self) -> Result<TcpStream> {
+        let mut stream = TcpStream::connect(&self.address)?;
+        stream.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+        stream.set_write_timeout(Some(NETWORK_TIMEOUT))?;
+
+        // Clean session, no keep alive, as we publish on every tick anyways.
+        let mut flags = 0x02;
+        let mut payload = encode_string(&self.client_id);
+        if let Some(username) = &self.username {
+            flags |= 0x80;
+            payload.extend(encode_string(username));
+        }
+        if let Some(password) = &self.password {
+            flags |= 0x40;
+            payload.extend(encode_string(password));
+        }
+
+        let mut body = encode_string("MQTT");
+        body.extend(&[4, flags, 0, 0]);
+        body.extend(payload);
+        stream.write_all(&encode_packet(CONNECT, &body))?;
+
+        let mut connack = [0; 4];
+        stream.read_exact(&mut connack)?;
+        if connack[0] != CONNACK || connack[3] != 0 {
+            return Err(BottomError::GenericError(format!(
+                "MQTT broker at {} refused the connection with code {}.",
+                self.address, connack[3]
+            )));
+        }
+
+        Ok(stream)
+    }
+}
+
+impl Exporter for MqttExporter {
+    fn export(&mut self, snapshot: &MetricSnapshot) -> Result<()> {
+        if self.stream.is_none() {
+            self.stream = Some(self.connect()?);
+        }
+
+        let mut packets = Vec::new();
+        for (name, value) in &snapshot.metrics {
+            let topic = if self.prefix.is_empty() {
+                name.replace('.', "/")
+            } else {
+                format!("{}/{}", self.prefix, name.replace('.', "/"))
+            };
+            let mut body = encode_string(&topic);
+            body.extend(value.to_string().as_bytes());
+            packets.extend(encode_packet(PUBLISH, &body));
+        }
+
+        if let Some(stream) = &mut self.stream {
+            if let Err(err) = stream.write_all(&packets) {
+                // Reconnect on the next tick.
+                self.stream = None;
+                return Err(err.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for MqttExporter {
+    fn drop(&mut self) {
+        if let Some(stream) = &mut self.stream {
+            let _ = stream.write_all(&[DISCONNECT, 0]);
+        }
+    }
+}
+
+fn encode_string(string: &str) -> Vec<u8> {
+    let bytes = string.as_bytes();
+    let mut encoded = Vec::with_capacity(bytes.len() + 2);
+    encoded.extend(&(bytes.len() as u16).to_be_bytes());
+    encoded.extend(bytes);
+    encoded
+}
+
+fn encode_packet(packet_type: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![packet_type];
+
+    // The remaining length is encoded 7 bits at a time, with the top bit as a continuation flag.
+    let mut remaining = body.len();
+    loop {
+        let mut byte = (remaining % 128) as u8;
+        remaining /= 128;
+        if remaining > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    packet.extend(body);
+    packet
+}
@@ -0,0 +1,105 @@
+//! Exports metrics as OpenTelemetry gauges, using OTLP's JSON encoding over HTTP.
+
+use serde_json::{json, Value};
+
+use super::{http_post, Exporter, DEFAULT_EXPORTER_PREFIX};
+use crate::{
+    app::metrics::MetricSnapshot,
+    options::exporter_options::ExporterConfig,
+    utils::error::{BottomError, Result},
+};
+
+/// The path OTLP/HTTP collectors accept metrics on.
+const DEFAULT_OTLP_PATH: &str = "/v1/metrics";
+
+pub struct OtlpExporter {
+    host: String,
+    path: String,
+    prefix: String,
+    token: Option<String>,
+}
+
+impl OtlpExporter {
+    pub fn new(config: &ExporterConfig) -> Result<Self> {
+        let rest = config.address.strip_prefix("http://").ok_or_else(|| {
+            BottomError::ConfigError(format!(
+                "\"{}\" is an invalid OTLP address.  It must start with http://.",
+                config.address
+            ))
+        })?;
+        let (host, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, DEFAULT_OTLP_PATH),
+        };
+
+        Ok(OtlpExporter {
+            host: host.to_string(),
+            path: path.to_string(),
+            prefix: config
+                .prefix
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EXPORTER_PREFIX.to_string()),
+            token: config.token.clone(),
+        })
+    }
+}
+
+impl Exporter for OtlpExporter {
+    fn export(&mut self, snapshot: &MetricSnapshot) -> Result<()> {
+        let headers = match &self.token {
+            Some(token) => vec![("Authorization", format!("Bearer {}", token))],
+            None => vec![],
+        };
+
+        http_post(
+            &self.host,
+            &self.path,
+            &headers,
+            "application/json",
+            &to_otlp_json(snapshot, &self.prefix).to_string(),
+        )
+    }
+}
+
+/// Converts a snapshot into an OTLP `ExportMetricsServiceRequest`, with every metric as a gauge.
+pub fn to_otlp_json(snapshot: &MetricSnapshot, prefix: &str) -> Value {
+    let time_unix_nano = (u128::from(snapshot.timestamp) * 1_000_000).to_string();
+    let metrics: Vec<Value> = snapshot
+        .metrics
+        .iter()
+        .filter(|(_, value)| value.is_finite())
+        .map(|(name, value)| {
+            json!({
+                "name": if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                },
+                "gauge": {
+                    "dataPoints": [{
+                        "timeUnixNano": time_unix_nano,
+                        "asDouble": value,
+                    }]
+                }
+            })
+        })
+        .collect();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [{
+                    "key": "service.name",
+                    "value": { "stringValue": "bottom" }
+                }]
+            },
+            "scopeMetrics": [{
+                "scope": {
+                    "name": "bottom",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "metrics": metrics,
+            }]
+        }]
+    })
+}
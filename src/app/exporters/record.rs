@@ -0,0 +1,176 @@
+//! Records every snapshot as a line of JSON to a file, rotating it by age or size so that bottom
+//! can run indefinitely as a flight recorder without filling the disk.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use super::Exporter;
+use crate::{
+    app::metrics::MetricSnapshot,
+    options::exporter_options::ExporterConfig,
+    utils::error::{BottomError, Result},
+};
+
+/// How many rotated files are kept by default.
+const DEFAULT_RECORD_KEEP: usize = 5;
+
+/// When the recording is moved aside and a new one started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// Once the recording is this old.
+    Every(Duration),
+    /// Once the recording is at least this many bytes.
+    Size(u64),
+}
+
+impl std::str::FromStr for Rotation {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lower_case = s.trim().to_lowercase();
+        let unit_start = lower_case
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(lower_case.len());
+        let (value, unit) = lower_case.split_at(unit_start);
+        let value = value.parse::<f64>().ok().filter(|value| *value > 0.0);
+
+        let rotation = value.and_then(|value| match unit.trim() {
+            "s" => Some(Rotation::Every(Duration::from_secs_f64(value))),
+            "m" => Some(Rotation::Every(Duration::from_secs_f64(value * 60.0))),
+            "h" => Some(Rotation::Every(Duration::from_secs_f64(value * 3600.0))),
+            "d" => Some(Rotation::Every(Duration::from_secs_f64(value * 86400.0))),
+            "b" => Some(Rotation::Size(value as u64)),
+            "kb" => Some(Rotation::Size((value * 1e3) as u64)),
+            "mb" => Some(Rotation::Size((value * 1e6) as u64)),
+            "gb" => Some(Rotation::Size((value * 1e9) as u64)),
+            "kib" => Some(Rotation::Size((value * 1024.0) as u64)),
+            "mib" => Some(Rotation::Size((value * 1024.0 * 1024.0) as u64)),
+            "gib" => Some(Rotation::Size((value * 1024.0 * 1024.0 * 1024.0) as u64)),
+            _ => None,
+        });
+
+        rotation.ok_or_else(|| {
+            BottomError::ConfigError(format!(
+                "\"{}\" is an invalid record_rotate value.  It must be a duration like \"1h\" or a size like \"100mb\".",
+                s
+            ))
+        })
+    }
+}
+
+/// Appends snapshots to a file as JSON lines.  Rotated files are named like the recording with
+/// `.1` for the newest, up to `.<record_keep>` for the oldest.
+pub struct RecordExporter {
+    path: PathBuf,
+    rotation: Option<Rotation>,
+    keep: usize,
+    file: Option<File>,
+    /// When the current recording was started, for rotating by age.
+    started: SystemTime,
+}
+
+impl RecordExporter {
+    pub fn new(config: &ExporterConfig) -> Result<Self> {
+        let rotation = config
+            .record_rotate
+            .as_deref()
+            .map(str::parse::<Rotation>)
+            .transpose()?;
+        let path = PathBuf::from(&config.address);
+
+        // Pick up where a previous run left off, so restarting bottom doesn't reset the clock.
+        let started = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.created())
+            .unwrap_or_else(|_| SystemTime::now());
+
+        Ok(RecordExporter {
+            path,
+            rotation,
+            keep: config.record_keep.unwrap_or(DEFAULT_RECORD_KEEP),
+            file: None,
+            started,
+        })
+    }
+
+    fn should_rotate(&self) -> bool {
+        match self.rotation {
+            Some(Rotation::Every(period)) => self
+                .started
+                .elapsed()
+                .is_ok_and(|elapsed| elapsed >= period),
+            Some(Rotation::Size(size)) => {
+                std::fs::metadata(&self.path).is_ok_and(|metadata| metadata.len() >= size)
+            }
+            None => false,
+        }
+    }
+
+    /// Shifts every rotated file up by one, dropping the oldest, and moves the recording to `.1`.
+    fn rotate(&mut self) -> Result<()> {
+        self.file = None;
+
+        if self.keep == 0 {
+            remove_if_exists(&self.path)?;
+        } else {
+            remove_if_exists(&rotated_path(&self.path, self.keep))?;
+            for index in (1..self.keep).rev() {
+                let from = rotated_path(&self.path, index);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, index + 1))?;
+                }
+            }
+            if self.path.exists() {
+                std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+            }
+        }
+
+        self.started = SystemTime::now();
+        Ok(())
+    }
+}
+
+impl Exporter for RecordExporter {
+    fn export(&mut self, snapshot: &MetricSnapshot) -> Result<()> {
+        if self.should_rotate() {
+            self.rotate()?;
+        }
+
+        let mut line = serde_json::to_string(snapshot)
+            .map_err(|err| BottomError::ConversionError(err.to_string()))?;
+        line.push('\n');
+
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self.file.insert(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            ),
+        };
+        if let Err(err) = file.write_all(line.as_bytes()) {
+            // Reopen the file next time, in case it was moved or deleted under us.
+            self.file = None;
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", index));
+    PathBuf::from(rotated)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
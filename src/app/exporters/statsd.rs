@@ -0,0 +1,77 @@
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use super::{Exporter, DEFAULT_EXPORTER_PREFIX};
+use crate::{
+    app::metrics::MetricSnapshot,
+    options::exporter_options::ExporterConfig,
+    utils::error::{BottomError, Result},
+};
+
+/// Keep packets small enough to avoid fragmentation on most networks.
+const MAX_PACKET_SIZE: usize = 1432;
+
+/// Sends every metric as a StatsD gauge over UDP.
+pub struct StatsdExporter {
+    socket: UdpSocket,
+    address: SocketAddr,
+    prefix: String,
+}
+
+impl StatsdExporter {
+    pub fn new(config: &ExporterConfig) -> Result<Self> {
+        let address = config
+            .address
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| {
+                BottomError::ConfigError(format!(
+                    "\"{}\" is an invalid StatsD address.",
+                    config.address
+                ))
+            })?;
+
+        let socket = UdpSocket::bind(if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        })?;
+
+        Ok(StatsdExporter {
+            socket,
+            address,
+            prefix: config
+                .prefix
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EXPORTER_PREFIX.to_string()),
+        })
+    }
+}
+
+impl Exporter for StatsdExporter {
+    fn export(&mut self, snapshot: &MetricSnapshot) -> Result<()> {
+        let mut packet = String::new();
+        for (name, value) in &snapshot.metrics {
+            let line = if self.prefix.is_empty() {
+                format!("{}:{}|g", name, value)
+            } else {
+                format!("{}.{}:{}|g", self.prefix, name, value)
+            };
+
+            if !packet.is_empty() && packet.len() + line.len() + 1 > MAX_PACKET_SIZE {
+                self.socket.send_to(packet.as_bytes(), self.address)?;
+                packet.clear();
+            }
+            if !packet.is_empty() {
+                packet.push('\n');
+            }
+            packet.push_str(&line);
+        }
+
+        if !packet.is_empty() {
+            self.socket.send_to(packet.as_bytes(), self.address)?;
+        }
+
+        Ok(())
+    }
+}
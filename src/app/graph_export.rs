@@ -0,0 +1,147 @@
+//! Exports the samples of a graph's visible time range to CSV, for analysis in a spreadsheet.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use super::data_farmer::DataCollection;
+use crate::utils::error::Result;
+
+/// Which graphs to export the samples of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportedGraphs {
+    Cpu,
+    Mem,
+    Net,
+    All,
+}
+
+impl ExportedGraphs {
+    fn name(&self) -> &'static str {
+        match self {
+            ExportedGraphs::Cpu => "cpu",
+            ExportedGraphs::Mem => "mem",
+            ExportedGraphs::Net => "net",
+            ExportedGraphs::All => "all",
+        }
+    }
+
+    fn has_cpu(&self) -> bool {
+        matches!(self, ExportedGraphs::Cpu | ExportedGraphs::All)
+    }
+
+    fn has_mem(&self) -> bool {
+        matches!(self, ExportedGraphs::Mem | ExportedGraphs::All)
+    }
+
+    fn has_net(&self) -> bool {
+        matches!(self, ExportedGraphs::Net | ExportedGraphs::All)
+    }
+}
+
+/// Returns where an export of the given graphs should be written, which is the `exports`
+/// directory in bottom's data directory.
+pub fn get_export_path(graphs: ExportedGraphs) -> PathBuf {
+    let mut path = crate::utils::logging::get_data_dir();
+    path.push("exports");
+    path.push(format!(
+        "{}-{}.csv",
+        graphs.name(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0)
+    ));
+    path
+}
+
+/// Writes the samples of the given graphs from the last `display_time` milliseconds shown to a
+/// CSV file, with one row per sample.  If the data is frozen, the range ends where it was frozen.
+/// Returns how many rows were written.
+pub fn export_graph_range(
+    data_collection: &DataCollection, is_frozen: bool, display_time: u64, graphs: ExportedGraphs,
+    path: &Path,
+) -> Result<usize> {
+    let end = match (is_frozen, data_collection.frozen_instant) {
+        (true, Some(frozen_instant)) => frozen_instant,
+        _ => data_collection.current_instant,
+    };
+    let start = end
+        .checked_sub(Duration::from_millis(display_time))
+        .unwrap_or(end);
+
+    let mut header = vec!["unix_time_ms".to_string(), "offset_secs".to_string()];
+    if graphs.has_cpu() {
+        header.extend(data_collection.cpu_harvest.iter().map(|cpu| {
+            match cpu.cpu_count {
+                Some(cpu_count) => format!("{}{}_percent", cpu.cpu_prefix, cpu_count),
+                None => format!("{}_percent", cpu.cpu_prefix),
+            }
+            .to_lowercase()
+        }));
+    }
+    if graphs.has_mem() {
+        header.push("mem_percent".to_string());
+        header.push("swap_percent".to_string());
+    }
+    if graphs.has_net() {
+        header.push("rx_bytes_per_sec".to_string());
+        header.push("tx_bytes_per_sec".to_string());
+    }
+
+    let now = (Instant::now(), SystemTime::now());
+    let mut csv = header.join(",");
+    csv.push('\n');
+    let mut rows = 0;
+    for (time, data) in data_collection
+        .timed_data_vec
+        .iter()
+        .filter(|(time, _)| *time >= start && *time <= end)
+    {
+        let unix_time_ms = (now.1 - now.0.saturating_duration_since(*time))
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or(0);
+        let mut row = vec![
+            unix_time_ms.to_string(),
+            format!("{:.3}", 0.0 - end.duration_since(*time).as_secs_f64()),
+        ];
+        if graphs.has_cpu() {
+            row.extend((0..data_collection.cpu_harvest.len()).map(|index| {
+                match data.cpu_data.get(index) {
+                    Some(cpu) => format!("{:.2}", cpu),
+                    None => String::new(),
+                }
+            }));
+        }
+        if graphs.has_mem() {
+            row.push(format!("{:.2}", data.mem_data));
+            row.push(format!("{:.2}", data.swap_data));
+        }
+        if graphs.has_net() {
+            // Network data is stored as the log2 of the rate.
+            row.push(format!("{:.0}", from_log2(data.rx_data)));
+            row.push(format!("{:.0}", from_log2(data.tx_data)));
+        }
+
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+        rows += 1;
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, csv)?;
+
+    Ok(rows)
+}
+
+fn from_log2(value: f64) -> f64 {
+    if value > 0.0 {
+        value.exp2()
+    } else {
+        0.0
+    }
+}
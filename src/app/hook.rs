@@ -0,0 +1,110 @@
+//! Runs a user-defined command after each collection, passing the latest [`MetricSnapshot`]
+//! as JSON on its stdin.  The command runs on a background thread, so a slow hook never
+//! blocks drawing.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    sync::mpsc::{self, SyncSender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{layout_manager::UsedWidgets, metrics::MetricSnapshot};
+use crate::utils::error::{BottomError, Result};
+
+/// A handle to the hook thread.  The thread stops once this is dropped.
+pub struct HookHandle {
+    sender: SyncSender<MetricSnapshot>,
+    interval: Duration,
+    last_sent: Option<Instant>,
+}
+
+impl HookHandle {
+    /// Queues a snapshot for the hook, unless it ran less than the configured interval ago or
+    /// is still handling the previous snapshot.
+    pub fn publish(&mut self, snapshot: MetricSnapshot) {
+        if let Some(last_sent) = self.last_sent {
+            if last_sent.elapsed() < self.interval {
+                return;
+            }
+        }
+
+        if self.sender.try_send(snapshot).is_ok() {
+            self.last_sent = Some(Instant::now());
+        } else {
+            debug!("Hook is still running, skipping a snapshot.");
+        }
+    }
+}
+
+/// Starts running the given hook command on a background thread.  Like exporters, the hook
+/// needs every metric except `proc.count`, so the matching data is marked as used.
+pub fn start_hook(
+    command: &str, interval_in_ms: u64, used_widgets: &mut UsedWidgets,
+) -> HookHandle {
+    used_widgets.use_cpu = true;
+    used_widgets.use_mem = true;
+    used_widgets.use_net = true;
+    used_widgets.use_temp = true;
+    used_widgets.use_disk = true;
+    used_widgets.use_battery = true;
+
+    let command = command.to_string();
+    let (sender, receiver) = mpsc::sync_channel::<MetricSnapshot>(1);
+    thread::spawn(move || {
+        for snapshot in receiver {
+            if let Err(err) = run_hook(&command, &snapshot) {
+                debug!("Unable to run hook \"{}\": {}", command, err);
+            }
+        }
+        trace!("Hook thread has closed.");
+    });
+
+    HookHandle {
+        sender,
+        interval: Duration::from_millis(interval_in_ms),
+        last_sent: None,
+    }
+}
+
+/// Runs the command through the system shell, writing the snapshot to its stdin, and waits
+/// for it to exit.
+fn run_hook(command: &str, snapshot: &MetricSnapshot) -> Result<()> {
+    let json = serde_json::to_string(snapshot)
+        .map_err(|err| BottomError::ConversionError(err.to_string()))?;
+
+    #[cfg(target_family = "unix")]
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    #[cfg(not(target_family = "unix"))]
+    let mut child = Command::new("cmd")
+        .arg("/C")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The hook may exit without reading its input, which is fine.
+        let _ = stdin.write_all(json.as_bytes());
+        let _ = stdin.write_all(b"\n");
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(BottomError::GenericError(format!(
+            "hook exited with {}.",
+            status
+        )));
+    }
+
+    Ok(())
+}
@@ -875,10 +875,15 @@ pub struct BottomWidget {
     /// Bottom right corner when drawn, for mouse click detection.  (x, y)
     #[builder(default = None)]
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// The search query a process widget starts with, if any.
+    #[builder(default = None)]
+    pub default_query: Option<String>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
 pub enum BottomWidgetType {
+    #[default]
     Empty,
     Cpu,
     CpuLegend,
@@ -894,6 +899,7 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Custom(String),
 }
 
 impl BottomWidgetType {
@@ -922,12 +928,6 @@ impl BottomWidgetType {
     }
 }
 
-impl Default for BottomWidgetType {
-    fn default() -> Self {
-        BottomWidgetType::Empty
-    }
-}
-
 impl std::str::FromStr for BottomWidgetType {
     type Err = BottomError;
 
@@ -942,6 +942,7 @@ impl std::str::FromStr for BottomWidgetType {
             "disk" => Ok(BottomWidgetType::Disk),
             "empty" => Ok(BottomWidgetType::Empty),
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
+            "custom" => Ok(BottomWidgetType::Custom(String::new())),
             _ => Err(BottomError::ConfigError(format!(
                 "\"{}\" is an invalid widget name.
 
@@ -960,6 +961,8 @@ Supported widget names:
 |           disk           |
 +--------------------------+
 |       batt, battery      |
++--------------------------+
+|          custom          |
 +--------------------------+
                 ",
                 s
@@ -0,0 +1,155 @@
+//! A flat snapshot of the most recently harvested metrics, shared by everything that
+//! sends data outside of bottom.
+
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use super::data_farmer::DataCollection;
+use crate::utils::error::{BottomError, Result};
+
+/// A set of named metrics, all collected at the same time.  Names are dot-separated,
+/// starting with a category; for example, `cpu.avg` or `disk.sda1.used_percent`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetricSnapshot {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+impl MetricSnapshot {
+    pub fn from_data_collection(data_collection: &DataCollection) -> Self {
+        let mut metrics = BTreeMap::new();
+
+        for cpu in &data_collection.cpu_harvest {
+            let name = match cpu.cpu_count {
+                Some(core) => format!("cpu.{}", core),
+                None => "cpu.avg".to_string(),
+            };
+            metrics.insert(name, cpu.cpu_usage);
+        }
+
+        for (category, harvest) in &[
+            ("mem", &data_collection.memory_harvest),
+            ("swap", &data_collection.swap_harvest),
+        ] {
+            if harvest.mem_total_in_mb > 0 {
+                metrics.insert(
+                    format!("{}.percent", category),
+                    harvest.mem_used_in_mb as f64 / harvest.mem_total_in_mb as f64 * 100.0,
+                );
+                metrics.insert(
+                    format!("{}.used_mb", category),
+                    harvest.mem_used_in_mb as f64,
+                );
+                metrics.insert(
+                    format!("{}.total_mb", category),
+                    harvest.mem_total_in_mb as f64,
+                );
+            }
+        }
+
+        let network_harvest = &data_collection.network_harvest;
+        metrics.insert("net.rx".to_string(), network_harvest.rx as f64);
+        metrics.insert("net.tx".to_string(), network_harvest.tx as f64);
+        metrics.insert("net.total_rx".to_string(), network_harvest.total_rx as f64);
+        metrics.insert("net.total_tx".to_string(), network_harvest.total_tx as f64);
+
+        for temp in &data_collection.temp_harvest {
+            if let Some(sensor) = temp
+                .component_label
+                .as_ref()
+                .or(temp.component_name.as_ref())
+            {
+                metrics.insert(
+                    format!("temp.{}", sanitize_name(sensor)),
+                    temp.temperature as f64,
+                );
+            }
+        }
+
+        for disk in &data_collection.disk_harvest {
+            let name = sanitize_name(disk.name.trim_start_matches("/dev/"));
+            if disk.total_space > 0 {
+                metrics.insert(
+                    format!("disk.{}.used_percent", name),
+                    disk.used_space as f64 / disk.total_space as f64 * 100.0,
+                );
+            }
+            metrics.insert(format!("disk.{}.used_bytes", name), disk.used_space as f64);
+            metrics.insert(
+                format!("disk.{}.total_bytes", name),
+                disk.total_space as f64,
+            );
+        }
+
+        for (index, battery) in data_collection.battery_harvest.iter().enumerate() {
+            metrics.insert(
+                format!("battery.{}.charge_percent", index),
+                battery.charge_percent,
+            );
+        }
+
+        if !data_collection.process_harvest.is_empty() {
+            metrics.insert(
+                "proc.count".to_string(),
+                data_collection.process_harvest.len() as f64,
+            );
+        }
+
+        MetricSnapshot {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or(0),
+            metrics,
+        }
+    }
+
+    /// Returns the metrics whose names start with one of the given prefixes, or all of them
+    /// if no prefixes are given.  A prefix matches whole segments, so `cpu` matches `cpu.avg`
+    /// but `cp` does not.
+    pub fn filtered<'a>(
+        &'a self, prefixes: Option<&'a [String]>,
+    ) -> impl Iterator<Item = (&'a String, &'a f64)> + 'a {
+        self.metrics.iter().filter(move |(name, _)| match prefixes {
+            Some(prefixes) => prefixes.iter().any(|prefix| {
+                name.as_str() == prefix
+                    || (name.starts_with(prefix.as_str()) && name[prefix.len()..].starts_with('.'))
+            }),
+            None => true,
+        })
+    }
+}
+
+/// Writes a snapshot as JSON to the given path.  The file is replaced atomically, so readers
+/// never see a partially written snapshot.
+pub fn write_snapshot_json(snapshot: &MetricSnapshot, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(snapshot)
+        .map_err(|err| BottomError::ConversionError(err.to_string()))?;
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+/// Makes a name safe to use as a single metric name segment.
+fn sanitize_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,218 @@
+//! Third-party data sources, run as subprocesses that speak a JSON line protocol.
+//!
+//! A plugin is any program that writes one JSON object per line to stdout.  Each line
+//! replaces the plugin's previous value, and must be one of:
+//!
+//! - `{"value": 42.0}` for a number,
+//! - `{"text": "some text"}` for text,
+//! - `{"headers": ["a", "b"], "rows": [["1", "2"]]}` for a table, or
+//! - `{"samples": [1.0, 2.5]}` for a batch of samples from a distribution, such as latencies.
+//!   Unlike other values, samples are buffered until each widget has read them.
+//!
+//! Plugins are only started if a widget is bound to them, and are killed when bottom exits.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde::Deserialize;
+
+use super::{
+    data_farmer::DataCollection,
+    data_source::{DataSource, DataValue},
+};
+use crate::{
+    options::widget_options::PluginConfig,
+    utils::error::{BottomError, Result},
+};
+
+/// A single line of output from a plugin.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum PluginMessage {
+    Value {
+        value: f64,
+    },
+    Text {
+        text: String,
+    },
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    Samples {
+        samples: Vec<f64>,
+    },
+}
+
+impl From<PluginMessage> for DataValue {
+    fn from(message: PluginMessage) -> Self {
+        match message {
+            PluginMessage::Value { value } => DataValue::Scalar(value),
+            PluginMessage::Text { text } => DataValue::Text(text),
+            PluginMessage::Table { headers, rows } => DataValue::Table { headers, rows },
+            PluginMessage::Samples { samples } => DataValue::Samples(samples),
+        }
+    }
+}
+
+/// Parses a line of plugin output.
+pub fn parse_plugin_line(line: &str) -> Result<DataValue> {
+    serde_json::from_str::<PluginMessage>(line)
+        .map(DataValue::from)
+        .map_err(|err| BottomError::GenericError(format!("invalid plugin output: {}", err)))
+}
+
+/// The most samples kept for sources that have not read them yet.
+const MAX_BUFFERED_SAMPLES: usize = 10_000;
+
+/// What a plugin has sent so far.
+#[derive(Default)]
+struct PluginOutput {
+    /// The most recent value, or the most recent batch of samples.
+    latest: Option<DataValue>,
+    /// Recently sent samples, so none are lost if several batches arrive between updates.
+    samples: Vec<f64>,
+    /// How many samples were dropped from the start of `samples`.
+    dropped_samples: usize,
+}
+
+impl PluginOutput {
+    fn push(&mut self, value: DataValue) {
+        if let DataValue::Samples(samples) = &value {
+            self.samples.extend(samples);
+            if self.samples.len() > MAX_BUFFERED_SAMPLES {
+                let excess = self.samples.len() - MAX_BUFFERED_SAMPLES;
+                self.samples.drain(..excess);
+                self.dropped_samples += excess;
+            }
+        }
+        self.latest = Some(value);
+    }
+}
+
+type SharedOutput = Arc<Mutex<PluginOutput>>;
+
+/// A data source backed by a running plugin.
+pub struct PluginSource {
+    output: SharedOutput,
+    /// The index of the next sample this source has not read yet, counting dropped samples.
+    next_sample: usize,
+}
+
+impl DataSource for PluginSource {
+    fn sample(&mut self, _data_collection: &DataCollection) -> Option<DataValue> {
+        let output = self.output.lock().ok()?;
+        match output.latest.as_ref()? {
+            DataValue::Samples(_) => {
+                let start = self
+                    .next_sample
+                    .saturating_sub(output.dropped_samples)
+                    .min(output.samples.len());
+                self.next_sample = output.dropped_samples + output.samples.len();
+                Some(DataValue::Samples(output.samples[start..].to_vec()))
+            }
+            value => Some(value.clone()),
+        }
+    }
+}
+
+/// Starts and owns all plugin subprocesses.
+#[derive(Default)]
+pub struct PluginHost {
+    configs: Vec<PluginConfig>,
+    running: HashMap<String, SharedOutput>,
+    children: Vec<Child>,
+}
+
+impl PluginHost {
+    pub fn new(configs: Vec<PluginConfig>) -> Self {
+        PluginHost {
+            configs,
+            running: HashMap::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns a source for the plugin with the given name, starting the plugin if it is
+    /// not already running.
+    pub fn get_source(&mut self, name: &str) -> Result<PluginSource> {
+        if let Some(output) = self.running.get(name) {
+            return Ok(PluginSource {
+                output: output.clone(),
+                next_sample: 0,
+            });
+        }
+
+        let config = self
+            .configs
+            .iter()
+            .find(|config| config.name == name)
+            .ok_or_else(|| {
+                BottomError::ConfigError(format!(
+                    "no [[plugin]] entry was found with the name \"{}\".",
+                    name
+                ))
+            })?;
+
+        let mut child = Command::new(&config.command)
+            .args(config.args.as_deref().unwrap_or_default())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| {
+                BottomError::ConfigError(format!(
+                    "unable to start the plugin \"{}\": {}",
+                    name, err
+                ))
+            })?;
+
+        let output: SharedOutput = Arc::new(Mutex::new(PluginOutput::default()));
+        if let Some(stdout) = child.stdout.take() {
+            let thread_output = output.clone();
+            let plugin_name = name.to_string();
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    let line = match line {
+                        Ok(line) => line,
+                        Err(_) => break,
+                    };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+
+                    match parse_plugin_line(&line) {
+                        Ok(value) => {
+                            if let Ok(mut output) = thread_output.lock() {
+                                output.push(value);
+                            }
+                        }
+                        Err(err) => debug!("Plugin \"{}\" sent {}: {}", plugin_name, err, line),
+                    }
+                }
+                debug!("Plugin \"{}\" closed its output.", plugin_name);
+            });
+        }
+
+        self.children.push(child);
+        self.running.insert(name.to_string(), output.clone());
+        Ok(PluginSource {
+            output,
+            next_sample: 0,
+        })
+    }
+}
+
+impl Drop for PluginHost {
+    fn drop(&mut self) {
+        for child in &mut self.children {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
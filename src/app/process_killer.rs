@@ -8,7 +8,7 @@ use winapi::{
     },
 };
 
-/// This file is meant to house (OS specific) implementations on how to kill processes.
+/// This file is meant to house (OS specific) implementations on how to kill and renice processes.
 use crate::utils::error::BottomError;
 use crate::Pid;
 
@@ -31,18 +31,45 @@ impl Process {
     }
 }
 
-/// Kills a process, given a PID.
-pub fn kill_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
+/// The signals that can be picked when killing a process, by name and number.  Windows can only
+/// terminate processes, so it has none to pick from.
+#[cfg(target_family = "unix")]
+pub const SIGNALS: [(&str, i32); 9] = [
+    ("SIGTERM", libc::SIGTERM),
+    ("SIGKILL", libc::SIGKILL),
+    ("SIGHUP", libc::SIGHUP),
+    ("SIGINT", libc::SIGINT),
+    ("SIGQUIT", libc::SIGQUIT),
+    ("SIGSTOP", libc::SIGSTOP),
+    ("SIGCONT", libc::SIGCONT),
+    ("SIGUSR1", libc::SIGUSR1),
+    ("SIGUSR2", libc::SIGUSR2),
+];
+
+#[cfg(not(target_family = "unix"))]
+pub const SIGNALS: [(&str, i32); 0] = [];
+
+/// Returns the name of a signal, if it is one of [`SIGNALS`].
+pub fn get_signal_name(signal: i32) -> Option<&'static str> {
+    SIGNALS
+        .iter()
+        .find(|(_, number)| *number == signal)
+        .map(|(name, _)| *name)
+}
+
+/// Sends a signal to a process, given a PID.  If no signal is given, the process is asked to
+/// terminate with `SIGTERM`.  On Windows, the process is always terminated.
+pub fn kill_process_given_pid(pid: Pid, signal: Option<i32>) -> crate::utils::error::Result<()> {
     if cfg!(target_family = "unix") {
-        #[cfg(any(target_family = "unix"))]
+        #[cfg(target_family = "unix")]
         {
-            let output = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
+            let output = unsafe { libc::kill(pid, signal.unwrap_or(libc::SIGTERM)) };
             if output != 0 {
                 // We had an error...
                 let err_code = std::io::Error::last_os_error().raw_os_error();
                 let err = match err_code {
                 Some(libc::ESRCH) => "the target process did not exist.",
-                Some(libc::EPERM) => "the calling process does not have the permissions to terminate the target process(es).",
+                Some(libc::EPERM) => "the calling process does not have the permissions to signal the target process(es).",
                 Some(libc::EINVAL) => "an invalid signal was specified.",
                 _ => "Unknown error occurred."
             };
@@ -63,6 +90,7 @@ pub fn kill_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
     } else if cfg!(target_family = "windows") {
         #[cfg(target_family = "windows")]
         {
+            let _ = signal;
             let process = Process::open(pid as DWORD)?;
             process.kill()?;
         }
@@ -75,3 +103,69 @@ pub fn kill_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
 
     Ok(())
 }
+
+/// The range of nice values a process can be given, from most to least favourable.
+pub const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// Sets the nice value of a process, given a PID.  Only root can make a process more favourable.
+pub fn renice_process_given_pid(pid: Pid, nice: i32) -> crate::utils::error::Result<()> {
+    #[cfg(target_family = "unix")]
+    {
+        let output = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+        if output != 0 {
+            let err_code = std::io::Error::last_os_error().raw_os_error();
+            let err = match err_code {
+                Some(libc::ESRCH) => "the target process did not exist.",
+                Some(libc::EPERM) | Some(libc::EACCES) => "the calling process does not have the permissions to change the nice value of the target process(es).  Lowering a nice value requires root.",
+                Some(libc::EINVAL) => "an invalid nice value was specified.",
+                _ => "Unknown error occurred.",
+            };
+
+            return if let Some(err_code) = err_code {
+                Err(BottomError::GenericError(format!(
+                    "Error code {} - {}",
+                    err_code, err,
+                )))
+            } else {
+                Err(BottomError::GenericError(format!(
+                    "Error code ??? - {}",
+                    err,
+                )))
+            };
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    {
+        let _ = (pid, nice);
+        Err(BottomError::GenericError(
+            "Sorry, changing nice values is only supported on Unix-like systems.".to_string(),
+        ))
+    }
+}
+
+/// Stops a process with `SIGSTOP`, or lets a stopped one continue with `SIGCONT` if
+/// `is_stopping` is false, given a PID.
+pub fn stop_process_given_pid(pid: Pid, is_stopping: bool) -> crate::utils::error::Result<()> {
+    #[cfg(target_family = "unix")]
+    {
+        kill_process_given_pid(
+            pid,
+            Some(if is_stopping {
+                libc::SIGSTOP
+            } else {
+                libc::SIGCONT
+            }),
+        )
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    {
+        let _ = (pid, is_stopping);
+        Err(BottomError::GenericError(
+            "Sorry, stopping processes is only supported on Unix-like systems.".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,131 @@
+//! Remembers previously entered process search queries, so they can be recalled
+//! with the up and down keys while searching.  The history is kept between runs
+//! as a plain text file with one query per line, oldest first.
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+use crate::utils::error;
+
+const SEARCH_HISTORY_FILE_NAME: &str = "search_history";
+
+/// How many queries are kept before the oldest are forgotten.
+const MAX_SEARCH_HISTORY_LEN: usize = 100;
+
+#[derive(Debug, Default, Clone)]
+pub struct SearchHistory {
+    /// The queries, oldest first, without duplicates.
+    entries: VecDeque<String>,
+    /// The entry being shown while recalling, if any.
+    position: Option<usize>,
+    /// Whatever was typed before recalling started, which is restored after the newest entry.
+    draft: String,
+}
+
+impl SearchHistory {
+    pub fn new(entries: Vec<String>) -> Self {
+        let mut search_history = SearchHistory::default();
+        for entry in entries {
+            search_history.push(&entry);
+        }
+        search_history
+    }
+
+    pub fn entries(&self) -> &VecDeque<String> {
+        &self.entries
+    }
+
+    /// Adds a query as the newest entry, moving it there if it was already in the history.
+    pub fn push(&mut self, query: &str) {
+        self.position = None;
+
+        let query = query.trim();
+        if query.is_empty() || query.contains('\n') {
+            return;
+        }
+
+        self.entries.retain(|entry| entry != query);
+        self.entries.push_back(query.to_string());
+        while self.entries.len() > MAX_SEARCH_HISTORY_LEN {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Returns the entry before the one being shown, or the newest one if the current query
+    /// isn't from the history.  Returns [`None`] if there is nothing older.
+    pub fn recall_older(&mut self, current_query: &str) -> Option<String> {
+        let position = match self.get_shown_position(current_query) {
+            Some(position) => position.checked_sub(1)?,
+            None => {
+                let position = self.entries.len().checked_sub(1)?;
+                self.draft = current_query.to_string();
+                position
+            }
+        };
+
+        self.position = Some(position);
+        self.entries.get(position).cloned()
+    }
+
+    /// Returns the entry after the one being shown, or what was typed before recalling started
+    /// if the newest one is being shown.  Returns [`None`] if the current query isn't from the
+    /// history.
+    pub fn recall_newer(&mut self, current_query: &str) -> Option<String> {
+        let position = self.get_shown_position(current_query)? + 1;
+        if position < self.entries.len() {
+            self.position = Some(position);
+            self.entries.get(position).cloned()
+        } else {
+            self.position = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Returns the position of the entry being shown, as long as it hasn't been edited since.
+    fn get_shown_position(&self, current_query: &str) -> Option<usize> {
+        self.position.filter(|position| {
+            self.entries.get(*position).map(String::as_str) == Some(current_query)
+        })
+    }
+}
+
+/// Returns where the search history is stored, if we can determine a data directory.
+pub fn get_search_history_path() -> Option<PathBuf> {
+    dirs_next::data_local_dir().map(|mut path| {
+        path.push("bottom");
+        path.push(SEARCH_HISTORY_FILE_NAME);
+        path
+    })
+}
+
+/// Reads the search history at the given path, or nothing if there isn't one.
+pub fn load_search_history(path: &Path) -> Vec<String> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => contents.lines().map(str::to_string).collect(),
+        Err(err) => {
+            debug!("Unable to read search history: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+pub fn save_search_history(path: &Path, search_history: &SearchHistory) -> error::Result<()> {
+    if let Some(parent_path) = path.parent() {
+        std::fs::create_dir_all(parent_path)?;
+    }
+
+    let mut contents = String::new();
+    for entry in search_history.entries() {
+        contents.push_str(entry);
+        contents.push('\n');
+    }
+
+    // Write to a temporary file first so we never leave a half-written history around.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+//! Persists a small binary snapshot of the last-known data between runs.  This
+//! lets us calculate correct disk rates on the very first sample after startup
+//! (rather than showing zero), optionally warm-start the graphs with data from the
+//! previous session, and compare two saved sessions.
+
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    app::data_farmer::{DataCollection, TimedData},
+    constants::STALE_MAX_MILLISECONDS,
+    utils::error::{self, BottomError},
+};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"BTMS";
+const SNAPSHOT_VERSION: u8 = 1;
+const SNAPSHOT_FILE_NAME: &str = "session.bin";
+
+/// Snapshots older than this are ignored, as their graph data would be out of range anyways.
+const MAX_SNAPSHOT_AGE_MILLISECONDS: u64 = STALE_MAX_MILLISECONDS;
+
+/// Counters older than this are ignored, as the first rate would otherwise be averaged
+/// over however long bottom was closed for.
+const MAX_COUNTER_AGE_MILLISECONDS: u64 = 10_000;
+
+#[derive(Debug, Default)]
+pub struct SessionSnapshot {
+    /// When the snapshot was taken, in milliseconds since the UNIX epoch.
+    pub saved_at: u64,
+    /// The device name along with the total bytes read and written.
+    pub io_counters: Vec<(String, u64, u64)>,
+    /// How many milliseconds before `saved_at` the data was taken, along with the data.
+    pub timed_data: Vec<(u64, TimedData)>,
+}
+
+impl SessionSnapshot {
+    pub fn from_data_collection(data: &DataCollection) -> Self {
+        let mut io_counters = data
+            .io_harvest
+            .iter()
+            .filter_map(|(name, io)| {
+                io.as_ref()
+                    .map(|io| (name.clone(), io.read_bytes, io.write_bytes))
+            })
+            .collect::<Vec<_>>();
+        io_counters.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let timed_data = data
+            .timed_data_vec
+            .iter()
+            .map(|(time, entry)| {
+                (
+                    data.current_instant
+                        .saturating_duration_since(*time)
+                        .as_millis() as u64,
+                    TimedData {
+                        rx_data: entry.rx_data,
+                        tx_data: entry.tx_data,
+                        cpu_data: entry.cpu_data.clone(),
+                        mem_data: entry.mem_data,
+                        swap_data: entry.swap_data,
+                    },
+                )
+            })
+            .collect();
+
+        SessionSnapshot {
+            saved_at: get_unix_millis(),
+            io_counters,
+            timed_data,
+        }
+    }
+
+    /// Returns how long ago this snapshot was taken.
+    pub fn age(&self) -> Duration {
+        Duration::from_millis(get_unix_millis().saturating_sub(self.saved_at))
+    }
+
+    /// Restores the snapshot into the given [`DataCollection`].  The disk counters are
+    /// only restored if the snapshot is recent enough for a rate based on them to make
+    /// sense.  If `warm_start` is set, then the graph data is restored as well, and the
+    /// point where the previous session ended is marked.
+    pub fn restore(self, data: &mut DataCollection, warm_start: bool) {
+        let age = self.age();
+        if age.as_millis() > MAX_SNAPSHOT_AGE_MILLISECONDS as u128 {
+            debug!("Ignoring stale session snapshot from {:?} ago.", age);
+            return;
+        }
+
+        let saved_instant = match Instant::now().checked_sub(age) {
+            Some(saved_instant) => saved_instant,
+            None => return,
+        };
+
+        if age.as_millis() <= MAX_COUNTER_AGE_MILLISECONDS as u128 {
+            data.io_counter_seeds = self
+                .io_counters
+                .into_iter()
+                .map(|(name, read_bytes, write_bytes)| {
+                    (name, (saved_instant, read_bytes, write_bytes))
+                })
+                .collect::<HashMap<_, _>>();
+        } else {
+            debug!("Ignoring stale disk counters from {:?} ago.", age);
+        }
+
+        if warm_start && !self.timed_data.is_empty() {
+            data.timed_data_vec = self
+                .timed_data
+                .into_iter()
+                .filter_map(|(offset, entry)| {
+                    saved_instant
+                        .checked_sub(Duration::from_millis(offset))
+                        .map(|time| (time, entry))
+                })
+                .collect();
+            data.previous_session_instant = Some(saved_instant);
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(SNAPSHOT_MAGIC);
+        bytes.push(SNAPSHOT_VERSION);
+        write_u64(&mut bytes, self.saved_at);
+
+        write_u32(&mut bytes, self.io_counters.len() as u32);
+        for (name, read_bytes, write_bytes) in &self.io_counters {
+            write_u32(&mut bytes, name.len() as u32);
+            bytes.extend_from_slice(name.as_bytes());
+            write_u64(&mut bytes, *read_bytes);
+            write_u64(&mut bytes, *write_bytes);
+        }
+
+        write_u32(&mut bytes, self.timed_data.len() as u32);
+        for (offset, entry) in &self.timed_data {
+            write_u64(&mut bytes, *offset);
+            write_f64(&mut bytes, entry.rx_data);
+            write_f64(&mut bytes, entry.tx_data);
+            write_f64(&mut bytes, entry.mem_data);
+            write_f64(&mut bytes, entry.swap_data);
+            write_u32(&mut bytes, entry.cpu_data.len() as u32);
+            for cpu in &entry.cpu_data {
+                write_f64(&mut bytes, *cpu);
+            }
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> error::Result<Self> {
+        let mut reader = SnapshotReader { bytes, position: 0 };
+
+        if reader.read_slice(SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC {
+            return Err(BottomError::ConversionError(
+                "not a valid session snapshot".to_string(),
+            ));
+        }
+        let version = reader.read_slice(1)?[0];
+        if version != SNAPSHOT_VERSION {
+            return Err(BottomError::ConversionError(format!(
+                "unsupported session snapshot version {}",
+                version
+            )));
+        }
+
+        let saved_at = reader.read_u64()?;
+
+        let num_io = reader.read_u32()?;
+        let mut io_counters = Vec::new();
+        for _ in 0..num_io {
+            let name_len = reader.read_u32()? as usize;
+            let name = String::from_utf8(reader.read_slice(name_len)?.to_vec()).map_err(|_| {
+                BottomError::ConversionError("invalid device name in session snapshot".to_string())
+            })?;
+            io_counters.push((name, reader.read_u64()?, reader.read_u64()?));
+        }
+
+        let num_entries = reader.read_u32()?;
+        let mut timed_data = Vec::new();
+        for _ in 0..num_entries {
+            let offset = reader.read_u64()?;
+            let rx_data = reader.read_f64()?;
+            let tx_data = reader.read_f64()?;
+            let mem_data = reader.read_f64()?;
+            let swap_data = reader.read_f64()?;
+            let num_cpus = reader.read_u32()?;
+            let mut cpu_data = Vec::new();
+            for _ in 0..num_cpus {
+                cpu_data.push(reader.read_f64()?);
+            }
+
+            timed_data.push((
+                offset,
+                TimedData {
+                    rx_data,
+                    tx_data,
+                    cpu_data,
+                    mem_data,
+                    swap_data,
+                },
+            ));
+        }
+
+        Ok(SessionSnapshot {
+            saved_at,
+            io_counters,
+            timed_data,
+        })
+    }
+}
+
+/// Returns where the session snapshot is stored, if we can determine a cache directory.
+pub fn get_snapshot_path() -> Option<PathBuf> {
+    dirs_next::cache_dir().map(|mut path| {
+        path.push("bottom");
+        path.push(SNAPSHOT_FILE_NAME);
+        path
+    })
+}
+
+/// Reads the session snapshot at the given path, if one exists and is valid.
+pub fn load_snapshot(path: &Path) -> Option<SessionSnapshot> {
+    let bytes = std::fs::read(path).ok()?;
+    match SessionSnapshot::from_bytes(&bytes) {
+        Ok(snapshot) => Some(snapshot),
+        Err(err) => {
+            debug!("Unable to read session snapshot: {}", err);
+            None
+        }
+    }
+}
+
+pub fn save_snapshot(path: &Path, snapshot: &SessionSnapshot) -> error::Result<()> {
+    if let Some(parent_path) = path.parent() {
+        std::fs::create_dir_all(parent_path)?;
+    }
+
+    // Write to a temporary file first so we never leave a half-written snapshot around.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, snapshot.to_bytes())?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+fn get_unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_u64(bytes: &mut Vec<u8>, value: u64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_f64(bytes: &mut Vec<u8>, value: f64) {
+    write_u64(bytes, value.to_bits());
+}
+
+struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    fn read_slice(&mut self, len: usize) -> error::Result<&'a [u8]> {
+        let end = self.position.saturating_add(len);
+        if let Some(slice) = self.bytes.get(self.position..end) {
+            self.position = end;
+            Ok(slice)
+        } else {
+            Err(BottomError::ConversionError(
+                "session snapshot is truncated".to_string(),
+            ))
+        }
+    }
+
+    fn read_u32(&mut self) -> error::Result<u32> {
+        let slice = self.read_slice(4)?;
+        Ok(u32::from_le_bytes(slice.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> error::Result<u64> {
+        let slice = self.read_slice(8)?;
+        Ok(u64::from_le_bytes(slice.try_into().unwrap()))
+    }
+
+    fn read_f64(&mut self) -> error::Result<f64> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
+}
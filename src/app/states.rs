@@ -1,30 +1,40 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    time::Instant,
+};
 
 use unicode_segmentation::GraphemeCursor;
+use unicode_width::UnicodeWidthStr;
 
 use tui::widgets::TableState;
 
 use crate::{
-    app::{layout_manager::BottomWidgetType, query::*},
+    app::{
+        data_farmer::DataCollection,
+        data_source::{DataSource, DataValue},
+        layout_manager::BottomWidgetType,
+        process_killer,
+        search_history::SearchHistory,
+    },
     constants,
-    data_harvester::processes::{self, ProcessSorting},
+    data_harvester::{
+        process_details::ProcessDetails,
+        processes::{self, ProcessSorting},
+    },
+    query::*,
 };
 use ProcessSorting::*;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum ScrollDirection {
     // UP means scrolling up --- this usually DECREMENTS
     Up,
     // DOWN means scrolling down --- this usually INCREMENTS
+    #[default]
     Down,
 }
 
-impl Default for ScrollDirection {
-    fn default() -> Self {
-        ScrollDirection::Down
-    }
-}
-
 #[derive(Debug)]
 pub enum CursorDirection {
     Left,
@@ -48,6 +58,82 @@ pub struct AppDeleteDialogState {
     pub yes_brc: Option<(u16, u16)>,
     pub no_tlc: Option<(u16, u16)>,
     pub no_brc: Option<(u16, u16)>,
+    /// The index of the signal picked from [`process_killer::SIGNALS`].
+    pub selected_signal: usize,
+    /// The number of a signal typed in, which is sent instead of the picked one.
+    pub typed_signal: String,
+}
+
+impl AppDeleteDialogState {
+    /// Returns the signal to send, either the one typed in or the one picked, if there are any
+    /// to pick from on this platform.
+    pub fn get_signal(&self) -> Option<i32> {
+        if self.typed_signal.is_empty() {
+            process_killer::SIGNALS
+                .get(self.selected_signal)
+                .map(|(_, number)| *number)
+        } else {
+            self.typed_signal.parse().ok()
+        }
+    }
+
+    /// Picks the previous signal, or the next one if `is_next` is true.
+    pub fn pick_signal(&mut self, is_next: bool) {
+        if process_killer::SIGNALS.is_empty() {
+            return;
+        }
+
+        self.typed_signal.clear();
+        self.selected_signal = if is_next {
+            (self.selected_signal + 1) % process_killer::SIGNALS.len()
+        } else {
+            (self.selected_signal + process_killer::SIGNALS.len() - 1)
+                % process_killer::SIGNALS.len()
+        };
+    }
+}
+
+/// The list of process columns to show, hide, and reorder.
+#[derive(Default)]
+pub struct AppColumnPickerState {
+    pub is_showing: bool,
+    /// The process widget whose columns are being picked.
+    pub widget_id: u64,
+    /// The index of the selected column in the list.
+    pub selected_index: usize,
+}
+
+/// The popup with details about the selected process.
+#[derive(Default)]
+pub struct AppProcessDetailsState {
+    pub is_showing: bool,
+    pub name: String,
+    /// The process whose details are shown.  For a group, this is its first process.
+    pub pid: crate::Pid,
+    /// How many processes were selected, which is more than one for a group.
+    pub num_processes: usize,
+    /// All of the selected processes.
+    pub group_pids: Vec<crate::Pid>,
+    pub user: Option<String>,
+    /// The details, which are read again with each update, or [`None`] if the process is gone.
+    pub details: Option<ProcessDetails>,
+    /// The titles of the windows of all of the selected processes.
+    pub window_titles: Vec<String>,
+    pub scroll_state: ParagraphScrollState,
+}
+
+/// The prompt for a new nice value for the selected process.
+#[derive(Default)]
+pub struct AppReniceDialogState {
+    pub is_showing: bool,
+    /// The name and PIDs of the processes to renice.
+    pub processes: Option<(String, Vec<crate::Pid>)>,
+    /// The nice value of the processes when the prompt was opened.
+    pub current_nice: i64,
+    /// The nice value typed in so far.
+    pub typed_nice: String,
+    /// Why the nice value couldn't be set, if it couldn't.
+    pub error: Option<String>,
 }
 
 pub struct AppHelpDialogState {
@@ -80,6 +166,8 @@ pub struct AppSearchState {
     /// The query
     pub query: Option<Query>,
     pub error_message: Option<String>,
+    /// Where in the query the error is, in bytes, if we know.
+    pub error_span: Option<Range<usize>>,
 }
 
 impl Default for AppSearchState {
@@ -95,6 +183,7 @@ impl Default for AppSearchState {
             char_cursor_position: 0,
             query: None,
             error_message: None,
+            error_span: None,
         }
     }
 }
@@ -126,6 +215,9 @@ pub struct ProcessSearchState {
     pub is_ignoring_case: bool,
     pub is_searching_whole_word: bool,
     pub is_searching_with_regex: bool,
+    /// Whether `*`, `?`, and `[...]` in searches are wildcards.  Regex takes priority over this.
+    pub is_searching_with_glob: bool,
+    pub history: SearchHistory,
 }
 
 impl Default for ProcessSearchState {
@@ -135,6 +227,8 @@ impl Default for ProcessSearchState {
             is_ignoring_case: true,
             is_searching_whole_word: false,
             is_searching_with_regex: false,
+            is_searching_with_glob: false,
+            history: SearchHistory::default(),
         }
     }
 }
@@ -151,6 +245,10 @@ impl ProcessSearchState {
     pub fn search_toggle_regex(&mut self) {
         self.is_searching_with_regex = !self.is_searching_with_regex;
     }
+
+    pub fn search_toggle_glob(&mut self) {
+        self.is_searching_with_glob = !self.is_searching_with_glob;
+    }
 }
 
 pub struct ColumnInfo {
@@ -158,9 +256,26 @@ pub struct ColumnInfo {
     pub shortcut: Option<&'static str>,
 }
 
+/// Returns the column that the given one takes the place of when switching between grouped and
+/// ungrouped processes, names and commands, or memory percentages and values.  Those columns are
+/// shown, hidden, and moved together.
+pub fn get_column_group(column: &ProcessSorting) -> ProcessSorting {
+    match column {
+        Count => Pid,
+        Command => ProcessName,
+        Mem => MemPercent,
+        _ => column.clone(),
+    }
+}
+
 pub struct ProcColumn {
     pub ordered_columns: Vec<ProcessSorting>,
     pub column_mapping: HashMap<ProcessSorting, ColumnInfo>,
+    /// Columns hidden from the column picker or the config, by their group.  These stay hidden
+    /// regardless of whether they'd otherwise be enabled.
+    pub hidden_columns: HashSet<ProcessSorting>,
+    /// Names shown for columns instead of their own, from `column_aliases` in the config file.
+    pub column_names: HashMap<ProcessSorting, String>,
     pub longest_header_len: u16,
     pub column_state: TableState,
     pub scroll_direction: ScrollDirection,
@@ -184,6 +299,18 @@ impl Default for ProcColumn {
             TotalRead,
             TotalWrite,
             State,
+            App,
+            GpuPercent,
+            GpuMem,
+            RtPriority,
+            Nice,
+            SecurityContext,
+            Namespaces,
+            StartTime,
+            RunTime,
+            Media,
+            Energy,
+            User,
         ];
 
         let mut column_mapping = HashMap::new();
@@ -245,7 +372,8 @@ impl Default for ProcColumn {
                         },
                     );
                 }
-                Count => {
+                Count | App | GpuPercent | GpuMem | RtPriority | Nice | SecurityContext
+                | Namespaces | StartTime | RunTime | Media | Energy | User => {
                     column_mapping.insert(
                         column,
                         ColumnInfo {
@@ -270,6 +398,8 @@ impl Default for ProcColumn {
         ProcColumn {
             ordered_columns,
             column_mapping,
+            hidden_columns: HashSet::new(),
+            column_names: HashMap::new(),
             longest_header_len,
             column_state: TableState::default(),
             scroll_direction: ScrollDirection::default(),
@@ -282,6 +412,20 @@ impl Default for ProcColumn {
 
 // TODO: [SORTING] Sort by clicking on column header (ie: click on cpu, sort/invert cpu sort)?
 impl ProcColumn {
+    /// Returns the name shown for a column, which is its alias if it has one.
+    pub fn get_column_name(&self, column: &ProcessSorting) -> String {
+        self.column_names
+            .get(column)
+            .cloned()
+            .unwrap_or_else(|| column.to_string())
+    }
+
+    /// Shows a column under another name.
+    pub fn set_column_name(&mut self, column: ProcessSorting, name: String) {
+        self.longest_header_len = self.longest_header_len.max(name.len() as u16);
+        self.column_names.insert(column, name);
+    }
+
     /// Returns its new status.
     pub fn toggle(&mut self, column: &ProcessSorting) -> Option<bool> {
         if let Some(mapping) = self.column_mapping.get_mut(column) {
@@ -294,27 +438,106 @@ impl ProcColumn {
 
     pub fn is_enabled(&self, column: &ProcessSorting) -> bool {
         if let Some(mapping) = self.column_mapping.get(column) {
-            mapping.enabled
+            mapping.enabled && !self.hidden_columns.contains(&get_column_group(column))
         } else {
             false
         }
     }
 
-    pub fn get_enabled_columns_len(&self) -> usize {
+    /// Returns the shown columns, in the order they're drawn in.
+    pub fn get_enabled_columns(&self) -> impl Iterator<Item = &ProcessSorting> {
         self.ordered_columns
             .iter()
-            .filter_map(|column_type| {
-                if let Some(col_map) = self.column_mapping.get(&column_type) {
-                    if col_map.enabled {
-                        Some(1)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+            .filter(move |column_type| self.is_enabled(column_type))
+    }
+
+    pub fn get_enabled_columns_len(&self) -> usize {
+        self.get_enabled_columns().count()
+    }
+
+    /// Returns the groups of columns in the order they're drawn in, which is what the column
+    /// picker lists.
+    pub fn get_column_groups(&self) -> Vec<ProcessSorting> {
+        let mut column_groups = Vec::new();
+        for column in &self.ordered_columns {
+            let column_group = get_column_group(column);
+            if !column_groups.contains(&column_group) {
+                column_groups.push(column_group);
+            }
+        }
+        column_groups
+    }
+
+    /// Returns whether a group of columns is meant to be shown.  The state column counts as shown
+    /// while grouping hides it, as it comes back when ungrouping.
+    pub fn is_column_group_shown(&self, column_group: &ProcessSorting) -> bool {
+        !self.hidden_columns.contains(column_group)
+            && (*column_group == State
+                || self.ordered_columns.iter().any(|column| {
+                    get_column_group(column) == *column_group
+                        && self.column_mapping.get(column).unwrap().enabled
+                }))
+    }
+
+    /// Shows or hides a group of columns.
+    pub fn set_column_group_shown(&mut self, column_group: &ProcessSorting, is_shown: bool) {
+        if is_shown {
+            self.hidden_columns.remove(column_group);
+
+            // Groups of more than one column always have one of them enabled, and the state column
+            // is only disabled while grouping, so only the optional columns need enabling.
+            if !matches!(column_group, Pid | ProcessName | MemPercent | State) {
+                if let Some(mapping) = self.column_mapping.get_mut(column_group) {
+                    mapping.enabled = true;
                 }
-            })
-            .sum()
+            }
+        } else {
+            self.hidden_columns.insert(column_group.clone());
+        }
+    }
+
+    /// Swaps a group of columns with the group before it, or after it if `is_later` is true.
+    pub fn move_column_group(&mut self, column_group: &ProcessSorting, is_later: bool) {
+        let mut column_groups = self.get_column_groups();
+        if let Some(index) = column_groups.iter().position(|group| group == column_group) {
+            let other_index = if is_later {
+                index + 1
+            } else {
+                index.wrapping_sub(1)
+            };
+            if other_index < column_groups.len() {
+                column_groups.swap(index, other_index);
+                self.set_column_order(&column_groups);
+            }
+        }
+    }
+
+    /// Orders the columns by the given groups.  Columns in groups that aren't given keep their
+    /// order, after the rest.
+    fn set_column_order(&mut self, column_groups: &[ProcessSorting]) {
+        let mut ordered_columns = Vec::with_capacity(self.ordered_columns.len());
+        for column_group in column_groups {
+            ordered_columns.extend(
+                self.ordered_columns
+                    .iter()
+                    .filter(|column| get_column_group(column) == *column_group)
+                    .cloned(),
+            );
+        }
+        for column in &self.ordered_columns {
+            if !ordered_columns.contains(column) {
+                ordered_columns.push(column.clone());
+            }
+        }
+        self.ordered_columns = ordered_columns;
+    }
+
+    /// Shows only the given groups of columns, in the given order, hiding the rest.
+    pub fn set_shown_columns(&mut self, column_groups: &[ProcessSorting]) {
+        self.set_column_order(column_groups);
+        for column_group in self.get_column_groups() {
+            self.set_column_group_shown(&column_group, column_groups.contains(&column_group));
+        }
     }
 
     /// ALWAYS call this when opening the sorted window.
@@ -325,7 +548,7 @@ impl ProcColumn {
             if *column == *proc_sorting_type {
                 break;
             }
-            if self.column_mapping.get(column).unwrap().enabled {
+            if self.is_enabled(column) {
                 true_index += 1;
             }
         }
@@ -344,16 +567,16 @@ impl ProcColumn {
         self.ordered_columns
             .iter()
             .filter_map(|column_type| {
-                let mapping = self.column_mapping.get(&column_type).unwrap();
+                let mapping = self.column_mapping.get(column_type).unwrap();
                 let mut command_str = String::default();
                 if let Some(command) = mapping.shortcut {
                     command_str = format!("({})", command);
                 }
 
-                if mapping.enabled {
+                if self.is_enabled(column_type) {
                     Some(format!(
                         "{}{}{}",
-                        column_type.to_string(),
+                        self.get_column_name(column_type),
                         command_str.as_str(),
                         if proc_sorting_type == column_type {
                             if sort_reverse {
@@ -376,22 +599,45 @@ impl ProcColumn {
 pub struct ProcWidgetState {
     pub process_search_state: ProcessSearchState,
     pub is_grouped: bool,
+    pub is_grouped_by_app: bool,
+    pub is_grouped_by_user: bool,
     pub scroll_state: AppScrollWidgetState,
     pub process_sorting_type: processes::ProcessSorting,
     pub is_process_sort_descending: bool,
     pub is_using_command: bool,
+    /// How many characters to skip at the start of each command, so long ones can be read.
+    pub command_scroll_offset: usize,
     pub current_column_index: usize,
     pub is_sort_open: bool,
     pub columns: ProcColumn,
     pub is_tree_mode: bool,
+    pub is_sectioned_by_state: bool,
+    /// Whether to pin a row with the totals of the shown processes above them.
+    pub is_showing_summary_row: bool,
+    /// The process to keep selected and in the same place on screen, even while it is hidden.
+    pub locked_pid: Option<crate::Pid>,
+    /// Processes pinned above the others in this widget.
+    pub pinned_pids: HashSet<crate::Pid>,
+    pub collapsed_state_sections: HashSet<processes::StateSection>,
+    pub quick_filters: Vec<QuickFilter>,
+    pub quick_filter_queries: Vec<Query>,
+    /// Named queries from the config file, which can be used in searches as `@name`.
+    pub query_aliases: HashMap<String, String>,
+    /// Names of columns from the config file that can be used as prefixes in searches, along
+    /// with the prefix each stands for.
+    pub prefix_aliases: HashMap<String, String>,
     pub table_width_state: CanvasTableWidthState,
     pub requires_redraw: bool,
 }
 
 impl ProcWidgetState {
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
-        is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool, is_grouped: bool,
-        show_memory_as_values: bool, is_tree_mode: bool,
+        is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool, is_use_glob: bool,
+        is_grouped: bool, show_memory_as_values: bool, is_tree_mode: bool, show_app_column: bool,
+        show_gpu_usage_column: bool, show_gpu_mem_column: bool, show_rt_column: bool,
+        show_security_column: bool, show_namespace_column: bool, show_nice_column: bool,
+        shown_columns: Option<&[ProcessSorting]>, is_using_command: bool,
     ) -> Self {
         let mut process_search_state = ProcessSearchState::default();
 
@@ -405,6 +651,9 @@ impl ProcWidgetState {
         if is_use_regex {
             process_search_state.search_toggle_regex();
         }
+        if is_use_glob {
+            process_search_state.search_toggle_glob();
+        }
 
         let (process_sorting_type, is_process_sort_descending) = if is_tree_mode {
             (processes::ProcessSorting::Pid, false)
@@ -412,36 +661,126 @@ impl ProcWidgetState {
             (processes::ProcessSorting::CpuPercent, true)
         };
 
-        // TODO: If we add customizable columns, this should pull from config
         let mut columns = ProcColumn::default();
+        if let Some(shown_columns) = shown_columns {
+            columns.set_shown_columns(shown_columns);
+        }
         columns.set_to_sorted_index(&process_sorting_type);
         if is_grouped {
             // Normally defaults to showing by PID, toggle count on instead.
             columns.toggle(&ProcessSorting::Count);
             columns.toggle(&ProcessSorting::Pid);
+            columns.toggle(&ProcessSorting::State);
+        }
+        if is_using_command {
+            columns.toggle(&ProcessSorting::ProcessName);
+            columns.toggle(&ProcessSorting::Command);
         }
         if show_memory_as_values {
             // Normally defaults to showing by percent, toggle value on instead.
             columns.toggle(&ProcessSorting::Mem);
             columns.toggle(&ProcessSorting::MemPercent);
         }
+        if show_app_column {
+            columns.set_column_group_shown(&ProcessSorting::App, true);
+        }
+        if show_gpu_usage_column {
+            columns.set_column_group_shown(&ProcessSorting::GpuPercent, true);
+        }
+        if show_gpu_mem_column {
+            columns.set_column_group_shown(&ProcessSorting::GpuMem, true);
+        }
+        if show_rt_column {
+            columns.set_column_group_shown(&ProcessSorting::RtPriority, true);
+        }
+        if show_nice_column {
+            columns.set_column_group_shown(&ProcessSorting::Nice, true);
+        }
+        if show_security_column {
+            columns.set_column_group_shown(&ProcessSorting::SecurityContext, true);
+        }
+        if show_namespace_column {
+            columns.set_column_group_shown(&ProcessSorting::Namespaces, true);
+        }
 
         ProcWidgetState {
             process_search_state,
             is_grouped,
+            is_grouped_by_app: false,
+            is_grouped_by_user: false,
             scroll_state: AppScrollWidgetState::default(),
             process_sorting_type,
             is_process_sort_descending,
-            is_using_command: false,
+            is_using_command,
+            command_scroll_offset: 0,
             current_column_index: 0,
             is_sort_open: false,
             columns,
             is_tree_mode,
+            is_sectioned_by_state: false,
+            is_showing_summary_row: false,
+            locked_pid: None,
+            pinned_pids: HashSet::new(),
+            collapsed_state_sections: HashSet::new(),
+            quick_filters: Vec::new(),
+            quick_filter_queries: Vec::new(),
+            query_aliases: HashMap::new(),
+            prefix_aliases: HashMap::new(),
             table_width_state: CanvasTableWidthState::default(),
             requires_redraw: false,
         }
     }
 
+    /// Returns whether the data of a column is needed: if it is enabled and fit into the widget
+    /// the last time it was drawn, or if the processes are sorted by it.
+    pub fn is_column_needed(&self, column: &ProcessSorting) -> bool {
+        if self.process_sorting_type == *column {
+            return true;
+        }
+
+        // Columns that don't fit are left off the end of the widths, which are only up to date
+        // once the widget is drawn again.
+        let column_widths = &self.table_width_state.calculated_column_widths;
+        self.columns
+            .get_enabled_columns()
+            .position(|enabled_column| enabled_column == column)
+            .is_some_and(|index| {
+                self.requires_redraw
+                    || column_widths.is_empty()
+                    || column_widths.get(index).is_some_and(|width| *width > 0)
+            })
+    }
+
+    /// Groups or ungroups processes, switching between the PID and count columns.  Ungrouping also
+    /// leaves the apps and users views.
+    pub fn set_is_grouped(&mut self, is_grouped: bool) {
+        if !is_grouped {
+            self.is_grouped_by_app = false;
+            self.is_grouped_by_user = false;
+        }
+        if is_grouped == self.is_grouped {
+            return;
+        }
+        self.is_grouped = is_grouped;
+
+        // Forcefully switch off column if we were on it...
+        if (self.is_grouped && self.process_sorting_type == ProcessSorting::Pid)
+            || (!self.is_grouped && self.process_sorting_type == ProcessSorting::Count)
+        {
+            self.process_sorting_type = ProcessSorting::CpuPercent; // Go back to default, negate PID for group
+            self.is_process_sort_descending = true;
+        }
+
+        if let Some(state_column) = self.columns.column_mapping.get_mut(&ProcessSorting::State) {
+            state_column.enabled = !self.is_grouped;
+        }
+
+        self.columns.toggle(&ProcessSorting::Count);
+        self.columns.toggle(&ProcessSorting::Pid);
+
+        self.requires_redraw = true;
+    }
+
     /// Updates sorting when using the column list.
     /// ...this really should be part of the ProcColumn struct (along with the sorting fields),
     /// but I'm too lazy.
@@ -453,7 +792,7 @@ impl ProcWidgetState {
         let mut enabled_index = 0;
         let target_itx = self.columns.current_scroll_position;
         for column in &self.columns.ordered_columns {
-            let enabled = self.columns.column_mapping.get(column).unwrap().enabled;
+            let enabled = self.columns.is_enabled(column);
             if enabled_index == target_itx && enabled {
                 break;
             }
@@ -473,7 +812,8 @@ impl ProcWidgetState {
                     ProcessSorting::State
                     | ProcessSorting::Pid
                     | ProcessSorting::ProcessName
-                    | ProcessSorting::Command => {
+                    | ProcessSorting::Command
+                    | ProcessSorting::App => {
                         // Also invert anything that uses alphabetical sorting by default.
                         self.is_process_sort_descending = false;
                     }
@@ -529,8 +869,20 @@ impl ProcWidgetState {
             self.process_search_state.search_state.is_blank_search = true;
             self.process_search_state.search_state.is_invalid_search = false;
             self.process_search_state.search_state.error_message = None;
+            self.process_search_state.search_state.error_span = None;
         } else {
-            let parsed_query = self.parse_query();
+            let parsed_query = parse(
+                self.get_current_search_query(),
+                &QueryOptions {
+                    is_searching_whole_word: self.process_search_state.is_searching_whole_word,
+                    is_ignoring_case: self.process_search_state.is_ignoring_case,
+                    is_searching_with_regex: self.process_search_state.is_searching_with_regex,
+                    is_searching_with_glob: self.process_search_state.is_searching_with_glob,
+                    query_aliases: Some(&self.query_aliases),
+                    prefix_aliases: Some(&self.prefix_aliases),
+                    search_target: SearchTarget::Processes,
+                },
+            );
             // debug!("Parsed query: {:#?}", parsed_query);
 
             if let Ok(parsed_query) = parsed_query {
@@ -538,16 +890,73 @@ impl ProcWidgetState {
                 self.process_search_state.search_state.is_blank_search = false;
                 self.process_search_state.search_state.is_invalid_search = false;
                 self.process_search_state.search_state.error_message = None;
+                self.process_search_state.search_state.error_span = None;
             } else if let Err(err) = parsed_query {
                 self.process_search_state.search_state.is_blank_search = false;
                 self.process_search_state.search_state.is_invalid_search = true;
-                self.process_search_state.search_state.error_message = Some(err.to_string());
+                self.process_search_state.search_state.error_message = Some(err.error.to_string());
+                self.process_search_state.search_state.error_span = err.span;
             }
         }
         self.scroll_state.previous_scroll_position = 0;
         self.scroll_state.current_scroll_position = 0;
     }
 
+    /// Returns the suggested completion of the search query, if the cursor is at its end.
+    pub fn get_search_completion(&self) -> Option<&'static str> {
+        let query = self.get_current_search_query();
+        if self.get_search_cursor_position() >= query.len() {
+            get_query_completion(query, SearchTarget::Processes)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the search query with the given one, moving the cursor to its end.
+    pub fn set_search_query(&mut self, query: &str) {
+        let search_state = &mut self.process_search_state.search_state;
+        search_state.current_search_query = query.to_string();
+        search_state.grapheme_cursor = GraphemeCursor::new(query.len(), query.len(), true);
+        search_state.char_cursor_position = UnicodeWidthStr::width(query);
+        search_state.cursor_bar = 0;
+        if !query.is_empty() {
+            search_state.is_enabled = true;
+        }
+
+        self.update_query();
+    }
+
+    /// Toggles a quick filter, which is applied on top of the current search query.
+    pub fn toggle_quick_filter(&mut self, quick_filter: QuickFilter) {
+        if let Some(index) = self.quick_filters.iter().position(|f| *f == quick_filter) {
+            self.quick_filters.remove(index);
+        } else if quick_filter.get_query_string().is_some() {
+            self.quick_filters.push(quick_filter);
+        }
+
+        self.quick_filter_queries = self
+            .quick_filters
+            .iter()
+            .filter_map(|quick_filter| quick_filter.get_query_string())
+            .filter_map(|query_string| {
+                parse(
+                    &query_string,
+                    &QueryOptions {
+                        is_searching_whole_word: true,
+                        is_ignoring_case: false,
+                        query_aliases: Some(&self.query_aliases),
+                        prefix_aliases: Some(&self.prefix_aliases),
+                        ..QueryOptions::default()
+                    },
+                )
+                .ok()
+            })
+            .collect();
+
+        self.scroll_state.previous_scroll_position = 0;
+        self.scroll_state.current_scroll_position = 0;
+    }
+
     pub fn clear_search(&mut self) {
         self.process_search_state.search_state.reset();
     }
@@ -579,6 +988,9 @@ pub struct ProcState {
     pub widget_states: HashMap<u64, ProcWidgetState>,
     pub force_update: Option<u64>,
     pub force_update_all: bool,
+    /// Processes that were stopped or continued from bottom, with whether they were stopped and
+    /// when.  They're shown that way until data collected after that comes in.
+    pub stopped_pids: HashMap<crate::Pid, (bool, Instant)>,
 }
 
 impl ProcState {
@@ -587,6 +999,7 @@ impl ProcState {
             widget_states,
             force_update: None,
             force_update_all: false,
+            stopped_pids: HashMap::new(),
         }
     }
 
@@ -638,6 +1051,9 @@ impl NetState {
 pub struct CpuWidgetState {
     pub current_display_time: u64,
     pub is_legend_hidden: bool,
+    /// Whether the legend was selected when it was hidden for being too narrow, so that it can be
+    /// selected again once it fits.
+    pub was_legend_selected: bool,
     pub autohide_timer: Option<Instant>,
     pub scroll_state: AppScrollWidgetState,
     pub is_multi_graph_mode: bool,
@@ -649,6 +1065,7 @@ impl CpuWidgetState {
         CpuWidgetState {
             current_display_time,
             is_legend_hidden: false,
+            was_legend_selected: false,
             autohide_timer,
             scroll_state: AppScrollWidgetState::default(),
             is_multi_graph_mode: false,
@@ -679,6 +1096,16 @@ impl CpuState {
     }
 }
 
+/// The process selected in a process widget while widgets are linked, which other widgets
+/// highlight what belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedProcess {
+    pub pid: crate::Pid,
+    pub name: String,
+    /// The CPUs the process may run on, as the kernel lists them and as indices, if known.
+    pub cpu_affinity: Option<(String, Vec<usize>)>,
+}
+
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
@@ -714,9 +1141,79 @@ impl MemState {
     }
 }
 
+/// The search of a disk or temperature table.  Unlike process searches, these are typed straight
+/// into the title of the table, and only support the prefixes of what the table shows.
+pub struct TableSearchState {
+    /// Whether keys are currently typed into the search.
+    pub is_typing: bool,
+    pub query_string: String,
+    /// The parsed query, or [`None`] if the search is blank or invalid.
+    pub query: Option<Query>,
+    pub error_message: Option<String>,
+    search_target: SearchTarget,
+}
+
+impl TableSearchState {
+    pub fn new(search_target: SearchTarget) -> Self {
+        TableSearchState {
+            is_typing: false,
+            query_string: String::default(),
+            query: None,
+            error_message: None,
+            search_target,
+        }
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query_string.push(c);
+        self.update_query();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query_string.pop();
+        self.update_query();
+    }
+
+    pub fn clear(&mut self) {
+        self.query_string.clear();
+        self.update_query();
+    }
+
+    fn update_query(&mut self) {
+        if self.query_string.trim().is_empty() {
+            self.query = None;
+            self.error_message = None;
+        } else {
+            let options = QueryOptions {
+                search_target: self.search_target,
+                ..QueryOptions::default()
+            };
+            match parse(&self.query_string, &options) {
+                Ok(query) => {
+                    self.query = Some(query);
+                    self.error_message = None;
+                }
+                Err(err) => {
+                    self.query = None;
+                    self.error_message = Some(err.to_string());
+                }
+            }
+        }
+    }
+
+    /// Returns the rows that match the search, which is all of them if there is no valid search.
+    pub fn filter<'a, T: Filterable>(&self, rows: &'a [T]) -> Vec<&'a T> {
+        match &self.query {
+            Some(query) => query.filter(rows),
+            None => rows.iter().collect(),
+        }
+    }
+}
+
 pub struct TempWidgetState {
     pub scroll_state: AppScrollWidgetState,
     pub table_width_state: CanvasTableWidthState,
+    pub search_state: TableSearchState,
 }
 
 impl TempWidgetState {
@@ -724,6 +1221,7 @@ impl TempWidgetState {
         TempWidgetState {
             scroll_state: AppScrollWidgetState::default(),
             table_width_state: CanvasTableWidthState::default(),
+            search_state: TableSearchState::new(SearchTarget::Temperatures),
         }
     }
 }
@@ -749,6 +1247,7 @@ impl TempState {
 pub struct DiskWidgetState {
     pub scroll_state: AppScrollWidgetState,
     pub table_width_state: CanvasTableWidthState,
+    pub search_state: TableSearchState,
 }
 
 impl DiskWidgetState {
@@ -756,6 +1255,7 @@ impl DiskWidgetState {
         DiskWidgetState {
             scroll_state: AppScrollWidgetState::default(),
             table_width_state: CanvasTableWidthState::default(),
+            search_state: TableSearchState::new(SearchTarget::Disks),
         }
     }
 }
@@ -814,6 +1314,154 @@ impl BatteryState {
     }
 }
 
+/// How a generic, config-defined widget is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomWidgetKind {
+    Table,
+    Graph,
+    Gauge,
+    Text,
+    Histogram,
+}
+
+impl std::str::FromStr for CustomWidgetKind {
+    type Err = crate::utils::error::BottomError;
+
+    fn from_str(s: &str) -> crate::utils::error::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(CustomWidgetKind::Table),
+            "graph" => Ok(CustomWidgetKind::Graph),
+            "gauge" => Ok(CustomWidgetKind::Gauge),
+            "text" => Ok(CustomWidgetKind::Text),
+            "histogram" => Ok(CustomWidgetKind::Histogram),
+            _ => Err(crate::utils::error::BottomError::ConfigError(format!(
+                "\"{}\" is an invalid widget kind.  Supported kinds are table, graph, gauge, text, and histogram.",
+                s
+            ))),
+        }
+    }
+}
+
+pub struct CustomWidgetState {
+    pub kind: CustomWidgetKind,
+    pub title: String,
+    pub max: f64,
+    pub unit: String,
+    pub source: Box<dyn DataSource>,
+    pub latest: Option<DataValue>,
+    pub history: Vec<(Instant, f64)>,
+    pub display_time: u64,
+    pub secondary: Option<SecondarySeries>,
+}
+
+impl CustomWidgetState {
+    pub fn init(
+        kind: CustomWidgetKind, title: String, max: f64, unit: String, source: Box<dyn DataSource>,
+        display_time: u64,
+    ) -> Self {
+        CustomWidgetState {
+            kind,
+            title,
+            max,
+            unit,
+            source,
+            latest: None,
+            history: Vec::new(),
+            display_time,
+            secondary: None,
+        }
+    }
+
+    /// Samples the widget's sources, and records scalar values for graphing.
+    pub fn update(&mut self, data_collection: &DataCollection) {
+        self.latest = self.source.sample(data_collection);
+        record_history(
+            &mut self.history,
+            self.latest.as_ref(),
+            data_collection,
+            self.display_time,
+        );
+
+        if let Some(secondary) = &mut self.secondary {
+            secondary.latest = secondary.source.sample(data_collection);
+            record_history(
+                &mut secondary.history,
+                secondary.latest.as_ref(),
+                data_collection,
+                self.display_time,
+            );
+        }
+    }
+}
+
+/// A second series on a graph widget, drawn against its own y-axis on the right.
+pub struct SecondarySeries {
+    pub max: f64,
+    pub unit: String,
+    pub source: Box<dyn DataSource>,
+    pub latest: Option<DataValue>,
+    pub history: Vec<(Instant, f64)>,
+}
+
+impl SecondarySeries {
+    pub fn init(max: f64, unit: String, source: Box<dyn DataSource>) -> Self {
+        SecondarySeries {
+            max,
+            unit,
+            source,
+            latest: None,
+            history: Vec::new(),
+        }
+    }
+}
+
+/// Records a scalar sample, or every value of a batch of samples, and drops any samples older
+/// than the display time.
+fn record_history(
+    history: &mut Vec<(Instant, f64)>, latest: Option<&DataValue>,
+    data_collection: &DataCollection, display_time: u64,
+) {
+    if let Some(DataValue::Samples(samples)) = latest {
+        history.extend(
+            samples
+                .iter()
+                .map(|sample| (data_collection.current_instant, *sample)),
+        );
+    } else if let Some(value) = latest.and_then(|latest| latest.as_scalar()) {
+        history.push((data_collection.current_instant, value));
+    }
+
+    let display_time = display_time as u128;
+    let current_instant = data_collection.current_instant;
+    history.retain(|(instant, _)| {
+        current_instant.duration_since(*instant).as_millis() <= display_time
+    });
+}
+
+pub struct CustomState {
+    pub widget_states: HashMap<u64, CustomWidgetState>,
+}
+
+impl CustomState {
+    pub fn init(widget_states: HashMap<u64, CustomWidgetState>) -> Self {
+        CustomState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CustomWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&CustomWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+
+    pub fn update(&mut self, data_collection: &DataCollection) {
+        for widget_state in self.widget_states.values_mut() {
+            widget_state.update(data_collection);
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
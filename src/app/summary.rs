@@ -0,0 +1,315 @@
+//! A compact summary of the system, for printing outside of the TUI (ie: for shell prompts
+//! and status bars).
+
+use std::{
+    io::{stdout, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::style::{style, Color};
+use serde::Serialize;
+
+use super::{
+    data_harvester::{temperature::TemperatureType, Data, DataCollector},
+    layout_manager::UsedWidgets,
+};
+use crate::utils::{
+    error::{BottomError, Result},
+    gen_util::{get_byte_values, ByteUnits},
+    number_format::format_number,
+};
+
+/// Usage percentages at or above this are shown as a warning.
+const WARNING_PERCENT: f64 = 50.0;
+/// Usage percentages at or above this are shown as critical.
+const CRITICAL_PERCENT: f64 = 80.0;
+/// Temperatures at or above this are shown as a warning.
+const WARNING_CELSIUS: f64 = 70.0;
+/// Temperatures at or above this are shown as critical.
+const CRITICAL_CELSIUS: f64 = 85.0;
+
+const WARNING_COLOUR: &str = "#FFFF00";
+const CRITICAL_COLOUR: &str = "#FF0000";
+
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub cpu_percent: Option<f64>,
+    pub mem_percent: Option<f64>,
+    /// The 1, 5, and 15 minute load averages.  Not available on Windows.
+    pub load_average: Option<(f64, f64, f64)>,
+    /// Bytes per second.
+    pub rx: Option<u64>,
+    /// Bytes per second.
+    pub tx: Option<u64>,
+    /// Sensor names and temperatures, in the unit the collector was set to.
+    pub temperatures: Vec<(String, f64)>,
+}
+
+impl Summary {
+    pub fn from_data(data: &Data) -> Self {
+        Summary {
+            cpu_percent: data.cpu.as_ref().and_then(|cpus| {
+                cpus.iter()
+                    .find(|cpu| cpu.cpu_count.is_none())
+                    .map(|cpu| cpu.cpu_usage)
+            }),
+            mem_percent: data.memory.as_ref().and_then(|memory| {
+                if memory.mem_total_in_mb > 0 {
+                    Some(memory.mem_used_in_mb as f64 / memory.mem_total_in_mb as f64 * 100.0)
+                } else {
+                    None
+                }
+            }),
+            load_average: get_load_average(),
+            rx: data.network.as_ref().map(|network| network.rx),
+            tx: data.network.as_ref().map(|network| network.tx),
+            temperatures: data
+                .temperature_sensors
+                .as_ref()
+                .map(|sensors| {
+                    sensors
+                        .iter()
+                        .filter_map(|sensor| {
+                            sensor
+                                .component_label
+                                .as_ref()
+                                .or(sensor.component_name.as_ref())
+                                .map(|name| (name.clone(), sensor.temperature as f64))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Formats the summary as a single line, ie: `CPU 12% MEM 43% LOAD 0.52 RX 1.2KiB/s TX 300B/s`.
+    /// Usage is coloured by severity if `use_colour` is set.
+    pub fn to_line(&self, use_colour: bool) -> String {
+        let percent = |label: &str, value: f64| {
+            let text = format!("{}%", format_number(value, 0));
+            if use_colour {
+                let colour = match Severity::of_percent(value) {
+                    Severity::Normal => Color::Green,
+                    Severity::Warning => Color::Yellow,
+                    Severity::Critical => Color::Red,
+                };
+                format!("{} {}", label, style(text).with(colour))
+            } else {
+                format!("{} {}", label, text)
+            }
+        };
+
+        let mut parts = Vec::new();
+        if let Some(cpu_percent) = self.cpu_percent {
+            parts.push(percent("CPU", cpu_percent));
+        }
+        if let Some(mem_percent) = self.mem_percent {
+            parts.push(percent("MEM", mem_percent));
+        }
+        if let Some((one, _, _)) = self.load_average {
+            parts.push(format!("LOAD {}", format_number(one, 2)));
+        }
+        if let Some(rx) = self.rx {
+            parts.push(format!("RX {}", format_rate(rx)));
+        }
+        if let Some(tx) = self.tx {
+            parts.push(format!("TX {}", format_rate(tx)));
+        }
+
+        parts.join(" ")
+    }
+
+    /// Converts the summary into i3bar blocks.  Of the temperature sensors, only the hottest
+    /// is shown, to keep the bar short.
+    pub fn to_i3bar_blocks(&self, temperature_type: &TemperatureType) -> Vec<I3barBlock> {
+        let mut blocks = Vec::new();
+        if let Some(cpu_percent) = self.cpu_percent {
+            blocks.push(I3barBlock::new(
+                "cpu",
+                None,
+                format!("CPU {}%", format_number(cpu_percent, 0)),
+                Severity::of_percent(cpu_percent),
+            ));
+        }
+        if let Some(mem_percent) = self.mem_percent {
+            blocks.push(I3barBlock::new(
+                "mem",
+                None,
+                format!("MEM {}%", format_number(mem_percent, 0)),
+                Severity::of_percent(mem_percent),
+            ));
+        }
+        if let (Some(rx), Some(tx)) = (self.rx, self.tx) {
+            blocks.push(I3barBlock::new(
+                "net",
+                None,
+                format!("RX {} TX {}", format_rate(rx), format_rate(tx)),
+                Severity::Normal,
+            ));
+        }
+        if let Some((name, temperature)) = self
+            .temperatures
+            .iter()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        {
+            let (unit, celsius) = match temperature_type {
+                TemperatureType::Celsius => ("°C", *temperature),
+                TemperatureType::Kelvin => ("K", temperature - 273.15),
+                TemperatureType::Fahrenheit => ("°F", (temperature - 32.0) * 5.0 / 9.0),
+            };
+            let severity = if celsius >= CRITICAL_CELSIUS {
+                Severity::Critical
+            } else if celsius >= WARNING_CELSIUS {
+                Severity::Warning
+            } else {
+                Severity::Normal
+            };
+            blocks.push(I3barBlock::new(
+                "temp",
+                Some(name.clone()),
+                format!("TEMP {}{}", format_number(*temperature, 0), unit),
+                severity,
+            ));
+        }
+
+        blocks
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    fn of_percent(percent: f64) -> Self {
+        if percent >= CRITICAL_PERCENT {
+            Severity::Critical
+        } else if percent >= WARNING_PERCENT {
+            Severity::Warning
+        } else {
+            Severity::Normal
+        }
+    }
+}
+
+/// A single block of the i3bar protocol, which is also understood by swaybar and waybar.
+#[derive(Debug, Clone, Serialize)]
+pub struct I3barBlock {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub full_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    pub urgent: bool,
+}
+
+impl I3barBlock {
+    fn new(name: &str, instance: Option<String>, full_text: String, severity: Severity) -> Self {
+        I3barBlock {
+            name: name.to_string(),
+            instance,
+            full_text,
+            color: match severity {
+                Severity::Normal => None,
+                Severity::Warning => Some(WARNING_COLOUR.to_string()),
+                Severity::Critical => Some(CRITICAL_COLOUR.to_string()),
+            },
+            urgent: severity == Severity::Critical,
+        }
+    }
+}
+
+/// Formats bytes per second, ie: `1.2KiB/s`.
+pub fn format_rate(bytes_per_second: u64) -> String {
+    let (value, unit) = get_byte_values(bytes_per_second, false, ByteUnits::Binary);
+    format!("{}{}/s", format_number(value, 1), unit)
+}
+
+/// Creates a collector for the given widgets, and takes an initial sample so that the next
+/// update has something to compute rates against.
+fn create_summary_collector(
+    used_widgets: UsedWidgets, temperature_type: TemperatureType,
+) -> DataCollector {
+    let mut data_collector = DataCollector::default();
+    data_collector.set_collected_data(used_widgets);
+    data_collector.set_temperature_type(temperature_type);
+    data_collector.set_show_average_cpu(true);
+    data_collector.init();
+    data_collector
+}
+
+/// Collects a single summary.  As CPU usage and network rates need two samples, this takes
+/// about as long as the collector's first run.
+pub fn collect_summary() -> Summary {
+    let mut data_collector = create_summary_collector(
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_net: true,
+            ..UsedWidgets::default()
+        },
+        TemperatureType::Celsius,
+    );
+    futures::executor::block_on(data_collector.update_data());
+    Summary::from_data(&data_collector.data)
+}
+
+/// Writes the i3bar protocol to stdout, with a new status line on every update.  This only
+/// returns once stdout is closed, ie: when the bar exits.
+pub fn run_i3bar(
+    update_rate_in_milliseconds: u64, temperature_type: TemperatureType,
+) -> Result<()> {
+    let mut data_collector = create_summary_collector(
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_net: true,
+            use_temp: true,
+            ..UsedWidgets::default()
+        },
+        temperature_type.clone(),
+    );
+    let update_rate = Duration::from_millis(update_rate_in_milliseconds);
+
+    let stdout = stdout();
+    let mut stdout = stdout.lock();
+    // The header, followed by the start of an infinite array of status lines.
+    writeln!(stdout, "{{\"version\":1}}")?;
+    writeln!(stdout, "[")?;
+
+    loop {
+        let update_start = Instant::now();
+        futures::executor::block_on(data_collector.update_data());
+        let blocks = Summary::from_data(&data_collector.data).to_i3bar_blocks(&temperature_type);
+        let line = serde_json::to_string(&blocks)
+            .map_err(|err| BottomError::ConversionError(err.to_string()))?;
+
+        if writeln!(stdout, "{},", line)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            // The bar went away.
+            return Ok(());
+        }
+
+        thread::sleep(update_rate.saturating_sub(update_start.elapsed()));
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_load_average() -> Option<(f64, f64, f64)> {
+    use sysinfo::{System, SystemExt};
+
+    let load_average = System::new().get_load_average();
+    Some((load_average.one, load_average.five, load_average.fifteen))
+}
+
+#[cfg(target_os = "windows")]
+fn get_load_average() -> Option<(f64, f64, f64)> {
+    None
+}
@@ -7,14 +7,14 @@ use bottom::{canvas, constants::*, data_conversion::*, options::*, *};
 
 use std::{
     boxed::Box,
-    io::{stdout, Write},
+    io::{stdout, BufWriter, Write},
     panic,
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc, Arc, Condvar, Mutex,
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
@@ -27,11 +27,36 @@ use tui::{backend::CrosstermBackend, Terminal};
 
 fn main() -> Result<()> {
     let matches = clap::get_matches();
-    let is_debug = matches.is_present("debug");
-    if is_debug {
+    if matches.is_present("capabilities") {
+        println!("{}", app::capabilities::Capabilities::detect().to_json()?);
+        return Ok(());
+    }
+    if let Some(mut session_paths) = matches.values_of("compare") {
+        if let (Some(before_path), Some(after_path)) = (session_paths.next(), session_paths.next())
+        {
+            app::compare::run_compare(
+                std::path::Path::new(before_path),
+                std::path::Path::new(after_path),
+            )
+            .context("Unable to compare the given sessions.")?;
+        }
+        return Ok(());
+    }
+    if matches.is_present("oneshot") {
+        let use_colour = std::env::var_os("NO_COLOR").is_none();
+        println!("{}", app::summary::collect_summary().to_line(use_colour));
+        return Ok(());
+    }
+
+    let debug_log_path = if matches.is_present("debug") {
         let mut tmp_dir = std::env::temp_dir();
         tmp_dir.push("bottom_debug.log");
         utils::logging::init_logger(log::LevelFilter::Trace, tmp_dir.as_os_str())?;
+        Some(tmp_dir)
+    } else if matches.is_present("debug_log") {
+        let debug_log_path = utils::logging::get_debug_log_path();
+        utils::logging::init_logger(log::LevelFilter::Debug, debug_log_path.as_os_str())?;
+        Some(debug_log_path)
     } else {
         #[cfg(debug_assertions)]
         {
@@ -40,7 +65,8 @@ fn main() -> Result<()> {
                 std::ffi::OsStr::new("debug.log"),
             )?;
         }
-    }
+        None
+    };
 
     let config_path = read_config(matches.value_of("config_location"))
         .context("Unable to access the given config file location.")?;
@@ -49,8 +75,27 @@ fn main() -> Result<()> {
         .context("Unable to properly parse or create the config file.")?;
     trace!("Current config: {:#?}", config);
 
+    utils::number_format::set_number_format(
+        get_number_format(&config).context("Update the number format in your config file.")?,
+    );
+    if let Some(byte_units) =
+        get_config_byte_units(&config).context("Update 'byte_units' in your config file.")?
+    {
+        utils::gen_util::set_byte_units(byte_units);
+    }
+
+    if matches.is_present("i3bar") {
+        app::summary::run_i3bar(
+            get_update_rate_in_milliseconds(&matches, &config)
+                .context("Update 'rate' in your config file.")?,
+            get_temperature(&matches, &config)
+                .context("Update 'temperature_type' in your config file.")?,
+        )?;
+        return Ok(());
+    }
+
     // Get widget layout separately
-    let (widget_layout, default_widget_id, default_widget_type_option) =
+    let (widget_layout, default_widget_id, default_widget_type_option, layout_notes) =
         get_widget_layout(&matches, &config)
             .context("Found an issue while trying to build the widget layout.")?;
 
@@ -64,6 +109,40 @@ fn main() -> Result<()> {
         config_path,
     )?;
 
+    // Restore any data persisted from the previous session.
+    if app.app_config_fields.persist_data {
+        if let Some(snapshot) = app::session_cache::get_snapshot_path()
+            .and_then(|path| app::session_cache::load_snapshot(&path))
+        {
+            trace!("Restoring session snapshot from {:?} ago.", snapshot.age());
+            snapshot.restore(&mut app.data_collection, app.app_config_fields.warm_start);
+        }
+    }
+
+    // Restore the search history from previous sessions.
+    let search_history_path = app::search_history::get_search_history_path();
+    let mut loaded_search_history = Vec::new();
+    if let Some(path) = &search_history_path {
+        loaded_search_history = app::search_history::load_search_history(path);
+        app.set_search_history(&app::search_history::SearchHistory::new(
+            loaded_search_history.clone(),
+        ));
+    }
+
+    // Start pushing metrics to any configured exporters.
+    let exporter_handle = app::exporters::start_exporters(
+        config.exporter.as_deref().unwrap_or_default(),
+        &mut app.used_widgets,
+    )
+    .context("Update 'exporter' in your config file.")?;
+    let mut hook_handle = app.app_config_fields.hook_command.clone().map(|command| {
+        app::hook::start_hook(
+            &command,
+            app.app_config_fields.hook_interval,
+            &mut app.used_widgets,
+        )
+    });
+
     // Create painter and set colours.
     let mut painter = canvas::Painter::init(
         widget_layout,
@@ -72,15 +151,24 @@ fn main() -> Result<()> {
         &config,
         get_color_scheme(&matches, &config)?,
     )?;
+    painter.add_help_notes(&layout_notes);
 
     // Create termination mutex and cvar
     #[allow(clippy::mutex_atomic)]
     let thread_termination_lock = Arc::new(Mutex::new(false));
     let thread_termination_cvar = Arc::new(Condvar::new());
 
-    // Set up input handling
-    let (sender, receiver) = mpsc::channel();
-    let _input_thread = create_input_thread(sender.clone(), thread_termination_lock.clone());
+    // Set up input handling.  Input gets its own channel so it can be handled before anything else.
+    let (input_sender, input_receiver) = crossbeam_channel::unbounded();
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let _input_thread = create_input_thread(input_sender, thread_termination_lock.clone());
+
+    // Set up the control FIFO, if requested
+    if let Some(control_path) = &app.app_config_fields.control_path {
+        app::control::create_control_fifo(control_path)
+            .context("Update 'control_path' in your config file.")?;
+        let _control_thread = create_control_thread(sender.clone(), control_path.clone());
+    }
 
     // Cleaning loop
     let _cleaning_thread = {
@@ -129,23 +217,82 @@ fn main() -> Result<()> {
     execute!(stdout_val, EnterAlternateScreen, EnableMouseCapture)?;
     enable_raw_mode()?;
 
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout_val))?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(BufWriter::with_capacity(
+        TERMINAL_BUFFER_CAPACITY,
+        stdout_val,
+    )))?;
     terminal.clear()?;
     terminal.hide_cursor()?;
 
-    // Set panic hook
-    panic::set_hook(Box::new(|info| panic_hook(info)));
-
     // Set termination hook
     let is_terminated = Arc::new(AtomicBool::new(false));
-    let ist_clone = is_terminated.clone();
-    ctrlc::set_handler(move || {
-        ist_clone.store(true, Ordering::SeqCst);
-    })?;
+
+    // Set panic hook.  If another thread panics, we also want to stop drawing rather than
+    // drawing over the crash output.
+    let panic_ist_clone = is_terminated.clone();
+    panic::set_hook(Box::new(move |info| {
+        panic_hook(info);
+        panic_ist_clone.store(true, Ordering::SeqCst);
+    }));
+
+    // Being asked to stop from outside, such as by SIGTERM, shuts down the same way as quitting.
+    // If we are logging to a file, SIGHUP reopens it instead, so it can be rotated.
+    utils::shutdown::register_shutdown_handlers(is_terminated.clone(), debug_log_path.is_some())?;
+
+    // On Unix, SIGUSR1 dumps a snapshot of the current metrics.
+    #[cfg(target_family = "unix")]
+    let dump_requested = Arc::new(AtomicBool::new(false));
+    #[cfg(target_family = "unix")]
+    let reopen_log_requested = Arc::new(AtomicBool::new(false));
+    #[cfg(target_family = "unix")]
+    {
+        signal_hook::flag::register(signal_hook::consts::SIGUSR1, dump_requested.clone())?;
+        if debug_log_path.is_some() {
+            signal_hook::flag::register(signal_hook::consts::SIGHUP, reopen_log_requested.clone())?;
+        }
+    }
+
     let mut first_run = true;
+    let mut is_collecting_connections = false;
+    let mut is_collecting_app_names = false;
+    let mut is_collecting_gpu_stats = false;
+    let mut is_collecting_security_contexts = false;
+    let mut is_collecting_namespaces = false;
+    let mut is_collecting_fds = false;
+    let mut is_collecting_env = false;
+    let mut is_collecting_cgroups = false;
+    let mut is_collecting_window_titles = false;
+    let mut is_collecting_media = false;
+    let mut is_collecting_energy = false;
+
+    // Frames are only drawn when something changed, and with max_fps, new data is drawn at most
+    // once per interval.
+    let min_draw_interval = app
+        .app_config_fields
+        .max_fps
+        .map(|max_fps| Duration::from_millis(1000 / max_fps));
+    let mut needs_redraw = true;
+    let mut last_draw_instant: Option<Instant> = None;
 
     while !is_terminated.load(Ordering::SeqCst) {
-        if let Ok(recv) = receiver.recv_timeout(Duration::from_millis(TICK_RATE_IN_MILLISECONDS)) {
+        let time_until_draw_allowed = match (min_draw_interval, last_draw_instant) {
+            (Some(min_draw_interval), Some(last_draw_instant)) => {
+                min_draw_interval.saturating_sub(last_draw_instant.elapsed())
+            }
+            _ => Duration::ZERO,
+        };
+        let timeout = if needs_redraw && !time_until_draw_allowed.is_zero() {
+            // Wake up in time to draw what was held back.
+            std::cmp::min(
+                time_until_draw_allowed,
+                Duration::from_millis(TICK_RATE_IN_MILLISECONDS),
+            )
+        } else {
+            Duration::from_millis(TICK_RATE_IN_MILLISECONDS)
+        };
+
+        let mut is_input = false;
+        if let Some(recv) = receive_event(&input_receiver, &receiver, timeout) {
             if log_enabled!(log::Level::Trace) {
                 if let BottomEvent::Update(_) = recv {
                     trace!("Main/drawing thread received Update event.");
@@ -153,19 +300,56 @@ fn main() -> Result<()> {
                     trace!("Main/drawing thread received event: {:?}", recv);
                 }
             }
+
+            let event_kind = match &recv {
+                BottomEvent::KeyInput(_) => "key",
+                BottomEvent::MouseInput(_) => "mouse",
+                BottomEvent::Resize => "resize",
+                BottomEvent::Update(_) => "update",
+                BottomEvent::Control(_) => "control",
+                BottomEvent::Clean => "clean",
+            };
+            let handle_start = Instant::now();
+
             match recv {
                 BottomEvent::KeyInput(event) => {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+                    app.update_widget_links();
+                    app.dispatch_actions();
                     handle_force_redraws(&mut app);
+                    is_input = true;
                 }
                 BottomEvent::MouseInput(event) => {
                     handle_mouse_event(event, &mut app);
+                    app.update_widget_links();
+                    app.dispatch_actions();
                     handle_force_redraws(&mut app);
+                    is_input = true;
+                }
+                BottomEvent::Resize => {
+                    // Widths and mouse bounds are worked out again on the next draw, while
+                    // selections, scroll positions, and zoom levels are kept.
+                    app.is_force_redraw = true;
+                    is_input = true;
                 }
                 BottomEvent::Update(data) => {
                     app.data_collection.eat_data(&data);
+                    app.data_collection
+                        .record_threshold_alerts(&app.app_config_fields.graph_thresholds);
+
+                    if exporter_handle.is_some() || hook_handle.is_some() {
+                        let snapshot = app::metrics::MetricSnapshot::from_data_collection(
+                            &app.data_collection,
+                        );
+                        if let Some(hook_handle) = &mut hook_handle {
+                            hook_handle.publish(snapshot.clone());
+                        }
+                        if let Some(exporter_handle) = &exporter_handle {
+                            exporter_handle.publish(snapshot);
+                        }
+                    }
 
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
@@ -199,8 +383,11 @@ fn main() -> Result<()> {
 
                         // Disk
                         if app.used_widgets.use_disk {
-                            app.canvas_data.disk_data =
-                                convert_disk_row(&app.data_collection, &app.filters.disk_filter);
+                            app.canvas_data.disk_data = convert_disk_row(
+                                &app.data_collection,
+                                &app.filters.disk_filter,
+                                app.app_config_fields.hide_removable_disks,
+                            );
                         }
 
                         // Temperatures
@@ -230,6 +417,9 @@ fn main() -> Result<()> {
                         // Processes
                         if app.used_widgets.use_proc {
                             update_all_process_lists(&mut app);
+                            app.update_widget_links();
+                            app.dispatch_actions();
+                            app.refresh_process_details();
                         }
 
                         // Battery
@@ -237,17 +427,163 @@ fn main() -> Result<()> {
                             app.canvas_data.battery_data =
                                 convert_battery_harvest(&app.data_collection);
                         }
+
+                        // Custom widgets
+                        if !app.custom_state.widget_states.is_empty() {
+                            app.custom_state.update(&app.data_collection);
+                        }
+
+                        // Previous session marker for graphs
+                        if app.data_collection.previous_session_instant.is_some() {
+                            app.canvas_data.previous_session_offset =
+                                convert_previous_session_offset(&app.data_collection, false);
+                        }
+
+                        // Alerts for graphs
+                        app.canvas_data.alert_markers =
+                            convert_alert_markers(&app.data_collection, false);
                     }
                 }
+                BottomEvent::Control(action) => {
+                    app.emit(action);
+                    app.dispatch_actions();
+                    handle_force_redraws(&mut app);
+                }
                 BottomEvent::Clean => {
                     app.data_collection
                         .clean_data(constants::STALE_MAX_MILLISECONDS);
                 }
             }
+            needs_redraw = true;
+
+            debug!(
+                "Handled {} event in {:?}.",
+                event_kind,
+                handle_start.elapsed()
+            );
+
+            // Expensive things are only collected while something shows or uses them.  When one
+            // starts being used, collect right away rather than at the next update, so a column
+            // that was just shown fills in at once.
+            let mut is_collecting_more = false;
+            is_collecting_more |= update_collection(
+                app.is_using_connections(),
+                &mut is_collecting_connections,
+                ThreadControlEvent::UpdateCollectConnections,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_app_names(),
+                &mut is_collecting_app_names,
+                ThreadControlEvent::UpdateCollectAppNames,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_gpu_stats(),
+                &mut is_collecting_gpu_stats,
+                ThreadControlEvent::UpdateCollectGpuStats,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_security_contexts(),
+                &mut is_collecting_security_contexts,
+                ThreadControlEvent::UpdateCollectSecurityContexts,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_namespaces(),
+                &mut is_collecting_namespaces,
+                ThreadControlEvent::UpdateCollectNamespaces,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_fds(),
+                &mut is_collecting_fds,
+                ThreadControlEvent::UpdateCollectFds,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_env(),
+                &mut is_collecting_env,
+                ThreadControlEvent::UpdateCollectEnv,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_cgroups(),
+                &mut is_collecting_cgroups,
+                ThreadControlEvent::UpdateCollectCgroups,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_window_titles(),
+                &mut is_collecting_window_titles,
+                ThreadControlEvent::UpdateCollectWindowTitles,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_media(),
+                &mut is_collecting_media,
+                ThreadControlEvent::UpdateCollectMedia,
+                &collection_thread_ctrl_sender,
+            );
+            is_collecting_more |= update_collection(
+                app.is_using_energy(),
+                &mut is_collecting_energy,
+                ThreadControlEvent::UpdateCollectEnergy,
+                &collection_thread_ctrl_sender,
+            );
+            if is_collecting_more {
+                thread_termination_cvar.notify_all();
+            }
+
+            if let Some(mount_point) = app.pending_mount_scan.take() {
+                collection_thread_ctrl_sender
+                    .send(ThreadControlEvent::FindProcessesOnMount(mount_point))
+                    .ok();
+                // Wake the collection thread up, so it doesn't wait for its next update first.
+                thread_termination_cvar.notify_all();
+            }
+        } else if app.has_expiring_elements() {
+            needs_redraw = true;
+        }
+
+        #[cfg(target_family = "unix")]
+        {
+            if dump_requested.swap(false, Ordering::SeqCst) {
+                dump_metric_snapshot(&app);
+            }
+            if reopen_log_requested.swap(false, Ordering::SeqCst) {
+                utils::logging::reopen_log_file();
+                debug!("Reopened the log file.");
+            }
         }
 
-        // TODO: [OPT] Should not draw if no change (ie: scroll max)
-        try_drawing(&mut terminal, &mut app, &mut painter, is_debug)?;
+        // Input is always drawn right away, so it doesn't feel sluggish.  If more input is already
+        // waiting though, it is all handled first and drawn once.
+        let is_draw_allowed = is_input
+            || app.is_force_redraw
+            || match (min_draw_interval, last_draw_instant) {
+                (Some(min_draw_interval), Some(last_draw_instant)) => {
+                    last_draw_instant.elapsed() >= min_draw_interval
+                }
+                _ => true,
+            };
+        if needs_redraw
+            && is_draw_allowed
+            && input_receiver.is_empty()
+            && !is_terminated.load(Ordering::SeqCst)
+        {
+            let draw_start = Instant::now();
+            try_drawing(
+                &mut terminal,
+                &mut app,
+                &mut painter,
+                debug_log_path.as_deref(),
+            )?;
+            debug!("Drew frame in {:?}.", draw_start.elapsed());
+            needs_redraw = false;
+            last_draw_instant = Some(draw_start);
+        }
     }
 
     // I think doing it in this order is safe...
@@ -257,8 +593,44 @@ fn main() -> Result<()> {
     thread_termination_cvar.notify_all();
 
     trace!("Main/drawing thread is cleaning up.");
-    cleanup_terminal(&mut terminal, is_debug)?;
+    // The terminal may be gone if it hung up, but everything else should still be saved.
+    if let Err(err) = cleanup_terminal(&mut terminal, debug_log_path.as_deref()) {
+        debug!("Unable to restore the terminal: {}", err);
+    }
+
+    if let Some(exporter_handle) = exporter_handle {
+        exporter_handle.finish();
+    }
+
+    if let Some(control_path) = &app.app_config_fields.control_path {
+        let _ = std::fs::remove_file(control_path);
+    }
+
+    if app.app_config_fields.persist_data {
+        if let Some(path) = app::session_cache::get_snapshot_path() {
+            let snapshot =
+                app::session_cache::SessionSnapshot::from_data_collection(&app.data_collection);
+            if let Err(err) = app::session_cache::save_snapshot(&path, &snapshot) {
+                debug!("Unable to save session snapshot: {}", err);
+            }
+        }
+    }
+
+    if let (Some(path), Some(search_history)) = (&search_history_path, app.get_search_history()) {
+        if !search_history
+            .entries()
+            .iter()
+            .eq(loaded_search_history.iter())
+        {
+            if let Err(err) = app::search_history::save_search_history(path, search_history) {
+                debug!("Unable to save search history: {}", err);
+            }
+        }
+    }
 
     trace!("Fini.");
+    if let Some(exit_code) = utils::shutdown::finish() {
+        std::process::exit(exit_code);
+    }
     Ok(())
 }
@@ -21,7 +21,10 @@ use crate::{
         App,
     },
     constants::*,
-    data_conversion::{ConvertedBatteryData, ConvertedCpuData, ConvertedProcessData},
+    data_conversion::{
+        ConvertedAlert, ConvertedBatteryData, ConvertedCpuData, ConvertedDiskData,
+        ConvertedProcessData, ConvertedTempData, ProcessTotals,
+    },
     options::Config,
     utils::error,
     utils::error::BottomError,
@@ -45,11 +48,12 @@ pub struct DisplayableData {
     pub total_tx_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
-    pub disk_data: Vec<Vec<String>>,
-    pub temp_sensor_data: Vec<Vec<String>>,
+    pub disk_data: Vec<ConvertedDiskData>,
+    pub temp_sensor_data: Vec<ConvertedTempData>,
     pub single_process_data: HashMap<Pid, ConvertedProcessData>, // Contains single process data, key is PID
     pub finalized_process_data_map: HashMap<u64, Vec<ConvertedProcessData>>, // What's actually displayed, key is the widget ID.
     pub stringified_process_data_map: HashMap<u64, Vec<(Vec<(String, Option<String>)>, bool)>>, // Represents the row and whether it is disabled, key is the widget ID
+    pub process_totals_map: HashMap<u64, ProcessTotals>, // Totals of the processes that match the search, key is the widget ID
     pub mem_label_percent: String,
     pub swap_label_percent: String,
     pub mem_label_frac: String,
@@ -58,6 +62,8 @@ pub struct DisplayableData {
     pub swap_data: Vec<Point>,
     pub cpu_data: Vec<ConvertedCpuData>,
     pub battery_data: Vec<ConvertedBatteryData>,
+    pub previous_session_offset: Option<f64>, // Where the previous session's data ends in graphs, if warm-started
+    pub alert_markers: Vec<ConvertedAlert>,   // Where thresholds were crossed in graphs
 }
 
 #[derive(Debug)]
@@ -217,15 +223,15 @@ impl Painter {
             }
             ColourScheme::DefaultLight => {
                 self.colours
-                    .set_colours_from_palette(&*DEFAULT_LIGHT_MODE_COLOUR_PALETTE)?;
+                    .set_colours_from_palette(&DEFAULT_LIGHT_MODE_COLOUR_PALETTE)?;
             }
             ColourScheme::Gruvbox => {
                 self.colours
-                    .set_colours_from_palette(&*GRUVBOX_COLOUR_PALETTE)?;
+                    .set_colours_from_palette(&GRUVBOX_COLOUR_PALETTE)?;
             }
             ColourScheme::GruvboxLight => {
                 self.colours
-                    .set_colours_from_palette(&*GRUVBOX_LIGHT_COLOUR_PALETTE)?;
+                    .set_colours_from_palette(&GRUVBOX_LIGHT_COLOUR_PALETTE)?;
             }
             // ColourScheme::Nord => {
             //     self.colours
@@ -274,13 +280,44 @@ impl Painter {
         self.styled_help_text = styled_help_spans.into_iter().map(Spans::from).collect();
     }
 
+    /// Adds a section to the end of the help menu with the given notes, such as why a widget
+    /// isn't shown.
+    pub fn add_help_notes(&mut self, notes: &[String]) {
+        if notes.is_empty() {
+            return;
+        }
+
+        self.styled_help_text.push(Spans::from(Span::raw("")));
+        self.styled_help_text.push(Spans::from(Span::styled(
+            "Notes",
+            self.colours.table_header_style,
+        )));
+        let text_style = self.colours.text_style;
+        self.styled_help_text.extend(
+            notes
+                .iter()
+                .map(|note| Spans::from(Span::styled(note.clone(), text_style))),
+        );
+    }
+
     // FIXME: [CONFIG] write this, should call painter init and any changed colour functions...
     pub fn update_painter_colours(&mut self) {}
 
-    fn draw_frozen_indicator<B: Backend>(&self, f: &mut Frame<'_, B>, draw_loc: Rect) {
+    /// Draws whether data is frozen, and a recent status message, if any.
+    fn draw_status_line<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &app::App, draw_loc: Rect,
+    ) {
+        let mut status = Vec::new();
+        if app_state.is_frozen {
+            status.push("Frozen, press 'f' to unfreeze");
+        }
+        if let Some(status_message) = get_recent_status_message(app_state) {
+            status.push(status_message);
+        }
+
         f.render_widget(
             Paragraph::new(Span::styled(
-                "Frozen, press 'f' to unfreeze",
+                status.join(" | "),
                 self.colours.currently_selected_text_style,
             )),
             Layout::default()
@@ -295,8 +332,10 @@ impl Painter {
     ) -> error::Result<()> {
         use BottomWidgetType::*;
 
-        terminal.draw(|mut f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.is_frozen {
+        terminal.draw(|f| {
+            let has_status_line =
+                app_state.is_frozen || get_recent_status_message(app_state).is_some();
+            let (terminal_size, status_draw_loc) = if has_status_line {
                 let split_loc = Layout::default()
                     .constraints([Constraint::Min(0), Constraint::Length(1)])
                     .split(f.size());
@@ -364,7 +403,102 @@ impl Painter {
                     })
                     .split(vertical_dialog_chunk[1]);
 
-                self.draw_help_dialog(&mut f, app_state, middle_dialog_chunk[1]);
+                self.draw_help_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.renice_dialog_state.is_showing {
+                let (text_width, text_height) = (
+                    if terminal_width < 100 {
+                        terminal_width * 90 / 100
+                    } else {
+                        terminal_width * 50 / 100
+                    },
+                    8,
+                );
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(vertical_bordering),
+                        Constraint::Length(text_height),
+                        Constraint::Length(vertical_bordering),
+                    ])
+                    .split(terminal_size);
+
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Length(horizontal_bordering),
+                        Constraint::Length(text_width),
+                        Constraint::Length(horizontal_bordering),
+                    ])
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_renice_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.column_picker_state.is_showing {
+                let num_column_groups = app_state
+                    .proc_state
+                    .widget_states
+                    .get(&app_state.column_picker_state.widget_id)
+                    .map(|proc_widget_state| proc_widget_state.columns.get_column_groups().len())
+                    .unwrap_or(0) as u16;
+                let (text_width, text_height) = (
+                    std::cmp::min(terminal_width, 50),
+                    std::cmp::min(
+                        terminal_height,
+                        num_column_groups + column_picker_dialog::COLUMN_PICKER_HINT_HEIGHT + 2,
+                    ),
+                );
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(vertical_bordering),
+                        Constraint::Length(text_height),
+                        Constraint::Length(vertical_bordering),
+                    ])
+                    .split(terminal_size);
+
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Length(horizontal_bordering),
+                        Constraint::Length(text_width),
+                        Constraint::Length(horizontal_bordering),
+                    ])
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_column_picker_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_details_state.is_showing {
+                let (text_width, text_height) = (
+                    if terminal_width < 100 {
+                        terminal_width * 90 / 100
+                    } else {
+                        terminal_width * 60 / 100
+                    },
+                    std::cmp::min(terminal_height, 18),
+                );
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(vertical_bordering),
+                        Constraint::Length(text_height),
+                        Constraint::Length(vertical_bordering),
+                    ])
+                    .split(terminal_size);
+
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Length(horizontal_bordering),
+                        Constraint::Length(text_width),
+                        Constraint::Length(horizontal_bordering),
+                    ])
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_details_dialog(f, app_state, middle_dialog_chunk[1]);
             } else if app_state.delete_dialog_state.is_showing_dd {
                 // TODO: This needs the paragraph wrap feature from tui-rs to be pushed to complete... but for now it's pretty close!
                 // The main problem right now is that I cannot properly calculate the height offset since
@@ -380,7 +514,11 @@ impl Painter {
                     } else {
                         terminal_width * 50 / 100
                     },
-                    7,
+                    if app::process_killer::SIGNALS.is_empty() {
+                        7
+                    } else {
+                        10
+                    },
                 );
                 // let (text_width, text_height) = if let Some(dd_text) = &dd_text {
                 //     let width = if current_width < 100 {
@@ -435,10 +573,10 @@ impl Painter {
 
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
-                    self.draw_dd_dialog(&mut f, dd_text, app_state, middle_dialog_chunk[1]);
+                    self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(&mut f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let rect = Layout::default()
@@ -446,40 +584,35 @@ impl Painter {
                     .constraints([Constraint::Percentage(100)])
                     .split(terminal_size);
                 match &app_state.current_widget.widget_type {
-                    Cpu => self.draw_cpu(
-                        &mut f,
-                        app_state,
-                        rect[0],
-                        app_state.current_widget.widget_id,
-                    ),
+                    Cpu => self.draw_cpu(f, app_state, rect[0], app_state.current_widget.widget_id),
                     CpuLegend => self.draw_cpu(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         app_state.current_widget.widget_id - 1,
                     ),
                     Mem | BasicMem => self.draw_memory_graph(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         app_state.current_widget.widget_id,
                     ),
                     Disk => self.draw_disk_table(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         true,
                         app_state.current_widget.widget_id,
                     ),
                     Temp => self.draw_temp_table(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         true,
                         app_state.current_widget.widget_id,
                     ),
                     Net => self.draw_network_graph(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         app_state.current_widget.widget_id,
@@ -493,15 +626,21 @@ impl Painter {
                                 _ => 0,
                             };
 
-                        self.draw_process_features(&mut f, app_state, rect[0], true, widget_id);
+                        self.draw_process_features(f, app_state, rect[0], true, widget_id);
                     }
                     Battery => self.draw_battery_display(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         true,
                         app_state.current_widget.widget_id,
                     ),
+                    Custom(_) => self.draw_custom_widget(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     _ => {}
                 }
             } else if app_state.is_config_open {
@@ -510,12 +649,12 @@ impl Painter {
                     .constraints([Constraint::Percentage(100)])
                     .split(f.size())[0];
 
-                self.draw_config_screen(&mut f, app_state, rect)
+                self.draw_config_screen(f, app_state, rect)
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(&mut f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let vertical_chunks = Layout::default()
@@ -523,7 +662,7 @@ impl Painter {
                     .constraints([
                         Constraint::Length(
                             (app_state.canvas_data.cpu_data.len() / 4) as u16
-                                + (if app_state.canvas_data.cpu_data.len() % 4 == 0 {
+                                + (if app_state.canvas_data.cpu_data.len().is_multiple_of(4) {
                                     0
                                 } else {
                                     1
@@ -540,22 +679,18 @@ impl Painter {
                     .direction(Direction::Horizontal)
                     .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
                     .split(vertical_chunks[2]);
-                self.draw_basic_cpu(&mut f, app_state, vertical_chunks[0], 1);
-                self.draw_basic_memory(&mut f, app_state, middle_chunks[0], 2);
-                self.draw_basic_network(&mut f, app_state, middle_chunks[1], 3);
+                self.draw_basic_cpu(f, app_state, vertical_chunks[0], 1);
+                self.draw_basic_memory(f, app_state, middle_chunks[0], 2);
+                self.draw_basic_network(f, app_state, middle_chunks[1], 3);
 
                 let mut later_widget_id: Option<u64> = None;
                 if let Some(basic_table_widget_state) = &app_state.basic_table_widget_state {
                     let widget_id = basic_table_widget_state.currently_displayed_widget_id;
                     later_widget_id = Some(widget_id);
                     match basic_table_widget_state.currently_displayed_widget_type {
-                        Disk => self.draw_disk_table(
-                            &mut f,
-                            app_state,
-                            vertical_chunks[4],
-                            false,
-                            widget_id,
-                        ),
+                        Disk => {
+                            self.draw_disk_table(f, app_state, vertical_chunks[4], false, widget_id)
+                        }
                         Proc | ProcSort => {
                             let wid = widget_id
                                 - match basic_table_widget_state.currently_displayed_widget_type {
@@ -564,22 +699,18 @@ impl Painter {
                                     _ => 0,
                                 };
                             self.draw_process_features(
-                                &mut f,
+                                f,
                                 app_state,
                                 vertical_chunks[4],
                                 false,
                                 wid,
                             );
                         }
-                        Temp => self.draw_temp_table(
-                            &mut f,
-                            app_state,
-                            vertical_chunks[4],
-                            false,
-                            widget_id,
-                        ),
+                        Temp => {
+                            self.draw_temp_table(f, app_state, vertical_chunks[4], false, widget_id)
+                        }
                         Battery => self.draw_battery_display(
-                            &mut f,
+                            f,
                             app_state,
                             vertical_chunks[4],
                             false,
@@ -590,18 +721,18 @@ impl Painter {
                 }
 
                 if let Some(widget_id) = later_widget_id {
-                    self.draw_basic_table_arrows(&mut f, app_state, vertical_chunks[3], widget_id);
+                    self.draw_basic_table_arrows(f, app_state, vertical_chunks[3], widget_id);
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(&mut f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
                     let row_draw_locs = Layout::default()
                         .margin(0)
-                        .constraints(self.row_constraints.as_ref())
+                        .constraints(self.row_constraints.as_slice())
                         .direction(Direction::Vertical)
                         .split(terminal_size);
                     let col_draw_locs = self
@@ -610,7 +741,7 @@ impl Painter {
                         .zip(&row_draw_locs)
                         .map(|(col_constraint, row_draw_loc)| {
                             Layout::default()
-                                .constraints(col_constraint.as_ref())
+                                .constraints(col_constraint.as_slice())
                                 .direction(Direction::Horizontal)
                                 .split(*row_draw_loc)
                         })
@@ -625,7 +756,7 @@ impl Painter {
                                 .zip(row_draw_loc)
                                 .map(|(col_row_constraint, col_draw_loc)| {
                                     Layout::default()
-                                        .constraints(col_row_constraint.as_ref())
+                                        .constraints(col_row_constraint.as_slice())
                                         .direction(Direction::Vertical)
                                         .split(*col_draw_loc)
                                 })
@@ -650,12 +781,12 @@ impl Painter {
                                         |(col_row_constraint_vec, col_row_draw_loc, widgets)| {
                                             // Note that col_row_constraint_vec CONTAINS the widget constraints
                                             let widget_draw_locs = Layout::default()
-                                                .constraints(col_row_constraint_vec.as_ref())
+                                                .constraints(col_row_constraint_vec.as_slice())
                                                 .direction(Direction::Horizontal)
                                                 .split(col_row_draw_loc);
 
                                             self.draw_widgets_with_constraints(
-                                                &mut f,
+                                                f,
                                                 app_state,
                                                 widgets,
                                                 &widget_draw_locs,
@@ -681,10 +812,10 @@ impl Painter {
                                     col_rows.children.iter().zip(col_row_layout).for_each(
                                         |(widgets, widget_draw_locs)| {
                                             self.draw_widgets_with_constraints(
-                                                &mut f,
+                                                f,
                                                 app_state,
                                                 widgets,
-                                                &widget_draw_locs,
+                                                widget_draw_locs,
                                             );
                                         },
                                     );
@@ -732,8 +863,22 @@ impl Painter {
                     true,
                     widget.widget_id,
                 ),
+                Custom(_) => {
+                    self.draw_custom_widget(f, app_state, *widget_draw_loc, widget.widget_id)
+                }
                 _ => {}
             }
         }
     }
 }
+
+/// Returns the last status message, if it was set recently enough to still show.
+fn get_recent_status_message(app_state: &app::App) -> Option<&str> {
+    app_state
+        .status_message
+        .as_ref()
+        .filter(|(time, _)| {
+            time.elapsed().as_millis() < u128::from(STATUS_MESSAGE_TIMEOUT_MILLISECONDS)
+        })
+        .map(|(_, message)| message.as_str())
+}
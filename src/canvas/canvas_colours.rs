@@ -27,7 +27,12 @@ pub struct CanvasColours {
     pub medium_battery_colour: Style,
     pub low_battery_colour: Style,
     pub invalid_query_style: Style,
+    pub warning_text_style: Style,
     pub disabled_text_style: Style,
+    pub pinned_text_style: Style,
+    pub query_operator_style: Style,
+    pub query_number_style: Style,
+    pub query_string_style: Style,
 }
 
 impl Default for CanvasColours {
@@ -70,7 +75,12 @@ impl Default for CanvasColours {
             medium_battery_colour: Style::default().fg(Color::Yellow),
             low_battery_colour: Style::default().fg(Color::Red),
             invalid_query_style: Style::default().fg(tui::style::Color::Red),
+            warning_text_style: Style::default().fg(Color::Red),
             disabled_text_style: Style::default().fg(Color::DarkGray),
+            pinned_text_style: Style::default().fg(STANDARD_THIRD_COLOUR),
+            query_operator_style: Style::default().fg(STANDARD_SECOND_COLOUR),
+            query_number_style: Style::default().fg(STANDARD_FIRST_COLOUR),
+            query_string_style: Style::default().fg(STANDARD_FOURTH_COLOUR),
         }
     }
 }
@@ -172,6 +182,11 @@ impl CanvasColours {
                 .context("Update 'disabled_text_color' in your config file.")?;
         }
 
+        if let Some(pinned_text_color) = &colours.pinned_text_color {
+            self.set_pinned_text_colour(pinned_text_color)
+                .context("Update 'pinned_text_color' in your config file.")?;
+        }
+
         if let Some(rx_total_color) = &colours.rx_total_color {
             self.set_rx_total_colour(rx_total_color)?;
         }
@@ -188,6 +203,11 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_pinned_text_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.pinned_text_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
     pub fn set_text_colour(&mut self, colour: &str) -> error::Result<()> {
         self.text_style = get_style_from_config(colour)?;
         Ok(())
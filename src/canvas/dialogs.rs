@@ -1,5 +1,11 @@
+pub mod column_picker_dialog;
 pub mod dd_dialog;
 pub mod help_dialog;
+pub mod process_details_dialog;
+pub mod renice_dialog;
 
+pub use column_picker_dialog::ColumnPickerDialog;
 pub use dd_dialog::KillDialog;
 pub use help_dialog::HelpDialog;
+pub use process_details_dialog::ProcessDetailsDialog;
+pub use renice_dialog::ReniceDialog;
@@ -0,0 +1,130 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{get_column_group, App},
+    canvas::Painter,
+};
+
+const COLUMN_PICKER_BASE: &str = " Columns ── Esc to close ";
+
+/// The lines above the list of columns.
+pub const COLUMN_PICKER_HINT_HEIGHT: u16 = 2;
+
+pub trait ColumnPickerDialog {
+    fn draw_column_picker_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    );
+}
+
+impl ColumnPickerDialog for Painter {
+    fn draw_column_picker_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let picker_state = &app_state.column_picker_state;
+        let proc_widget_state = match app_state
+            .proc_state
+            .widget_states
+            .get(&picker_state.widget_id)
+        {
+            Some(proc_widget_state) => proc_widget_state,
+            None => return,
+        };
+        let columns = &proc_widget_state.columns;
+
+        // Keep the selected column in view if the list doesn't fit.
+        let num_shown_rows = usize::from(
+            draw_loc
+                .height
+                .saturating_sub(2 + COLUMN_PICKER_HINT_HEIGHT)
+                .max(1),
+        );
+        let start_index = (picker_state.selected_index + 1).saturating_sub(num_shown_rows);
+
+        let mut picker_text = vec![
+            Spans::from("Space to show or hide, K and J to move"),
+            Spans::default(),
+        ];
+        picker_text.extend(
+            columns
+                .get_column_groups()
+                .iter()
+                .enumerate()
+                .skip(start_index)
+                .take(num_shown_rows)
+                .map(|(index, column_group)| {
+                    let group_columns = columns
+                        .ordered_columns
+                        .iter()
+                        .filter(|column| get_column_group(column) == *column_group)
+                        .collect::<Vec<_>>();
+                    let name = group_columns
+                        .iter()
+                        .map(|column| columns.get_column_name(column))
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    let line = format!(
+                        "[{}] {}{}",
+                        if columns.is_column_group_shown(column_group) {
+                            "x"
+                        } else {
+                            " "
+                        },
+                        name,
+                        if proc_widget_state.is_grouped
+                            && columns.is_column_group_shown(column_group)
+                            && !group_columns
+                                .iter()
+                                .any(|column| columns.is_enabled(column))
+                        {
+                            " (hidden while grouped)"
+                        } else {
+                            ""
+                        }
+                    );
+
+                    if index == picker_state.selected_index {
+                        Spans::from(Span::styled(
+                            line,
+                            self.colours.currently_selected_text_style,
+                        ))
+                    } else {
+                        Spans::from(line)
+                    }
+                }),
+        );
+
+        let picker_title = Spans::from(vec![
+            Span::styled(" Columns ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(COLUMN_PICKER_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(Text::from(picker_text))
+                .block(
+                    Block::default()
+                        .title(picker_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
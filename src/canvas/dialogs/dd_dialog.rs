@@ -6,7 +6,10 @@ use tui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use crate::{app::App, canvas::Painter};
+use crate::{
+    app::{process_killer, App},
+    canvas::Painter,
+};
 
 const DD_BASE: &str = " Confirm Kill Process ── Esc to close ";
 const DD_ERROR_BASE: &str = " Error ── Esc to close ";
@@ -30,28 +33,63 @@ impl KillDialog for Painter {
             ]));
         } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
             if let Some(first_pid) = to_kill_processes.1.first() {
-                return Some(Text::from(vec![
+                let dd_state = &app_state.delete_dialog_state;
+                let action = match dd_state.get_signal() {
+                    Some(signal) => match process_killer::get_signal_name(signal) {
+                        Some(signal_name) => format!("Send {} to", signal_name),
+                        None => format!("Send signal {} to", signal),
+                    },
+                    None => "Kill".to_string(),
+                };
+
+                let mut dd_text = vec![
                     Spans::from(""),
                     if app_state.is_grouped(app_state.current_widget.widget_id) {
                         if to_kill_processes.1.len() != 1 {
                             Spans::from(format!(
-                                "Kill {} processes with the name \"{}\"?  Press ENTER to confirm.",
+                                "{} {} processes with the name \"{}\"?  Press ENTER to confirm.",
+                                action,
                                 to_kill_processes.1.len(),
                                 to_kill_processes.0
                             ))
                         } else {
                             Spans::from(format!(
-                                "Kill 1 process with the name \"{}\"?  Press ENTER to confirm.",
-                                to_kill_processes.0
+                                "{} 1 process with the name \"{}\"?  Press ENTER to confirm.",
+                                action, to_kill_processes.0
                             ))
                         }
                     } else {
                         Spans::from(format!(
-                            "Kill process \"{}\" with PID {}?  Press ENTER to confirm.",
-                            to_kill_processes.0, first_pid
+                            "{} process \"{}\" with PID {}?  Press ENTER to confirm.",
+                            action, to_kill_processes.0, first_pid
                         ))
                     },
-                ]));
+                ];
+
+                if !process_killer::SIGNALS.is_empty() {
+                    let mut signal_spans = Vec::new();
+                    for (index, (signal_name, _)) in process_killer::SIGNALS.iter().enumerate() {
+                        if index > 0 {
+                            signal_spans.push(Span::raw(" "));
+                        }
+                        if dd_state.typed_signal.is_empty() && index == dd_state.selected_signal {
+                            signal_spans.push(Span::styled(
+                                *signal_name,
+                                self.colours.currently_selected_text_style,
+                            ));
+                        } else {
+                            signal_spans.push(Span::raw(*signal_name));
+                        }
+                    }
+                    dd_text.push(Spans::from(signal_spans));
+                    dd_text.push(Spans::from(if dd_state.typed_signal.is_empty() {
+                        "Use Up/Down to pick a signal, or type its number.".to_string()
+                    } else {
+                        format!("Signal number: {}", dd_state.typed_signal)
+                    }));
+                }
+
+                return Some(Text::from(dd_text));
             }
         }
 
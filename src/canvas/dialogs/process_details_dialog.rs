@@ -0,0 +1,253 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::App,
+    canvas::Painter,
+    utils::{
+        gen_util::{get_byte_values, ByteUnits},
+        number_format::format_number,
+    },
+};
+
+const PROCESS_DETAILS_BASE: &str = " Process Details ── Esc to close ";
+
+/// How wide the labels in front of each value are.
+const LABEL_WIDTH: usize = 10;
+
+pub trait ProcessDetailsDialog {
+    fn draw_process_details_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+/// Splits a value into lines no wider than the given width.
+fn split_to_width(value: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::default()];
+    let mut line_width = 0;
+    for grapheme in UnicodeSegmentation::graphemes(value, true) {
+        let grapheme_width = UnicodeWidthStr::width(grapheme);
+        if line_width + grapheme_width > width && line_width > 0 {
+            lines.push(String::default());
+            line_width = 0;
+        }
+        if let Some(line) = lines.last_mut() {
+            line.push_str(grapheme);
+        }
+        line_width += grapheme_width;
+    }
+    lines
+}
+
+/// Draws values as a line of block characters as wide as the given width, scaled so the largest
+/// value is a full block.  If there are more values than fit, each character shows the largest of
+/// the values it covers.
+fn get_sparkline(values: &[f64], width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    if values.is_empty() || width == 0 {
+        return String::default();
+    }
+
+    let buckets = if values.len() > width {
+        (0..width)
+            .map(|index| {
+                values[index * values.len() / width..(index + 1) * values.len() / width]
+                    .iter()
+                    .copied()
+                    .fold(0.0, f64::max)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        values.to_vec()
+    };
+    let max = buckets.iter().copied().fold(0.0, f64::max);
+
+    buckets
+        .iter()
+        .map(|value| {
+            if max > 0.0 {
+                BARS[((value / max * (BARS.len() - 1) as f64).round() as usize).min(BARS.len() - 1)]
+            } else {
+                BARS[0]
+            }
+        })
+        .collect()
+}
+
+/// Shows a sparkline followed by a label, fitting both into the given width.
+fn get_sparkline_with_label(values: &[f64], label: &str, width: usize) -> String {
+    let sparkline_width = width.saturating_sub(label.len() + 1);
+    format!("{} {}", get_sparkline(values, sparkline_width), label)
+}
+
+fn get_bytes_string(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(bytes) => {
+            let (value, unit) = get_byte_values(bytes, false, ByteUnits::Binary);
+            format!("{}{}", format_number(value, 1), unit)
+        }
+        None => "N/A".to_string(),
+    }
+}
+
+impl ProcessDetailsDialog for Painter {
+    fn draw_process_details_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let details_state = &app_state.process_details_state;
+        let value_width = usize::from(draw_loc.width)
+            .saturating_sub(2 + LABEL_WIDTH)
+            .max(1);
+
+        let mut rows = vec![
+            ("Name", details_state.name.clone()),
+            (
+                "PID",
+                if details_state.num_processes > 1 {
+                    format!(
+                        "{} (first of {} grouped processes)",
+                        details_state.pid, details_state.num_processes
+                    )
+                } else {
+                    details_state.pid.to_string()
+                },
+            ),
+            (
+                "User",
+                details_state
+                    .user
+                    .clone()
+                    .unwrap_or_else(|| "N/A".to_string()),
+            ),
+        ];
+        match &details_state.details {
+            Some(details) => {
+                let or_unknown =
+                    |value: &Option<String>| value.clone().unwrap_or_else(|| "N/A".to_string());
+                let count_or_unknown = |value: Option<u64>| {
+                    value.map_or_else(|| "N/A".to_string(), |value| value.to_string())
+                };
+                rows.extend(vec![
+                    ("Command", details.command.clone()),
+                    ("Exe", or_unknown(&details.exe)),
+                    ("Cwd", or_unknown(&details.cwd)),
+                    ("Threads", count_or_unknown(details.num_threads)),
+                    ("Open FDs", count_or_unknown(details.num_fds)),
+                    ("RSS", get_bytes_string(details.rss_bytes)),
+                    ("Virtual", get_bytes_string(details.virtual_bytes)),
+                    ("Shared", get_bytes_string(details.shared_bytes)),
+                    ("Read", get_bytes_string(details.total_read_bytes)),
+                    ("Written", get_bytes_string(details.total_write_bytes)),
+                ]);
+            }
+            None => rows.push(("", "The process has exited.".to_string())),
+        }
+        for (index, title) in details_state.window_titles.iter().enumerate() {
+            rows.push((if index == 0 { "Window" } else { "" }, title.clone()));
+        }
+
+        if let Some(history) = app_state
+            .data_collection
+            .process_history
+            .get(&details_state.pid)
+            .filter(|history| !history.samples.is_empty())
+        {
+            let cpu_values = history
+                .samples
+                .iter()
+                .map(|(_, cpu, _)| *cpu)
+                .collect::<Vec<_>>();
+            let mem_values = history
+                .samples
+                .iter()
+                .map(|(_, _, mem)| *mem as f64)
+                .collect::<Vec<_>>();
+            let max_cpu = cpu_values.iter().copied().fold(0.0, f64::max);
+            let max_mem = mem_values.iter().copied().fold(0.0, f64::max) as u64;
+
+            rows.push((
+                "CPU",
+                get_sparkline_with_label(
+                    &cpu_values,
+                    &format!("max {}%", format_number(max_cpu, 1)),
+                    value_width,
+                ),
+            ));
+            rows.push((
+                "Memory",
+                get_sparkline_with_label(
+                    &mem_values,
+                    &format!("max {}", get_bytes_string(Some(max_mem))),
+                    value_width,
+                ),
+            ));
+        }
+
+        let details_text = rows
+            .into_iter()
+            .flat_map(|(label, value)| {
+                split_to_width(&value, value_width)
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, line)| {
+                        Spans::from(vec![
+                            Span::styled(
+                                format!(
+                                    "{:width$}",
+                                    if index == 0 { label } else { "" },
+                                    width = LABEL_WIDTH
+                                ),
+                                self.colours.table_header_style,
+                            ),
+                            Span::raw(line),
+                        ])
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        // Keep the scroll position within what can be scrolled to, which depends on the size.
+        let scroll_state = &mut app_state.process_details_state.scroll_state;
+        scroll_state.max_scroll_index =
+            (details_text.len() as u16).saturating_sub(draw_loc.height.saturating_sub(2));
+        scroll_state.current_scroll_index = scroll_state
+            .current_scroll_index
+            .min(scroll_state.max_scroll_index);
+
+        let details_title = Spans::from(vec![
+            Span::styled(" Process Details ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(PROCESS_DETAILS_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(Text::from(details_text))
+                .block(
+                    Block::default()
+                        .title(details_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .scroll((scroll_state.current_scroll_index, 0)),
+            draw_loc,
+        );
+    }
+}
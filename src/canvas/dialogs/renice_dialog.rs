@@ -0,0 +1,102 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{process_killer, App},
+    canvas::Painter,
+};
+
+const RENICE_BASE: &str = " Renice Process ── Esc to close ";
+const RENICE_ERROR_BASE: &str = " Error ── Esc to close ";
+
+pub trait ReniceDialog {
+    fn draw_renice_dialog<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect);
+}
+
+impl ReniceDialog for Painter {
+    fn draw_renice_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let renice_state = &app_state.renice_dialog_state;
+        let (title, title_base, renice_text) = if let Some(error) = &renice_state.error {
+            (
+                " Error ",
+                RENICE_ERROR_BASE,
+                Text::from(vec![
+                    Spans::default(),
+                    Spans::from("Failed to renice process."),
+                    Spans::from(error.clone()),
+                    Spans::from("Please press ENTER or ESC to close this dialog."),
+                ]),
+            )
+        } else if let Some((name, pids)) = &renice_state.processes {
+            let target = match pids.as_slice() {
+                [pid] if !app_state.is_grouped(app_state.current_widget.widget_id) => {
+                    format!("process \"{}\" with PID {}", name, pid)
+                }
+                _ => format!(
+                    "{} process{} with the name \"{}\"",
+                    pids.len(),
+                    if pids.len() == 1 { "" } else { "es" },
+                    name
+                ),
+            };
+
+            (
+                " Renice Process ",
+                RENICE_BASE,
+                Text::from(vec![
+                    Spans::default(),
+                    Spans::from(format!(
+                        "Set the nice value of {}, currently {}, to:",
+                        target, renice_state.current_nice
+                    )),
+                    Spans::from(vec![
+                        Span::raw(renice_state.typed_nice.clone()),
+                        Span::styled(" ", self.colours.currently_selected_text_style),
+                    ]),
+                    Spans::from(format!(
+                        "Type a value from {} (most favourable) to {}, then press ENTER.",
+                        process_killer::NICE_RANGE.start(),
+                        process_killer::NICE_RANGE.end()
+                    )),
+                ]),
+            )
+        } else {
+            return;
+        };
+
+        let renice_title = Spans::from(vec![
+            Span::styled(title, self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width).saturating_sub(title_base.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(renice_text)
+                .block(
+                    Block::default()
+                        .title(renice_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
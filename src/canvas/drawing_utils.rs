@@ -1,11 +1,22 @@
-use crate::app;
+use crate::{
+    app::{self, data_farmer::AlertGraph},
+    data_conversion::ConvertedAlert,
+};
 use std::cmp::{max, min};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::Style,
+    symbols::Marker,
+    terminal::Frame,
+    widgets::{Axis, Block, Chart, Dataset, GraphType},
+};
 
 /// Return a (hard)-width vector for column widths.
 ///
 /// * `total_width` is the, well, total width available.  **NOTE:** This function automatically
-/// takes away 2 from the width as part of the left/right
-/// bounds.
+///   takes away 2 from the width as part of the left/right
+///   bounds.
 /// * `hard_widths` is inflexible column widths.  Use a `None` to represent a soft width.
 /// * `soft_widths_min` is the lower limit for a soft width.  Use `None` if a hard width goes there.
 /// * `soft_widths_max` is the upper limit for a soft width, in percentage of the total width.  Use
@@ -144,12 +155,23 @@ pub fn get_search_start_position(
     }
 }
 
+/// Returns which of `num_entries` entries a table starts at, so that the selected one is shown.
+///
+/// When forcing a redraw, such as after the terminal is resized, the table keeps starting from
+/// where it did if the selected entry is still shown from there, but starts early enough to fill
+/// the table if it has grown.
 pub fn get_start_position(
-    num_rows: usize, scroll_direction: &app::ScrollDirection, scroll_position_bar: &mut usize,
-    currently_selected_position: usize, is_force_redraw: bool,
+    num_rows: usize, num_entries: usize, scroll_direction: &app::ScrollDirection,
+    scroll_position_bar: &mut usize, currently_selected_position: usize, is_force_redraw: bool,
 ) -> usize {
     if is_force_redraw {
-        *scroll_position_bar = 0;
+        *scroll_position_bar = std::cmp::min(
+            *scroll_position_bar,
+            std::cmp::min(
+                currently_selected_position,
+                num_entries.saturating_sub(num_rows + 1),
+            ),
+        );
     }
 
     match scroll_direction {
@@ -184,6 +206,25 @@ pub fn get_start_position(
     }
 }
 
+/// Returns what the title of a disk or temperature table shows of its search, like `/used > 80 `,
+/// or nothing if there is no search.
+pub fn get_table_search_title(search_state: &app::TableSearchState) -> String {
+    if !search_state.is_typing && search_state.query_string.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "/{}{}{} ",
+            search_state.query_string,
+            if search_state.is_typing { "_" } else { "" },
+            if search_state.error_message.is_some() {
+                " (invalid)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
 /// Calculate how many bars are to be
 /// drawn within basic mode's components.
 pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize) -> usize {
@@ -192,3 +233,113 @@ pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize)
         num_bars_available,
     )
 }
+
+/// Returns the points of a vertical line marking where data from a previous session ends,
+/// or an empty vector if there is nothing to mark within the displayed time range.
+pub fn get_session_marker_points(
+    previous_session_offset: Option<f64>, display_time: u64, y_bounds: [f64; 2],
+) -> Vec<(f64, f64)> {
+    match previous_session_offset {
+        Some(offset) if offset >= -(display_time as f64) => {
+            vec![(offset, y_bounds[0]), (offset, y_bounds[1])]
+        }
+        _ => vec![],
+    }
+}
+
+/// How many points make up the dotted vertical line of an alert marker.
+const ALERT_MARKER_POINTS: usize = 100;
+
+/// Returns the points of a dotted vertical line for each alert on the given graph within the
+/// displayed time range, grouped by the name of the alert so that each name shows up once in a
+/// legend.  These are meant to be drawn as a scatter graph.
+pub fn get_alert_marker_points(
+    alert_markers: &[ConvertedAlert], graph: AlertGraph, display_time: u64, y_bounds: [f64; 2],
+) -> Vec<(String, Vec<(f64, f64)>)> {
+    let mut markers: Vec<(String, Vec<(f64, f64)>)> = Vec::new();
+    for alert in alert_markers
+        .iter()
+        .filter(|alert| alert.graph == graph && alert.offset >= -(display_time as f64))
+    {
+        let line = (0..=ALERT_MARKER_POINTS).map(|index| {
+            let y = y_bounds[0]
+                + (y_bounds[1] - y_bounds[0]) * index as f64 / ALERT_MARKER_POINTS as f64;
+            (alert.offset, y)
+        });
+        match markers.iter_mut().find(|(name, _)| *name == alert.name) {
+            Some((_, points)) => points.extend(line),
+            None => markers.push((alert.name.clone(), line.collect())),
+        }
+    }
+
+    markers
+}
+
+/// Draws the given alert markers on top of a chart with the same block and axes, with a legend
+/// of their names.  This is for charts that have no legend of their own to add the names to.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_alert_markers<B: Backend>(
+    f: &mut Frame<'_, B>, draw_loc: Rect, block: Block<'_>, x_axis: Axis<'_>, y_axis: Axis<'_>,
+    markers: &[(String, Vec<(f64, f64)>)], style: Style, marker: Marker,
+) {
+    if markers.is_empty() {
+        return;
+    }
+
+    let datasets = markers
+        .iter()
+        .map(|(name, points)| {
+            Dataset::default()
+                .name(name.as_str())
+                .marker(marker)
+                .style(style)
+                .data(points)
+                .graph_type(GraphType::Scatter)
+        })
+        .collect();
+
+    f.render_widget(
+        Chart::new(datasets)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2))),
+        draw_loc,
+    );
+}
+
+/// Draws horizontal guide lines at each of the given y-values, spanning the displayed time range.
+/// This is meant to be drawn first, and then drawn over by a chart with the same block and axes,
+/// so that the lines end up behind that chart's data.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_threshold_lines<B: Backend>(
+    f: &mut Frame<'_, B>, draw_loc: Rect, block: Block<'_>, x_axis: Axis<'_>, y_axis: Axis<'_>,
+    thresholds: &[f64], display_time: u64, style: Style, marker: Marker,
+) {
+    if thresholds.is_empty() {
+        return;
+    }
+
+    let lines: Vec<[(f64, f64); 2]> = thresholds
+        .iter()
+        .map(|threshold| [(-(display_time as f64), *threshold), (0.0, *threshold)])
+        .collect();
+    let datasets = lines
+        .iter()
+        .map(|line| {
+            Dataset::default()
+                .marker(marker)
+                .style(style)
+                .data(line)
+                .graph_type(GraphType::Line)
+        })
+        .collect();
+
+    f.render_widget(
+        Chart::new(datasets)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis),
+        draw_loc,
+    );
+}
@@ -2,6 +2,7 @@ pub mod basic_table_arrows;
 pub mod battery_display;
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod custom_widget;
 pub mod disk_table;
 pub mod mem_basic;
 pub mod mem_graph;
@@ -14,6 +15,7 @@ pub use basic_table_arrows::BasicTableArrows;
 pub use battery_display::BatteryDisplayWidget;
 pub use cpu_basic::CpuBasicWidget;
 pub use cpu_graph::CpuGraphWidget;
+pub use custom_widget::CustomWidgetDisplay;
 pub use disk_table::DiskTableWidget;
 pub use mem_basic::MemBasicWidget;
 pub use mem_graph::MemGraphWidget;
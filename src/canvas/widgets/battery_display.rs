@@ -2,6 +2,7 @@ use crate::{
     app::App,
     canvas::{drawing_utils::calculate_basic_use_bars, Painter},
     constants::*,
+    utils::number_format::format_number,
 };
 
 use tui::{
@@ -118,13 +119,13 @@ impl BatteryDisplayWidget for Painter {
                 let charge_percentage = battery_details.charge_percentage;
                 let num_bars = calculate_basic_use_bars(charge_percentage, bar_length);
                 let bars = format!(
-                    "[{}{}{:3.0}%]",
+                    "[{}{}{:>3}%]",
                     "|".repeat(num_bars),
                     " ".repeat(bar_length - num_bars),
-                    charge_percentage,
+                    format_number(charge_percentage, 0)
                 );
 
-                let battery_items = vec![
+                let battery_items = [
                     ["Charge %", &bars],
                     ["Consumption", &battery_details.watt_consumption],
                     if let Some(duration_until_full) = &battery_details.duration_until_full {
@@ -5,6 +5,7 @@ use crate::{
     canvas::{drawing_utils::*, Painter},
     constants::*,
     data_conversion::ConvertedCpuData,
+    utils::number_format::format_number,
 };
 
 use tui::{
@@ -86,7 +87,7 @@ impl CpuBasicWidget for Painter {
 
                             let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
                             format!(
-                                "{:3}[{}{}{:3.0}%]",
+                                "{:3}[{}{}{:>3}%]",
                                 if app_state.app_config_fields.show_average_cpu {
                                     if cpu_index == 0 {
                                         "AVG".to_string()
@@ -98,7 +99,7 @@ impl CpuBasicWidget for Painter {
                                 },
                                 "|".repeat(num_bars),
                                 " ".repeat(bar_length - num_bars),
-                                use_percentage.round(),
+                                format_number(use_percentage.round(), 0)
                             )
                         })
                         .collect::<Vec<_>>()
@@ -113,7 +114,7 @@ impl CpuBasicWidget for Painter {
                                 };
 
                             format!(
-                                "{:3} {:3.0}%",
+                                "{:3} {:>3}%",
                                 if app_state.app_config_fields.show_average_cpu {
                                     if cpu_index == 0 {
                                         "AVG".to_string()
@@ -123,7 +124,7 @@ impl CpuBasicWidget for Painter {
                                 } else {
                                     cpu_index.to_string()
                                 },
-                                use_percentage.round(),
+                                format_number(use_percentage.round(), 0)
                             )
                         })
                         .collect::<Vec<_>>()
@@ -137,7 +138,7 @@ impl CpuBasicWidget for Painter {
                                     0.0
                                 };
 
-                            format!("{:3.0}%", use_percentage.round(),)
+                            format!("{:>3}%", format_number(use_percentage.round(), 0))
                         })
                         .collect::<Vec<_>>()
                 };
@@ -152,7 +153,11 @@ impl CpuBasicWidget for Painter {
                         let how_many_cpus = min(
                             remaining_height,
                             (row_counter / to_divide)
-                                + (if row_counter % to_divide == 0 { 0 } else { 1 }),
+                                + (if row_counter.is_multiple_of(to_divide) {
+                                    0
+                                } else {
+                                    1
+                                }),
                         );
                         row_counter -= how_many_cpus;
                         let end_index = min(start_index + how_many_cpus, num_cpus);
@@ -3,18 +3,25 @@ use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App},
+    app::{data_farmer::AlertGraph, layout_manager::WidgetDirection, App, LinkedProcess},
     canvas::{
-        drawing_utils::{get_column_widths, get_start_position},
+        drawing_utils::{
+            draw_alert_markers, draw_threshold_lines, get_alert_marker_points, get_column_widths,
+            get_session_marker_points, get_start_position,
+        },
         Painter,
     },
     constants::*,
-    data_conversion::ConvertedCpuData,
+    data_conversion::{
+        format_graph_stats, get_graph_stats, get_stacked_cpu_points, ConvertedCpuData,
+    },
+    utils::number_format::format_number,
 };
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
     symbols::Marker,
     terminal::Frame,
     text::Span,
@@ -52,6 +59,11 @@ impl CpuGraphWidget for Painter {
         if draw_loc.width as f64 * 0.15 <= 6.0 {
             // Skip drawing legend
             if app_state.current_widget.widget_id == (widget_id + 1) {
+                if let Some(cpu_widget_state) =
+                    app_state.cpu_state.widget_states.get_mut(&widget_id)
+                {
+                    cpu_widget_state.was_legend_selected = true;
+                }
                 if app_state.app_config_fields.left_legend {
                     app_state.move_widget_selection(&WidgetDirection::Right);
                 } else {
@@ -72,6 +84,18 @@ impl CpuGraphWidget for Painter {
                 }
             }
         } else {
+            // Select the legend again if it was selected before it was hidden, and the graph
+            // still is.
+            if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&widget_id) {
+                if std::mem::take(&mut cpu_widget_state.was_legend_selected)
+                    && app_state.current_widget.widget_id == widget_id
+                {
+                    if let Some(legend_widget) = app_state.widget_map.get(&(widget_id + 1)) {
+                        app_state.current_widget = legend_widget.clone();
+                    }
+                }
+            }
+
             let (graph_index, legend_index, constraints) =
                 if app_state.app_config_fields.left_legend {
                     (
@@ -185,7 +209,49 @@ impl CpuGraphWidget for Painter {
             let use_dot = app_state.app_config_fields.use_dot;
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
             let current_scroll_position = cpu_widget_state.scroll_state.current_scroll_position;
-            let dataset_vector: Vec<Dataset<'_>> = if current_scroll_position == ALL_POSITION {
+            let session_marker = get_session_marker_points(
+                app_state.canvas_data.previous_session_offset,
+                cpu_widget_state.current_display_time,
+                [0.0, 100.5],
+            );
+            let alert_markers = get_alert_marker_points(
+                &app_state.canvas_data.alert_markers,
+                AlertGraph::Cpu,
+                cpu_widget_state.current_display_time,
+                [0.0, 100.5],
+            );
+            // The stacked graph is only used when all CPUs are shown, and replaces the average, as
+            // the top of the stack is the same line.
+            let first_core_position = if show_avg_cpu { AVG_POSITION + 1 } else { 1 };
+            let stacked_points = if app_state.app_config_fields.stacked_cpu
+                && current_scroll_position == ALL_POSITION
+            {
+                get_stacked_cpu_points(cpu_data.get(first_core_position..).unwrap_or(&[]))
+            } else {
+                vec![]
+            };
+
+            let mut dataset_vector: Vec<Dataset<'_>> = if !stacked_points.is_empty() {
+                stacked_points
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(itx, points)| {
+                        Dataset::default()
+                            .marker(if use_dot {
+                                Marker::Dot
+                            } else {
+                                Marker::Braille
+                            })
+                            .style(
+                                self.colours.cpu_colour_styles[(itx + first_core_position)
+                                    % self.colours.cpu_colour_styles.len()],
+                            )
+                            .data(&points[..])
+                            .graph_type(tui::widgets::GraphType::Line)
+                    })
+                    .collect()
+            } else if current_scroll_position == ALL_POSITION {
                 cpu_data
                     .iter()
                     .enumerate()
@@ -234,6 +300,74 @@ impl CpuGraphWidget for Painter {
                 vec![]
             };
 
+            // The CPU graph has no legend of its own (that's the table), so stats for the selected
+            // CPU, or the average if all are selected, go in the title instead.
+            let graph_stats = if app_state.app_config_fields.show_graph_stats {
+                let stats_position = if current_scroll_position == ALL_POSITION && show_avg_cpu {
+                    Some(AVG_POSITION)
+                } else if current_scroll_position != ALL_POSITION {
+                    Some(current_scroll_position)
+                } else {
+                    None
+                };
+                let cpu_peaks = &app_state.data_collection.peaks.cpu;
+                stats_position.and_then(|position| {
+                    let cpu = cpu_data.get(position)?;
+                    let stats = get_graph_stats(
+                        &cpu.cpu_data,
+                        cpu_widget_state.current_display_time,
+                        |value| value,
+                    )?;
+                    let peak = cpu_peaks.get(position - 1).copied().unwrap_or_default();
+                    Some(format!(
+                        "{} ",
+                        format_graph_stats(&cpu.cpu_name, &stats, peak, |value| {
+                            format!("{}%", format_number(value, 0))
+                        })
+                    ))
+                })
+            } else {
+                None
+            }
+            .unwrap_or_default();
+
+            // Steal time is easy to miss in a VM, so keep it up front rather than in the legend.
+            let graph_stats = match &app_state.data_collection.guest_harvest {
+                Some(guest) => match guest.steal_percent {
+                    Some(steal_percent) => format!(
+                        "{} guest, {}% steal {}",
+                        guest.hypervisor,
+                        format_number(steal_percent, 0),
+                        graph_stats
+                    ),
+                    None => format!("{} guest {}", guest.hypervisor, graph_stats),
+                },
+                None => graph_stats,
+            };
+
+            let graph_stats = match &app_state.linked_process {
+                Some(LinkedProcess {
+                    pid,
+                    name,
+                    cpu_affinity: Some((cpu_list, _)),
+                }) => format!("{} ({}) on CPUs {} {}", name, pid, cpu_list, graph_stats),
+                _ => graph_stats,
+            };
+
+            if !session_marker.is_empty() {
+                dataset_vector.push(
+                    Dataset::default()
+                        .marker(if use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.graph_style)
+                        .data(&session_marker)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
                 self.colours.highlighted_border_style
@@ -245,32 +379,77 @@ impl CpuGraphWidget for Painter {
                 const TITLE_BASE: &str = " CPU ── Esc to go back ";
                 Spans::from(vec![
                     Span::styled(" CPU ", self.colours.widget_title_style),
+                    Span::styled(graph_stats.clone(), self.colours.text_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(
+                                    UnicodeSegmentation::graphemes(TITLE_BASE, true).count()
+                                        + UnicodeSegmentation::graphemes(
+                                            graph_stats.as_str(),
+                                            true
+                                        )
+                                        .count()
+                                        + 2
+                                )
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" CPU ", self.colours.widget_title_style))
+                Spans::from(vec![
+                    Span::styled(" CPU ", self.colours.widget_title_style),
+                    Span::styled(graph_stats.clone(), self.colours.text_style),
+                ])
             };
 
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style);
+
+            draw_threshold_lines(
+                f,
+                draw_loc,
+                block.clone(),
+                x_axis.clone(),
+                y_axis.clone(),
+                &app_state.app_config_fields.graph_thresholds.cpu,
+                cpu_widget_state.current_display_time,
+                self.colours.graph_style,
+                if use_dot {
+                    Marker::Dot
+                } else {
+                    Marker::Braille
+                },
+            );
+
             f.render_widget(
                 Chart::new(dataset_vector)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(border_style),
-                    )
-                    .x_axis(x_axis)
-                    .y_axis(y_axis),
+                    .block(block.clone())
+                    .x_axis(x_axis.clone())
+                    .y_axis(y_axis.clone())
+                    .hidden_legend_constraints((Constraint::Ratio(1, 4), Constraint::Ratio(1, 4))),
                 draw_loc,
             );
+
+            // The CPU graph has no legend to name alerts in, so they get their own on top.
+            draw_alert_markers(
+                f,
+                draw_loc,
+                block,
+                x_axis,
+                y_axis,
+                &alert_markers,
+                self.colours.graph_style,
+                if use_dot {
+                    Marker::Dot
+                } else {
+                    Marker::Braille
+                },
+            );
         }
     }
 
@@ -293,6 +472,7 @@ impl CpuGraphWidget for Painter {
                 usize::from(
                     (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
                 ),
+                cpu_data.len(),
                 &cpu_widget_state.scroll_state.scroll_direction,
                 &mut cpu_widget_state.scroll_state.previous_scroll_position,
                 cpu_widget_state.scroll_state.current_scroll_position,
@@ -312,6 +492,11 @@ impl CpuGraphWidget for Painter {
                 .current_scroll_position
                 .saturating_sub(start_position);
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
+            let linked_cpus = app_state
+                .linked_process
+                .as_ref()
+                .and_then(|process| process.cpu_affinity.as_ref())
+                .map(|(_, cpus)| cpus);
 
             // Calculate widths
             if recalculate_column_widths {
@@ -339,7 +524,7 @@ impl CpuGraphWidget for Painter {
             let cpu_rows = sliced_cpu_data.iter().enumerate().filter_map(|(itx, cpu)| {
                 let truncated_name: Cow<'_, str> =
                     if let (Some(desired_column_width), Some(calculated_column_width)) =
-                        (dcw.get(0), ccw.get(0))
+                        (dcw.first(), ccw.first())
                     {
                         if *desired_column_width > *calculated_column_width {
                             Cow::Borrowed(&cpu.short_cpu_name)
@@ -350,7 +535,7 @@ impl CpuGraphWidget for Painter {
                         Cow::Borrowed(&cpu.cpu_name)
                     };
                 let truncated_legend: Cow<'_, str> =
-                    if let Some(calculated_column_width) = ccw.get(0) {
+                    if let Some(calculated_column_width) = ccw.first() {
                         if *calculated_column_width == 0 && cpu.legend_value.is_empty() {
                             Cow::Borrowed("All")
                         } else {
@@ -366,26 +551,33 @@ impl CpuGraphWidget for Painter {
                     offset_scroll_index += 1;
                     None
                 } else {
+                    let first_core_position = if show_avg_cpu {
+                        AVG_POSITION + 1
+                    } else {
+                        ALL_POSITION + 1
+                    };
+                    let core = (itx + start_position).checked_sub(first_core_position);
+                    let style = if itx == offset_scroll_index {
+                        self.colours.currently_selected_text_style
+                    } else if itx + start_position == ALL_POSITION {
+                        self.colours.all_colour_style
+                    } else if let Some(core) = core {
+                        self.colours.cpu_colour_styles[core % self.colours.cpu_colour_styles.len()]
+                    } else {
+                        self.colours.avg_colour_style
+                    };
+
+                    // Underline the cores the linked process may run on.
+                    let is_linked_core = match (core, linked_cpus) {
+                        (Some(core), Some(linked_cpus)) => linked_cpus.contains(&core),
+                        _ => false,
+                    };
                     Some(Row::StyledData(
                         cpu_string_row.into_iter(),
-                        if itx == offset_scroll_index {
-                            self.colours.currently_selected_text_style
-                        } else if itx + start_position == ALL_POSITION {
-                            self.colours.all_colour_style
-                        } else if show_avg_cpu {
-                            if itx + start_position == AVG_POSITION {
-                                self.colours.avg_colour_style
-                            } else {
-                                self.colours.cpu_colour_styles[(itx + start_position
-                                    - AVG_POSITION
-                                    - 1)
-                                    % self.colours.cpu_colour_styles.len()]
-                            }
+                        if is_linked_core {
+                            style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
                         } else {
-                            self.colours.cpu_colour_styles[(itx + start_position
-                                - ALL_POSITION
-                                - 1)
-                                % self.colours.cpu_colour_styles.len()]
+                            style
                         },
                     ))
                 }
@@ -413,7 +605,7 @@ impl CpuGraphWidget for Painter {
                             .table_width_state
                             .calculated_column_widths
                             .iter()
-                            .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                            .map(|calculated_width| Constraint::Length(*calculated_width))
                             .collect::<Vec<_>>()),
                     )
                     .header_gap(table_gap),
@@ -0,0 +1,303 @@
+use std::{cmp::Ordering, time::Instant};
+
+use crate::{
+    app::{data_source::DataValue, App, CustomWidgetKind},
+    canvas::Painter,
+    data_conversion::{get_histogram_buckets, get_percentile},
+    utils::number_format::format_number,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    symbols::Marker,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Axis, BarChart, Block, Borders, Chart, Dataset, Gauge, Paragraph, Row, Table, Wrap},
+};
+
+/// The most buckets a histogram is split into, regardless of its width.
+const MAX_HISTOGRAM_BUCKETS: u16 = 20;
+
+pub trait CustomWidgetDisplay {
+    fn draw_custom_widget<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl CustomWidgetDisplay for Painter {
+    fn draw_custom_widget<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        if let Some(custom_widget_state) = app_state.custom_state.get_widget_state(widget_id) {
+            let block = Block::default()
+                .title(Spans::from(Span::styled(
+                    format!(" {} ", custom_widget_state.title),
+                    self.colours.widget_title_style,
+                )))
+                .borders(Borders::ALL)
+                .border_style(if is_on_widget {
+                    self.colours.highlighted_border_style
+                } else {
+                    self.colours.border_style
+                });
+
+            let unit = &custom_widget_state.unit;
+            let latest = custom_widget_state.latest.as_ref();
+            let latest_text = match latest {
+                Some(DataValue::Scalar(value)) => format!("{}{}", format_number(*value, 1), unit),
+                Some(DataValue::Text(text)) => text.clone(),
+                Some(DataValue::Table { .. }) => String::default(),
+                Some(DataValue::Samples(samples)) => format!("{} samples", samples.len()),
+                None => "N/A".to_string(),
+            };
+
+            match custom_widget_state.kind {
+                CustomWidgetKind::Text => {
+                    f.render_widget(
+                        Paragraph::new(Span::styled(latest_text, self.colours.text_style))
+                            .block(block)
+                            .wrap(Wrap { trim: false }),
+                        draw_loc,
+                    );
+                }
+                CustomWidgetKind::Gauge => {
+                    let ratio = latest
+                        .and_then(|latest| latest.as_scalar())
+                        .map(|value| value / custom_widget_state.max)
+                        .unwrap_or(0.0);
+                    f.render_widget(
+                        Gauge::default()
+                            .block(block)
+                            .gauge_style(self.colours.ram_style)
+                            .ratio(if ratio.is_finite() {
+                                ratio.clamp(0.0, 1.0)
+                            } else {
+                                0.0
+                            })
+                            .label(Span::styled(latest_text, self.colours.text_style)),
+                        draw_loc,
+                    );
+                }
+                CustomWidgetKind::Graph => {
+                    let display_time = custom_widget_state.display_time as f64;
+                    let current_instant = app_state.data_collection.current_instant;
+                    let to_points = |history: &[(Instant, f64)], scale: f64| {
+                        history
+                            .iter()
+                            .map(|(instant, value)| {
+                                (
+                                    -(current_instant.duration_since(*instant).as_millis() as f64),
+                                    *value * scale,
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    };
+                    let points = to_points(&custom_widget_state.history, 1.0);
+                    let y_max = points
+                        .iter()
+                        .map(|(_, value)| *value)
+                        .fold(custom_widget_state.max, f64::max);
+                    let marker = if app_state.app_config_fields.use_dot {
+                        Marker::Dot
+                    } else {
+                        Marker::Braille
+                    };
+
+                    // A secondary series is scaled to fit the primary y-axis, and gets its own
+                    // axis labels on the right.
+                    let secondary_graph = custom_widget_state.secondary.as_ref().map(|secondary| {
+                        let secondary_max = secondary
+                            .history
+                            .iter()
+                            .map(|(_, value)| *value)
+                            .fold(secondary.max, f64::max);
+                        let scale = if secondary_max > 0.0 {
+                            y_max / secondary_max
+                        } else {
+                            0.0
+                        };
+                        let latest_text = match secondary
+                            .latest
+                            .as_ref()
+                            .and_then(|latest| latest.as_scalar())
+                        {
+                            Some(value) => format!("{}{}", format_number(value, 1), secondary.unit),
+                            None => "N/A".to_string(),
+                        };
+                        (
+                            format!("{}{}", format_number(secondary_max, 0), secondary.unit),
+                            to_points(&secondary.history, scale),
+                            latest_text,
+                        )
+                    });
+
+                    let mut datasets = vec![Dataset::default()
+                        .name(&latest_text)
+                        .marker(marker)
+                        .style(self.colours.ram_style)
+                        .data(&points)
+                        .graph_type(tui::widgets::GraphType::Line)];
+                    if let Some((_, secondary_points, secondary_latest_text)) = &secondary_graph {
+                        datasets.push(
+                            Dataset::default()
+                                .name(secondary_latest_text)
+                                .marker(marker)
+                                .style(self.colours.swap_style)
+                                .data(secondary_points)
+                                .graph_type(tui::widgets::GraphType::Line),
+                        );
+                    }
+
+                    let chart = Chart::new(datasets)
+                        .x_axis(Axis::default().bounds([-display_time, 0.0]))
+                        .y_axis(
+                            Axis::default()
+                                .style(self.colours.graph_style)
+                                .bounds([0.0, y_max])
+                                .labels(vec![
+                                    Span::styled("0", self.colours.graph_style),
+                                    Span::styled(
+                                        format!("{}{}", format_number(y_max, 0), unit),
+                                        self.colours.graph_style,
+                                    ),
+                                ]),
+                        )
+                        .hidden_legend_constraints((
+                            Constraint::Ratio(3, 4),
+                            Constraint::Ratio(3, 4),
+                        ));
+
+                    if let Some((secondary_max_label, _, _)) = &secondary_graph {
+                        let inner_loc = block.inner(draw_loc);
+                        f.render_widget(block, draw_loc);
+
+                        let axis_width = secondary_max_label.len() as u16 + 1;
+                        let chunks = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints([Constraint::Min(0), Constraint::Length(axis_width)])
+                            .split(inner_loc);
+                        let axis_text = (0..inner_loc.height)
+                            .map(|row| {
+                                let label = if row == 0 {
+                                    secondary_max_label.as_str()
+                                } else if row + 1 == inner_loc.height {
+                                    "0"
+                                } else {
+                                    ""
+                                };
+                                Spans::from(vec![
+                                    Span::styled("│", self.colours.graph_style),
+                                    Span::styled(label, self.colours.swap_style),
+                                ])
+                            })
+                            .collect::<Vec<_>>();
+
+                        f.render_widget(chart, chunks[0]);
+                        f.render_widget(Paragraph::new(axis_text), chunks[1]);
+                    } else {
+                        f.render_widget(chart.block(block), draw_loc);
+                    }
+                }
+                CustomWidgetKind::Histogram => {
+                    let mut values: Vec<f64> = custom_widget_state
+                        .history
+                        .iter()
+                        .map(|(_, value)| *value)
+                        .collect();
+                    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+                    let percentiles = [50.0, 95.0, 99.0]
+                        .iter()
+                        .filter_map(|percentile| {
+                            get_percentile(&values, *percentile).map(|value| {
+                                format!(
+                                    "p{} {}{}",
+                                    format_number(*percentile, 0),
+                                    format_number(value, 1),
+                                    unit
+                                )
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    let summary_text = if percentiles.is_empty() {
+                        "No samples".to_string()
+                    } else {
+                        format!("{}  n={}", percentiles.join("  "), values.len())
+                    };
+
+                    let inner_loc = block.inner(draw_loc);
+                    f.render_widget(block, draw_loc);
+                    let chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(1), Constraint::Min(0)])
+                        .split(inner_loc);
+                    f.render_widget(
+                        Paragraph::new(Span::styled(summary_text, self.colours.text_style)),
+                        chunks[0],
+                    );
+
+                    // Bars are as wide as the widest label, so every label fits.
+                    let bar_width = values
+                        .last()
+                        .map(|max| format_number(*max, 0).chars().count())
+                        .unwrap_or(0)
+                        .max(3) as u16;
+                    let num_buckets =
+                        ((chunks[1].width + 1) / (bar_width + 1)).clamp(1, MAX_HISTOGRAM_BUCKETS);
+                    let buckets = get_histogram_buckets(&values, num_buckets as usize);
+                    let labels = buckets
+                        .iter()
+                        .map(|(lower_bound, _)| format_number(*lower_bound, 0))
+                        .collect::<Vec<_>>();
+                    let bars = labels
+                        .iter()
+                        .zip(&buckets)
+                        .map(|(label, (_, count))| (label.as_str(), *count))
+                        .collect::<Vec<_>>();
+
+                    f.render_widget(
+                        BarChart::default()
+                            .data(&bars)
+                            .bar_width(bar_width)
+                            .bar_gap(1)
+                            .bar_style(self.colours.ram_style)
+                            .value_style(self.colours.text_style)
+                            .label_style(self.colours.graph_style),
+                        chunks[1],
+                    );
+                }
+                CustomWidgetKind::Table => {
+                    let (headers, rows) = match latest {
+                        Some(DataValue::Table { headers, rows }) => (headers.clone(), rows.clone()),
+                        _ => (vec!["Value".to_string()], vec![vec![latest_text.clone()]]),
+                    };
+                    let num_columns = std::cmp::max(headers.len(), 1) as u32;
+                    let widths = vec![Constraint::Ratio(1, num_columns); num_columns as usize];
+
+                    f.render_widget(
+                        Table::new(
+                            headers.iter(),
+                            rows.iter()
+                                .map(|row| Row::StyledData(row.iter(), self.colours.text_style)),
+                        )
+                        .block(block)
+                        .header_style(self.colours.table_header_style)
+                        .widths(&widths),
+                        draw_loc,
+                    );
+                }
+            }
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
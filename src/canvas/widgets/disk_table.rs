@@ -11,7 +11,7 @@ use tui::{
 use crate::{
     app,
     canvas::{
-        drawing_utils::{get_column_widths, get_start_position},
+        drawing_utils::{get_column_widths, get_start_position, get_table_search_title},
         Painter,
     },
     constants::*,
@@ -19,7 +19,9 @@ use crate::{
 use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 
-const DISK_HEADERS: [&str; 7] = ["Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s"];
+const DISK_HEADERS: [&str; 8] = [
+    "Disk", "Mount", "Flags", "Used", "Free", "Total", "R/s", "W/s",
+];
 
 static DISK_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
     DISK_HEADERS
@@ -47,10 +49,14 @@ impl DiskTableWidget for Painter {
             } else {
                 app_state.app_config_fields.table_gap
             };
+            let shown_rows = disk_widget_state
+                .search_state
+                .filter(&app_state.canvas_data.disk_data);
             let start_position = get_start_position(
                 usize::from(
                     (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
                 ),
+                shown_rows.len(),
                 &disk_widget_state.scroll_state.scroll_direction,
                 &mut disk_widget_state.scroll_state.previous_scroll_position,
                 disk_widget_state.scroll_state.current_scroll_position,
@@ -64,15 +70,24 @@ impl DiskTableWidget for Painter {
                     .current_scroll_position
                     .saturating_sub(start_position),
             ));
-            let sliced_vec = &app_state.canvas_data.disk_data[start_position..];
+            let sliced_vec = shown_rows.get(start_position..).unwrap_or_default();
 
             // Calculate widths
-            let hard_widths = [None, None, Some(4), Some(6), Some(6), Some(7), Some(7)];
+            let hard_widths = [
+                None,
+                None,
+                None,
+                Some(4),
+                Some(6),
+                Some(6),
+                Some(7),
+                Some(7),
+            ];
             if recalculate_column_widths {
                 disk_widget_state.table_width_state.desired_column_widths = {
                     let mut column_widths = DISK_HEADERS_LENS.clone();
                     for row in sliced_vec {
-                        for (col, entry) in row.iter().enumerate() {
+                        for (col, entry) in row.row.iter().enumerate() {
                             if entry.len() as u16 > column_widths[col] {
                                 column_widths[col] = entry.len() as u16;
                             }
@@ -105,7 +120,16 @@ impl DiskTableWidget for Painter {
                         .iter()
                         .map(|w| Some(*w))
                         .collect::<Vec<_>>()),
-                    &[Some(0.2), Some(0.2), None, None, None, None, None],
+                    &[
+                        Some(0.2),
+                        Some(0.2),
+                        Some(0.1),
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                    ],
                     &(disk_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -118,40 +142,43 @@ impl DiskTableWidget for Painter {
 
             let dcw = &disk_widget_state.table_width_state.desired_column_widths;
             let ccw = &disk_widget_state.table_width_state.calculated_column_widths;
-            let disk_rows =
-                sliced_vec.iter().map(|disk_row| {
-                    let truncated_data = disk_row.iter().zip(&hard_widths).enumerate().map(
-                        |(itx, (entry, width))| {
-                            if width.is_none() {
-                                if let (Some(desired_col_width), Some(calculated_col_width)) =
-                                    (dcw.get(itx), ccw.get(itx))
+            let disk_rows = sliced_vec.iter().map(|disk_row| {
+                let truncated_data = disk_row.row.iter().zip(&hard_widths).enumerate().map(
+                    |(itx, (entry, width))| {
+                        if width.is_none() {
+                            if let (Some(desired_col_width), Some(calculated_col_width)) =
+                                (dcw.get(itx), ccw.get(itx))
+                            {
+                                if *desired_col_width > *calculated_col_width
+                                    && *calculated_col_width > 0
                                 {
-                                    if *desired_col_width > *calculated_col_width
-                                        && *calculated_col_width > 0
-                                    {
-                                        let graphemes =
-                                            UnicodeSegmentation::graphemes(entry.as_str(), true)
-                                                .collect::<Vec<&str>>();
+                                    let graphemes =
+                                        UnicodeSegmentation::graphemes(entry.as_str(), true)
+                                            .collect::<Vec<&str>>();
 
-                                        if graphemes.len() > *calculated_col_width as usize
-                                            && *calculated_col_width > 1
-                                        {
-                                            // Truncate with ellipsis
-                                            let first_n = graphemes
-                                                [..(*calculated_col_width as usize - 1)]
-                                                .concat();
-                                            return Cow::Owned(format!("{}…", first_n));
-                                        }
+                                    if graphemes.len() > *calculated_col_width as usize
+                                        && *calculated_col_width > 1
+                                    {
+                                        // Truncate with ellipsis
+                                        let first_n = graphemes
+                                            [..(*calculated_col_width as usize - 1)]
+                                            .concat();
+                                        return Cow::Owned(format!("{}…", first_n));
                                     }
                                 }
                             }
+                        }
 
-                            Cow::Borrowed(entry)
-                        },
-                    );
+                        Cow::Borrowed(entry)
+                    },
+                );
 
+                if disk_row.is_unexpectedly_read_only {
+                    Row::StyledData(truncated_data, self.colours.warning_text_style)
+                } else {
                     Row::Data(truncated_data)
-                });
+                }
+            });
 
             let (border_style, highlight_style) = if is_on_widget {
                 (
@@ -162,23 +189,25 @@ impl DiskTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
+            let search_title = get_table_search_title(&disk_widget_state.search_state);
             let title_base = if app_state.app_config_fields.show_table_scroll_position {
                 let title_string = format!(
-                    " Disk ({} of {}) ",
+                    " Disk ({} of {}) {}",
                     disk_widget_state
                         .scroll_state
                         .current_scroll_position
                         .saturating_add(1),
-                    app_state.canvas_data.disk_data.len()
+                    shown_rows.len(),
+                    search_title
                 );
 
                 if title_string.len() <= draw_loc.width as usize {
                     title_string
                 } else {
-                    " Disk ".to_string()
+                    format!(" Disk {}", search_title)
                 }
             } else {
-                " Disk ".to_string()
+                format!(" Disk {}", search_title)
             };
 
             let title = if app_state.is_expanded {
@@ -190,7 +219,7 @@ impl DiskTableWidget for Painter {
                     if temp_title_base.len() > draw_loc.width as usize {
                         (
                             " Disk ".to_string(),
-                            format!("{}{}", " Disk ".to_string(), ESCAPE_ENDING),
+                            format!("{}{}", " Disk ", ESCAPE_ENDING),
                         )
                     } else {
                         (title_base, temp_title_base)
@@ -251,7 +280,7 @@ impl DiskTableWidget for Painter {
                             .table_width_state
                             .calculated_column_widths
                             .iter()
-                            .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                            .map(|calculated_width| Constraint::Length(*calculated_width))
                             .collect::<Vec<_>>()),
                     )
                     .header_gap(table_gap),
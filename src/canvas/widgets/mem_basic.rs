@@ -2,6 +2,7 @@ use crate::{
     app::App,
     canvas::{drawing_utils::*, Painter},
     constants::*,
+    utils::number_format::format_number,
 };
 
 use tui::{
@@ -71,14 +72,14 @@ impl MemBasicWidget for Painter {
         // TODO: Use different styling for the frac.
         let mem_label = if app_state.basic_mode_use_percent {
             format!(
-                "RAM[{}{}{:3.0}%]\n",
+                "RAM[{}{}{:>3}%]\n",
                 "|".repeat(num_bars_ram),
                 " ".repeat(
                     ram_bar_length - num_bars_ram
                         + app_state.canvas_data.mem_label_frac.trim().len()
                         - 4
                 ),
-                ram_use_percentage.round()
+                format_number(ram_use_percentage.round(), 0)
             )
         } else {
             format!(
@@ -90,14 +91,14 @@ impl MemBasicWidget for Painter {
         };
         let swap_label = if app_state.basic_mode_use_percent {
             format!(
-                "SWP[{}{}{:3.0}%]",
+                "SWP[{}{}{:>3}%]",
                 "|".repeat(num_bars_swap),
                 " ".repeat(
                     swap_bar_length - num_bars_swap
                         + app_state.canvas_data.swap_label_frac.trim().len()
                         - 4
                 ),
-                swap_use_percentage.round()
+                format_number(swap_use_percentage.round(), 0)
             )
         } else {
             format!(
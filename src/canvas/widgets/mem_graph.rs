@@ -1,4 +1,16 @@
-use crate::{app::App, canvas::Painter, constants::*};
+use crate::{
+    app::{data_farmer::AlertGraph, App},
+    canvas::{
+        drawing_utils::{draw_threshold_lines, get_alert_marker_points, get_session_marker_points},
+        Painter,
+    },
+    constants::*,
+    data_conversion::{format_graph_stats, get_graph_stats},
+    utils::{
+        gen_util::{get_byte_values, ByteUnits},
+        number_format::format_number,
+    },
+};
 
 use tui::{
     backend::Backend,
@@ -68,6 +80,17 @@ impl MemGraphWidget for Painter {
                 .bounds([0.0, 100.5])
                 .labels(y_axis_label);
 
+            let session_marker = get_session_marker_points(
+                app_state.canvas_data.previous_session_offset,
+                mem_widget_state.current_display_time,
+                [0.0, 100.5],
+            );
+            let alert_markers = get_alert_marker_points(
+                &app_state.canvas_data.alert_markers,
+                AlertGraph::Mem,
+                mem_widget_state.current_display_time,
+                [0.0, 100.5],
+            );
             let mut mem_canvas_vec: Vec<Dataset<'_>> = vec![];
             let mem_label = format!(
                 "RAM:{}{}",
@@ -82,7 +105,7 @@ impl MemGraphWidget for Painter {
                         Marker::Braille
                     })
                     .style(self.colours.ram_style)
-                    .data(&mem_data)
+                    .data(mem_data)
                     .graph_type(tui::widgets::GraphType::Line),
             );
 
@@ -100,10 +123,79 @@ impl MemGraphWidget for Painter {
                         Marker::Braille
                     })
                     .style(self.colours.swap_style)
-                    .data(&swap_data)
+                    .data(swap_data)
                     .graph_type(tui::widgets::GraphType::Line),
             );
 
+            if let Some(balloon_bytes) = app_state
+                .data_collection
+                .guest_harvest
+                .as_ref()
+                .and_then(|guest| guest.balloon_bytes)
+            {
+                let (balloon_value, balloon_unit) =
+                    get_byte_values(balloon_bytes, false, ByteUnits::Binary);
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(format!(
+                            "Balloon: {}{}",
+                            format_number(balloon_value, 1),
+                            balloon_unit
+                        ))
+                        .style(self.colours.swap_style),
+                );
+            }
+
+            if app_state.app_config_fields.show_graph_stats {
+                let peaks = &app_state.data_collection.peaks;
+                for (label, data, peak, style) in &[
+                    ("RAM", mem_data, peaks.mem, self.colours.ram_style),
+                    ("SWP", swap_data, peaks.swap, self.colours.swap_style),
+                ] {
+                    if let Some(stats) =
+                        get_graph_stats(data, mem_widget_state.current_display_time, |value| value)
+                    {
+                        mem_canvas_vec.push(
+                            Dataset::default()
+                                .name(format_graph_stats(label, &stats, *peak, |value| {
+                                    format!("{}%", format_number(value, 0))
+                                }))
+                                .style(*style),
+                        );
+                    }
+                }
+            }
+
+            if !session_marker.is_empty() {
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(PREVIOUS_SESSION_LABEL)
+                        .marker(if app_state.app_config_fields.use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.graph_style)
+                        .data(&session_marker)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
+            for (name, points) in &alert_markers {
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(name.as_str())
+                        .marker(if app_state.app_config_fields.use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.graph_style)
+                        .data(points)
+                        .graph_type(tui::widgets::GraphType::Scatter),
+                );
+            }
+
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
                 self.colours.highlighted_border_style
@@ -132,18 +224,34 @@ impl MemGraphWidget for Painter {
                 ))
             };
 
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(if app_state.current_widget.widget_id == widget_id {
+                    self.colours.highlighted_border_style
+                } else {
+                    self.colours.border_style
+                });
+
+            draw_threshold_lines(
+                f,
+                draw_loc,
+                block.clone(),
+                x_axis.clone(),
+                y_axis.clone(),
+                &app_state.app_config_fields.graph_thresholds.mem,
+                mem_widget_state.current_display_time,
+                self.colours.graph_style,
+                if app_state.app_config_fields.use_dot {
+                    Marker::Dot
+                } else {
+                    Marker::Braille
+                },
+            );
+
             f.render_widget(
                 Chart::new(mem_canvas_vec)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(if app_state.current_widget.widget_id == widget_id {
-                                self.colours.highlighted_border_style
-                            } else {
-                                self.colours.border_style
-                            }),
-                    )
+                    .block(block)
                     .x_axis(x_axis)
                     .y_axis(y_axis)
                     .hidden_legend_constraints((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
@@ -3,10 +3,17 @@ use std::cmp::max;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::App,
-    canvas::{drawing_utils::get_column_widths, Painter},
+    app::{data_farmer::AlertGraph, App},
+    canvas::{
+        drawing_utils::{
+            draw_threshold_lines, get_alert_marker_points, get_column_widths,
+            get_session_marker_points,
+        },
+        Painter,
+    },
     constants::*,
-    utils::gen_util::*,
+    data_conversion::{format_graph_stats, get_graph_stats},
+    utils::{gen_util::*, number_format::format_number},
 };
 
 use tui::{
@@ -84,10 +91,11 @@ impl NetworkGraphWidget for Painter {
 
         /// Returns the required max data point and labels.
         fn adjust_network_data_point(
-            rx: &[Point], tx: &[Point], time_start: f64, time_end: f64,
+            rx: &[Point], tx: &[Point], time_start: f64, time_end: f64, thresholds: &[f64],
         ) -> (f64, Vec<String>) {
-            // First, filter and find the maximal rx or tx so we know how to scale
-            let mut max_val_bytes = 0.0;
+            // First, filter and find the maximal rx or tx so we know how to scale.  Thresholds
+            // are included so their guide lines are always visible.
+            let mut max_val_bytes = thresholds.iter().cloned().fold(0.0, f64::max);
             let filtered_rx = rx
                 .iter()
                 .cloned()
@@ -104,6 +112,10 @@ impl NetworkGraphWidget for Painter {
                 }
             }
 
+            if get_byte_units() == Some(ByteUnits::Si) {
+                return get_si_labels(max_val_bytes);
+            }
+
             // FIXME [NETWORKING]: Granularity.  Just scale up the values.
             // FIXME [NETWORKING]: Ability to set fixed scale in config.
             // Currently we do 32 -> 33... which skips some gigabit values
@@ -169,15 +181,89 @@ impl NetworkGraphWidget for Painter {
             (true_max_val, labels)
         }
 
+        /// Like [`adjust_network_data_point`], but with labels in units of 1000.  The data is
+        /// still on a log2 scale, so the labels are placed on the nearest power of two.
+        fn get_si_labels(max_val_bytes: f64) -> (f64, Vec<String>) {
+            let log_kilo = (KILO_LIMIT as f64).log2();
+            let log_mega = (MEGA_LIMIT as f64).log2();
+            let log_giga = (GIGA_LIMIT as f64).log2();
+
+            if max_val_bytes < log_kilo {
+                (log_kilo, vec!["0B".to_string(), "1KB".to_string()])
+            } else if max_val_bytes < log_mega {
+                (
+                    log_mega,
+                    vec!["0B".to_string(), "1KB".to_string(), "1MB".to_string()],
+                )
+            } else if max_val_bytes < log_giga {
+                (
+                    log_giga,
+                    vec![
+                        "0B".to_string(),
+                        "1KB".to_string(),
+                        "1MB".to_string(),
+                        "1GB".to_string(),
+                    ],
+                )
+            } else {
+                let true_max_val = max_val_bytes.ceil() + 1.0;
+                let cap_u32 = true_max_val as u32;
+                let log_giga_u32 = log_giga.round() as u32;
+
+                let labels = (0..=cap_u32)
+                    .map(|i| {
+                        if i == 0 {
+                            "0B".to_string()
+                        } else if i == log_kilo.round() as u32 {
+                            "1KB".to_string()
+                        } else if i == log_mega.round() as u32 {
+                            "1MB".to_string()
+                        } else if i == log_giga_u32 {
+                            "1GB".to_string()
+                        } else if i == cap_u32 || i == (log_giga_u32 + cap_u32) / 2 {
+                            let (value, unit) =
+                                get_simple_byte_values(2_f64.powi(i as i32) as u64, false);
+                            format!("{}{}", format_number(value, 0), unit)
+                        } else {
+                            String::default()
+                        }
+                    })
+                    .collect();
+
+                (true_max_val, labels)
+            }
+        }
+
         if let Some(network_widget_state) = app_state.net_state.widget_states.get_mut(&widget_id) {
             let network_data_rx: &[(f64, f64)] = &app_state.canvas_data.network_data_rx;
             let network_data_tx: &[(f64, f64)] = &app_state.canvas_data.network_data_tx;
 
+            // Like the data, thresholds are drawn on a log2 scale.
+            let thresholds: Vec<f64> = app_state
+                .app_config_fields
+                .graph_thresholds
+                .network
+                .iter()
+                .map(|threshold| threshold.log2())
+                .collect();
+
             let (max_range, labels) = adjust_network_data_point(
                 network_data_rx,
                 network_data_tx,
                 -(network_widget_state.current_display_time as f64),
                 0.0,
+                &thresholds,
+            );
+            let session_marker = get_session_marker_points(
+                app_state.canvas_data.previous_session_offset,
+                network_widget_state.current_display_time,
+                [0.0, max_range],
+            );
+            let alert_markers = get_alert_marker_points(
+                &app_state.canvas_data.alert_markers,
+                AlertGraph::Net,
+                network_widget_state.current_display_time,
+                [0.0, max_range],
             );
             let display_time_labels = vec![
                 Span::styled(
@@ -249,11 +335,15 @@ impl NetworkGraphWidget for Painter {
 
             let legend_constraints = if hide_legend {
                 (Constraint::Ratio(0, 1), Constraint::Ratio(0, 1))
+            } else if app_state.app_config_fields.show_graph_stats {
+                // The stats double the legend's height, so let it take up the whole graph height.
+                (Constraint::Ratio(3, 4), Constraint::Ratio(1, 1))
             } else {
                 (Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))
             };
 
-            let dataset = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let mut dataset = if app_state.app_config_fields.use_old_network_legend && !hide_legend
+            {
                 let mut ret_val = vec![];
                 ret_val.push(
                     Dataset::default()
@@ -264,7 +354,7 @@ impl NetworkGraphWidget for Painter {
                             Marker::Braille
                         })
                         .style(self.colours.rx_style)
-                        .data(&network_data_rx)
+                        .data(network_data_rx)
                         .graph_type(tui::widgets::GraphType::Line),
                 );
 
@@ -277,7 +367,7 @@ impl NetworkGraphWidget for Painter {
                             Marker::Braille
                         })
                         .style(self.colours.tx_style)
-                        .data(&network_data_tx)
+                        .data(network_data_tx)
                         .graph_type(tui::widgets::GraphType::Line),
                 );
                 ret_val.push(
@@ -311,7 +401,7 @@ impl NetworkGraphWidget for Painter {
                             Marker::Braille
                         })
                         .style(self.colours.rx_style)
-                        .data(&network_data_rx)
+                        .data(network_data_rx)
                         .graph_type(tui::widgets::GraphType::Line),
                 );
 
@@ -324,25 +414,100 @@ impl NetworkGraphWidget for Painter {
                             Marker::Braille
                         })
                         .style(self.colours.tx_style)
-                        .data(&network_data_tx)
+                        .data(network_data_tx)
                         .graph_type(tui::widgets::GraphType::Line),
                 );
 
                 ret_val
             };
 
+            if app_state.app_config_fields.show_graph_stats && !hide_legend {
+                let peaks = &app_state.data_collection.peaks;
+                for (label, data, peak, style) in &[
+                    ("RX", network_data_rx, peaks.rx, self.colours.rx_style),
+                    ("TX", network_data_tx, peaks.tx, self.colours.tx_style),
+                ] {
+                    // Points are stored on a log2 scale.
+                    if let Some(stats) =
+                        get_graph_stats(data, network_widget_state.current_display_time, |value| {
+                            if value > 0.0 {
+                                value.exp2()
+                            } else {
+                                0.0
+                            }
+                        })
+                    {
+                        dataset.push(
+                            Dataset::default()
+                                .name(format_graph_stats(label, &stats, *peak as f64, |value| {
+                                    let (value, unit) =
+                                        get_byte_values(value as u64, false, ByteUnits::Binary);
+                                    format!("{}{}", format_number(value, 1), unit)
+                                }))
+                                .style(*style),
+                        );
+                    }
+                }
+            }
+
+            if !session_marker.is_empty() {
+                dataset.push(
+                    Dataset::default()
+                        .name(PREVIOUS_SESSION_LABEL)
+                        .marker(if app_state.app_config_fields.use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.graph_style)
+                        .data(&session_marker)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
+            for (name, points) in &alert_markers {
+                dataset.push(
+                    Dataset::default()
+                        .name(name.as_str())
+                        .marker(if app_state.app_config_fields.use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.graph_style)
+                        .data(points)
+                        .graph_type(tui::widgets::GraphType::Scatter),
+                );
+            }
+
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(if app_state.current_widget.widget_id == widget_id {
+                    self.colours.highlighted_border_style
+                } else {
+                    self.colours.border_style
+                });
+
+            draw_threshold_lines(
+                f,
+                draw_loc,
+                block.clone(),
+                x_axis.clone(),
+                y_axis.clone(),
+                &thresholds,
+                network_widget_state.current_display_time,
+                self.colours.graph_style,
+                if app_state.app_config_fields.use_dot {
+                    Marker::Dot
+                } else {
+                    Marker::Braille
+                },
+            );
+
             f.render_widget(
                 Chart::new(dataset)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(if app_state.current_widget.widget_id == widget_id {
-                                self.colours.highlighted_border_style
-                            } else {
-                                self.colours.border_style
-                            }),
-                    )
+                    .block(block)
                     .x_axis(x_axis)
                     .y_axis(y_axis)
                     .hidden_legend_constraints(legend_constraints),
@@ -361,7 +526,7 @@ impl NetworkGraphWidget for Painter {
         let total_tx_display = &app_state.canvas_data.total_tx_display;
 
         // Gross but I need it to work...
-        let total_network = vec![vec![
+        let total_network = [vec![
             rx_display,
             tx_display,
             total_rx_display,
@@ -402,7 +567,7 @@ impl NetworkGraphWidget for Painter {
                 .widths(
                     &(intrinsic_widths
                         .iter()
-                        .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                        .map(|calculated_width| Constraint::Length(*calculated_width))
                         .collect::<Vec<_>>()),
                 ),
             draw_loc,
@@ -1,15 +1,20 @@
 use crate::{
+    app::data_harvester::processes::ProcessSorting,
     app::App,
     canvas::{
         drawing_utils::{get_column_widths, get_search_start_position, get_start_position},
         Painter,
     },
     constants::*,
+    data_conversion::stringify_process_totals,
+    query::{get_query_highlights, QueryHighlight, SearchTarget},
+    utils::number_format::format_number,
 };
 
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     terminal::Frame,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph, Row, Table},
@@ -19,80 +24,39 @@ use std::borrow::Cow;
 use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
 use unicode_width::UnicodeWidthStr;
 
-use once_cell::sync::Lazy;
-
-static PROCESS_HEADERS_HARD_WIDTH_NO_GROUP: Lazy<Vec<Option<u16>>> = Lazy::new(|| {
-    vec![
-        Some(7),
-        None,
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(7),
-        Some(8),
-    ]
-});
-static PROCESS_HEADERS_HARD_WIDTH_GROUPED: Lazy<Vec<Option<u16>>> = Lazy::new(|| {
-    vec![
-        Some(7),
-        None,
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(7),
-        Some(8),
-        None,
-    ]
-});
-
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_COMMAND: Lazy<Vec<Option<f64>>> =
-    Lazy::new(|| vec![None, Some(0.7), None, None, None, None, None, None]);
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_TREE: Lazy<Vec<Option<f64>>> =
-    Lazy::new(|| vec![None, Some(0.5), None, None, None, None, None, None]);
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_ELSE: Lazy<Vec<Option<f64>>> =
-    Lazy::new(|| vec![None, Some(0.4), None, None, None, None, None, None]);
-
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_COMMAND: Lazy<Vec<Option<f64>>> = Lazy::new(|| {
-    vec![
-        None,
-        Some(0.7),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        Some(0.2),
-    ]
-});
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_TREE: Lazy<Vec<Option<f64>>> = Lazy::new(|| {
-    vec![
-        None,
-        Some(0.5),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        Some(0.2),
-    ]
-});
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_ELSE: Lazy<Vec<Option<f64>>> = Lazy::new(|| {
-    vec![
-        None,
-        Some(0.3),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        Some(0.2),
-    ]
-});
+/// Returns the fixed width of a process column, if it has one.
+fn get_hard_width(column: &ProcessSorting) -> Option<u16> {
+    match column {
+        ProcessSorting::Pid | ProcessSorting::Count | ProcessSorting::TotalRead => Some(7),
+        ProcessSorting::CpuPercent
+        | ProcessSorting::Mem
+        | ProcessSorting::MemPercent
+        | ProcessSorting::ReadPerSecond
+        | ProcessSorting::WritePerSecond
+        | ProcessSorting::TotalWrite => Some(8),
+        _ => None,
+    }
+}
+
+/// Returns the largest share of the table's width that a process column without a fixed width
+/// may take up.  Names get more room when they're full commands or indented in a tree.
+fn get_soft_width_max(
+    column: &ProcessSorting, is_using_command: bool, is_tree_mode: bool, is_grouped: bool,
+) -> Option<f64> {
+    match column {
+        ProcessSorting::ProcessName | ProcessSorting::Command => Some(if is_using_command {
+            0.7
+        } else if is_tree_mode {
+            0.5
+        } else if is_grouped {
+            0.4
+        } else {
+            0.3
+        }),
+        _ if get_hard_width(column).is_some() => None,
+        _ => Some(0.2),
+    }
+}
 
 pub trait ProcessTableWidget {
     /// Draws and handles all process-related drawing.  Use this.
@@ -113,7 +77,7 @@ pub trait ProcessTableWidget {
 
     /// Draws the process search field.
     /// - `widget_id` represents the widget ID of the search box itself --- NOT the process widget
-    /// state that is stored.
+    ///   state that is stored.
     ///
     /// This should not be directly called.
     fn draw_search_field<B: Backend>(
@@ -123,7 +87,7 @@ pub trait ProcessTableWidget {
 
     /// Draws the process sort box.
     /// - `widget_id` represents the widget ID of the sort box itself --- NOT the process widget
-    /// state that is stored.
+    ///   state that is stored.
     ///
     /// This should not be directly called.
     fn draw_process_sort<B: Backend>(
@@ -142,6 +106,8 @@ impl ProcessTableWidget for Painter {
             let is_sort_open = process_widget_state.is_sort_open;
             let header_len = process_widget_state.columns.longest_header_len;
 
+            let is_showing_share = app_state.app_config_fields.show_process_share;
+
             let mut proc_draw_loc = draw_loc;
             if process_widget_state.is_search_enabled() {
                 let processes_chunk = Layout::default()
@@ -175,7 +141,42 @@ impl ProcessTableWidget for Painter {
                 );
             }
 
+            // The share footer goes over the bottom border, or takes the last line if there isn't one.
+            let mut share_draw_loc = None;
+            if is_showing_share && proc_draw_loc.height > 2 {
+                if draw_border {
+                    share_draw_loc = Some(Rect::new(
+                        proc_draw_loc.x + 1,
+                        proc_draw_loc.y + proc_draw_loc.height - 1,
+                        proc_draw_loc.width.saturating_sub(2),
+                        1,
+                    ));
+                } else {
+                    let processes_chunk = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Min(0), Constraint::Length(1)])
+                        .split(proc_draw_loc);
+                    proc_draw_loc = processes_chunk[0];
+                    share_draw_loc = Some(processes_chunk[1]);
+                }
+            }
+
             self.draw_processes_table(f, app_state, proc_draw_loc, draw_border, widget_id);
+
+            if let (Some(share_draw_loc), Some(totals)) = (
+                share_draw_loc,
+                app_state.canvas_data.process_totals_map.get(&widget_id),
+            ) {
+                let share_text = format!(
+                    " matched: {}% of CPU, {}% of RAM ",
+                    format_number(totals.cpu_share_percent, 0),
+                    format_number(totals.mem_share_percent, 0)
+                );
+                f.render_widget(
+                    Paragraph::new(Span::styled(share_text, self.colours.widget_title_style)),
+                    share_draw_loc,
+                );
+            }
         }
     }
 
@@ -207,6 +208,31 @@ impl ProcessTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
+            let widget_name = if proc_widget_state.is_grouped_by_app {
+                "Apps"
+            } else if proc_widget_state.is_grouped_by_user {
+                "Users"
+            } else {
+                "Processes"
+            };
+            let mut processes_title = if proc_widget_state.quick_filters.is_empty() {
+                format!(" {} ", widget_name)
+            } else {
+                format!(
+                    " {} [{}] ",
+                    widget_name,
+                    proc_widget_state
+                        .quick_filters
+                        .iter()
+                        .map(|quick_filter| quick_filter.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            if let Some(locked_pid) = proc_widget_state.locked_pid {
+                processes_title = format!("{}(following {}) ", processes_title, locked_pid);
+            }
+
             let title_base = if app_state.app_config_fields.show_table_scroll_position {
                 if let Some(finalized_process_data) = app_state
                     .canvas_data
@@ -214,7 +240,8 @@ impl ProcessTableWidget for Painter {
                     .get(&widget_id)
                 {
                     let title = format!(
-                        " Processes ({} of {}) ",
+                        "{}({} of {}) ",
+                        processes_title,
                         proc_widget_state
                             .scroll_state
                             .current_scroll_position
@@ -225,13 +252,13 @@ impl ProcessTableWidget for Painter {
                     if title.len() <= draw_loc.width as usize {
                         title
                     } else {
-                        " Processes ".to_string()
+                        processes_title.clone()
                     }
                 } else {
-                    " Processes ".to_string()
+                    processes_title.clone()
                 }
             } else {
-                " Processes ".to_string()
+                processes_title.clone()
             };
 
             let title = if app_state.is_expanded
@@ -248,8 +275,8 @@ impl ProcessTableWidget for Painter {
 
                     if temp_title_base.len() > draw_loc.width as usize {
                         (
-                            " Processes ".to_string(),
-                            format!("{}{}", " Processes ".to_string(), ESCAPE_ENDING),
+                            processes_title.clone(),
+                            format!("{}{}", processes_title, ESCAPE_ENDING),
                         )
                     } else {
                         (title_base, temp_title_base)
@@ -302,11 +329,25 @@ impl ProcessTableWidget for Painter {
                 } else {
                     app_state.app_config_fields.table_gap
                 };
+
+                // The summary row stays above the processes, so it takes up one of their rows.
+                let summary_row = if proc_widget_state.is_showing_summary_row {
+                    app_state
+                        .canvas_data
+                        .process_totals_map
+                        .get(&widget_id)
+                        .map(|totals| stringify_process_totals(proc_widget_state, totals))
+                } else {
+                    None
+                };
+                let num_summary_rows = if summary_row.is_some() { 1 } else { 0 };
+
                 let position = get_start_position(
                     usize::from(
                         (draw_loc.height + (1 - table_gap))
-                            .saturating_sub(self.table_height_offset),
+                            .saturating_sub(self.table_height_offset + num_summary_rows),
                     ),
+                    process_data.len(),
                     &proc_widget_state.scroll_state.scroll_direction,
                     &mut proc_widget_state.scroll_state.previous_scroll_position,
                     proc_widget_state.scroll_state.current_scroll_position,
@@ -320,7 +361,10 @@ impl ProcessTableWidget for Painter {
                     position
                 };
 
-                let sliced_vec = &process_data[start_position..];
+                let sliced_vec = summary_row
+                    .iter()
+                    .chain(&process_data[start_position..])
+                    .collect::<Vec<_>>();
                 let processed_sliced_vec = sliced_vec.iter().map(|(data, disabled)| {
                     (
                         data.iter()
@@ -335,7 +379,8 @@ impl ProcessTableWidget for Painter {
                     proc_widget_state
                         .scroll_state
                         .current_scroll_position
-                        .saturating_sub(start_position),
+                        .saturating_sub(start_position)
+                        + usize::from(num_summary_rows),
                 ));
 
                 // Draw!
@@ -345,11 +390,15 @@ impl ProcessTableWidget for Painter {
                 );
 
                 // Calculate widths
-                let hard_widths = if proc_widget_state.is_grouped {
-                    &*PROCESS_HEADERS_HARD_WIDTH_GROUPED
-                } else {
-                    &*PROCESS_HEADERS_HARD_WIDTH_NO_GROUP
-                };
+                let enabled_columns = proc_widget_state
+                    .columns
+                    .get_enabled_columns()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let hard_widths = enabled_columns
+                    .iter()
+                    .map(get_hard_width)
+                    .collect::<Vec<_>>();
 
                 if recalculate_column_widths {
                     let mut column_widths = process_headers
@@ -379,7 +428,7 @@ impl ProcessTableWidget for Painter {
                         .table_width_state
                         .desired_column_widths
                         .iter()
-                        .zip(hard_widths)
+                        .zip(&hard_widths)
                         .map(|(current, hard)| {
                             if let Some(hard) = hard {
                                 if *hard > *current {
@@ -393,28 +442,24 @@ impl ProcessTableWidget for Painter {
                         })
                         .collect::<Vec<_>>();
 
-                    let soft_widths_max = if proc_widget_state.is_grouped {
-                        if proc_widget_state.is_using_command {
-                            &*PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_COMMAND
-                        } else if proc_widget_state.is_tree_mode {
-                            &*PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_TREE
-                        } else {
-                            &*PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_ELSE
-                        }
-                    } else if proc_widget_state.is_using_command {
-                        &*PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_COMMAND
-                    } else if proc_widget_state.is_tree_mode {
-                        &*PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_TREE
-                    } else {
-                        &*PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_ELSE
-                    };
+                    let (is_using_command, is_tree_mode, is_grouped) = (
+                        proc_widget_state.is_using_command,
+                        proc_widget_state.is_tree_mode,
+                        proc_widget_state.is_grouped,
+                    );
+                    let soft_widths_max = enabled_columns
+                        .iter()
+                        .map(|column| {
+                            get_soft_width_max(column, is_using_command, is_tree_mode, is_grouped)
+                        })
+                        .collect::<Vec<_>>();
 
                     proc_widget_state.table_width_state.calculated_column_widths =
                         get_column_widths(
                             draw_loc.width,
                             &hard_widths,
                             &soft_widths_min,
-                            soft_widths_max,
+                            &soft_widths_max,
                             &(proc_widget_state
                                 .table_width_state
                                 .desired_column_widths
@@ -437,51 +482,74 @@ impl ProcessTableWidget for Painter {
                 let dcw = &proc_widget_state.table_width_state.desired_column_widths;
                 let ccw = &proc_widget_state.table_width_state.calculated_column_widths;
 
-                let process_rows = sliced_vec.iter().map(|(data, disabled)| {
-                    let truncated_data = data.iter().zip(hard_widths).enumerate().map(
-                        |(itx, ((entry, alternative), width))| {
-                            if let (Some(desired_col_width), Some(calculated_col_width)) =
-                                (dcw.get(itx), ccw.get(itx))
-                            {
-                                if width.is_none() {
-                                    if *desired_col_width > *calculated_col_width
-                                        && *calculated_col_width > 0
+                // Pinned processes stand out in their own colour, unless they are dimmed.
+                let finalized_process_data = app_state
+                    .canvas_data
+                    .finalized_process_data_map
+                    .get(&widget_id);
+                let is_pinned_row = |index: usize| {
+                    index
+                        .checked_sub(usize::from(num_summary_rows))
+                        .and_then(|index| finalized_process_data?.get(start_position + index))
+                        .is_some_and(|process| process.is_pinned)
+                };
+
+                let process_rows =
+                    sliced_vec
+                        .iter()
+                        .enumerate()
+                        .map(|(index, (data, disabled))| {
+                            let truncated_data = data.iter().zip(&hard_widths).enumerate().map(
+                                |(itx, ((entry, alternative), width))| {
+                                    if let (Some(desired_col_width), Some(calculated_col_width)) =
+                                        (dcw.get(itx), ccw.get(itx))
                                     {
-                                        let graphemes =
-                                            UnicodeSegmentation::graphemes(entry.as_str(), true)
+                                        if width.is_none() {
+                                            if *desired_col_width > *calculated_col_width
+                                                && *calculated_col_width > 0
+                                            {
+                                                let graphemes = UnicodeSegmentation::graphemes(
+                                                    entry.as_str(),
+                                                    true,
+                                                )
                                                 .collect::<Vec<&str>>();
 
-                                        if let Some(alternative) = alternative {
-                                            Cow::Borrowed(alternative)
-                                        } else if graphemes.len() > *calculated_col_width as usize
-                                            && *calculated_col_width > 1
-                                        {
-                                            // Truncate with ellipsis
-                                            let first_n = graphemes
-                                                [..(*calculated_col_width as usize - 1)]
-                                                .concat();
-                                            Cow::Owned(format!("{}…", first_n))
+                                                if let Some(alternative) = alternative {
+                                                    Cow::Borrowed(alternative)
+                                                } else if graphemes.len()
+                                                    > *calculated_col_width as usize
+                                                    && *calculated_col_width > 1
+                                                {
+                                                    // Truncate with ellipsis
+                                                    let first_n = graphemes
+                                                        [..(*calculated_col_width as usize - 1)]
+                                                        .concat();
+                                                    Cow::Owned(format!("{}…", first_n))
+                                                } else {
+                                                    Cow::Borrowed(entry)
+                                                }
+                                            } else {
+                                                Cow::Borrowed(entry)
+                                            }
                                         } else {
                                             Cow::Borrowed(entry)
                                         }
                                     } else {
                                         Cow::Borrowed(entry)
                                     }
-                                } else {
-                                    Cow::Borrowed(entry)
-                                }
+                                },
+                            );
+
+                            if index < usize::from(num_summary_rows) {
+                                Row::StyledData(truncated_data, self.colours.table_header_style)
+                            } else if *disabled {
+                                Row::StyledData(truncated_data, self.colours.disabled_text_style)
+                            } else if is_pinned_row(index) {
+                                Row::StyledData(truncated_data, self.colours.pinned_text_style)
                             } else {
-                                Cow::Borrowed(entry)
+                                Row::Data(truncated_data)
                             }
-                        },
-                    );
-
-                    if *disabled {
-                        Row::StyledData(truncated_data, self.colours.disabled_text_style)
-                    } else {
-                        Row::Data(truncated_data)
-                    }
-                });
+                        });
 
                 f.render_stateful_widget(
                     Table::new(process_headers.iter(), process_rows)
@@ -494,9 +562,7 @@ impl ProcessTableWidget for Painter {
                                 .table_width_state
                                 .calculated_column_widths
                                 .iter()
-                                .map(|calculated_width| {
-                                    Constraint::Length(*calculated_width as u16)
-                                })
+                                .map(|calculated_width| Constraint::Length(*calculated_width))
                                 .collect::<Vec<_>>()),
                         )
                         .header_gap(table_gap),
@@ -526,8 +592,8 @@ impl ProcessTableWidget for Painter {
     ) {
         fn build_query<'a>(
             is_on_widget: bool, grapheme_indices: GraphemeIndices<'a>, start_position: usize,
-            cursor_position: usize, query: &str, currently_selected_text_style: tui::style::Style,
-            text_style: tui::style::Style,
+            cursor_position: usize, query: &str, currently_selected_text_style: Style,
+            get_style: &dyn Fn(usize) -> Style,
         ) -> Vec<Span<'a>> {
             let mut current_grapheme_posn = 0;
 
@@ -542,7 +608,7 @@ impl ProcessTableWidget for Painter {
                             let styled = if grapheme.0 == cursor_position {
                                 Span::styled(grapheme.1, currently_selected_text_style)
                             } else {
-                                Span::styled(grapheme.1, text_style)
+                                Span::styled(grapheme.1, get_style(grapheme.0))
                             };
                             Some(styled)
                         }
@@ -564,7 +630,7 @@ impl ProcessTableWidget for Painter {
                         if current_grapheme_posn <= start_position {
                             None
                         } else {
-                            let styled = Span::styled(grapheme.1, text_style);
+                            let styled = Span::styled(grapheme.1, get_style(grapheme.0));
                             Some(styled)
                         }
                     })
@@ -597,21 +663,70 @@ impl ProcessTableWidget for Painter {
                 app_state.is_force_redraw,
             );
 
-            let query = proc_widget_state.get_current_search_query().as_str();
+            let search_state = &proc_widget_state.process_search_state.search_state;
+            let query = search_state.current_search_query.as_str();
             let grapheme_indices = UnicodeSegmentation::grapheme_indices(query, true);
 
+            // Colour each part of the query by what it is, and underline where any error is.
+            let highlights = get_query_highlights(
+                query,
+                &proc_widget_state.prefix_aliases,
+                SearchTarget::Processes,
+            );
+            let error_span = if search_state.is_invalid_search {
+                search_state.error_span.clone()
+            } else {
+                None
+            };
+            let get_style = |index: usize| {
+                let style = highlights
+                    .iter()
+                    .find(|(span, _)| span.contains(&index))
+                    .map_or(self.colours.text_style, |(_, highlight)| match highlight {
+                        QueryHighlight::Prefix => self.colours.table_header_style,
+                        QueryHighlight::Operator => self.colours.query_operator_style,
+                        QueryHighlight::Number => self.colours.query_number_style,
+                        QueryHighlight::Quoted => self.colours.query_string_style,
+                    });
+                if error_span
+                    .as_ref()
+                    .is_some_and(|error_span| error_span.contains(&index))
+                {
+                    style
+                        .patch(self.colours.invalid_query_style)
+                        .add_modifier(Modifier::UNDERLINED)
+                } else {
+                    style
+                }
+            };
+
             // TODO: [CURSOR] blank cursor if not selected
             // TODO: [CURSOR] blinking cursor?
-            let query_with_cursor = build_query(
+            let mut query_with_cursor = build_query(
                 is_on_widget,
                 grapheme_indices,
                 start_position,
                 cursor_position,
                 query,
                 self.colours.currently_selected_text_style,
-                self.colours.text_style,
+                &get_style,
             );
 
+            // Show the suggested completion after the query, starting under the cursor.
+            if is_on_widget {
+                if let Some(completion) = proc_widget_state.get_search_completion() {
+                    query_with_cursor.pop();
+                    query_with_cursor.push(Span::styled(
+                        &completion[..1],
+                        self.colours.currently_selected_text_style,
+                    ));
+                    query_with_cursor.push(Span::styled(
+                        &completion[1..],
+                        self.colours.disabled_text_style,
+                    ));
+                }
+            }
+
             let mut search_text = vec![Spans::from({
                 let mut search_vec = vec![Span::styled(
                     search_title,
@@ -650,6 +765,15 @@ impl ProcessTableWidget for Painter {
                 self.colours.text_style
             };
 
+            let glob_style = if proc_widget_state
+                .process_search_state
+                .is_searching_with_glob
+            {
+                self.colours.currently_selected_text_style
+            } else {
+                self.colours.text_style
+            };
+
             // FIXME: [MOUSE] Mouse support for these in search
             // FIXME: [MOVEMENT] Movement support for these in search
             let option_text = Spans::from(vec![
@@ -667,18 +791,42 @@ impl ProcessTableWidget for Painter {
                     format!("Regex({})", if self.is_mac_os { "F3" } else { "Alt+R" }),
                     regex_style,
                 ),
+                Span::raw("  "),
+                Span::styled(
+                    format!("Glob({})", if self.is_mac_os { "F4" } else { "Alt+G" }),
+                    glob_style,
+                ),
             ]);
 
+            // Put a caret under where the error is, with the error after it, or before it if it
+            // doesn't fit.  If that part of the query is scrolled out of view, say where it is.
+            let error_text = match (&search_state.error_message, &search_state.error_span) {
+                (Some(err), Some(span)) => {
+                    let error_column =
+                        UnicodeWidthStr::width(query.get(..span.start).unwrap_or(query));
+                    let error_width = UnicodeWidthStr::width(err.as_str());
+                    let num_inner_columns = num_columns.saturating_sub(2);
+                    let caret_column = (search_title.len() + error_column)
+                        .checked_sub(start_position)
+                        .filter(|caret_column| *caret_column >= search_title.len());
+
+                    match caret_column {
+                        Some(caret_column)
+                            if caret_column + error_width + 2 <= num_inner_columns =>
+                        {
+                            format!("{}^ {}", " ".repeat(caret_column), err)
+                        }
+                        Some(caret_column) if error_width < caret_column => {
+                            format!("{}{} ^", " ".repeat(caret_column - error_width - 1), err)
+                        }
+                        _ => format!("{} (at column {})", err, error_column + 1),
+                    }
+                }
+                (Some(err), None) => err.clone(),
+                _ => String::new(),
+            };
             search_text.push(Spans::from(Span::styled(
-                if let Some(err) = &proc_widget_state
-                    .process_search_state
-                    .search_state
-                    .error_message
-                {
-                    err.as_str()
-                } else {
-                    ""
-                },
+                error_text,
                 self.colours.invalid_query_style,
             )));
             search_text.push(option_text);
@@ -759,17 +907,8 @@ impl ProcessTableWidget for Painter {
             let current_scroll_position = proc_widget_state.columns.current_scroll_position;
             let sort_string = proc_widget_state
                 .columns
-                .ordered_columns
-                .iter()
-                .filter(|column_type| {
-                    proc_widget_state
-                        .columns
-                        .column_mapping
-                        .get(&column_type)
-                        .unwrap()
-                        .enabled
-                })
-                .map(|column_type| column_type.to_string())
+                .get_enabled_columns()
+                .map(|column_type| proc_widget_state.columns.get_column_name(column_type))
                 .collect::<Vec<_>>();
 
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
@@ -781,6 +920,7 @@ impl ProcessTableWidget for Painter {
                 usize::from(
                     (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
                 ),
+                sort_string.len(),
                 &proc_widget_state.columns.scroll_direction,
                 &mut proc_widget_state.columns.previous_scroll_position,
                 current_scroll_position,
@@ -11,7 +11,7 @@ use tui::{
 use crate::{
     app,
     canvas::{
-        drawing_utils::{get_column_widths, get_start_position},
+        drawing_utils::{get_column_widths, get_start_position, get_table_search_title},
         Painter,
     },
     constants::*,
@@ -47,10 +47,14 @@ impl TempTableWidget for Painter {
             } else {
                 app_state.app_config_fields.table_gap
             };
+            let shown_rows = temp_widget_state
+                .search_state
+                .filter(&app_state.canvas_data.temp_sensor_data);
             let start_position = get_start_position(
                 usize::from(
                     (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
                 ),
+                shown_rows.len(),
                 &temp_widget_state.scroll_state.scroll_direction,
                 &mut temp_widget_state.scroll_state.previous_scroll_position,
                 temp_widget_state.scroll_state.current_scroll_position,
@@ -64,7 +68,7 @@ impl TempTableWidget for Painter {
                     .current_scroll_position
                     .saturating_sub(start_position),
             ));
-            let sliced_vec = &app_state.canvas_data.temp_sensor_data[start_position..];
+            let sliced_vec = shown_rows.get(start_position..).unwrap_or_default();
 
             // Calculate widths
             let hard_widths = [None, None];
@@ -72,7 +76,7 @@ impl TempTableWidget for Painter {
                 temp_widget_state.table_width_state.desired_column_widths = {
                     let mut column_widths = TEMP_HEADERS_LENS.clone();
                     for row in sliced_vec {
-                        for (col, entry) in row.iter().enumerate() {
+                        for (col, entry) in row.row.iter().enumerate() {
                             if entry.len() as u16 > column_widths[col] {
                                 column_widths[col] = entry.len() as u16;
                             }
@@ -101,32 +105,28 @@ impl TempTableWidget for Painter {
 
             let dcw = &temp_widget_state.table_width_state.desired_column_widths;
             let ccw = &temp_widget_state.table_width_state.calculated_column_widths;
-            let temperature_rows =
-                sliced_vec.iter().map(|temp_row| {
-                    let truncated_data = temp_row.iter().zip(&hard_widths).enumerate().map(
-                        |(itx, (entry, width))| {
-                            if width.is_none() {
-                                if let (Some(desired_col_width), Some(calculated_col_width)) =
-                                    (dcw.get(itx), ccw.get(itx))
+            let temperature_rows = sliced_vec.iter().map(|temp_row| {
+                let truncated_data = temp_row.row.iter().zip(&hard_widths).enumerate().map(
+                    |(itx, (entry, width))| {
+                        if width.is_none() {
+                            if let (Some(desired_col_width), Some(calculated_col_width)) =
+                                (dcw.get(itx), ccw.get(itx))
+                            {
+                                if *desired_col_width > *calculated_col_width
+                                    && *calculated_col_width > 0
                                 {
-                                    if *desired_col_width > *calculated_col_width
-                                        && *calculated_col_width > 0
-                                    {
-                                        let graphemes =
-                                            UnicodeSegmentation::graphemes(entry.as_str(), true)
-                                                .collect::<Vec<&str>>();
+                                    let graphemes =
+                                        UnicodeSegmentation::graphemes(entry.as_str(), true)
+                                            .collect::<Vec<&str>>();
 
-                                        if graphemes.len() > *calculated_col_width as usize
-                                            && *calculated_col_width > 1
-                                        {
-                                            // Truncate with ellipsis
-                                            let first_n = graphemes
-                                                [..(*calculated_col_width as usize - 1)]
-                                                .concat();
-                                            Cow::Owned(format!("{}…", first_n))
-                                        } else {
-                                            Cow::Borrowed(entry)
-                                        }
+                                    if graphemes.len() > *calculated_col_width as usize
+                                        && *calculated_col_width > 1
+                                    {
+                                        // Truncate with ellipsis
+                                        let first_n = graphemes
+                                            [..(*calculated_col_width as usize - 1)]
+                                            .concat();
+                                        Cow::Owned(format!("{}…", first_n))
                                     } else {
                                         Cow::Borrowed(entry)
                                     }
@@ -136,11 +136,14 @@ impl TempTableWidget for Painter {
                             } else {
                                 Cow::Borrowed(entry)
                             }
-                        },
-                    );
+                        } else {
+                            Cow::Borrowed(entry)
+                        }
+                    },
+                );
 
-                    Row::Data(truncated_data)
-                });
+                Row::Data(truncated_data)
+            });
 
             let (border_style, highlight_style) = if is_on_widget {
                 (
@@ -151,23 +154,25 @@ impl TempTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
+            let search_title = get_table_search_title(&temp_widget_state.search_state);
             let title_base = if app_state.app_config_fields.show_table_scroll_position {
                 let title_string = format!(
-                    " Temperatures ({} of {}) ",
+                    " Temperatures ({} of {}) {}",
                     temp_widget_state
                         .scroll_state
                         .current_scroll_position
                         .saturating_add(1),
-                    app_state.canvas_data.temp_sensor_data.len()
+                    shown_rows.len(),
+                    search_title
                 );
 
                 if title_string.len() <= draw_loc.width as usize {
                     title_string
                 } else {
-                    " Temperatures ".to_string()
+                    format!(" Temperatures {}", search_title)
                 }
             } else {
-                " Temperatures ".to_string()
+                format!(" Temperatures {}", search_title)
             };
 
             let title = if app_state.is_expanded {
@@ -179,7 +184,7 @@ impl TempTableWidget for Painter {
                     if temp_title_base.len() > draw_loc.width as usize {
                         (
                             " Temperatures ".to_string(),
-                            format!("{}{}", " Temperatures ".to_string(), ESCAPE_ENDING),
+                            format!("{}{}", " Temperatures ", ESCAPE_ENDING),
                         )
                     } else {
                         (title_base, temp_title_base)
@@ -240,7 +245,7 @@ impl TempTableWidget for Painter {
                             .table_width_state
                             .calculated_column_widths
                             .iter()
-                            .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                            .map(|calculated_width| Constraint::Length(*calculated_width))
                             .collect::<Vec<_>>()),
                     )
                     .header_gap(table_gap),
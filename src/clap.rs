@@ -20,6 +20,44 @@ pub fn get_matches() -> clap::ArgMatches<'static> {
 
 pub fn build_app() -> App<'static, 'static> {
     // Temps
+    let i3bar = Arg::with_name("i3bar")
+        .long("i3bar")
+        .help("Writes status lines for i3bar or swaybar.")
+        .long_help(
+            "\
+Instead of showing the interface, continuously writes CPU,
+memory, network, and temperature blocks to stdout using the
+i3bar JSON protocol, at the refresh rate.  Works with i3bar,
+swaybar, and anything else that speaks the protocol.\n\n",
+        );
+    let compare = Arg::with_name("compare")
+        .long("compare")
+        .takes_value(true)
+        .number_of_values(2)
+        .value_names(&["BEFORE", "AFTER"])
+        .help("Overlays the graphs of two saved sessions.")
+        .long_help(
+            "\
+Instead of showing live data, overlays the CPU, memory, and
+network graphs of two sessions saved with --persist_data, with
+the time axes lined up from the start of each session and the
+difference between their averages in the legends.  Sessions are
+saved to session.bin in bottom's cache directory, so copy it
+somewhere after each session to compare them later.  Press q or
+Esc to quit.\n\n",
+        );
+    let capabilities = Arg::with_name("capabilities")
+        .long("capabilities")
+        .help("Prints what this build supports as JSON and exits.")
+        .long_help(
+            "\
+Prints a JSON report of what this build of bottom supports and
+then exits.  This includes the version, the cargo features it
+was built with, which data can be collected on this platform,
+and the temperature sensors, batteries, and GPUs that were
+found.  The format_version field only changes if an existing
+field is removed or changes meaning.\n\n",
+        );
     let kelvin = Arg::with_name("kelvin")
         .short("k")
         .long("kelvin")
@@ -47,6 +85,16 @@ option.\n\n",
         );
 
     // All flags.  These are in alphabetical order
+    let app_column = Arg::with_name("app_column")
+        .long("app_column")
+        .help("Shows which app a process belongs to.")
+        .long_help(
+            "\
+Shows an App column in the process widget, with the name of the
+app each process belongs to.  On Linux, this is its Flatpak,
+Snap, or AppImage, or the app the desktop started it as.  On
+macOS, this is its app bundle.\n\n",
+        );
     let autohide_time = Arg::with_name("autohide_time")
         .long("autohide_time")
         .help("Temporarily shows the time scale in graphs.")
@@ -88,6 +136,18 @@ When searching for a process, enables case sensitivity by default.\n\n",
             "\
 Enables debug logging.  The program will print where it logged to after running.",
         );
+    let debug_log = Arg::with_name("debug_log")
+        .long("debug_log")
+        .alias("debug-log")
+        .conflicts_with("debug")
+        .help("Logs collection and event loop activity for bug reports.")
+        .long_help(
+            "\
+Logs data collection and event loop activity (timings, events, and
+errors) to a file in the data directory, which can be attached
+to bug reports.  This is less verbose than --debug.  The program
+will print where it logged to after running.\n\n",
+        );
     // TODO: [DIAGNOSE] Add a diagnose option to help with debugging.
     let disable_click = Arg::with_name("disable_click")
         .long("disable_click")
@@ -105,6 +165,46 @@ Disables mouse clicks from interacting with the program.\n\n",
 Uses a dot marker for graphs as opposed to the default braille
 marker.\n\n",
         );
+    let enable_gpu_memory = Arg::with_name("enable_gpu_memory")
+        .long("enable_gpu_memory")
+        .help("Shows how much GPU memory each process uses.")
+        .long_help(
+            "\
+Shows a GMem column in the process widget, with how much GPU
+memory each process uses.  On Linux, this works for AMD and Intel
+GPUs.  NVIDIA GPUs require bottom to be built with the \"nvidia\"
+feature.\n\n",
+        );
+    let enable_gpu_usage = Arg::with_name("enable_gpu_usage")
+        .long("enable_gpu_usage")
+        .help("Shows how busy each process keeps the GPU.")
+        .long_help(
+            "\
+Shows a GPU% column in the process widget, with how busy each
+process keeps the busiest GPU engine it uses.  Only supported on
+Linux, for AMD and Intel GPUs.\n\n",
+        );
+    let glob = Arg::with_name("glob")
+        .long("glob")
+        .help("Enables glob wildcards in searches by default.")
+        .long_help(
+            "\
+When searching for a process, makes \"*\", \"?\", and \"[...]\"
+wildcards by default, so \"fire*\" matches any name starting
+with \"fire\".  Regex takes priority if it is also enabled.\n\n",
+        );
+    let gpu_memory_rate = Arg::with_name("gpu_memory_rate")
+        .long("gpu_memory_rate")
+        .takes_value(true)
+        .value_name("MS")
+        .help("Sets how often GPU memory usage is refreshed in ms.")
+        .long_help(
+            "\
+Sets how often the GPU memory usage of processes is refreshed,
+in milliseconds.  This is slower to get than other process data,
+so it defaults to 5000 milliseconds.  The lowest it can be is
+250 milliseconds.\n\n",
+        );
     let group = Arg::with_name("group")
         .short("g")
         .long("group")
@@ -121,6 +221,35 @@ Groups processes with the same name by default.\n\n",
             "\
 Hides the average CPU usage from being shown.\n\n",
         );
+    let hide_removable_disks = Arg::with_name("hide_removable_disks")
+        .long("hide_removable_disks")
+        .help("Hides automatically mounted removable disks.")
+        .long_help(
+            "\
+Hides removable disks, like USB drives and SD cards, from the disk
+widget unless they are listed in /etc/fstab, so that drives mounted
+automatically don't clutter it.  Only supported on Linux.\n\n",
+        );
+    let synchronized_output = Arg::with_name("synchronized_output")
+        .long("synchronized_output")
+        .help("Asks the terminal to show each frame all at once.")
+        .long_help(
+            "\
+Wraps each frame in synchronized output escape sequences, so that
+terminals that support them show the whole frame at once.  This
+avoids tearing over slow connections like SSH.  Terminals that
+don't support them should ignore them.\n\n",
+        );
+    let window_titles = Arg::with_name("window_titles")
+        .long("window_titles")
+        .help("Also matches process searches against window titles.")
+        .long_help(
+            "\
+Also matches searches by name against the titles of the windows of
+each process, so searching for a page finds the browser showing it.
+Window titles are read from X11, which includes apps running through
+XWayland, or from sway.  Only supported on Linux.\n\n",
+        );
     let hide_table_gap = Arg::with_name("hide_table_gap")
         .long("hide_table_gap")
         .help("Hides the spacing between table headers and entries.")
@@ -135,6 +264,52 @@ Hides the spacing between table headers and entries.\n\n",
             "\
 Completely hides the time scaling from being shown.\n\n",
         );
+    let show_graph_stats = Arg::with_name("show_graph_stats")
+        .long("show_graph_stats")
+        .help("Shows min, max, and average values in graph legends.")
+        .long_help(
+            "\
+Shows the minimum, maximum, and average values over the visible
+time window in the legends of the CPU, memory, and network graphs,
+as well as the peak value since bottom started.  For the CPU graph,
+these are shown in the title for the selected CPU.\n\n",
+        );
+    let stacked_cpu = Arg::with_name("stacked_cpu")
+        .long("stacked_cpu")
+        .help("Stacks per-core usage in the CPU graph.")
+        .long_help(
+            "\
+When all CPUs are selected, draws each core's usage stacked on top
+of the previous cores, scaled so the top line is the total usage,
+instead of drawing overlapping lines.\n\n",
+        );
+    let security_column = Arg::with_name("security_column")
+        .long("security_column")
+        .help("Shows the SELinux context or AppArmor profile of each process.")
+        .long_help(
+            "\
+Shows a Security column in the process widget, with the SELinux
+context or AppArmor profile of each process.  Only supported on
+Linux.\n\n",
+        );
+    let show_process_share = Arg::with_name("show_process_share")
+        .long("show_process_share")
+        .help("Shows how much of the system's usage the shown processes make up.")
+        .long_help(
+            "\
+Shows a footer in the process widget with how much of the CPU and
+memory usage of the whole system the processes that match the
+current search make up.\n\n",
+        );
+    let link_widgets = Arg::with_name("link_widgets")
+        .long("link_widgets")
+        .help("Highlights what belongs to the selected process in other widgets.")
+        .long_help(
+            "\
+While a process widget is selected, highlights what belongs to the
+selected process in other widgets.  Currently, this highlights the
+cores the process may run on in the CPU widget.\n\n",
+        );
     let show_table_scroll_position = Arg::with_name("show_table_scroll_position")
         .long("show_table_scroll_position")
         .help("Shows the scroll position tracker in table widgets")
@@ -142,6 +317,50 @@ Completely hides the time scaling from being shown.\n\n",
             "\
     Shows the list scroll position tracker in the widget title for table widgets.\n\n",
         );
+    let hook_command = Arg::with_name("hook_command")
+        .long("hook_command")
+        .takes_value(true)
+        .value_name("COMMAND")
+        .help("Runs a command with the latest metrics on every update.")
+        .long_help(
+            "\
+Runs a command through the shell after each update, passing
+the latest metrics as JSON on its stdin.  Runs are limited by
+--hook_interval, and skipped while the previous run is still
+going.\n\n",
+        );
+    let max_fps = Arg::with_name("max_fps")
+        .long("max_fps")
+        .takes_value(true)
+        .value_name("FPS")
+        .help("Limits how many times per second new data is drawn.")
+        .long_help(
+            "\
+Limits how many times per second new data is drawn, regardless of
+how often it is collected.  Input is still drawn right away.  By
+default, new data is drawn as soon as it is collected.\n\n",
+        );
+    let hook_interval = Arg::with_name("hook_interval")
+        .long("hook_interval")
+        .takes_value(true)
+        .value_name("MS")
+        .help("The minimum time in ms between hook runs.")
+        .long_help(
+            "\
+The minimum time in milliseconds between runs of the command
+set by --hook_command.  Defaults to 1000 milliseconds.\n\n",
+        );
+    let snapshot_path = Arg::with_name("snapshot_path")
+        .long("snapshot_path")
+        .takes_value(true)
+        .value_name("PATH")
+        .help("Sets where a JSON snapshot is written on SIGUSR1.")
+        .long_help(
+            "\
+Sets where a JSON snapshot of the current metrics is written
+when bottom receives SIGUSR1.  Defaults to snapshot.json in
+bottom's data directory.  Only supported on Unix-like systems.\n\n",
+        );
     let left_legend = Arg::with_name("left_legend")
         .short("l")
         .long("left_legend")
@@ -150,6 +369,78 @@ Completely hides the time scaling from being shown.\n\n",
             "\
 Puts the CPU chart legend to the left side rather than the right side.\n\n",
         );
+    let namespace_column = Arg::with_name("namespace_column")
+        .long("namespace_column")
+        .help("Shows which namespaces each process doesn't share with the host.")
+        .long_help(
+            "\
+Shows an NS column in the process widget, listing which of the pid,
+net, and mnt namespaces each process doesn't share with the host,
+such as processes in containers.  Only supported on Linux.\n\n",
+        );
+    let nice_column = Arg::with_name("nice_column")
+        .long("nice_column")
+        .help("Shows the nice value of each process.")
+        .long_help(
+            "\
+Shows a Nice column in the process widget, with the nice value of
+each process, from -20 (most favourable) to 19.  Nice values are
+only read on Linux.\n\n",
+        );
+    let oneshot = Arg::with_name("oneshot")
+        .long("oneshot")
+        .help("Prints a one-line summary and exits.")
+        .long_help(
+            "\
+Prints a single, compact line summarizing CPU, memory, load,
+and network usage, then exits.  Useful for shell prompts and
+status bars.  Usage is coloured unless the NO_COLOR environment
+variable is set.\n\n",
+        );
+    let persist_data = Arg::with_name("persist_data")
+        .long("persist_data")
+        .help("Persists the last-known data between runs.")
+        .long_help(
+            "\
+Saves the last-known disk counters and graph data to the cache
+directory on exit, and loads the counters on startup if bottom was
+only closed briefly.  This allows disk rates to be shown correctly
+on the very first sample after starting.  The graph data can be
+restored with --warm_start or compared with --compare.\n\n",
+        );
+    let process_command = Arg::with_name("process_command")
+        .long("process_command")
+        .help("Shows the full command of each process instead of its name.")
+        .long_help(
+            "\
+Defaults to showing the full command line of each process, with its
+arguments, instead of just its name.  Searches by name then match
+against the command.  This can be toggled with P.\n\n",
+        );
+    let process_name_source = Arg::with_name("process_name_source")
+        .long("process_name_source")
+        .takes_value(true)
+        .value_name("SOURCE")
+        .help("Where to get full process names from on Linux.")
+        .long_help(
+            "\
+Sets where to look for a process' full name on Linux, as the
+kernel cuts names off at 15 characters.  Supported values are:
+
++---------+-------------------------------------------------+
+| exe     | The executable's file name, then the first      |
+|         | argument of the command line                    |
++---------+-------------------------------------------------+
+| cmdline | The first argument of the command line, then    |
+|         | the executable's file name                      |
++---------+-------------------------------------------------+
+| comm    | Always use the truncated kernel name            |
++---------+-------------------------------------------------+
+
+Names are only replaced if they start with the truncated name.
+Defaults to \"exe\".\n\n",
+        )
+        .possible_values(&["exe", "cmdline", "comm"]);
     //     let no_write = Arg::with_name("no_write")
     //         .long("no_write")
     //         .help("Disables writing to the config file.")
@@ -165,6 +456,17 @@ Puts the CPU chart legend to the left side rather than the right side.\n\n",
             "\
 When searching for a process, enables regex by default.\n\n",
         );
+    let rt_column = Arg::with_name("rt_column")
+        .long("rt_column")
+        .help("Shows the real-time scheduling of each process.")
+        .long_help(
+            "\
+Shows an RT column in the process widget, with the real-time
+scheduling policy and priority of processes that use one, such
+as \"FIFO 99\".  As these always run before other processes, a
+busy one can starve the rest of the system.  Only supported on
+Linux.\n\n",
+        );
     let current_usage = Arg::with_name("current_usage")
         .short("u")
         .long("current_usage")
@@ -182,6 +484,30 @@ rather than total CPU usage.\n\n",
 DEPRECATED - uses the older (pre-0.4) network widget legend.
 This display is not tested anymore and could be broken.\n\n\n",
         );
+    let warm_start = Arg::with_name("warm_start")
+        .long("warm_start")
+        .help("Restores graph data from the previous session.")
+        .long_help(
+            "\
+Restores the graph data from the previous session on startup,
+with the point where the previous session ended marked in the
+graphs.  Only data from the last 10 minutes is restored.  This
+implies --persist_data.\n\n",
+        );
+    let widget = Arg::with_name("widget")
+        .long("widget")
+        .takes_value(true)
+        .value_name("WIDGET")
+        .help("Only shows the given widget, filling the terminal.")
+        .long_help(
+            "\
+Only shows the given widget, filling the whole terminal and
+ignoring any layout in the config file.  Takes the same widget
+types as layouts (cpu, mem, net, proc, temp, disk, battery),
+optionally suffixed with _graph or _table (ie: cpu_graph).  Use
+custom:<NAME> for a custom widget.  Useful for building your own
+dashboard out of terminal panes.\n\n",
+        );
     let whole_word = Arg::with_name("whole_word")
         .short("W")
         .long("whole_word")
@@ -193,6 +519,36 @@ entire query by default.\n\n",
         );
 
     // All options.  Again, alphabetical order.
+    let filter = Arg::with_name("filter")
+        .long("filter")
+        .takes_value(true)
+        .value_name("QUERY")
+        .help("Starts with process widgets filtered by a search query.")
+        .long_help(
+            "\
+Starts with the search of every process widget set to QUERY,
+which uses the same syntax as the search bar, and overrides any
+default_query from the layout.  It can still be edited or cleared
+once bottom is running.  For example:
+
+    btm --filter \"cpu > 10\"\n\n",
+        );
+    let control_path = Arg::with_name("control_path")
+        .long("control_path")
+        .takes_value(true)
+        .value_name("PATH")
+        .help("Reads control commands from a FIFO at PATH.")
+        .long_help(
+            "\
+Creates a FIFO at PATH, if it does not exist, and reads control
+commands from it, one per line.  Supported commands are freeze,
+unfreeze, toggle-freeze, set-filter <QUERY>, clear-filter,
+switch-tab <N>, and help.  For example:
+
+    echo 'set-filter cpu > 50' > PATH
+
+Only supported on Unix-like systems.\n\n",
+        );
     let config_location = Arg::with_name("config_location")
         .short("C")
         .long("config")
@@ -356,31 +712,63 @@ Defaults to showing the process widget in tree mode.\n\n",
         .arg(fahrenheit)
         .arg(celsius)
         .group(ArgGroup::with_name("TEMPERATURE_TYPE").args(&["kelvin", "fahrenheit", "celsius"]))
+        .arg(app_column)
         .arg(autohide_time)
         .arg(basic)
         .arg(battery)
+        .arg(capabilities)
         .arg(case_sensitive)
+        .arg(compare)
         .arg(config_location)
+        .arg(control_path)
         .arg(color)
         .arg(debug)
+        .arg(debug_log)
+        .arg(max_fps)
         .arg(mem_as_value)
         .arg(default_time_value)
         .arg(default_widget_count)
         .arg(default_widget_type)
         .arg(disable_click)
         .arg(dot_marker)
+        .arg(enable_gpu_memory)
+        .arg(enable_gpu_usage)
+        .arg(filter)
+        .arg(glob)
+        .arg(gpu_memory_rate)
         .arg(group)
         .arg(hide_avg_cpu)
+        .arg(hide_removable_disks)
         .arg(hide_table_gap)
         .arg(hide_time)
+        .arg(hook_command)
+        .arg(hook_interval)
+        .arg(i3bar)
+        .arg(show_graph_stats)
+        .arg(show_process_share)
+        .arg(link_widgets)
         .arg(show_table_scroll_position)
         .arg(left_legend)
+        .arg(namespace_column)
+        .arg(nice_column)
         // .arg(no_write)
+        .arg(oneshot)
+        .arg(persist_data)
+        .arg(process_command)
+        .arg(process_name_source)
         .arg(rate)
         .arg(regex)
+        .arg(rt_column)
+        .arg(security_column)
+        .arg(snapshot_path)
+        .arg(stacked_cpu)
+        .arg(synchronized_output)
         .arg(time_delta)
         .arg(tree)
         .arg(current_usage)
         .arg(use_old_network_legend)
+        .arg(warm_start)
         .arg(whole_word)
+        .arg(widget)
+        .arg(window_titles)
 }
@@ -12,16 +12,26 @@ pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
 pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
 pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
+pub const STATUS_MESSAGE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to show status messages
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+pub const DEFAULT_HOOK_INTERVAL_IN_MILLISECONDS: u64 = 1000;
+// GPU memory usage is slower to get, so it's refreshed less often by default
+pub const DEFAULT_GPU_MEMORY_RATE_IN_MILLISECONDS: u64 = 5000;
 
 // Limits for when we should stop showing table gaps/labels (anything less means not shown)
 pub const TABLE_GAP_HEIGHT_LIMIT: u16 = 7;
 pub const TIME_LABEL_HEIGHT_LIMIT: u16 = 7;
 
+// How many bytes of terminal output to buffer, so that each frame is written all at once
+pub const TERMINAL_BUFFER_CAPACITY: usize = 256 * 1024;
+
+// Legend label for the marker showing where data from a previous session ends
+pub const PREVIOUS_SESSION_LABEL: &str = "Prev. session";
+
 // Side borders
 pub static SIDE_BORDERS: Lazy<tui::widgets::Borders> =
     Lazy::new(|| tui::widgets::Borders::from_bits_truncate(20));
@@ -80,6 +90,7 @@ pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColo
     border_color: Some("#ebdbb2".to_string()),
     highlighted_border_color: Some("#fe8019".to_string()),
     disabled_text_color: Some("#665c54".to_string()),
+    pinned_text_color: Some("#8ec07c".to_string()),
     text_color: Some("#ebdbb2".to_string()),
     selected_text_color: Some("#1d2021".to_string()),
     selected_bg_color: Some("#ebdbb2".to_string()),
@@ -124,6 +135,7 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
     border_color: Some("#3c3836".to_string()),
     highlighted_border_color: Some("#af3a03".to_string()),
     disabled_text_color: Some("#d5c4a1".to_string()),
+    pinned_text_color: Some("#427b58".to_string()),
     text_color: Some("#3c3836".to_string()),
     selected_text_color: Some("#ebdbb2".to_string()),
     selected_bg_color: Some("#3c3836".to_string()),
@@ -135,7 +147,7 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
 });
 
 // Help text
-pub const HELP_CONTENTS_TEXT: [&str; 8] = [
+pub const HELP_CONTENTS_TEXT: [&str; 9] = [
     "Press the corresponding numbers to jump to the section, or scroll:",
     "1 - General",
     "2 - CPU widget",
@@ -144,11 +156,12 @@ pub const HELP_CONTENTS_TEXT: [&str; 8] = [
     "5 - Process sort widget",
     "6 - Battery widget",
     "7 - Basic memory widget",
+    "8 - Disk and temperature widgets",
 ];
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const GENERAL_HELP_TEXT: [&str; 30] = [
+pub const GENERAL_HELP_TEXT: [&str; 32] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
@@ -177,6 +190,8 @@ pub const GENERAL_HELP_TEXT: [&str; 30] = [
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
+    "x                Export the visible range of the selected chart to CSV",
+    "X                Export the visible range of every chart to CSV",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
 ];
@@ -186,9 +201,12 @@ pub const CPU_HELP_TEXT: [&str; 2] = [
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
 ];
 
-pub const PROCESS_HELP_TEXT: [&str; 14] = [
+pub const PROCESS_HELP_TEXT: [&str; 32] = [
     "3 - Process widget",
-    "dd               Kill the selected process",
+    "dd               Kill or send a signal to the selected process",
+    "r                Change the nice value of the selected process",
+    "z                Stop (pause) the selected process",
+    "Z                Continue (resume) the selected process",
     "c                Sort by CPU usage, press again to reverse sorting order",
     "m                Sort by memory usage, press again to reverse sorting order",
     "p                Sort by PID name, press again to reverse sorting order",
@@ -196,16 +214,31 @@ pub const PROCESS_HELP_TEXT: [&str; 14] = [
     "Tab              Group/un-group processes with the same name",
     "Ctrl-f, /        Open process search widget",
     "P                Toggle between showing the full command or just the process name",
+    "Left, Right      Scroll long commands while showing full commands",
     "s, F6            Open process sort widget",
     "I                Invert current sort",
     "%                Toggle between values and percentages for memory usage",
     "t, F5            Toggle tree mode",
     "+, -, click      Collapse/expand a branch while in tree mode",
+    "T                Toggle sectioning processes by state",
+    "+, -, click      Collapse/expand a state section while sectioned by state",
+    "u                Toggle only showing your own processes",
+    "a                Toggle only showing processes using the CPU",
+    "o                Toggle only showing processes with network connections",
+    "!                Toggle only showing suspicious processes",
+    "M                Toggle only showing processes using the camera, microphone, or audio",
+    "v                Toggle grouping processes by the app they belong to",
+    "U                Toggle grouping processes by the user they belong to",
+    "#                Toggle a summary row with the totals of the shown processes",
+    "F                Open the column picker to show, hide, or reorder columns",
+    "l                Lock onto the selected process, keeping it in place as the list changes",
+    "*                Pin/unpin the selected process above the others",
+    "Enter            Show details about the selected process",
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 46] = [
+pub const SEARCH_HELP_TEXT: [&str; 81] = [
     "4 - Process search widget",
-    "Tab              Toggle between searching for PID and name",
+    "Tab              Accept the suggested search keyword",
     "Esc              Close the search widget (retains the filter)",
     "Ctrl-a           Skip to the start of the search query",
     "Ctrl-e           Skip to the end of the search query",
@@ -215,31 +248,66 @@ pub const SEARCH_HELP_TEXT: [&str; 46] = [
     "Alt-c, F1        Toggle matching case",
     "Alt-w, F2        Toggle matching the entire word",
     "Alt-r, F3        Toggle using regex",
+    "Alt-g, F4        Toggle using glob wildcards",
     "Left, Alt-h      Move cursor left",
     "Right, Alt-l     Move cursor right",
+    "Enter            Go back to the processes, keeping the search",
+    "Up, Down         Recall older or newer searches",
     "",
     "Supported search types:",
     "<by name/cmd>    ex: btm",
+    "name             ex: name = btm",
     "pid              ex: pid 825",
+    "                 ex: pid in (825, 1044), pid = 825,1044",
+    "                 ex: pid 1000-2000, pid > 1000",
+    "ppid             ex: ppid = 1",
     "cpu, cpu%        ex: cpu > 4.2",
-    "mem, mem%        ex: mem < 4.2",
+    "mem, mem%        ex: mem < 4.2, mem > 500mb",
     "memb             ex: memb < 100 kb",
     "read, r/s        ex: read >= 1 b",
     "write, w/s       ex: write <= 1 tb",
     "tread, t.read    ex: tread = 1",
     "twrite, t.write  ex: twrite = 1",
-    "state            ex: state = running",
+    "state            ex: state = zombie",
+    "user, uid        ex: user = root",
+    "conns            ex: conns > 0",
+    "threads, nth     ex: threads > 100",
+    "fds              ex: fds > 1000",
+    "nice             ex: nice < 0",
+    "priority, prio   ex: prio <= -2",
+    "time, runtime    ex: time > 5m",
+    "args, cmd        ex: args = \"--config /etc/foo\"",
+    "gpu, gpu%        ex: gpu > 50",
+    "gpumem, gmem     ex: gmem > 1 gb",
+    "context, label   ex: context = httpd_t",
+    "suspicious       ex: suspicious = deleted",
+    "ns, namespace    ex: ns = pid",
+    "exe              ex: exe = /usr/lib/firefox",
+    "cgroup,container ex: cgroup = docker-abc123",
+    "title, window    ex: title = youtube",
+    "media            ex: media = mic",
+    "env, env.<KEY>   ex: env.DISPLAY = :1",
+    "@<alias>         ex: @browsers and cpu > 5",
+    "",
+    "Search modifiers, after a prefix and a ~:",
+    "r, g, l          ex: name~r \"^ssh\", as a regex, glob, or literal",
+    "c, i             ex: name~c Firefox, to match or ignore case",
+    "w                ex: user~w root, to match the entire word",
     "",
     "Comparison operators:",
     "=                ex: cpu = 1",
+    "!=, <>           ex: cpu != 0",
     ">                ex: cpu > 1",
     "<                ex: cpu < 1",
     ">=               ex: cpu >= 1",
     "<=               ex: cpu <= 1",
+    "..               ex: cpu 10..50",
+    "between, and     ex: mem between 100 mb and 1 gb",
     "",
     "Logical operators:",
     "and, &&, <Space> ex: btm and cpu > 1 and mem > 1",
     "or, ||           ex: btm or firefox",
+    "not, !           ex: not firefox, !(cpu > 50)",
     "",
     "Supported units:",
     "B                ex: read > 1 b",
@@ -272,6 +340,19 @@ pub const BASIC_MEM_HELP_TEXT: [&str; 2] = [
     "%                Toggle between values and percentages for memory usage",
 ];
 
+pub const TABLE_HELP_TEXT: [&str; 10] = [
+    "8 - Disk and temperature widgets",
+    "/                Search the table, typed into its title",
+    "Esc, Enter       Stop typing the search (retains it)",
+    "Ctrl-u           Clear the search while typing",
+    "Enter            Show the processes using the selected disk",
+    "",
+    "mount            ex: mount = /home",
+    "used             ex: used > 80",
+    "free, total      ex: free < 10 gb",
+    "temp             ex: temp > 70",
+];
+
 pub static HELP_TEXT: Lazy<Vec<Vec<&'static str>>> = Lazy::new(|| {
     vec![
         HELP_CONTENTS_TEXT.to_vec(),
@@ -282,6 +363,7 @@ pub static HELP_TEXT: Lazy<Vec<Vec<&'static str>>> = Lazy::new(|| {
         SORT_HELP_TEXT.to_vec(),
         BATTERY_HELP_TEXT.to_vec(),
         BASIC_MEM_HELP_TEXT.to_vec(),
+        TABLE_HELP_TEXT.to_vec(),
     ]
 });
 
@@ -370,6 +452,8 @@ pub const OLD_CONFIG_TEXT: &str = r##"# This is a default config file for bottom
 #whole_word = false
 # Whether to make process searching use regex by default.
 #regex = false
+# Whether to make "*", "?", and "[...]" wildcards in process searches by default.
+#glob = false
 # Defaults to Celsius.  Temperature is one of:
 #temperature_type = "k"
 #temperature_type = "f"
@@ -398,6 +482,68 @@ pub const OLD_CONFIG_TEXT: &str = r##"# This is a default config file for bottom
 #mem_as_value = false
 # Show tree mode by default in the processes widget.
 #tree = false
+# Show the min, max, average, and peak values in graph legends.
+#show_graph_stats = false
+# Show how much of the system's CPU and memory usage the processes matching a search make up.
+#show_process_share = false
+# Highlight the cores the selected process may run on in the CPU widget.
+#link_widgets = false
+# Hide removable disks that aren't listed in /etc/fstab.  Only supported on Linux.
+#hide_removable_disks = false
+# Ask the terminal to show each frame all at once, which avoids tearing over slow connections like SSH.
+#synchronized_output = false
+# Show the full command line of each process by default, instead of just its name.
+#process_command = false
+# The most times per second that new data is drawn.  Input is still drawn right away.
+#max_fps = 2
+# Also match searches by name against the titles of each process' windows.  Only supported on Linux with X11 or sway.
+#window_titles = false
+# Stack per-core usage in the CPU graph, instead of drawing overlapping lines.
+#stacked_cpu = false
+# Persist the last-known disk counters and graph data between runs.
+#persist_data = false
+# Restore graph data from the previous session on startup.  Implies persist_data.
+#warm_start = false
+# Where a JSON snapshot of the current metrics is written on SIGUSR1.
+#snapshot_path = "/tmp/bottom_snapshot.json"
+# A command run after each update, which receives the latest metrics as JSON on stdin.
+#hook_command = "~/.config/bottom/hook.sh"
+# The minimum time in milliseconds between hook runs.
+#hook_interval = 1000
+# A FIFO that other programs can write control commands to, like "freeze" or "set-filter cpu>50".
+#control_path = "/tmp/bottom.ctl"
+# Where to get full process names from on Linux, as the kernel cuts them off at 15 characters.
+# One of "exe", "cmdline", or "comm".
+#process_name_source = "exe"
+# Show which app (such as a Flatpak, Snap, or AppImage) each process belongs to in the processes widget.
+#app_column = false
+# Show how much GPU memory each process uses.  NVIDIA GPUs require the "nvidia" feature.
+#enable_gpu_memory = false
+# Show how busy each process keeps the GPU.  Only supported on Linux, for AMD and Intel GPUs.
+#enable_gpu_usage = false
+# How often GPU memory usage is refreshed, in milliseconds.
+#gpu_memory_rate = 5000
+# Show the real-time scheduling policy and priority of processes that use one.  Only supported on Linux.
+#rt_column = false
+# Show the SELinux context or AppArmor profile of each process.  Only supported on Linux.
+#security_column = false
+# Show which namespaces each process doesn't share with the host.  Only supported on Linux.
+#namespace_column = false
+# Show the nice value of each process.  Nice values are only read on Linux.
+#nice_column = false
+# Which process columns to show, and in what order.  Columns left out can still be shown with the
+# column picker (F) or the flags above.
+#process_columns = ["pid", "name", "cpu", "mem", "r/s", "w/s", "t.read", "t.write", "state"]
+# Use the decimal separator and digit grouping of your locale when showing numbers.
+#locale_number_format = false
+# The decimal separator and digit grouping used when showing numbers.  These take priority over the locale.
+#decimal_separator = "."
+#digit_grouping = ""
+# Show every number with this many decimal places, rather than however many suits each value.
+#decimal_places = 1
+# Show sizes and rates in units of 1000 ("si", ie: KB, MB) or 1024 ("binary", ie: KiB, MiB).  This also
+# changes what units like "kb" mean in process searches.
+#byte_units = "binary"
 
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
@@ -435,6 +581,8 @@ pub const OLD_CONFIG_TEXT: &str = r##"# This is a default config file for bottom
 #high_battery_color="green"
 #medium_battery_color="yellow"
 #low_battery_color="red"
+# Represents the colour of processes pinned above the others.
+#pinned_text_color="LightCyan"
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
@@ -481,6 +629,29 @@ pub const OLD_CONFIG_TEXT: &str = r##"# This is a default config file for bottom
 #list = ["cpu", "wifi"]
 #regex = false
 #case_sensitive = false
+
+# Process searches that hide any matching processes from all process widgets:
+#[process_filter]
+#exclude = ["kworker", "state = idle"]
+# Process searches whose matching processes are pinned above the others in all process widgets:
+#pin = ["sshd"]
+
+# Named process searches, which can be used in any search as "@name":
+#[query_aliases]
+#browsers = "firefox or chrome or chromium"
+
+# Other names for process columns, which are shown instead of their own names and can be used in
+# any search in place of the column's prefix, like "load > 50":
+#[column_aliases]
+#load = "cpu"
+
+
+# Thresholds - draws horizontal guide lines on graphs, and marks when they are crossed.  CPU and
+# memory values are percentages, and network values are in bytes per second:
+#[thresholds]
+#cpu = [80.0]
+#mem = [75.0, 90.0]
+#network = [125000000]
 "##;
 
 pub const CONFIG_TOP_HEAD: &str = r##"# This is bottom's config file.
@@ -3,11 +3,16 @@
 use crate::Pid;
 use crate::{
     app::{data_farmer, data_harvester, App, Filter, ProcWidgetState},
-    utils::{self, gen_util::*},
+    query::{Filterable, PrefixType},
+    utils::{self, gen_util::*, number_format::format_number},
 };
-use data_harvester::processes::ProcessSorting;
+use data_harvester::processes::{ProcessSorting, RealtimePolicy, StateSection, SuspiciousReason};
+use data_harvester::{energy::EnergyImpact, media::MediaUsage};
 use indexmap::IndexSet;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Point is of time, data
 type Point = (f64, f64);
@@ -30,13 +35,14 @@ pub struct ConvertedNetworkData {
     pub tx_display: String,
     pub total_rx_display: Option<String>,
     pub total_tx_display: Option<String>,
-    // TODO: [NETWORKING] add min/max/mean of each
-    // min_rx : f64,
-    // max_rx : f64,
-    // mean_rx: f64,
-    // min_tx: f64,
-    // max_tx: f64,
-    // mean_tx: f64,
+}
+
+/// Statistics of a graph's series over the currently visible time window.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct GraphStats {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
 }
 
 // TODO: [REFACTOR] Process data... stuff really needs a rewrite.  Again.
@@ -62,15 +68,62 @@ pub struct ConvertedProcessData {
     pub tw_f64: f64,
     pub process_state: String,
     pub process_char: char,
+    pub uid: Option<u32>,
+    pub user: Option<String>,
+    pub num_connections: u64,
+    pub num_threads: u64,
+    pub num_fds: u64,
+    pub run_time: u64,
+    pub start_time: u64,
+    pub app: Option<String>,
+    pub gpu_mem_usage_bytes: u64,
+    pub gpu_usage_percent: f64,
+    pub rt_policy: Option<RealtimePolicy>,
+    pub rt_priority: u32,
+    pub nice: i64,
+    pub priority: i64,
+    pub security_context: Option<String>,
+    pub suspicious_reason: Option<SuspiciousReason>,
+    pub exe: Option<String>,
+    pub namespaces: Option<String>,
+    pub cgroup: Option<String>,
+    /// The environment of the process as `KEY=value` entries, if it was collected.
+    pub environ: Option<Arc<Vec<String>>>,
+    /// The titles of the windows of the process, if they were collected.
+    pub window_titles: Vec<String>,
+    /// Whether the process is stopped, like by `SIGSTOP`.  For a group, this is whether all of
+    /// its processes are.
+    pub is_stopped: bool,
+    pub media_usage: MediaUsage,
+    pub energy_impact: EnergyImpact,
 
     /// Prefix printed before the process when displayed.
     pub process_description_prefix: Option<String>,
+    /// The state section this entry is the header of, if it is one.
+    pub section_header: Option<StateSection>,
+    /// Whether this entry is pinned above the others, by PID or by a query from the config file.
+    pub is_pinned: bool,
     /// Whether to mark this process entry as disabled (mostly for tree mode).
     pub is_disabled_entry: bool,
     /// Whether this entry is collapsed, hiding all its children (for tree mode).
     pub is_collapsed_entry: bool,
 }
 
+/// Totals of the processes that match the search of a process widget.
+#[derive(Clone, Default, Debug)]
+pub struct ProcessTotals {
+    pub num_processes: usize,
+    pub cpu_percent_usage: f64,
+    pub mem_percent_usage: f64,
+    pub mem_usage_bytes: u64,
+    pub rps_f64: f64,
+    pub wps_f64: f64,
+    /// How much of the CPU usage of all processes these make up, as a percentage.
+    pub cpu_share_percent: f64,
+    /// How much of the memory used by the system these make up, as a percentage.
+    pub mem_share_percent: f64,
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct ConvertedCpuData {
     pub cpu_name: String,
@@ -81,12 +134,34 @@ pub struct ConvertedCpuData {
     pub legend_value: String,
 }
 
-pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
+/// A row of the temperature table, along with the values it shows for searching.
+#[derive(Clone, Debug, Default)]
+pub struct ConvertedTempData {
+    pub name: String,
+    /// In the unit temperatures are shown in.
+    pub temperature: f64,
+    pub row: Vec<String>,
+}
+
+/// A row of the disk table, along with the values it shows for searching.
+#[derive(Clone, Debug, Default)]
+pub struct ConvertedDiskData {
+    pub name: String,
+    pub mount_point: String,
+    pub used_percent: f64,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    /// Whether the disk has gone read-only when it shouldn't have, so that it can be highlighted.
+    pub is_unexpectedly_read_only: bool,
+    pub row: Vec<String>,
+}
+
+pub fn convert_temp_row(app: &App) -> Vec<ConvertedTempData> {
     let current_data = &app.data_collection;
     let temp_type = &app.app_config_fields.temperature_type;
     let temp_filter = &app.filters.temp_filter;
 
-    let mut sensor_vector: Vec<Vec<String>> = current_data
+    let mut sensor_vector: Vec<ConvertedTempData> = current_data
         .temp_harvest
         .iter()
         .filter_map(|temp_harvest| {
@@ -111,15 +186,19 @@ pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
             };
 
             if to_keep {
-                Some(vec![
+                Some(ConvertedTempData {
+                    row: vec![
+                        name.clone(),
+                        format_number(temp_harvest.temperature.ceil().into(), 0)
+                            + match temp_type {
+                                data_harvester::temperature::TemperatureType::Celsius => "C",
+                                data_harvester::temperature::TemperatureType::Kelvin => "K",
+                                data_harvester::temperature::TemperatureType::Fahrenheit => "F",
+                            },
+                    ],
                     name,
-                    (temp_harvest.temperature.ceil() as u64).to_string()
-                        + match temp_type {
-                            data_harvester::temperature::TemperatureType::Celsius => "C",
-                            data_harvester::temperature::TemperatureType::Kelvin => "K",
-                            data_harvester::temperature::TemperatureType::Fahrenheit => "F",
-                        },
-                ])
+                    temperature: temp_harvest.temperature.into(),
+                })
             } else {
                 None
             }
@@ -127,16 +206,22 @@ pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
         .collect();
 
     if sensor_vector.is_empty() {
-        sensor_vector.push(vec!["No Sensors Found".to_string(), "".to_string()]);
+        sensor_vector.push(ConvertedTempData {
+            row: vec!["No Sensors Found".to_string(), "".to_string()],
+            ..ConvertedTempData::default()
+        });
     }
 
     sensor_vector
 }
 
+/// Converts disks into rows of the disk table.  If `hide_removable_disks` is set, removable media
+/// that isn't listed in `/etc/fstab`, and so was likely mounted automatically, is left out.
 pub fn convert_disk_row(
     current_data: &data_farmer::DataCollection, disk_filter: &Option<Filter>,
-) -> Vec<Vec<String>> {
-    let mut disk_vector: Vec<Vec<String>> = Vec::new();
+    hide_removable_disks: bool,
+) -> Vec<ConvertedDiskData> {
+    let mut disk_vector: Vec<ConvertedDiskData> = Vec::new();
 
     current_data
         .disk_harvest
@@ -154,29 +239,217 @@ pub fn convert_disk_row(
             }
         })
         .zip(&current_data.io_labels)
+        .filter(|(disk, _)| {
+            !(hide_removable_disks && disk.is_removable && disk.is_in_fstab != Some(true))
+        })
         .for_each(|(disk, (io_read, io_write))| {
-            let converted_free_space = get_simple_byte_values(disk.free_space, false);
-            let converted_total_space = get_simple_byte_values(disk.total_space, false);
-            disk_vector.push(vec![
-                disk.name.to_string(),
-                disk.mount_point.to_string(),
-                format!(
-                    "{:.0}%",
-                    disk.used_space as f64 / disk.total_space as f64 * 100_f64
-                ),
-                format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1),
-                format!(
-                    "{:.*}{}",
-                    0, converted_total_space.0, converted_total_space.1
-                ),
-                io_read.to_string(),
-                io_write.to_string(),
-            ]);
+            let converted_free_space = get_byte_values(disk.free_space, false, ByteUnits::Si);
+            let converted_total_space = get_byte_values(disk.total_space, false, ByteUnits::Si);
+            let used_percent = disk.used_space as f64 / disk.total_space as f64 * 100_f64;
+            disk_vector.push(ConvertedDiskData {
+                name: disk.name.to_string(),
+                mount_point: disk.mount_point.to_string(),
+                used_percent,
+                free_bytes: disk.free_space,
+                total_bytes: disk.total_space,
+                is_unexpectedly_read_only: disk.is_unexpectedly_read_only,
+                row: vec![
+                    match &disk.composite {
+                        Some(composite) => format!("{} ({})", disk.name, composite.kind),
+                        None if disk.is_removable => format!("{} (removable)", disk.name),
+                        None => disk.name.to_string(),
+                    },
+                    disk.mount_point.to_string(),
+                    get_mount_flags_label(disk),
+                    format!("{}%", format_number(used_percent, 0)),
+                    format!(
+                        "{}{}",
+                        format_number(converted_free_space.0, 0),
+                        converted_free_space.1
+                    ),
+                    format!(
+                        "{}{}",
+                        format_number(converted_total_space.0, 0),
+                        converted_total_space.1
+                    ),
+                    io_read.to_string(),
+                    io_write.to_string(),
+                ],
+            });
+
+            // Members are listed under their disk, and are searched as part of its filesystem.
+            if let Some(composite) = &disk.composite {
+                for (index, member) in composite.members.iter().enumerate() {
+                    let branch = if index + 1 == composite.members.len() {
+                        BRANCH_ENDING
+                    } else {
+                        BRANCH_SPLIT
+                    };
+                    let (member_read, member_write) = current_data
+                        .io_member_labels
+                        .get(&member.name)
+                        .cloned()
+                        .unwrap_or_else(|| ("N/A".to_string(), "N/A".to_string()));
+                    disk_vector.push(ConvertedDiskData {
+                        name: member.name.clone(),
+                        mount_point: disk.mount_point.to_string(),
+                        used_percent,
+                        free_bytes: disk.free_space,
+                        total_bytes: member.total_space.unwrap_or_default(),
+                        is_unexpectedly_read_only: false,
+                        row: vec![
+                            format!("{}{} {}", branch, BRANCH_HORIZONTAL, member.name),
+                            String::new(),
+                            String::new(),
+                            String::new(),
+                            String::new(),
+                            member
+                                .total_space
+                                .map(|total_space| {
+                                    let converted_total_space =
+                                        get_byte_values(total_space, false, ByteUnits::Si);
+                                    format!(
+                                        "{}{}",
+                                        format_number(converted_total_space.0, 0),
+                                        converted_total_space.1
+                                    )
+                                })
+                                .unwrap_or_default(),
+                            member_read,
+                            member_write,
+                        ],
+                    });
+                }
+            }
         });
 
     disk_vector
 }
 
+/// Returns the flags column of a disk, which lists its notable mount options, and marks it if it
+/// was mounted without being listed in `/etc/fstab`.
+fn get_mount_flags_label(disk: &data_harvester::disks::DiskHarvest) -> String {
+    let mut flags = disk.mount_flags.clone();
+    if disk.is_in_fstab == Some(false) {
+        flags.push("nofstab".to_string());
+    }
+    flags.join(",")
+}
+
+/// Calculates the statistics of the points within the last `display_time` milliseconds.  Each
+/// value is passed through `to_value` first, so scaled series (ie: the network graph's log scale)
+/// can be measured in their original units.  Returns `None` if no points are visible.
+pub fn get_graph_stats(
+    points: &[Point], display_time: u64, to_value: fn(f64) -> f64,
+) -> Option<GraphStats> {
+    let visible_values = points
+        .iter()
+        .filter(|(time, _)| *time >= -(display_time as f64))
+        .map(|(_, value)| to_value(*value));
+
+    let mut stats: Option<GraphStats> = None;
+    let mut count = 0;
+    for value in visible_values {
+        count += 1;
+        stats = Some(match stats {
+            Some(stats) => GraphStats {
+                min: stats.min.min(value),
+                max: stats.max.max(value),
+                avg: stats.avg + value,
+            },
+            None => GraphStats {
+                min: value,
+                max: value,
+                avg: value,
+            },
+        });
+    }
+
+    stats.map(|stats| GraphStats {
+        avg: stats.avg / count as f64,
+        ..stats
+    })
+}
+
+/// Formats graph statistics and the all-time peak as a legend entry, ie:
+/// `RAM min 20% avg 35% max 60% peak 80%`.
+pub fn format_graph_stats(
+    label: &str, stats: &GraphStats, peak: f64, format_value: fn(f64) -> String,
+) -> String {
+    format!(
+        "{} min {} avg {} max {} peak {}",
+        label,
+        format_value(stats.min),
+        format_value(stats.avg),
+        format_value(stats.max),
+        format_value(peak.max(stats.max))
+    )
+}
+
+/// Stacks the usage of each core on top of the previous cores.  Values are divided by the
+/// number of cores, so the last series is the total usage, from 0 to 100%.
+pub fn get_stacked_cpu_points(cores: &[ConvertedCpuData]) -> Vec<Vec<Point>> {
+    let num_cores = cores.len() as f64;
+    let mut stacked_points: Vec<Vec<Point>> = Vec::with_capacity(cores.len());
+
+    for core in cores {
+        let points = match stacked_points.last() {
+            Some(below) => core
+                .cpu_data
+                .iter()
+                .zip(below)
+                .map(|((time, value), (_, below_value))| (*time, below_value + value / num_cores))
+                .collect(),
+            None => core
+                .cpu_data
+                .iter()
+                .map(|(time, value)| (*time, value / num_cores))
+                .collect(),
+        };
+        stacked_points.push(points);
+    }
+
+    stacked_points
+}
+
+/// Returns the given percentile of some sorted values, using the nearest-rank method.
+pub fn get_percentile(sorted_values: &[f64], percentile: f64) -> Option<f64> {
+    if sorted_values.is_empty() {
+        return None;
+    }
+
+    let rank = (percentile / 100.0 * sorted_values.len() as f64).ceil() as usize;
+    sorted_values
+        .get(rank.saturating_sub(1))
+        .or_else(|| sorted_values.last())
+        .copied()
+}
+
+/// Splits sorted values into `num_buckets` equally wide buckets between the smallest and largest
+/// value.  Returns the lower bound and number of values of each bucket.
+pub fn get_histogram_buckets(sorted_values: &[f64], num_buckets: usize) -> Vec<(f64, u64)> {
+    let (min, max) = match (sorted_values.first(), sorted_values.last()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => return vec![],
+    };
+    let num_buckets = if max > min { num_buckets.max(1) } else { 1 };
+    let bucket_width = (max - min) / num_buckets as f64;
+
+    let mut buckets: Vec<(f64, u64)> = (0..num_buckets)
+        .map(|index| (min + bucket_width * index as f64, 0))
+        .collect();
+    for value in sorted_values {
+        let index = if bucket_width > 0.0 {
+            (((value - min) / bucket_width) as usize).min(num_buckets - 1)
+        } else {
+            0
+        };
+        buckets[index].1 += 1;
+    }
+
+    buckets
+}
+
 pub fn convert_cpu_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<ConvertedCpuData> {
@@ -223,7 +496,7 @@ pub fn convert_cpu_data_points(
             }
 
             if let Some(cpu_data) = cpu_data_vector.get_mut(itx) {
-                cpu_data.legend_value = format!("{:.0}%", cpu.round());
+                cpu_data.legend_value = format!("{}%", format_number(cpu.round(), 0));
                 cpu_data.cpu_data.push((-time_from_start, *cpu));
             }
         }
@@ -243,6 +516,64 @@ pub fn convert_cpu_data_points(
     extended_vec
 }
 
+/// An alert placed on a graph, at its time offset.
+#[derive(Clone, Debug)]
+pub struct ConvertedAlert {
+    pub graph: data_farmer::AlertGraph,
+    pub offset: f64,
+    pub name: String,
+}
+
+/// Returns the time offsets of every alert up to the current (or frozen) time, oldest first.
+pub fn convert_alert_markers(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<ConvertedAlert> {
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    current_data
+        .alert_events
+        .iter()
+        .filter(|event| event.instant <= current_time)
+        .map(|event| ConvertedAlert {
+            graph: event.graph,
+            offset: -(current_time.duration_since(event.instant).as_millis() as f64).floor(),
+            name: event.name.clone(),
+        })
+        .collect()
+}
+
+/// Returns the time offset at which data from a previous session ends, if we were warm-started.
+pub fn convert_previous_session_offset(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Option<f64> {
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    current_data
+        .previous_session_instant
+        .map(|previous_session_instant| {
+            -(current_time
+                .saturating_duration_since(previous_session_instant)
+                .as_millis() as f64)
+                .floor()
+        })
+}
+
 pub fn convert_mem_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<Point> {
@@ -293,37 +624,65 @@ pub fn convert_swap_data_points(
     result
 }
 
+/// Formats used and total memory, given in mebibytes, as gigabytes, ie: `1.2GB/7.8GB`.  If byte
+/// units were set, the matching unit is used.
+fn format_mem_fraction(used_in_mb: u64, total_in_mb: u64) -> String {
+    let (divisor, unit) = match get_byte_units() {
+        None => (1024.0, "GB"),
+        Some(ByteUnits::Binary) => (1024.0, "GiB"),
+        Some(ByteUnits::Si) => (1_000_000_000.0 / 1_048_576.0, "GB"),
+    };
+
+    format!(
+        "{}{}/{}{}",
+        format_number(used_in_mb as f64 / divisor, 1),
+        unit,
+        format_number(total_in_mb as f64 / divisor, 1),
+        unit
+    )
+}
+
 pub fn convert_mem_labels(
     current_data: &data_farmer::DataCollection,
 ) -> (String, String, String, String) {
     (
         format!(
-            "{:3.0}%",
-            match current_data.memory_harvest.mem_total_in_mb {
-                0 => 0.0,
-                _ =>
-                    current_data.memory_harvest.mem_used_in_mb as f64 * 100.0
-                        / current_data.memory_harvest.mem_total_in_mb as f64,
-            }
+            "{:>3}%",
+            format_number(
+                match current_data.memory_harvest.mem_total_in_mb {
+                    0 => 0.0,
+                    _ =>
+                        current_data.memory_harvest.mem_used_in_mb as f64 * 100.0
+                            / current_data.memory_harvest.mem_total_in_mb as f64,
+                },
+                0
+            )
         ),
         format!(
-            "   {:.1}GB/{:.1}GB",
-            current_data.memory_harvest.mem_used_in_mb as f64 / 1024.0,
-            (current_data.memory_harvest.mem_total_in_mb as f64 / 1024.0)
+            "   {}",
+            format_mem_fraction(
+                current_data.memory_harvest.mem_used_in_mb,
+                current_data.memory_harvest.mem_total_in_mb
+            )
         ),
         format!(
-            "{:3.0}%",
-            match current_data.swap_harvest.mem_total_in_mb {
-                0 => 0.0,
-                _ =>
-                    current_data.swap_harvest.mem_used_in_mb as f64 * 100.0
-                        / current_data.swap_harvest.mem_total_in_mb as f64,
-            }
+            "{:>3}%",
+            format_number(
+                match current_data.swap_harvest.mem_total_in_mb {
+                    0 => 0.0,
+                    _ =>
+                        current_data.swap_harvest.mem_used_in_mb as f64 * 100.0
+                            / current_data.swap_harvest.mem_total_in_mb as f64,
+                },
+                0
+            )
         ),
         format!(
-            "   {:.1}GB/{:.1}GB",
-            current_data.swap_harvest.mem_used_in_mb as f64 / 1024.0,
-            (current_data.swap_harvest.mem_total_in_mb as f64 / 1024.0)
+            "   {}",
+            format_mem_fraction(
+                current_data.swap_harvest.mem_used_in_mb,
+                current_data.swap_harvest.mem_total_in_mb
+            )
         ),
     )
 }
@@ -361,27 +720,42 @@ pub fn convert_network_data_points(
 ) -> ConvertedNetworkData {
     let (rx, tx) = get_rx_tx_data_points(current_data, is_frozen);
 
-    let total_rx_converted_result: (f64, String);
-    let rx_converted_result: (f64, String);
-    let total_tx_converted_result: (f64, String);
-    let tx_converted_result: (f64, String);
-
-    rx_converted_result = get_exact_byte_values(current_data.network_harvest.rx, false);
-    total_rx_converted_result = get_exact_byte_values(current_data.network_harvest.total_rx, false);
-
-    tx_converted_result = get_exact_byte_values(current_data.network_harvest.tx, false);
-    total_tx_converted_result = get_exact_byte_values(current_data.network_harvest.total_tx, false);
+    let rx_converted_result: (f64, String) =
+        get_byte_values(current_data.network_harvest.rx, false, ByteUnits::Binary);
+    let total_rx_converted_result: (f64, String) = get_byte_values(
+        current_data.network_harvest.total_rx,
+        false,
+        ByteUnits::Binary,
+    );
+
+    let tx_converted_result: (f64, String) =
+        get_byte_values(current_data.network_harvest.tx, false, ByteUnits::Binary);
+    let total_tx_converted_result: (f64, String) = get_byte_values(
+        current_data.network_harvest.total_tx,
+        false,
+        ByteUnits::Binary,
+    );
 
     if need_four_points {
-        let rx_display = format!("{:.*}{}", 1, rx_converted_result.0, rx_converted_result.1);
+        let rx_display = format!(
+            "{}{}",
+            format_number(rx_converted_result.0, 1),
+            rx_converted_result.1
+        );
         let total_rx_display = Some(format!(
-            "{:.*}{}",
-            1, total_rx_converted_result.0, total_rx_converted_result.1
+            "{}{}",
+            format_number(total_rx_converted_result.0, 1),
+            total_rx_converted_result.1
         ));
-        let tx_display = format!("{:.*}{}", 1, tx_converted_result.0, tx_converted_result.1);
+        let tx_display = format!(
+            "{}{}",
+            format_number(tx_converted_result.0, 1),
+            tx_converted_result.1
+        );
         let total_tx_display = Some(format!(
-            "{:.*}{}",
-            1, total_tx_converted_result.0, total_tx_converted_result.1
+            "{}{}",
+            format_number(total_tx_converted_result.0, 1),
+            total_tx_converted_result.1
         ));
         ConvertedNetworkData {
             rx,
@@ -394,18 +768,28 @@ pub fn convert_network_data_points(
     } else {
         let rx_display = format!(
             "RX: {:<9} All: {:<9}",
-            format!("{:.1}{:3}", rx_converted_result.0, rx_converted_result.1),
             format!(
-                "{:.1}{:3}",
-                total_rx_converted_result.0, total_rx_converted_result.1
+                "{}{:3}",
+                format_number(rx_converted_result.0, 1),
+                rx_converted_result.1
+            ),
+            format!(
+                "{}{:3}",
+                format_number(total_rx_converted_result.0, 1),
+                total_rx_converted_result.1
             )
         );
         let tx_display = format!(
             "TX: {:<9} All: {:<9}",
-            format!("{:.1}{:3}", tx_converted_result.0, tx_converted_result.1),
             format!(
-                "{:.1}{:3}",
-                total_tx_converted_result.0, total_tx_converted_result.1
+                "{}{:3}",
+                format_number(tx_converted_result.0, 1),
+                tx_converted_result.1
+            ),
+            format!(
+                "{}{:3}",
+                format_number(total_tx_converted_result.0, 1),
+                total_tx_converted_result.1
             )
         );
 
@@ -443,17 +827,24 @@ pub fn convert_process_data(
         existing_converted_process_data.keys().copied().collect();
 
     for process in &current_data.process_harvest {
-        let converted_rps = get_exact_byte_values(process.read_bytes_per_sec, false);
-        let converted_wps = get_exact_byte_values(process.write_bytes_per_sec, false);
-        let converted_total_read = get_exact_byte_values(process.total_read_bytes, false);
-        let converted_total_write = get_exact_byte_values(process.total_write_bytes, false);
-
-        let read_per_sec = format!("{:.*}{}/s", 0, converted_rps.0, converted_rps.1);
-        let write_per_sec = format!("{:.*}{}/s", 0, converted_wps.0, converted_wps.1);
-        let total_read = format!("{:.*}{}", 0, converted_total_read.0, converted_total_read.1);
+        let converted_rps = get_byte_values(process.read_bytes_per_sec, false, ByteUnits::Binary);
+        let converted_wps = get_byte_values(process.write_bytes_per_sec, false, ByteUnits::Binary);
+        let converted_total_read =
+            get_byte_values(process.total_read_bytes, false, ByteUnits::Binary);
+        let converted_total_write =
+            get_byte_values(process.total_write_bytes, false, ByteUnits::Binary);
+
+        let read_per_sec = format!("{}{}/s", format_number(converted_rps.0, 0), converted_rps.1);
+        let write_per_sec = format!("{}{}/s", format_number(converted_wps.0, 0), converted_wps.1);
+        let total_read = format!(
+            "{}{}",
+            format_number(converted_total_read.0, 0),
+            converted_total_read.1
+        );
         let total_write = format!(
-            "{:.*}{}",
-            0, converted_total_write.0, converted_total_write.1
+            "{}{}",
+            format_number(converted_total_write.0, 0),
+            converted_total_write.1
         );
 
         if let Some(process_entry) = existing_converted_process_data.get_mut(&process.pid) {
@@ -466,7 +857,8 @@ pub fn convert_process_data(
                 process_entry.cpu_percent_usage = process.cpu_usage_percent;
                 process_entry.mem_percent_usage = process.mem_usage_percent;
                 process_entry.mem_usage_bytes = process.mem_usage_bytes;
-                process_entry.mem_usage_str = get_exact_byte_values(process.mem_usage_bytes, false);
+                process_entry.mem_usage_str =
+                    get_byte_values(process.mem_usage_bytes, false, ByteUnits::Binary);
                 process_entry.group_pids = vec![process.pid];
                 process_entry.read_per_sec = read_per_sec;
                 process_entry.write_per_sec = write_per_sec;
@@ -478,6 +870,30 @@ pub fn convert_process_data(
                 process_entry.tw_f64 = process.total_write_bytes as f64;
                 process_entry.process_state = process.process_state.to_owned();
                 process_entry.process_char = process.process_state_char;
+                process_entry.uid = process.uid;
+                process_entry.user = process.user.clone();
+                process_entry.num_connections = process.num_connections;
+                process_entry.num_threads = process.num_threads;
+                process_entry.num_fds = process.num_fds;
+                process_entry.run_time = process.run_time;
+                process_entry.start_time = process.start_time;
+                process_entry.app = process.app.clone();
+                process_entry.gpu_mem_usage_bytes = process.gpu_mem_bytes;
+                process_entry.gpu_usage_percent = process.gpu_usage_percent;
+                process_entry.rt_policy = process.rt_policy;
+                process_entry.rt_priority = process.rt_priority;
+                process_entry.nice = process.nice;
+                process_entry.priority = process.priority;
+                process_entry.security_context = process.security_context.clone();
+                process_entry.suspicious_reason = process.suspicious_reason;
+                process_entry.exe = process.exe.clone();
+                process_entry.namespaces = process.namespaces.clone();
+                process_entry.cgroup = process.cgroup.clone();
+                process_entry.environ = process.environ.clone();
+                process_entry.window_titles = process.window_titles.clone();
+                process_entry.is_stopped = process.process_state_char == STOPPED_STATE_CHAR;
+                process_entry.media_usage = process.media_usage;
+                process_entry.energy_impact = process.energy_impact;
                 process_entry.process_description_prefix = None;
                 process_entry.is_disabled_entry = false;
             } else {
@@ -491,7 +907,11 @@ pub fn convert_process_data(
                     cpu_percent_usage: process.cpu_usage_percent,
                     mem_percent_usage: process.mem_usage_percent,
                     mem_usage_bytes: process.mem_usage_bytes,
-                    mem_usage_str: get_exact_byte_values(process.mem_usage_bytes, false),
+                    mem_usage_str: get_byte_values(
+                        process.mem_usage_bytes,
+                        false,
+                        ByteUnits::Binary,
+                    ),
                     group_pids: vec![process.pid],
                     read_per_sec,
                     write_per_sec,
@@ -503,7 +923,33 @@ pub fn convert_process_data(
                     tw_f64: process.total_write_bytes as f64,
                     process_state: process.process_state.to_owned(),
                     process_char: process.process_state_char,
+                    uid: process.uid,
+                    user: process.user.clone(),
+                    num_connections: process.num_connections,
+                    num_threads: process.num_threads,
+                    num_fds: process.num_fds,
+                    run_time: process.run_time,
+                    start_time: process.start_time,
+                    app: process.app.clone(),
+                    gpu_mem_usage_bytes: process.gpu_mem_bytes,
+                    gpu_usage_percent: process.gpu_usage_percent,
+                    rt_policy: process.rt_policy,
+                    rt_priority: process.rt_priority,
+                    nice: process.nice,
+                    priority: process.priority,
+                    security_context: process.security_context.clone(),
+                    suspicious_reason: process.suspicious_reason,
+                    exe: process.exe.clone(),
+                    namespaces: process.namespaces.clone(),
+                    cgroup: process.cgroup.clone(),
+                    environ: process.environ.clone(),
+                    window_titles: process.window_titles.clone(),
+                    is_stopped: process.process_state_char == STOPPED_STATE_CHAR,
+                    media_usage: process.media_usage,
+                    energy_impact: process.energy_impact,
                     process_description_prefix: None,
+                    section_header: None,
+                    is_pinned: false,
                     is_disabled_entry: false,
                     is_collapsed_entry: false,
                 };
@@ -520,7 +966,11 @@ pub fn convert_process_data(
                     cpu_percent_usage: process.cpu_usage_percent,
                     mem_percent_usage: process.mem_usage_percent,
                     mem_usage_bytes: process.mem_usage_bytes,
-                    mem_usage_str: get_exact_byte_values(process.mem_usage_bytes, false),
+                    mem_usage_str: get_byte_values(
+                        process.mem_usage_bytes,
+                        false,
+                        ByteUnits::Binary,
+                    ),
                     group_pids: vec![process.pid],
                     read_per_sec,
                     write_per_sec,
@@ -532,7 +982,33 @@ pub fn convert_process_data(
                     tw_f64: process.total_write_bytes as f64,
                     process_state: process.process_state.to_owned(),
                     process_char: process.process_state_char,
+                    uid: process.uid,
+                    user: process.user.clone(),
+                    num_connections: process.num_connections,
+                    num_threads: process.num_threads,
+                    num_fds: process.num_fds,
+                    run_time: process.run_time,
+                    start_time: process.start_time,
+                    app: process.app.clone(),
+                    gpu_mem_usage_bytes: process.gpu_mem_bytes,
+                    gpu_usage_percent: process.gpu_usage_percent,
+                    rt_policy: process.rt_policy,
+                    rt_priority: process.rt_priority,
+                    nice: process.nice,
+                    priority: process.priority,
+                    security_context: process.security_context.clone(),
+                    suspicious_reason: process.suspicious_reason,
+                    exe: process.exe.clone(),
+                    namespaces: process.namespaces.clone(),
+                    cgroup: process.cgroup.clone(),
+                    environ: process.environ.clone(),
+                    window_titles: process.window_titles.clone(),
+                    is_stopped: process.process_state_char == STOPPED_STATE_CHAR,
+                    media_usage: process.media_usage,
+                    energy_impact: process.energy_impact,
                     process_description_prefix: None,
+                    section_header: None,
+                    is_pinned: false,
                     is_disabled_entry: false,
                     is_collapsed_entry: false,
                 },
@@ -546,6 +1022,44 @@ pub fn convert_process_data(
     })
 }
 
+/// Splits an already sorted process list into sections by state, each led by a header entry
+/// with the number of processes in it.  Collapsed sections only show their header.
+pub fn section_process_data_by_state(
+    process_data: Vec<ConvertedProcessData>, collapsed_sections: &HashSet<StateSection>,
+) -> Vec<ConvertedProcessData> {
+    let mut sections: BTreeMap<StateSection, Vec<ConvertedProcessData>> = BTreeMap::new();
+    for process in process_data {
+        sections
+            .entry(StateSection::from_state_char(process.process_char))
+            .or_default()
+            .push(process);
+    }
+
+    let mut sectioned_process_data = Vec::new();
+    for (section, processes) in sections {
+        let is_collapsed = collapsed_sections.contains(&section);
+        sectioned_process_data.push(ConvertedProcessData {
+            name: section.to_string(),
+            command: section.to_string(),
+            process_description_prefix: Some(format!(
+                "{} {} ({})",
+                if is_collapsed { "+" } else { "-" },
+                section,
+                processes.len()
+            )),
+            section_header: Some(section),
+            is_collapsed_entry: is_collapsed,
+            ..ConvertedProcessData::default()
+        });
+
+        if !is_collapsed {
+            sectioned_process_data.extend(processes);
+        }
+    }
+
+    sectioned_process_data
+}
+
 const BRANCH_ENDING: char = '└';
 const BRANCH_VERTICAL: char = '│';
 const BRANCH_SPLIT: char = '├';
@@ -573,9 +1087,7 @@ pub fn tree_process_data(
 
     filtered_process_data.iter().for_each(|process| {
         // Create a mapping for the process if it DNE.
-        parent_child_mapping
-            .entry(process.pid)
-            .or_insert_with(IndexSet::new);
+        parent_child_mapping.entry(process.pid).or_default();
         pid_process_mapping.insert(process.pid, process);
 
         if process.is_collapsed_entry {
@@ -587,7 +1099,7 @@ pub fn tree_process_data(
             orphan_set.remove(&process.pid);
             parent_child_mapping
                 .entry(ppid)
-                .or_insert_with(IndexSet::new)
+                .or_default()
                 .insert(process.pid);
         }
     });
@@ -596,7 +1108,7 @@ pub fn tree_process_data(
     // if their parents DNE in our pid to process mapping...
     let old_orphan_set = orphan_set.clone();
     old_orphan_set.iter().for_each(|pid| {
-        if pid_process_mapping.get(pid).is_none() {
+        if !pid_process_mapping.contains_key(pid) {
             // DNE!  Promote the mapped children and remove the current parent...
             orphan_set.remove(pid);
             if let Some(children) = parent_child_mapping.get(pid) {
@@ -605,6 +1117,40 @@ pub fn tree_process_data(
         }
     });
 
+    // Collapsed processes hide everything under them, so show and sort them by the usage of
+    // their whole subtree instead.
+    let collapsed_totals: Vec<ConvertedProcessData> = collapsed_set
+        .iter()
+        .filter_map(|pid| {
+            let mut process = (*pid_process_mapping.get(pid)?).clone();
+            let mut seen_pids: HashSet<Pid> = HashSet::new();
+            let mut pids_to_add: Vec<Pid> = parent_child_mapping
+                .get(pid)
+                .map(|children| children.iter().copied().collect())
+                .unwrap_or_default();
+            while let Some(child_pid) = pids_to_add.pop() {
+                // PIDs can be reused, so guard against a child somehow also being an ancestor.
+                if child_pid == *pid || !seen_pids.insert(child_pid) {
+                    continue;
+                }
+                if let Some(child) = pid_process_mapping.get(&child_pid) {
+                    process.cpu_percent_usage += child.cpu_percent_usage;
+                    process.mem_percent_usage += child.mem_percent_usage;
+                    process.mem_usage_bytes += child.mem_usage_bytes;
+                }
+                if let Some(grandchildren) = parent_child_mapping.get(&child_pid) {
+                    pids_to_add.extend(grandchildren.iter().copied());
+                }
+            }
+            process.mem_usage_str =
+                get_byte_values(process.mem_usage_bytes, false, ByteUnits::Binary);
+            Some(process)
+        })
+        .collect();
+    for process in &collapsed_totals {
+        pid_process_mapping.insert(process.pid, process);
+    }
+
     // Turn the parent-child mapping into a "list" via DFS...
     let mut pids_to_explore: VecDeque<Pid> = orphan_set.into_iter().collect();
     let mut explored_pids: Vec<Pid> = vec![];
@@ -780,6 +1326,76 @@ pub fn tree_process_data(
                     is_sort_descending,
                 )
             }),
+            ProcessSorting::App => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.app.as_ref().map(|app| app.to_lowercase()),
+                    &b.1.app.as_ref().map(|app| app.to_lowercase()),
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::GpuPercent => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.gpu_usage_percent,
+                        b.1.gpu_usage_percent,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::GpuMem => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.gpu_mem_usage_bytes,
+                        b.1.gpu_mem_usage_bytes,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::SecurityContext => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.security_context,
+                    &b.1.security_context,
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::Namespaces => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(&a.1.namespaces, &b.1.namespaces, is_sort_descending)
+            }),
+            ProcessSorting::RtPriority => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        (a.1.rt_policy.is_some(), a.1.rt_priority),
+                        (b.1.rt_policy.is_some(), b.1.rt_priority),
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::Nice => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.1.nice, b.1.nice, is_sort_descending)
+            }),
+            ProcessSorting::StartTime => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.1.start_time, b.1.start_time, is_sort_descending)
+            }),
+            ProcessSorting::Energy => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.1.energy_impact,
+                    b.1.energy_impact,
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::Media => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.1.media_usage, b.1.media_usage, is_sort_descending)
+            }),
+            ProcessSorting::User => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    get_user_name(a.1).to_lowercase(),
+                    get_user_name(b.1).to_lowercase(),
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::RunTime => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.1.run_time, b.1.run_time, is_sort_descending)
+            }),
             ProcessSorting::Count => {
                 // Should never occur in this case.
             }
@@ -878,9 +1494,10 @@ pub fn tree_process_data(
             Some(process) => {
                 let mut p = process.clone();
                 p.process_description_prefix = Some(format!(
-                    "{}{}{}",
+                    "{}{}{}{}",
                     prefix,
                     if p.is_collapsed_entry { "+ " } else { "" }, // I do the + sign thing here because I'm kinda too lazy to do it in the prefix, tbh.
+                    get_name_markers(&p),
                     if is_using_command {
                         &p.command
                     } else {
@@ -894,67 +1511,318 @@ pub fn tree_process_data(
         .collect::<Vec<_>>()
 }
 
+/// Shown before the name of processes that look suspicious.
+const SUSPICIOUS_MARKER: &str = "[!] ";
+
+/// Shown before the name of processes that are stopped.
+const STOPPED_MARKER: &str = "⏸ ";
+
+/// The state of processes that are stopped, like by `SIGSTOP`.
+const STOPPED_STATE_CHAR: char = 'T';
+
+/// Returns the markers shown before the name of a process.
+/// Returns the name of the user that owns a process, or its UID if the name isn't known.
+pub fn get_user_name(process: &ConvertedProcessData) -> String {
+    match (&process.user, process.uid) {
+        (Some(user), _) => user.clone(),
+        (None, Some(uid)) => uid.to_string(),
+        (None, None) => String::default(),
+    }
+}
+
+fn get_name_markers(process: &ConvertedProcessData) -> String {
+    format!(
+        "{}{}",
+        if process.is_stopped {
+            STOPPED_MARKER
+        } else {
+            ""
+        },
+        if process.suspicious_reason.is_some() {
+            SUSPICIOUS_MARKER
+        } else {
+            ""
+        }
+    )
+}
+
 pub fn stringify_process_data(
     proc_widget_state: &ProcWidgetState, finalized_process_data: &[ConvertedProcessData],
 ) -> Vec<(Vec<(String, Option<String>)>, bool)> {
-    let is_proc_widget_grouped = proc_widget_state.is_grouped;
-    let is_using_command = proc_widget_state.is_using_command;
-    let is_tree = proc_widget_state.is_tree_mode;
-    let mem_enabled = proc_widget_state.columns.is_enabled(&ProcessSorting::Mem);
+    let enabled_columns = proc_widget_state
+        .columns
+        .get_enabled_columns()
+        .collect::<Vec<_>>();
 
     finalized_process_data
         .iter()
         .map(|process| {
+            if process.section_header.is_some() {
+                // Section headers go where the name would, or first if names are hidden.
+                let mut row = vec![(String::default(), None); enabled_columns.len()];
+                let name_index = enabled_columns
+                    .iter()
+                    .position(|column| {
+                        matches!(
+                            column,
+                            ProcessSorting::ProcessName | ProcessSorting::Command
+                        )
+                    })
+                    .unwrap_or(0);
+                if let Some(name_cell) = row.get_mut(name_index) {
+                    name_cell.0 = process
+                        .process_description_prefix
+                        .clone()
+                        .unwrap_or_default();
+                }
+                return (row, false);
+            }
+
+            // Cells line up with the enabled headers, so build them in the same order.
+            let row = enabled_columns
+                .iter()
+                .map(|column| stringify_process_cell(proc_widget_state, process, column))
+                .collect();
+
+            // Stopped processes are dimmed like filtered out ones, as they aren't doing anything.
+            (row, process.is_disabled_entry || process.is_stopped)
+        })
+        .collect()
+}
+
+/// Returns what to show for a process in the given column, along with a shorter alternative to
+/// show if it doesn't fit, if there is one.
+fn stringify_process_cell(
+    proc_widget_state: &ProcWidgetState, process: &ConvertedProcessData, column: &ProcessSorting,
+) -> (String, Option<String>) {
+    match column {
+        ProcessSorting::Pid => (process.pid.to_string(), None),
+        ProcessSorting::Count => (process.group_pids.len().to_string(), None),
+        ProcessSorting::ProcessName | ProcessSorting::Command => {
+            let name = if proc_widget_state.is_using_command {
+                &process.command
+            } else {
+                &process.name
+            };
+            let shown_name = get_scrolled_command(name, proc_widget_state.command_scroll_offset);
+
             (
-                vec![
-                    (
-                        if is_proc_widget_grouped {
-                            process.group_pids.len().to_string()
-                        } else {
-                            process.pid.to_string()
-                        },
-                        None,
-                    ),
-                    (
-                        if is_tree {
-                            if let Some(prefix) = &process.process_description_prefix {
-                                prefix.clone()
-                            } else {
-                                String::default()
-                            }
-                        } else if is_using_command {
-                            process.command.clone()
-                        } else {
-                            process.name.clone()
-                        },
-                        None,
-                    ),
-                    (format!("{:.1}%", process.cpu_percent_usage), None),
-                    (
-                        if mem_enabled {
-                            format!("{:.0}{}", process.mem_usage_str.0, process.mem_usage_str.1)
-                        } else {
-                            format!("{:.1}%", process.mem_percent_usage)
-                        },
-                        None,
-                    ),
-                    (process.read_per_sec.clone(), None),
-                    (process.write_per_sec.clone(), None),
-                    (process.total_read.clone(), None),
-                    (process.total_write.clone(), None),
-                    (
-                        process.process_state.clone(),
-                        Some(process.process_char.to_string()),
-                    ),
-                ],
-                process.is_disabled_entry,
+                if proc_widget_state.is_tree_mode {
+                    let description = process
+                        .process_description_prefix
+                        .clone()
+                        .unwrap_or_default();
+
+                    // Only scroll the name, not the branches before it.
+                    match description.strip_suffix(name.as_str()) {
+                        Some(branches) => format!("{}{}", branches, shown_name),
+                        None => description,
+                    }
+                } else {
+                    format!("{}{}", get_name_markers(process), shown_name)
+                },
+                None,
             )
+        }
+        ProcessSorting::CpuPercent => (
+            format!("{}%", format_number(process.cpu_percent_usage, 1)),
+            None,
+        ),
+        ProcessSorting::Mem => (
+            format!(
+                "{}{}",
+                format_number(process.mem_usage_str.0, 0),
+                process.mem_usage_str.1
+            ),
+            None,
+        ),
+        ProcessSorting::MemPercent => (
+            format!("{}%", format_number(process.mem_percent_usage, 1)),
+            None,
+        ),
+        ProcessSorting::ReadPerSecond => (process.read_per_sec.clone(), None),
+        ProcessSorting::WritePerSecond => (process.write_per_sec.clone(), None),
+        ProcessSorting::TotalRead => (process.total_read.clone(), None),
+        ProcessSorting::TotalWrite => (process.total_write.clone(), None),
+        ProcessSorting::State => (
+            process.process_state.clone(),
+            Some(process.process_char.to_string()),
+        ),
+        ProcessSorting::App => (process.app.clone().unwrap_or_default(), None),
+        ProcessSorting::GpuPercent => (
+            format!("{}%", format_number(process.gpu_usage_percent, 1)),
+            None,
+        ),
+        ProcessSorting::GpuMem => {
+            let (gpu_mem, gpu_mem_unit) =
+                get_byte_values(process.gpu_mem_usage_bytes, false, ByteUnits::Binary);
+            (
+                format!("{}{}", format_number(gpu_mem, 0), gpu_mem_unit),
+                None,
+            )
+        }
+        ProcessSorting::RtPriority => (
+            match process.rt_policy {
+                Some(RealtimePolicy::Deadline) => RealtimePolicy::Deadline.to_string(),
+                Some(rt_policy) => format!("{} {}", rt_policy, process.rt_priority),
+                None => String::default(),
+            },
+            None,
+        ),
+        ProcessSorting::Nice => (process.nice.to_string(), None),
+        ProcessSorting::SecurityContext => {
+            (process.security_context.clone().unwrap_or_default(), None)
+        }
+        ProcessSorting::Namespaces => (process.namespaces.clone().unwrap_or_default(), None),
+        ProcessSorting::StartTime => (get_start_time_string(process.start_time), None),
+        ProcessSorting::RunTime => (get_humanized_duration(process.run_time), None),
+        ProcessSorting::Media => (process.media_usage.to_string(), None),
+        ProcessSorting::User => (get_user_name(process), None),
+        ProcessSorting::Energy => (
+            match process.energy_impact.watts {
+                Some(watts) => format!("{}W", format_number(watts, 1)),
+                None => format_number(process.energy_impact.score, 1),
+            },
+            None,
+        ),
+    }
+}
+
+/// Shows when a process started like `ps` does: the time if it was today, the date if it was this
+/// year, and otherwise the year.
+fn get_start_time_string(start_time: u64) -> String {
+    use chrono::{Datelike, TimeZone};
+
+    match chrono::Local.timestamp_opt(start_time as i64, 0).single() {
+        Some(start_time) => {
+            let now = chrono::Local::now();
+            if start_time.date() == now.date() {
+                start_time.format("%H:%M").to_string()
+            } else if start_time.year() == now.year() {
+                start_time.format("%b %d").to_string()
+            } else {
+                start_time.format("%Y").to_string()
+            }
+        }
+        None => String::default(),
+    }
+}
+
+/// Skips the given number of characters at the start of a command, marking that some were skipped.
+fn get_scrolled_command(command: &str, scroll_offset: usize) -> Cow<'_, str> {
+    if scroll_offset == 0 {
+        Cow::Borrowed(command)
+    } else {
+        Cow::Owned(format!(
+            "…{}",
+            UnicodeSegmentation::graphemes(command, true)
+                .skip(scroll_offset)
+                .collect::<String>()
+        ))
+    }
+}
+
+/// Adds up the given processes, skipping disabled ones, which are only there to keep the process
+/// tree together.
+pub fn get_process_totals(filtered_process_data: &[ConvertedProcessData]) -> ProcessTotals {
+    filtered_process_data
+        .iter()
+        .filter(|process| !process.is_disabled_entry)
+        .fold(ProcessTotals::default(), |mut totals, process| {
+            totals.num_processes += 1;
+            totals.cpu_percent_usage += process.cpu_percent_usage;
+            totals.mem_percent_usage += process.mem_percent_usage;
+            totals.mem_usage_bytes += process.mem_usage_bytes;
+            totals.rps_f64 += process.rps_f64;
+            totals.wps_f64 += process.wps_f64;
+            totals
         })
-        .collect()
 }
 
+/// Works out how much of the system's usage the processes with the given totals make up.  CPU
+/// usage is compared against all processes, as that is in the same terms regardless of whether
+/// process CPU usage is out of the current usage or the whole CPU.
+pub fn convert_process_share(
+    totals: &mut ProcessTotals, current_data: &data_farmer::DataCollection,
+    all_process_data: &HashMap<Pid, ConvertedProcessData>,
+) {
+    let all_cpu_percent_usage: f64 = all_process_data
+        .values()
+        .map(|process| process.cpu_percent_usage)
+        .sum();
+    totals.cpu_share_percent = if all_cpu_percent_usage > 0.0 {
+        (totals.cpu_percent_usage / all_cpu_percent_usage * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+
+    let memory = &current_data.memory_harvest;
+    let used_mem_percent = if memory.mem_total_in_mb > 0 {
+        memory.mem_used_in_mb as f64 / memory.mem_total_in_mb as f64 * 100.0
+    } else {
+        0.0
+    };
+    totals.mem_share_percent = if used_mem_percent > 0.0 {
+        (totals.mem_percent_usage / used_mem_percent * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+}
+
+/// Turns process totals into a row lined up with the name, CPU, memory, and I/O rate columns of
+/// the process table.
+pub fn stringify_process_totals(
+    proc_widget_state: &ProcWidgetState, totals: &ProcessTotals,
+) -> (Vec<(String, Option<String>)>, bool) {
+    let (mem_usage, mem_unit) = get_byte_values(totals.mem_usage_bytes, false, ByteUnits::Binary);
+    let (rps, rps_unit) = get_byte_values(totals.rps_f64 as u64, false, ByteUnits::Binary);
+    let (wps, wps_unit) = get_byte_values(totals.wps_f64 as u64, false, ByteUnits::Binary);
+
+    let row = proc_widget_state
+        .columns
+        .get_enabled_columns()
+        .map(|column| match column {
+            ProcessSorting::ProcessName | ProcessSorting::Command => (
+                format!(
+                    "Total of {} process{}",
+                    totals.num_processes,
+                    if totals.num_processes == 1 { "" } else { "es" }
+                ),
+                Some(format!("Total ({})", totals.num_processes)),
+            ),
+            ProcessSorting::CpuPercent => (
+                format!("{}%", format_number(totals.cpu_percent_usage, 1)),
+                None,
+            ),
+            ProcessSorting::Mem => (format!("{}{}", format_number(mem_usage, 0), mem_unit), None),
+            ProcessSorting::MemPercent => (
+                format!("{}%", format_number(totals.mem_percent_usage, 1)),
+                None,
+            ),
+            ProcessSorting::ReadPerSecond => {
+                (format!("{}{}/s", format_number(rps, 0), rps_unit), None)
+            }
+            ProcessSorting::WritePerSecond => {
+                (format!("{}{}/s", format_number(wps, 0), wps_unit), None)
+            }
+            _ => (String::default(), None),
+        })
+        .collect();
+
+    (row, false)
+}
+
+/// The name of the group of processes that don't belong to any app, when grouping by app.
+pub const BACKGROUND_PROCESSES_NAME: &str = "Background processes";
+
+/// The name of the group of processes whose owner isn't known, when grouping by user.
+pub const UNKNOWN_USER_NAME: &str = "Unknown user";
+
+/// Groups processes by name or command, or by the app or user they belong to if
+/// `is_grouped_by_app` or `is_grouped_by_user` is set.
 pub fn group_process_data(
-    single_process_data: &[ConvertedProcessData], is_using_command: bool,
+    single_process_data: &[ConvertedProcessData], is_using_command: bool, is_grouped_by_app: bool,
+    is_grouped_by_user: bool,
 ) -> Vec<ConvertedProcessData> {
     #[derive(Clone, Default, Debug)]
     struct SingleProcessData {
@@ -968,47 +1836,126 @@ pub fn group_process_data(
         pub total_read: f64,
         pub total_write: f64,
         pub process_state: String,
+        pub uid: Option<u32>,
+        pub user: Option<String>,
+        pub num_connections: u64,
+        pub num_threads: u64,
+        pub num_fds: u64,
+        pub run_time: u64,
+        pub start_time: u64,
+        pub app: Option<String>,
+        pub gpu_mem_usage_bytes: u64,
+        pub gpu_usage_percent: f64,
+        pub rt_policy: Option<RealtimePolicy>,
+        pub rt_priority: u32,
+        pub nice: i64,
+        pub priority: i64,
+        pub security_context: Option<String>,
+        pub suspicious_reason: Option<SuspiciousReason>,
+        pub exe: Option<String>,
+        pub namespaces: Option<String>,
+        pub cgroup: Option<String>,
+        pub environ: Option<Arc<Vec<String>>>,
+        pub window_titles: Vec<String>,
+        pub is_stopped: bool,
+        pub media_usage: MediaUsage,
+        pub energy_impact: EnergyImpact,
     }
 
     let mut grouped_hashmap: HashMap<String, SingleProcessData> = std::collections::HashMap::new();
 
     single_process_data.iter().for_each(|process| {
         let entry = grouped_hashmap
-            .entry(if is_using_command {
+            .entry(if is_grouped_by_app {
+                process
+                    .app
+                    .clone()
+                    .unwrap_or_else(|| BACKGROUND_PROCESSES_NAME.to_string())
+            } else if is_grouped_by_user {
+                Some(get_user_name(process))
+                    .filter(|user_name| !user_name.is_empty())
+                    .unwrap_or_else(|| UNKNOWN_USER_NAME.to_string())
+            } else if is_using_command {
                 process.command.to_string()
             } else {
                 process.name.to_string()
             })
             .or_insert(SingleProcessData {
                 pid: process.pid,
+                app: process.app.clone(),
+                // Only a user's own processes are grouped together when grouping by user.
+                uid: process.uid.filter(|_| is_grouped_by_user),
+                user: process.user.clone().filter(|_| is_grouped_by_user),
+                security_context: process.security_context.clone(),
+                exe: process.exe.clone(),
+                namespaces: process.namespaces.clone(),
+                cgroup: process.cgroup.clone(),
+                environ: process.environ.clone(),
+                nice: process.nice,
+                priority: process.priority,
+                start_time: process.start_time,
+                is_stopped: true,
                 ..SingleProcessData::default()
             });
 
-        (*entry).cpu_percent_usage += process.cpu_percent_usage;
-        (*entry).mem_percent_usage += process.mem_percent_usage;
-        (*entry).mem_usage_bytes += process.mem_usage_bytes;
-        (*entry).group_pids.push(process.pid);
-        (*entry).read_per_sec += process.rps_f64;
-        (*entry).write_per_sec += process.wps_f64;
-        (*entry).total_read += process.tr_f64;
-        (*entry).total_write += process.tw_f64;
+        entry.cpu_percent_usage += process.cpu_percent_usage;
+        entry.mem_percent_usage += process.mem_percent_usage;
+        entry.mem_usage_bytes += process.mem_usage_bytes;
+        entry.group_pids.push(process.pid);
+        entry.read_per_sec += process.rps_f64;
+        entry.write_per_sec += process.wps_f64;
+        entry.total_read += process.tr_f64;
+        entry.total_write += process.tw_f64;
+        entry.num_connections += process.num_connections;
+        entry.num_threads += process.num_threads;
+        entry.num_fds += process.num_fds;
+        entry.run_time = entry.run_time.max(process.run_time);
+        entry.start_time = entry.start_time.min(process.start_time);
+        entry
+            .window_titles
+            .extend(process.window_titles.iter().cloned());
+        entry.is_stopped = entry.is_stopped && process.is_stopped;
+        entry.media_usage.merge(process.media_usage);
+        entry.energy_impact.add(process.energy_impact);
+        entry.gpu_mem_usage_bytes += process.gpu_mem_usage_bytes;
+        entry.gpu_usage_percent += process.gpu_usage_percent;
+        // Show the most urgent real-time process of the group, so it isn't hidden by the rest.
+        if process.rt_policy.is_some()
+            && (entry.rt_policy.is_none() || process.rt_priority > entry.rt_priority)
+        {
+            entry.rt_policy = process.rt_policy;
+            entry.rt_priority = process.rt_priority;
+        }
+        entry.suspicious_reason = entry.suspicious_reason.or(process.suspicious_reason);
+        // Like real-time processes, show the most favoured process of the group.
+        entry.nice = entry.nice.min(process.nice);
+        entry.priority = entry.priority.min(process.priority);
     });
 
     grouped_hashmap
         .iter()
         .map(|(identifier, process_details)| {
             let p = process_details.clone();
-            let converted_rps = get_exact_byte_values(p.read_per_sec as u64, false);
-            let converted_wps = get_exact_byte_values(p.write_per_sec as u64, false);
-            let converted_total_read = get_exact_byte_values(p.total_read as u64, false);
-            let converted_total_write = get_exact_byte_values(p.total_write as u64, false);
-
-            let read_per_sec = format!("{:.*}{}/s", 0, converted_rps.0, converted_rps.1);
-            let write_per_sec = format!("{:.*}{}/s", 0, converted_wps.0, converted_wps.1);
-            let total_read = format!("{:.*}{}", 0, converted_total_read.0, converted_total_read.1);
+            let converted_rps = get_byte_values(p.read_per_sec as u64, false, ByteUnits::Binary);
+            let converted_wps = get_byte_values(p.write_per_sec as u64, false, ByteUnits::Binary);
+            let converted_total_read =
+                get_byte_values(p.total_read as u64, false, ByteUnits::Binary);
+            let converted_total_write =
+                get_byte_values(p.total_write as u64, false, ByteUnits::Binary);
+
+            let read_per_sec =
+                format!("{}{}/s", format_number(converted_rps.0, 0), converted_rps.1);
+            let write_per_sec =
+                format!("{}{}/s", format_number(converted_wps.0, 0), converted_wps.1);
+            let total_read = format!(
+                "{}{}",
+                format_number(converted_total_read.0, 0),
+                converted_total_read.1
+            );
             let total_write = format!(
-                "{:.*}{}",
-                0, converted_total_write.0, converted_total_write.1
+                "{}{}",
+                format_number(converted_total_write.0, 0),
+                converted_total_write.1
             );
 
             ConvertedProcessData {
@@ -1020,7 +1967,7 @@ pub fn group_process_data(
                 cpu_percent_usage: p.cpu_percent_usage,
                 mem_percent_usage: p.mem_percent_usage,
                 mem_usage_bytes: p.mem_usage_bytes,
-                mem_usage_str: get_exact_byte_values(p.mem_usage_bytes, false),
+                mem_usage_str: get_byte_values(p.mem_usage_bytes, false, ByteUnits::Binary),
                 group_pids: p.group_pids,
                 read_per_sec,
                 write_per_sec,
@@ -1032,7 +1979,33 @@ pub fn group_process_data(
                 tw_f64: p.total_write,
                 process_state: p.process_state,
                 process_description_prefix: None,
+                section_header: None,
                 process_char: char::default(),
+                uid: p.uid,
+                user: p.user,
+                num_connections: p.num_connections,
+                num_threads: p.num_threads,
+                num_fds: p.num_fds,
+                run_time: p.run_time,
+                start_time: p.start_time,
+                app: p.app,
+                gpu_mem_usage_bytes: p.gpu_mem_usage_bytes,
+                gpu_usage_percent: p.gpu_usage_percent,
+                rt_policy: p.rt_policy,
+                rt_priority: p.rt_priority,
+                nice: p.nice,
+                priority: p.priority,
+                security_context: p.security_context,
+                suspicious_reason: p.suspicious_reason,
+                exe: p.exe,
+                namespaces: p.namespaces,
+                cgroup: p.cgroup,
+                environ: p.environ,
+                window_titles: p.window_titles,
+                is_stopped: p.is_stopped,
+                media_usage: p.media_usage,
+                energy_impact: p.energy_impact,
+                is_pinned: false,
                 is_disabled_entry: false,
                 is_collapsed_entry: false,
             }
@@ -1050,7 +2023,10 @@ pub fn convert_battery_harvest(
         .map(|(itx, battery_harvest)| ConvertedBatteryData {
             battery_name: format!("Battery {}", itx),
             charge_percentage: battery_harvest.charge_percent,
-            watt_consumption: format!("{:.2}W", battery_harvest.power_consumption_rate_watts),
+            watt_consumption: format!(
+                "{}W",
+                format_number(battery_harvest.power_consumption_rate_watts, 2)
+            ),
             duration_until_empty: if let Some(secs_till_empty) = battery_harvest.secs_until_empty {
                 let time = chrono::Duration::seconds(secs_till_empty);
                 let num_minutes = time.num_minutes() - time.num_hours() * 60;
@@ -1083,7 +2059,162 @@ pub fn convert_battery_harvest(
             } else {
                 None
             },
-            health: format!("{:.2}%", battery_harvest.health_percent),
+            health: format!("{}%", format_number(battery_harvest.health_percent, 2)),
         })
         .collect()
 }
+
+/// A process as seen by a process widget, which matches names against the full command instead
+/// if it is showing commands, and also against the titles of its windows if asked to.
+pub struct ProcessTarget<'a> {
+    pub process: &'a ConvertedProcessData,
+    pub is_using_command: bool,
+    pub is_searching_window_titles: bool,
+}
+
+impl Filterable for ProcessTarget<'_> {
+    fn is_string_match(&self, prefix_type: &PrefixType, r: &regex::Regex) -> bool {
+        let process = self.process;
+        match prefix_type {
+            PrefixType::Name => {
+                r.is_match(if self.is_using_command {
+                    process.command.as_str()
+                } else {
+                    process.name.as_str()
+                }) || (self.is_searching_window_titles
+                    && process.window_titles.iter().any(|title| r.is_match(title)))
+            }
+            PrefixType::Pid => r.is_match(process.pid.to_string().as_str()),
+            PrefixType::Command => r.is_match(process.command.as_str()),
+            PrefixType::SecurityContext => process
+                .security_context
+                .as_ref()
+                .is_some_and(|security_context| r.is_match(security_context)),
+            PrefixType::Suspicious => process
+                .suspicious_reason
+                .is_some_and(|reason| r.is_match(&reason.to_string())),
+            PrefixType::Namespace => process
+                .namespaces
+                .as_ref()
+                .is_some_and(|namespaces| r.is_match(namespaces)),
+            PrefixType::Exe => process.exe.as_ref().is_some_and(|exe| r.is_match(exe)),
+            PrefixType::Cgroup => process
+                .cgroup
+                .as_ref()
+                .is_some_and(|cgroup| r.is_match(cgroup)),
+            PrefixType::Media => process.media_usage.names().any(|name| r.is_match(name)),
+            PrefixType::WindowTitle => process.window_titles.iter().any(|title| r.is_match(title)),
+            PrefixType::Env(None) => process
+                .environ
+                .as_ref()
+                .is_some_and(|environ| environ.iter().any(|entry| r.is_match(entry))),
+            PrefixType::Env(Some(key)) => process.environ.as_ref().is_some_and(|environ| {
+                environ.iter().any(|entry| {
+                    entry
+                        .strip_prefix(key.as_str())
+                        .and_then(|rest| rest.strip_prefix('='))
+                        .is_some_and(|value| r.is_match(value))
+                })
+            }),
+            PrefixType::State => {
+                r.is_match(process.process_state.as_str())
+                    || r.is_match(get_state_name(process.process_char))
+            }
+            PrefixType::User => {
+                process.user.as_ref().is_some_and(|user| r.is_match(user))
+                    || process
+                        .uid
+                        .is_some_and(|uid| r.is_match(uid.to_string().as_str()))
+            }
+            _ => false,
+        }
+    }
+
+    fn get_number(&self, prefix_type: &PrefixType) -> Option<f64> {
+        let process = self.process;
+        match prefix_type {
+            PrefixType::PCpu => Some(process.cpu_percent_usage),
+            PrefixType::PMem => Some(process.mem_percent_usage),
+            PrefixType::MemBytes => Some(process.mem_usage_bytes as f64),
+            PrefixType::Rps => Some(process.rps_f64),
+            PrefixType::Wps => Some(process.wps_f64),
+            PrefixType::TRead => Some(process.tr_f64),
+            PrefixType::TWrite => Some(process.tw_f64),
+            PrefixType::Connections => Some(process.num_connections as f64),
+            PrefixType::Threads => Some(process.num_threads as f64),
+            PrefixType::Fds => Some(process.num_fds as f64),
+            PrefixType::Nice => Some(process.nice as f64),
+            PrefixType::Priority => Some(process.priority as f64),
+            PrefixType::RunTime => Some(process.run_time as f64),
+            PrefixType::GpuMem => Some(process.gpu_mem_usage_bytes as f64),
+            PrefixType::GpuUsage => Some(process.gpu_usage_percent),
+            PrefixType::Pid => Some(process.pid as f64),
+            PrefixType::Ppid => process.ppid.map(|ppid| ppid as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Disks are searched with:
+///
+/// - Names: No prefix required.  Matches the name of the disk or where it is mounted.
+/// - Mount point: Use prefix `mount`, can use regex, match word, or case.
+/// - Used: Use prefix `used`.  Can compare, in percent.
+/// - Free space: Use prefix `free`.  Can compare, and supports byte units.
+/// - Total space: Use prefix `total`.  Can compare, and supports byte units.
+impl Filterable for ConvertedDiskData {
+    fn is_string_match(&self, prefix_type: &PrefixType, r: &regex::Regex) -> bool {
+        match prefix_type {
+            PrefixType::Name => r.is_match(&self.name) || r.is_match(&self.mount_point),
+            PrefixType::Mount => r.is_match(&self.mount_point),
+            _ => false,
+        }
+    }
+
+    fn get_number(&self, prefix_type: &PrefixType) -> Option<f64> {
+        match prefix_type {
+            PrefixType::UsedPercent => Some(self.used_percent),
+            PrefixType::FreeBytes => Some(self.free_bytes as f64),
+            PrefixType::TotalBytes => Some(self.total_bytes as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Temperature sensors are searched with:
+///
+/// - Names: No prefix required.  Matches the name of the sensor.
+/// - Temperature: Use prefix `temp`.  Can compare, in the unit temperatures are shown in.
+impl Filterable for ConvertedTempData {
+    fn is_string_match(&self, prefix_type: &PrefixType, r: &regex::Regex) -> bool {
+        match prefix_type {
+            PrefixType::Name => r.is_match(&self.name),
+            _ => false,
+        }
+    }
+
+    fn get_number(&self, prefix_type: &PrefixType) -> Option<f64> {
+        match prefix_type {
+            PrefixType::Temperature => Some(self.temperature),
+            _ => None,
+        }
+    }
+}
+
+/// Returns a common name for a process state character, as platforms name states differently
+/// (ie: a running process is "Runnable" on Linux).
+fn get_state_name(state_char: char) -> &'static str {
+    match state_char {
+        'R' => "running",
+        'S' => "sleeping",
+        'D' => "disk sleep",
+        'T' => "stopped",
+        't' => "tracing stop",
+        'Z' => "zombie",
+        'X' => "dead",
+        'I' => "idle",
+        'W' => "paging",
+        'P' => "parked",
+        _ => "unknown",
+    }
+}
@@ -7,10 +7,11 @@ extern crate log;
 
 use std::{
     boxed::Box,
+    collections::HashSet,
     fs,
-    io::{stdout, Write},
-    panic::PanicInfo,
-    path::PathBuf,
+    io::{stdout, BufWriter, Stdout, Write},
+    panic::PanicHookInfo,
+    path::{Path, PathBuf},
     sync::Arc,
     sync::Condvar,
     sync::Mutex,
@@ -19,6 +20,7 @@ use std::{
 };
 
 use crossterm::{
+    cursor::Show,
     event::{poll, read, DisableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent},
     execute,
     style::Print,
@@ -28,7 +30,7 @@ use crossterm::{
 use app::{
     data_harvester::{self, processes::ProcessSorting},
     layout_manager::{UsedWidgets, WidgetDirection},
-    App,
+    App, AppAction,
 };
 use constants::*;
 use data_conversion::*;
@@ -37,15 +39,19 @@ use utils::error;
 
 pub mod app;
 pub mod utils {
+    pub mod crash_report;
     pub mod error;
     pub mod gen_util;
     pub mod logging;
+    pub mod number_format;
+    pub mod shutdown;
 }
 pub mod canvas;
 pub mod clap;
 pub mod constants;
 pub mod data_conversion;
 pub mod options;
+pub mod query;
 
 #[cfg(target_family = "windows")]
 pub type Pid = usize;
@@ -57,26 +63,77 @@ pub type Pid = libc::pid_t;
 pub enum BottomEvent<I, J> {
     KeyInput(I),
     MouseInput(J),
+    /// The terminal was resized, so everything should be laid out again right away.
+    Resize,
     Update(Box<data_harvester::Data>),
+    Control(app::AppAction),
     Clean,
 }
 
+/// Waits up to `timeout` for the next event.  Input is kept on its own channel and always handled
+/// before anything else that is waiting, so keys and mouse events are never stuck behind data
+/// updates.
+pub fn receive_event<I, J>(
+    input_receiver: &crossbeam_channel::Receiver<BottomEvent<I, J>>,
+    receiver: &crossbeam_channel::Receiver<BottomEvent<I, J>>, timeout: Duration,
+) -> Option<BottomEvent<I, J>> {
+    if let Ok(event) = input_receiver.try_recv() {
+        return Some(event);
+    }
+
+    crossbeam_channel::select! {
+        recv(input_receiver) -> event => event.ok(),
+        recv(receiver) -> event => event.ok(),
+        default(timeout) => None,
+    }
+}
+
 #[derive(Debug)]
 pub enum ThreadControlEvent {
     Reset,
     UpdateConfig(Box<app::AppConfigFields>),
     UpdateUsedWidgets(Box<UsedWidgets>),
     UpdateUpdateTime(u64),
+    UpdateCollectConnections(bool),
+    UpdateCollectAppNames(bool),
+    UpdateCollectGpuStats(bool),
+    UpdateCollectSecurityContexts(bool),
+    UpdateCollectNamespaces(bool),
+    UpdateCollectFds(bool),
+    UpdateCollectEnv(bool),
+    UpdateCollectCgroups(bool),
+    UpdateCollectWindowTitles(bool),
+    UpdateCollectMedia(bool),
+    UpdateCollectEnergy(bool),
+    /// Find the processes using the filesystem mounted at the given mount point.
+    FindProcessesOnMount(String),
+}
+
+/// Tells the collection thread to start or stop collecting something that is only collected while
+/// used, if whether it is used changed.  Returns whether it just started being used.
+pub fn update_collection(
+    is_using: bool, is_collecting: &mut bool, update_event: fn(bool) -> ThreadControlEvent,
+    collection_thread_ctrl_sender: &std::sync::mpsc::Sender<ThreadControlEvent>,
+) -> bool {
+    if is_using == *is_collecting {
+        return false;
+    }
+
+    *is_collecting = is_using;
+    collection_thread_ctrl_sender
+        .send(update_event(is_using))
+        .ok();
+    is_using
 }
 
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
     match event {
         MouseEvent::ScrollUp(_x, _y, _modifiers) => app.handle_scroll_up(),
         MouseEvent::ScrollDown(_x, _y, _modifiers) => app.handle_scroll_down(),
-        MouseEvent::Down(button, x, y, _modifiers) => {
+        MouseEvent::Down(button, x, y, _modifiers)
             // debug!("Button down: {:?}, x: {}, y: {}", button, x, y);
 
-            if !app.app_config_fields.disable_click {
+            if !app.app_config_fields.disable_click => {
                 match button {
                     crossterm::event::MouseButton::Left => {
                         // Trigger left click widget activity
@@ -86,7 +143,6 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                     _ => {}
                 }
             }
-        }
         _ => {}
     };
 }
@@ -103,7 +159,10 @@ pub fn handle_key_event_or_break(
 
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
+        if event.code == KeyCode::Char('q')
+            && !app.is_in_search_widget()
+            && !app.is_typing_table_search()
+        {
             return true;
         }
         match event.code {
@@ -122,6 +181,7 @@ pub fn handle_key_event_or_break(
             KeyCode::F(1) => app.toggle_ignore_case(),
             KeyCode::F(2) => app.toggle_search_whole_word(),
             KeyCode::F(3) => app.toggle_search_regex(),
+            KeyCode::F(4) => app.toggle_search_glob(),
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort(),
             _ => {}
@@ -133,6 +193,7 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_ignore_case(),
                 KeyCode::Char('w') | KeyCode::Char('W') => app.toggle_search_whole_word(),
                 KeyCode::Char('r') | KeyCode::Char('R') => app.toggle_search_regex(),
+                KeyCode::Char('g') | KeyCode::Char('G') => app.toggle_search_glob(),
                 KeyCode::Char('h') => app.on_left_key(),
                 KeyCode::Char('l') => app.on_right_key(),
                 _ => {}
@@ -148,10 +209,8 @@ pub fn handle_key_event_or_break(
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
                 KeyCode::Up => app.move_widget_selection(&WidgetDirection::Up),
                 KeyCode::Down => app.move_widget_selection(&WidgetDirection::Down),
-                KeyCode::Char('r') => {
-                    if reset_sender.send(ThreadControlEvent::Reset).is_ok() {
-                        app.reset();
-                    }
+                KeyCode::Char('r') if reset_sender.send(ThreadControlEvent::Reset).is_ok() => {
+                    app.reset();
                 }
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
                 KeyCode::Char('e') => app.skip_cursor_end(),
@@ -235,12 +294,34 @@ pub fn create_or_get_config(config_path: &Option<PathBuf>) -> error::Result<Conf
     }
 }
 
+/// Tells terminals that support it to hold off on showing output until the frame is done.
+const BEGIN_SYNCHRONIZED_UPDATE: &[u8] = b"\x1b[?2026h";
+const END_SYNCHRONIZED_UPDATE: &[u8] = b"\x1b[?2026l";
+
+/// Draws a frame.  Only the cells that changed since the last frame are written, and output is
+/// buffered until the frame is done, so each frame reaches the terminal in one write.
 pub fn try_drawing(
-    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
-    app: &mut App, painter: &mut canvas::Painter, is_debug: bool,
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<BufWriter<Stdout>>>,
+    app: &mut App, painter: &mut canvas::Painter, debug_log_path: Option<&Path>,
 ) -> error::Result<()> {
-    if let Err(err) = painter.draw_data(terminal, app) {
-        cleanup_terminal(terminal, is_debug)?;
+    let synchronized_output = app.app_config_fields.synchronized_output;
+    if synchronized_output {
+        terminal
+            .backend_mut()
+            .write_all(BEGIN_SYNCHRONIZED_UPDATE)?;
+    }
+
+    let mut result = painter.draw_data(terminal, app);
+    if synchronized_output && result.is_ok() {
+        result = terminal
+            .backend_mut()
+            .write_all(END_SYNCHRONIZED_UPDATE)
+            .and_then(|_| terminal.backend_mut().flush())
+            .map_err(|err| err.into());
+    }
+
+    if let Err(err) = result {
+        cleanup_terminal(terminal, debug_log_path)?;
         return Err(err);
     }
 
@@ -248,8 +329,8 @@ pub fn try_drawing(
 }
 
 pub fn cleanup_terminal(
-    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
-    is_debug: bool,
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<BufWriter<Stdout>>>,
+    debug_log_path: Option<&Path>,
 ) -> error::Result<()> {
     disable_raw_mode()?;
     execute!(
@@ -259,17 +340,18 @@ pub fn cleanup_terminal(
     )?;
     terminal.show_cursor()?;
 
-    if is_debug {
-        let mut tmp_dir = std::env::temp_dir();
-        tmp_dir.push("bottom_debug.log");
-        println!("Your debug file is located at {:?}", tmp_dir.as_os_str());
+    if let Some(debug_log_path) = debug_log_path {
+        println!(
+            "Your debug file is located at {:?}",
+            debug_log_path.as_os_str()
+        );
     }
 
     Ok(())
 }
 
 /// Based on https://github.com/Rigellute/spotify-tui/blob/master/src/main.rs
-pub fn panic_hook(panic_info: &PanicInfo<'_>) {
+pub fn panic_hook(panic_info: &PanicHookInfo<'_>) {
     let mut stdout = stdout();
 
     let msg = match panic_info.payload().downcast_ref::<&'static str>() {
@@ -280,22 +362,60 @@ pub fn panic_hook(panic_info: &PanicInfo<'_>) {
         },
     };
 
+    let location = panic_info
+        .location()
+        .map(|location| location.to_string())
+        .unwrap_or_else(|| "<unknown>".to_string());
     let stacktrace: String = format!("{:?}", backtrace::Backtrace::new());
 
-    disable_raw_mode().unwrap();
-    execute!(stdout, DisableMouseCapture, LeaveAlternateScreen).unwrap();
+    // Restore the terminal first, and don't bail if any of this fails - we're already
+    // panicking, and we really don't want to leave the user with a broken shell.
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout, DisableMouseCapture, LeaveAlternateScreen, Show);
+
+    error!("Panicked at '{}', {}", msg, location);
+    let crash_report_path = utils::crash_report::write_crash_report(msg, &location, &stacktrace);
 
     // Print stack trace.  Must be done after!
-    execute!(
+    let _ = execute!(
         stdout,
         Print(format!(
-            "thread '<unnamed>' panicked at '{}', {}\n\r{}",
+            "thread '{}' panicked at '{}', {}\n\r{}",
+            thread::current().name().unwrap_or("<unnamed>"),
             msg,
-            panic_info.location().unwrap(),
+            location,
             stacktrace
         )),
-    )
-    .unwrap();
+    );
+
+    if let Some(crash_report_path) = crash_report_path {
+        let _ = execute!(
+            stdout,
+            Print(format!(
+                "\n\rA crash report was written to {:?}.\n\r",
+                crash_report_path.as_os_str()
+            )),
+        );
+    }
+}
+
+/// Writes a JSON snapshot of the current metrics, ie: when SIGUSR1 is received.
+pub fn dump_metric_snapshot(app: &App) {
+    let path = app
+        .app_config_fields
+        .snapshot_path
+        .clone()
+        .unwrap_or_else(|| {
+            let mut path = utils::logging::get_data_dir();
+            path.push("snapshot.json");
+            path
+        });
+
+    let snapshot = app::metrics::MetricSnapshot::from_data_collection(&app.data_collection);
+    match app::metrics::write_snapshot_json(&snapshot, &path) {
+        Ok(()) => debug!("Wrote a metric snapshot to {:?}.", path),
+        Err(err) => error!("Unable to write a metric snapshot to {:?}: {}", path, err),
+    }
 }
 
 pub fn handle_force_redraws(app: &mut App) {
@@ -326,6 +446,13 @@ pub fn handle_force_redraws(app: &mut App) {
         app.canvas_data.network_data_tx = tx;
         app.net_state.force_update = None;
     }
+
+    if app.data_collection.previous_session_instant.is_some() {
+        app.canvas_data.previous_session_offset =
+            convert_previous_session_offset(&app.data_collection, app.is_frozen);
+    }
+
+    app.canvas_data.alert_markers = convert_alert_markers(&app.data_collection, app.is_frozen);
 }
 
 #[allow(clippy::needless_collect)]
@@ -347,65 +474,111 @@ pub fn update_all_process_lists(app: &mut App) {
 }
 
 fn update_final_process_list(app: &mut App, widget_id: u64) {
-    let process_states = match app.proc_state.widget_states.get(&widget_id) {
-        Some(process_state) => Some((
-            process_state
-                .process_search_state
-                .search_state
-                .is_invalid_or_blank_search(),
-            process_state.is_using_command,
-            process_state.is_grouped,
-            process_state.is_tree_mode,
-        )),
-        None => None,
-    };
+    let process_states = app
+        .proc_state
+        .widget_states
+        .get(&widget_id)
+        .map(|process_state| {
+            (
+                process_state.is_using_command,
+                process_state.is_grouped,
+                process_state.is_grouped_by_app,
+                process_state.is_grouped_by_user,
+                process_state.is_tree_mode,
+            )
+        });
 
-    if let Some((is_invalid_or_blank, is_using_command, is_grouped, is_tree)) = process_states {
+    if let Some((is_using_command, is_grouped, is_grouped_by_app, is_grouped_by_user, is_tree)) =
+        process_states
+    {
         if !app.is_frozen {
             convert_process_data(
                 &app.data_collection,
                 &mut app.canvas_data.single_process_data,
             );
+
+            // Show processes that were just stopped or continued that way until the data catches
+            // up, rather than waiting for the next update.
+            let harvest_instant = app.data_collection.current_instant;
+            app.proc_state
+                .stopped_pids
+                .retain(|_pid, (_is_stopped, signal_instant)| *signal_instant >= harvest_instant);
+            for (pid, (is_stopped, _signal_instant)) in &app.proc_state.stopped_pids {
+                if let Some(process) = app.canvas_data.single_process_data.get_mut(pid) {
+                    process.is_stopped = *is_stopped;
+                }
+            }
+        }
+        // Forget about pinned processes once they exit, as their PIDs could be reused.
+        if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+            let single_process_data = &app.canvas_data.single_process_data;
+            proc_widget_state
+                .pinned_pids
+                .retain(|pid| single_process_data.contains_key(pid));
         }
-        let process_filter = app.get_process_filter(widget_id);
+
+        let query_set = app.get_query_set(widget_id);
+        let is_searching_window_titles = app.app_config_fields.window_titles;
+        let widget_pinned_pids = app
+            .proc_state
+            .widget_states
+            .get(&widget_id)
+            .map(|proc_widget_state| &proc_widget_state.pinned_pids);
+        let pinned_pids = app
+            .canvas_data
+            .single_process_data
+            .values()
+            .filter(|process| {
+                widget_pinned_pids.is_some_and(|pinned_pids| pinned_pids.contains(&process.pid))
+                    || app.filters.process_pins.iter().any(|pin| {
+                        pin.check(&ProcessTarget {
+                            process,
+                            is_using_command,
+                            is_searching_window_titles,
+                        })
+                    })
+            })
+            .map(|process| process.pid)
+            .collect::<HashSet<_>>();
+        // Pinned processes are always shown, whatever the search is.
+        let matches_filters = |process: &ConvertedProcessData| {
+            pinned_pids.contains(&process.pid)
+                || query_set.is_empty()
+                || query_set.check(&ProcessTarget {
+                    process,
+                    is_using_command,
+                    is_searching_window_titles,
+                })
+        };
         let filtered_process_data: Vec<ConvertedProcessData> = if is_tree {
             app.canvas_data
                 .single_process_data
-                .iter()
-                .map(|(_pid, process)| {
+                .values()
+                .map(|process| {
                     let mut process_clone = process.clone();
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            process_clone.is_disabled_entry =
-                                !process_filter.check(&process_clone, is_using_command);
-                        }
-                    }
+                    process_clone.is_disabled_entry = !matches_filters(&process_clone);
                     process_clone
                 })
                 .collect::<Vec<_>>()
         } else {
             app.canvas_data
                 .single_process_data
-                .iter()
-                .filter_map(|(_pid, process)| {
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            if process_filter.check(&process, is_using_command) {
-                                Some(process)
-                            } else {
-                                None
-                            }
-                        } else {
-                            Some(process)
-                        }
-                    } else {
-                        Some(process)
-                    }
-                })
+                .values()
+                .filter(|process| matches_filters(process))
                 .cloned()
                 .collect::<Vec<_>>()
         };
 
+        let mut process_totals = get_process_totals(&filtered_process_data);
+        convert_process_share(
+            &mut process_totals,
+            &app.data_collection,
+            &app.canvas_data.single_process_data,
+        );
+        app.canvas_data
+            .process_totals_map
+            .insert(widget_id, process_totals);
+
         if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
             let mut finalized_process_data = if is_tree {
                 tree_process_data(
@@ -415,7 +588,12 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                     proc_widget_state.is_process_sort_descending,
                 )
             } else if is_grouped {
-                group_process_data(&filtered_process_data, is_using_command)
+                group_process_data(
+                    &filtered_process_data,
+                    is_using_command,
+                    is_grouped_by_app,
+                    is_grouped_by_user,
+                )
             } else {
                 filtered_process_data
             };
@@ -425,6 +603,86 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                 sort_process_data(&mut finalized_process_data, proc_widget_state);
             }
 
+            // Grouped entries don't have a state, so we only section individual processes.
+            if proc_widget_state.is_sectioned_by_state && !is_tree && !is_grouped {
+                finalized_process_data = section_process_data_by_state(
+                    finalized_process_data,
+                    &proc_widget_state.collapsed_state_sections,
+                );
+            }
+
+            // Pinned processes go above everything else, keeping their order.  Tree mode leaves them
+            // in their branches, as moving them would break the tree apart.
+            if !pinned_pids.is_empty() {
+                for process in &mut finalized_process_data {
+                    process.is_pinned = process.section_header.is_none()
+                        && (pinned_pids.contains(&process.pid)
+                            || process
+                                .group_pids
+                                .iter()
+                                .any(|pid| pinned_pids.contains(pid)));
+                }
+                if !is_tree {
+                    finalized_process_data.sort_by_key(|process| !process.is_pinned);
+                }
+            }
+
+            // Keep the selected process selected as rows move around from sorting and processes
+            // coming and going, rather than whatever ends up in its place.
+            let is_entry_of = |process: &ConvertedProcessData, pid: Pid| {
+                process.section_header.is_none()
+                    && (process.pid == pid || process.group_pids.contains(&pid))
+            };
+            let scroll_state = &mut proc_widget_state.scroll_state;
+            let prev_process_data = app.canvas_data.finalized_process_data_map.get(&widget_id);
+            if let Some(locked_pid) = proc_widget_state.locked_pid {
+                // Moving the selection off the followed process, or it exiting, ends the lock.
+                let is_moved_off = prev_process_data
+                    .and_then(|prev_process_data| {
+                        prev_process_data
+                            .iter()
+                            .position(|process| is_entry_of(process, locked_pid))
+                    })
+                    .is_some_and(|prev_position| {
+                        prev_position != scroll_state.current_scroll_position
+                    });
+                if is_moved_off
+                    || !app
+                        .canvas_data
+                        .single_process_data
+                        .contains_key(&locked_pid)
+                {
+                    proc_widget_state.locked_pid = None;
+                }
+            }
+            let followed_pid = proc_widget_state.locked_pid.or_else(|| {
+                prev_process_data
+                    .and_then(|prev_process_data| {
+                        prev_process_data.get(scroll_state.current_scroll_position)
+                    })
+                    .filter(|process| process.section_header.is_none())
+                    .map(|process| process.pid)
+            });
+            if let Some(position) = followed_pid.and_then(|followed_pid| {
+                finalized_process_data
+                    .iter()
+                    .position(|process| is_entry_of(process, followed_pid))
+            }) {
+                let prev_position = scroll_state.current_scroll_position;
+                if proc_widget_state.locked_pid.is_some() {
+                    // Scroll the list around the followed process, keeping it on the same row.
+                    scroll_state.previous_scroll_position = (position
+                        + scroll_state.previous_scroll_position)
+                        .saturating_sub(prev_position);
+                    scroll_state.scroll_direction = app::ScrollDirection::Down;
+                } else if position < prev_position {
+                    scroll_state.scroll_direction = app::ScrollDirection::Up;
+                } else if position > prev_position {
+                    scroll_state.scroll_direction = app::ScrollDirection::Down;
+                }
+                scroll_state.current_scroll_position = position;
+            }
+
             if proc_widget_state.scroll_state.current_scroll_position
                 >= finalized_process_data.len()
             {
@@ -436,7 +694,7 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
 
             app.canvas_data.stringified_process_data_map.insert(
                 widget_id,
-                stringify_process_data(&proc_widget_state, &finalized_process_data),
+                stringify_process_data(proc_widget_state, &finalized_process_data),
             );
             app.canvas_data
                 .finalized_process_data_map
@@ -446,7 +704,7 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
 }
 
 fn sort_process_data(
-    to_sort_vec: &mut Vec<ConvertedProcessData>, proc_widget_state: &app::ProcWidgetState,
+    to_sort_vec: &mut [ConvertedProcessData], proc_widget_state: &app::ProcWidgetState,
 ) {
     to_sort_vec.sort_by(|a, b| {
         utils::gen_util::get_ordering(&a.name.to_lowercase(), &b.name.to_lowercase(), false)
@@ -553,6 +811,96 @@ fn sort_process_data(
                 proc_widget_state.is_process_sort_descending,
             )
         }),
+        ProcessSorting::App => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                &a.app.as_ref().map(|app| app.to_lowercase()),
+                &b.app.as_ref().map(|app| app.to_lowercase()),
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::GpuPercent => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.gpu_usage_percent,
+                    b.gpu_usage_percent,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::GpuMem => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.gpu_mem_usage_bytes,
+                    b.gpu_mem_usage_bytes,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::SecurityContext => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                &a.security_context,
+                &b.security_context,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::Nice => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                a.nice,
+                b.nice,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::StartTime => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                a.start_time,
+                b.start_time,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::RunTime => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                a.run_time,
+                b.run_time,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::Energy => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                a.energy_impact,
+                b.energy_impact,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::Media => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                a.media_usage,
+                b.media_usage,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::User => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                get_user_name(a).to_lowercase(),
+                get_user_name(b).to_lowercase(),
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::Namespaces => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                &a.namespaces,
+                &b.namespaces,
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
+        ProcessSorting::RtPriority => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    (a.rt_policy.is_some(), a.rt_priority),
+                    (b.rt_policy.is_some(), b.rt_priority),
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
         ProcessSorting::Count => {
             if proc_widget_state.is_grouped {
                 to_sort_vec.sort_by(|a, b| {
@@ -568,7 +916,7 @@ fn sort_process_data(
 }
 
 pub fn create_input_thread(
-    sender: std::sync::mpsc::Sender<
+    sender: crossbeam_channel::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
     >,
     termination_ctrl_lock: Arc<Mutex<bool>>,
@@ -577,7 +925,6 @@ pub fn create_input_thread(
     thread::spawn(move || {
         trace!("Spawned input thread.");
         let mut mouse_timer = Instant::now();
-        let mut keyboard_timer = Instant::now();
 
         loop {
             if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
@@ -593,13 +940,11 @@ pub fn create_input_thread(
                     if let Ok(event) = read() {
                         trace!("Input thread received an event: {:?}", event);
                         if let Event::Key(key) = event {
-                            if Instant::now().duration_since(keyboard_timer).as_millis() >= 20 {
-                                if sender.send(BottomEvent::KeyInput(key)).is_err() {
-                                    break;
-                                }
-                                trace!("Input thread sent keyboard data.");
-                                keyboard_timer = Instant::now();
+                            // Every key is sent, as dropping any would lose typed characters.
+                            if sender.send(BottomEvent::KeyInput(key)).is_err() {
+                                break;
                             }
+                            trace!("Input thread sent keyboard data.");
                         } else if let Event::Mouse(mouse) = event {
                             if Instant::now().duration_since(mouse_timer).as_millis() >= 20 {
                                 if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
@@ -608,6 +953,10 @@ pub fn create_input_thread(
                                 trace!("Input thread sent mouse data.");
                                 mouse_timer = Instant::now();
                             }
+                        } else if let Event::Resize(..) = event {
+                            if sender.send(BottomEvent::Resize).is_err() {
+                                break;
+                            }
                         }
                     }
                 }
@@ -617,8 +966,58 @@ pub fn create_input_thread(
     })
 }
 
+/// Reads control commands from the FIFO at the given path, and forwards them as actions.
+pub fn create_control_thread(
+    sender: crossbeam_channel::Sender<
+        BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
+    >,
+    control_path: PathBuf,
+) -> std::thread::JoinHandle<()> {
+    trace!("Creating control thread.");
+    thread::spawn(move || {
+        use std::io::BufRead;
+
+        // Opening for writing as well means we always have a writer, so reads block rather
+        // than hitting EOF whenever a script closes its end.
+        let fifo = match fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&control_path)
+        {
+            Ok(fifo) => fifo,
+            Err(err) => {
+                error!("Unable to open control FIFO {:?}: {}", control_path, err);
+                return;
+            }
+        };
+
+        for line in std::io::BufReader::new(fifo).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    debug!("Unable to read from the control FIFO: {}", err);
+                    break;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match app::control::parse_control_command(&line) {
+                Ok(action) => {
+                    if sender.send(BottomEvent::Control(action)).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => debug!("Ignoring control command \"{}\": {}", line, err),
+            }
+        }
+        trace!("Control thread loop has closed.");
+    })
+}
+
 pub fn create_collection_thread(
-    sender: std::sync::mpsc::Sender<
+    sender: crossbeam_channel::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
     >,
     control_receiver: std::sync::mpsc::Receiver<ThreadControlEvent>,
@@ -629,6 +1028,8 @@ pub fn create_collection_thread(
     let temp_type = app_config_fields.temperature_type.clone();
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let show_average_cpu = app_config_fields.show_average_cpu;
+    let process_name_source = app_config_fields.process_name_source;
+    let gpu_memory_rate_in_milliseconds = app_config_fields.gpu_memory_rate_in_milliseconds;
     let update_rate_in_milliseconds = app_config_fields.update_rate_in_milliseconds;
 
     thread::spawn(move || {
@@ -639,6 +1040,8 @@ pub fn create_collection_thread(
         data_state.set_temperature_type(temp_type);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_process_name_source(process_name_source);
+        data_state.set_gpu_mem_rate(gpu_memory_rate_in_milliseconds);
         trace!("Set default data state settings.");
 
         data_state.init();
@@ -656,8 +1059,9 @@ pub fn create_collection_thread(
 
             trace!("Checking for collection control receiver event...");
             let mut update_time = update_rate_in_milliseconds;
-            if let Ok(message) = control_receiver.try_recv() {
-                trace!("Received message in collection thread: {:?}", message);
+            // Handle every waiting message, so all of them apply to this collection.
+            while let Ok(message) = control_receiver.try_recv() {
+                debug!("Received message in collection thread: {:?}", message);
                 match message {
                     ThreadControlEvent::Reset => {
                         data_state.data.cleanup();
@@ -667,6 +1071,9 @@ pub fn create_collection_thread(
                         data_state
                             .set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
                         data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+                        data_state.set_process_name_source(app_config_fields.process_name_source);
+                        data_state
+                            .set_gpu_mem_rate(app_config_fields.gpu_memory_rate_in_milliseconds);
                     }
                     ThreadControlEvent::UpdateUsedWidgets(used_widget_set) => {
                         data_state.set_collected_data(*used_widget_set);
@@ -674,9 +1081,60 @@ pub fn create_collection_thread(
                     ThreadControlEvent::UpdateUpdateTime(new_time) => {
                         update_time = new_time;
                     }
+                    ThreadControlEvent::UpdateCollectConnections(collect_connections) => {
+                        data_state.set_collect_connections(collect_connections);
+                    }
+                    ThreadControlEvent::UpdateCollectAppNames(collect_app_names) => {
+                        data_state.set_collect_app_names(collect_app_names);
+                    }
+                    ThreadControlEvent::UpdateCollectGpuStats(collect_gpu_stats) => {
+                        data_state.set_collect_gpu_stats(collect_gpu_stats);
+                    }
+                    ThreadControlEvent::UpdateCollectSecurityContexts(
+                        collect_security_contexts,
+                    ) => {
+                        data_state.set_collect_security_contexts(collect_security_contexts);
+                    }
+                    ThreadControlEvent::UpdateCollectNamespaces(collect_namespaces) => {
+                        data_state.set_collect_namespaces(collect_namespaces);
+                    }
+                    ThreadControlEvent::UpdateCollectFds(collect_fds) => {
+                        data_state.set_collect_fds(collect_fds);
+                    }
+                    ThreadControlEvent::UpdateCollectEnv(collect_env) => {
+                        data_state.set_collect_env(collect_env);
+                    }
+                    ThreadControlEvent::UpdateCollectCgroups(collect_cgroups) => {
+                        data_state.set_collect_cgroups(collect_cgroups);
+                    }
+                    ThreadControlEvent::UpdateCollectWindowTitles(collect_window_titles) => {
+                        data_state.set_collect_window_titles(collect_window_titles);
+                    }
+                    ThreadControlEvent::UpdateCollectMedia(collect_media) => {
+                        data_state.set_collect_media(collect_media);
+                    }
+                    ThreadControlEvent::UpdateCollectEnergy(collect_energy) => {
+                        data_state.set_collect_energy(collect_energy);
+                    }
+                    ThreadControlEvent::FindProcessesOnMount(mount_point) => {
+                        // This gets its own thread, so a hung filesystem can't stall collection.
+                        let sender = sender.clone();
+                        thread::spawn(move || {
+                            let pids =
+                                data_harvester::processes::get_pids_using_mount(&mount_point);
+                            sender
+                                .send(BottomEvent::Control(AppAction::ShowProcessesFoundOnMount {
+                                    mount_point,
+                                    pids,
+                                }))
+                                .ok();
+                        });
+                    }
                 }
             }
+            let collection_start = Instant::now();
             futures::executor::block_on(data_state.update_data());
+            debug!("Data collection took {:?}.", collection_start.elapsed());
 
             // Yet another check to bail if needed...
             if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
@@ -12,14 +12,23 @@ use crate::{
     app::{layout_manager::*, *},
     canvas::ColourScheme,
     constants::*,
-    utils::error::{self, BottomError},
+    query::{self, QueryOptions},
+    utils::{
+        error::{self, BottomError},
+        gen_util::ByteUnits,
+        number_format::NumberFormat,
+    },
 };
 
 use typed_builder::*;
 
+use exporter_options::*;
 use layout_options::*;
+use widget_options::*;
 
+pub mod exporter_options;
 pub mod layout_options;
+pub mod widget_options;
 
 use anyhow::{Context, Result};
 
@@ -28,17 +37,22 @@ pub struct Config {
     pub flags: Option<ConfigFlags>,
     pub colors: Option<ConfigColours>,
     pub row: Option<Vec<Row>>,
+    pub widget: Option<Vec<CustomWidget>>,
+    pub plugin: Option<Vec<PluginConfig>>,
+    pub exporter: Option<Vec<ExporterConfig>>,
     pub disk_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
+    pub process_filter: Option<ProcessFilter>,
+    pub query_aliases: Option<HashMap<String, String>>,
+    pub column_aliases: Option<HashMap<String, String>>,
+    pub thresholds: Option<ConfigThresholds>,
 }
 
 impl Config {
     pub fn get_config_as_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        let mut config_string: Vec<Cow<'_, str>> = Vec::default();
-
         // Top level
-        config_string.push(CONFIG_TOP_HEAD.into());
-        config_string.push(toml::to_string_pretty(self)?.into());
+        let config_string: Vec<Cow<'_, str>> =
+            vec![CONFIG_TOP_HEAD.into(), toml::to_string_pretty(self)?.into()];
 
         Ok(config_string.concat().as_bytes().to_vec())
     }
@@ -150,6 +164,106 @@ pub struct ConfigFlags {
 
     #[builder(default, setter(strip_option))]
     show_table_scroll_position: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub show_graph_stats: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub stacked_cpu: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub persist_data: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub warm_start: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub snapshot_path: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub hook_command: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub hook_interval: Option<u64>,
+
+    #[builder(default, setter(strip_option))]
+    pub control_path: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_name_source: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub app_column: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub enable_gpu_memory: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub gpu_memory_rate: Option<u64>,
+
+    #[builder(default, setter(strip_option))]
+    pub enable_gpu_usage: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub rt_column: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub security_column: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub namespace_column: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub nice_column: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub show_process_share: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub link_widgets: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub hide_removable_disks: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub synchronized_output: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_command: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub max_fps: Option<u64>,
+
+    #[builder(default, setter(strip_option))]
+    pub window_titles: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub glob: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    #[serde(skip)]
+    pub search_glob_enabled_widgets_map: Option<HashMap<u64, bool>>,
+
+    #[builder(default, setter(strip_option))]
+    pub search_glob_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+
+    #[builder(default, setter(strip_option))]
+    pub locale_number_format: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub decimal_separator: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub digit_grouping: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub decimal_places: Option<u8>,
+
+    #[builder(default, setter(strip_option))]
+    pub byte_units: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_columns: Option<Vec<String>>,
 }
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -185,6 +299,7 @@ pub struct ConfigColours {
     pub border_color: Option<String>,
     pub highlighted_border_color: Option<String>,
     pub disabled_text_color: Option<String>,
+    pub pinned_text_color: Option<String>,
     pub text_color: Option<String>,
     pub selected_text_color: Option<String>,
     pub selected_bg_color: Option<String>,
@@ -215,22 +330,39 @@ pub struct IgnoreList {
     pub case_sensitive: Option<bool>,
 }
 
+/// Process search queries that are always applied to process widgets.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProcessFilter {
+    pub exclude: Option<Vec<String>>,
+    pub pin: Option<Vec<String>>,
+}
+
+/// Values to draw horizontal guide lines at on graphs.  CPU and memory values are percentages,
+/// and network values are in bytes per second.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigThresholds {
+    pub cpu: Option<Vec<f64>>,
+    pub mem: Option<Vec<f64>>,
+    pub network: Option<Vec<f64>>,
+}
+
 pub fn build_app(
     matches: &clap::ArgMatches<'static>, config: &mut Config, widget_layout: &BottomLayout,
     default_widget_id: u64, default_widget_type_option: &Option<BottomWidgetType>,
     config_path: Option<PathBuf>,
 ) -> Result<App> {
     use BottomWidgetType::*;
-    let autohide_time = get_autohide_time(&matches, &config);
-    let default_time_value = get_default_time_value(&matches, &config)
+    let autohide_time = get_autohide_time(matches, config);
+    let default_time_value = get_default_time_value(matches, config)
         .context("Update 'default_time_value' in your config file.")?;
-    let use_basic_mode = get_use_basic_mode(&matches, &config);
+    let use_basic_mode = get_use_basic_mode(matches, config);
 
     // For processes
     let is_grouped = get_app_grouping(matches, config);
     let is_case_sensitive = get_app_case_sensitive(matches, config);
     let is_match_whole_word = get_app_match_whole_word(matches, config);
     let is_use_regex = get_app_use_regex(matches, config);
+    let is_use_glob = get_app_use_glob(matches, config);
 
     let mut widget_map = HashMap::new();
     let mut cpu_state_map: HashMap<u64, CpuWidgetState> = HashMap::new();
@@ -240,6 +372,8 @@ pub fn build_app(
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskWidgetState> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut custom_state_map: HashMap<u64, CustomWidgetState> = HashMap::new();
+    let mut plugin_host = plugins::PluginHost::new(config.plugin.clone().unwrap_or_default());
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -254,6 +388,38 @@ pub fn build_app(
 
     let show_memory_as_values = get_mem_as_value(matches, config);
     let is_default_tree = get_is_default_tree(matches, config);
+    let is_default_command = get_is_default_command(matches, config);
+    let show_app_column = get_show_app_column(matches, config);
+    let show_gpu_usage_column = get_enable_gpu_usage(matches, config);
+    let show_gpu_mem_column = get_enable_gpu_memory(matches, config)
+        .context("Update 'enable_gpu_memory' in your config file.")?;
+    let show_rt_column = get_show_rt_column(matches, config);
+    let show_security_column = get_show_security_column(matches, config);
+    let show_namespace_column = get_show_namespace_column(matches, config);
+    let show_nice_column = get_show_nice_column(matches, config);
+    let process_columns = get_config_process_columns(config)
+        .context("Update 'process_columns' in your config file.")?;
+    let column_aliases = get_column_aliases(&config.column_aliases)
+        .context("Update 'column_aliases' in your config file")?;
+    let prefix_aliases = get_prefix_aliases(&column_aliases);
+    let query_aliases = get_query_aliases(&config.query_aliases, &prefix_aliases)
+        .context("Update 'query_aliases' in your config file")?;
+    let filter = matches.value_of("filter");
+    if let Some(filter) = filter {
+        query::parse(
+            filter,
+            &QueryOptions {
+                is_searching_whole_word: is_match_whole_word,
+                is_ignoring_case: !is_case_sensitive,
+                is_searching_with_regex: is_use_regex,
+                is_searching_with_glob: is_use_glob,
+                query_aliases: Some(&query_aliases),
+                prefix_aliases: Some(&prefix_aliases),
+                search_target: query::SearchTarget::Processes,
+            },
+        )
+        .context("Update the query passed to --filter.")?;
+    }
 
     for row in &widget_layout.rows {
         for col in &row.children {
@@ -313,17 +479,48 @@ pub fn build_app(
                             );
                         }
                         Proc => {
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::init(
-                                    is_case_sensitive,
-                                    is_match_whole_word,
-                                    is_use_regex,
-                                    is_grouped,
-                                    show_memory_as_values,
-                                    is_default_tree,
-                                ),
+                            let mut proc_widget_state = ProcWidgetState::init(
+                                is_case_sensitive,
+                                is_match_whole_word,
+                                is_use_regex,
+                                is_use_glob,
+                                is_grouped,
+                                show_memory_as_values,
+                                is_default_tree,
+                                show_app_column,
+                                show_gpu_usage_column,
+                                show_gpu_mem_column,
+                                show_rt_column,
+                                show_security_column,
+                                show_namespace_column,
+                                show_nice_column,
+                                process_columns.as_deref(),
+                                is_default_command,
                             );
+                            proc_widget_state.query_aliases = query_aliases.clone();
+                            proc_widget_state.prefix_aliases = prefix_aliases.clone();
+                            for (alias, column) in &column_aliases {
+                                proc_widget_state
+                                    .columns
+                                    .set_column_name(column.clone(), alias.clone());
+                            }
+                            if let Some(filter) = filter {
+                                proc_widget_state.set_search_query(filter);
+                            } else if let Some(default_query) = &widget.default_query {
+                                proc_widget_state.set_search_query(default_query);
+                                if let Some(error_message) = &proc_widget_state
+                                    .process_search_state
+                                    .search_state
+                                    .error_message
+                                {
+                                    return Err(BottomError::ConfigError(format!(
+                                        "invalid default_query \"{}\": {}",
+                                        default_query, error_message
+                                    ))
+                                    .into());
+                                }
+                            }
+                            proc_state_map.insert(widget.widget_id, proc_widget_state);
                         }
                         Disk => {
                             disk_state_map.insert(widget.widget_id, DiskWidgetState::init());
@@ -335,6 +532,17 @@ pub fn build_app(
                             battery_state_map
                                 .insert(widget.widget_id, BatteryWidgetState::default());
                         }
+                        Custom(ref name) => {
+                            custom_state_map.insert(
+                                widget.widget_id,
+                                get_custom_widget_state(
+                                    name,
+                                    config,
+                                    default_time_value,
+                                    &mut plugin_host,
+                                )?,
+                            );
+                        }
                         _ => {}
                     }
                 }
@@ -392,22 +600,71 @@ pub fn build_app(
         // no_write: get_no_write(matches, config),
         no_write: false,
         show_table_scroll_position: get_show_table_scroll_position(matches, config),
+        show_graph_stats: get_show_graph_stats(matches, config),
+        show_process_share: get_show_process_share(matches, config),
+        link_widgets: get_link_widgets(matches, config),
+        hide_removable_disks: get_hide_removable_disks(matches, config),
+        synchronized_output: get_synchronized_output(matches, config),
+        max_fps: get_max_fps(matches, config).context("Update 'max_fps' in your config file.")?,
+        window_titles: get_window_titles(matches, config),
+        stacked_cpu: get_stacked_cpu(matches, config),
+        persist_data: get_persist_data(matches, config),
+        warm_start: get_warm_start(matches, config),
+        snapshot_path: get_snapshot_path(matches, config),
+        hook_command: get_hook_command(matches, config),
+        hook_interval: get_hook_interval(matches, config)?,
+        control_path: get_control_path(matches, config),
+        process_name_source: get_process_name_source(matches, config)
+            .context("Update 'process_name_source' in your config file.")?,
+        gpu_memory_rate_in_milliseconds: get_gpu_memory_rate_in_milliseconds(matches, config)
+            .context("Update 'gpu_memory_rate' in your config file.")?,
+        graph_thresholds: get_graph_thresholds(config)
+            .context("Update 'thresholds' in your config file.")?,
     };
 
-    let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
-        use_mem: used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some(),
-        use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
-        use_proc: used_widget_set.get(&Proc).is_some(),
-        use_disk: used_widget_set.get(&Disk).is_some(),
-        use_temp: used_widget_set.get(&Temp).is_some(),
-        use_battery: used_widget_set.get(&Battery).is_some(),
+    let mut used_widgets = UsedWidgets {
+        use_cpu: used_widget_set.contains(&Cpu) || used_widget_set.contains(&BasicCpu),
+        // The process share footer compares against the memory used by the system.
+        use_mem: used_widget_set.contains(&Mem)
+            || used_widget_set.contains(&BasicMem)
+            || (app_config_fields.show_process_share && used_widget_set.contains(&Proc)),
+        use_net: used_widget_set.contains(&Net) || used_widget_set.contains(&BasicNet),
+        use_proc: used_widget_set.contains(&Proc),
+        use_disk: used_widget_set.contains(&Disk),
+        use_temp: used_widget_set.contains(&Temp),
+        use_battery: used_widget_set.contains(&Battery),
     };
+    for custom_widget_state in custom_state_map.values() {
+        custom_widget_state
+            .source
+            .mark_used_widgets(&mut used_widgets);
+        if let Some(secondary) = &custom_widget_state.secondary {
+            secondary.source.mark_used_widgets(&mut used_widgets);
+        }
+    }
 
     let disk_filter =
         get_ignore_list(&config.disk_filter).context("Update 'disk_filter' in your config file")?;
     let temp_filter =
         get_ignore_list(&config.temp_filter).context("Update 'temp_filter' in your config file")?;
+    let process_excludes = get_process_filter_queries(
+        config
+            .process_filter
+            .as_ref()
+            .and_then(|process_filter| process_filter.exclude.as_ref()),
+        &query_aliases,
+        &prefix_aliases,
+    )
+    .context("Update 'exclude' in 'process_filter' in your config file")?;
+    let process_pins = get_process_filter_queries(
+        config
+            .process_filter
+            .as_ref()
+            .and_then(|process_filter| process_filter.pin.as_ref()),
+        &query_aliases,
+        &prefix_aliases,
+    )
+    .context("Update 'pin' in 'process_filter' in your config file")?;
 
     // One more thing - we have to update the search settings of our proc_state_map, and create the hashmaps if needed!
     // Note that if you change your layout, this might not actually match properly... not sure if/where we should deal with that...
@@ -449,6 +706,18 @@ pub fn build_app(
                 flags.search_regex_enabled_widgets_map = Some(mapping);
             }
         }
+
+        if flags.glob.is_none() && !matches.is_present("glob") {
+            if let Some(search_glob_enabled_widgets) = &flags.search_glob_enabled_widgets {
+                let mapping = HashMap::new();
+                for widget in search_glob_enabled_widgets {
+                    if let Some(proc_widget) = proc_state_map.get_mut(&widget.id) {
+                        proc_widget.process_search_state.is_searching_with_glob = widget.enabled;
+                    }
+                }
+                flags.search_glob_enabled_widgets_map = Some(mapping);
+            }
+        }
     }
 
     Ok(App::builder()
@@ -460,6 +729,8 @@ pub fn build_app(
         .disk_state(DiskState::init(disk_state_map))
         .temp_state(TempState::init(temp_state_map))
         .battery_state(BatteryState::init(battery_state_map))
+        .custom_state(CustomState::init(custom_state_map))
+        .plugin_host(plugin_host)
         .basic_table_widget_state(basic_table_widget_state)
         .current_widget(widget_map.get(&initial_widget_id).unwrap().clone()) // TODO: [UNWRAP] - many of the unwraps are fine (like this one) but do a once-over and/or switch to expect?
         .widget_map(widget_map)
@@ -467,37 +738,69 @@ pub fn build_app(
         .filters(DataFilters {
             disk_filter,
             temp_filter,
+            process_excludes,
+            process_pins,
         })
         .config(config.clone())
         .config_path(config_path)
         .build())
 }
 
+/// Builds the widget layout.  Along with the layout, this returns notes about any widgets that
+/// were left out of the default layout, as there is nothing on this system for them to show.
+/// Layouts from the config file are left as-is.
 pub fn get_widget_layout(
     matches: &clap::ArgMatches<'static>, config: &Config,
-) -> error::Result<(BottomLayout, u64, Option<BottomWidgetType>)> {
+) -> error::Result<(BottomLayout, u64, Option<BottomWidgetType>, Vec<String>)> {
     let left_legend = get_use_left_legend(matches, config);
     let (default_widget_type, mut default_widget_count) =
         get_default_widget_and_count(matches, config)?;
     let mut default_widget_id = 1;
+    let mut layout_notes = Vec::new();
 
     let bottom_layout = if get_use_basic_mode(matches, config) {
         default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(matches, config))
+        BottomLayout::init_basic_default(
+            get_use_battery(matches, config) && has_batteries(&mut layout_notes),
+        )
     } else {
         let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
+        let rows = match (get_single_widget_row(matches), &config.row) {
+            (Some(single_widget_row), _) => {
+                ref_row = vec![single_widget_row];
+                &ref_row
+            }
+            (None, Some(r)) => r,
+            (None, None) => {
+                let use_battery =
+                    get_use_battery(matches, config) && has_batteries(&mut layout_notes);
+
                 // This cannot (like it really shouldn't) fail!
-                ref_row = toml::from_str::<Config>(if get_use_battery(matches, config) {
+                let mut default_rows = toml::from_str::<Config>(if use_battery {
                     DEFAULT_BATTERY_LAYOUT
                 } else {
                     DEFAULT_LAYOUT
                 })?
                 .row
                 .unwrap();
+
+                if let Some(wsl_version) = data_harvester::guest::detect_wsl() {
+                    remove_widget_type(&mut default_rows, "temp");
+                    layout_notes.push(format!(
+                        "Temperature sensors can't be read under {}, so the temperature widget is \
+                         hidden.",
+                        wsl_version
+                    ));
+                } else if capabilities::detect_temperature_sensors().is_empty() {
+                    remove_widget_type(&mut default_rows, "temp");
+                    layout_notes.push(
+                        "No temperature sensors were found, so the temperature widget is hidden."
+                            .to_string(),
+                    );
+                }
+
+                ref_row = default_rows;
                 &ref_row
             }
         };
@@ -535,10 +838,75 @@ pub fn get_widget_layout(
         }
     };
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+    for note in &layout_notes {
+        info!("{}", note);
+    }
+
+    Ok((
+        bottom_layout,
+        default_widget_id,
+        default_widget_type,
+        layout_notes,
+    ))
+}
+
+/// Returns whether there are any batteries, adding a note about hiding the battery widget if not.
+fn has_batteries(layout_notes: &mut Vec<String>) -> bool {
+    if capabilities::detect_batteries() > 0 {
+        true
+    } else {
+        layout_notes.push("No batteries were found, so the battery widget is hidden.".to_string());
+        false
+    }
+}
+
+/// Removes every widget of the given type from the rows, along with any columns or rows that
+/// are left empty.
+fn remove_widget_type(rows: &mut Vec<Row>, widget_type: &str) {
+    for row in rows.iter_mut() {
+        if let Some(children) = &mut row.child {
+            children.retain_mut(|child| match child {
+                RowChildren::Widget(widget) => widget.widget_type != widget_type,
+                RowChildren::Col { child, .. } => {
+                    child.retain(|widget| widget.widget_type != widget_type);
+                    !child.is_empty()
+                }
+            });
+        }
+    }
+    rows.retain(|row| {
+        row.child
+            .as_ref()
+            .is_some_and(|children| !children.is_empty())
+    });
+}
+
+/// Returns a layout row containing only the widget passed to `--widget`, if it was set.  The
+/// widget can be any layout widget type, optionally suffixed with `_graph` or `_table` (ie:
+/// `cpu_graph`), or a custom widget written as `custom:<name>`.
+fn get_single_widget_row(matches: &clap::ArgMatches<'static>) -> Option<Row> {
+    let widget = matches.value_of("widget")?;
+    let (widget_type, name) = match widget.strip_prefix("custom:") {
+        Some(name) => ("custom", Some(name.to_string())),
+        None => (
+            widget.trim_end_matches("_graph").trim_end_matches("_table"),
+            None,
+        ),
+    };
+
+    Some(Row {
+        ratio: None,
+        child: Some(vec![RowChildren::Widget(FinalWidget {
+            ratio: None,
+            widget_type: widget_type.to_string(),
+            default: Some(true),
+            name,
+            default_query: None,
+        })]),
+    })
 }
 
-fn get_update_rate_in_milliseconds(
+pub fn get_update_rate_in_milliseconds(
     matches: &clap::ArgMatches<'static>, config: &Config,
 ) -> error::Result<u64> {
     let update_rate_in_milliseconds = if let Some(update_rate) = matches.value_of("rate") {
@@ -557,7 +925,7 @@ fn get_update_rate_in_milliseconds(
         return Err(BottomError::ConfigError(
             "set your update rate to be at least 250 milliseconds.".to_string(),
         ));
-    } else if update_rate_in_milliseconds as u128 > std::u64::MAX as u128 {
+    } else if update_rate_in_milliseconds > u64::MAX as u128 {
         return Err(BottomError::ConfigError(
             "set your update rate to be at most unsigned INT_MAX.".to_string(),
         ));
@@ -566,7 +934,7 @@ fn get_update_rate_in_milliseconds(
     Ok(update_rate_in_milliseconds as u64)
 }
 
-fn get_temperature(
+pub fn get_temperature(
     matches: &clap::ArgMatches<'static>, config: &Config,
 ) -> error::Result<data_harvester::temperature::TemperatureType> {
     if matches.is_present("fahrenheit") {
@@ -641,7 +1009,10 @@ fn get_use_current_cpu_total(matches: &clap::ArgMatches<'static>, config: &Confi
 }
 
 fn get_use_basic_mode(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
-    if matches.is_present("basic") {
+    // Showing a single widget always uses the normal look.
+    if matches.is_present("widget") {
+        return false;
+    } else if matches.is_present("basic") {
         return true;
     } else if let Some(flags) = &config.flags {
         if let Some(basic) = flags.basic {
@@ -671,7 +1042,7 @@ fn get_default_time_value(
         return Err(BottomError::ConfigError(
             "set your default value to be at least 30000 milliseconds.".to_string(),
         ));
-    } else if default_time as u128 > STALE_MAX_MILLISECONDS as u128 {
+    } else if default_time > STALE_MAX_MILLISECONDS as u128 {
         return Err(BottomError::ConfigError(format!(
             "set your default value to be at most {} milliseconds.",
             STALE_MAX_MILLISECONDS
@@ -752,6 +1123,17 @@ pub fn get_app_use_regex(matches: &clap::ArgMatches<'static>, config: &Config) -
     false
 }
 
+pub fn get_app_use_glob(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("glob") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(glob) = flags.glob {
+            return glob;
+        }
+    }
+    false
+}
+
 fn get_hide_time(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("hide_time") {
         return true;
@@ -803,18 +1185,16 @@ fn get_default_widget_and_count(
     let widget_count = if let Some(widget_count) = matches.value_of("default_widget_count") {
         Some(widget_count.parse::<u128>()?)
     } else if let Some(flags) = &config.flags {
-        if let Some(widget_count) = flags.default_widget_count {
-            Some(widget_count as u128)
-        } else {
-            None
-        }
+        flags
+            .default_widget_count
+            .map(|widget_count| widget_count as u128)
     } else {
         None
     };
 
     match (widget_type, widget_count) {
         (Some(widget_type), Some(widget_count)) => {
-            if widget_count > std::u64::MAX as u128 {
+            if widget_count > u64::MAX as u128 {
                 Err(BottomError::ConfigError(
                     "set your widget count to be at most unsigned INT_MAX.".to_string(),
                 ))
@@ -892,16 +1272,8 @@ fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option<Fil
             .list
             .iter()
             .map(|name| {
-                let use_regex = if let Some(use_regex) = ignore_list.regex {
-                    use_regex
-                } else {
-                    false
-                };
-                let use_cs = if let Some(use_cs) = ignore_list.case_sensitive {
-                    use_cs
-                } else {
-                    false
-                };
+                let use_regex = ignore_list.regex.unwrap_or_default();
+                let use_cs = ignore_list.case_sensitive.unwrap_or_default();
 
                 let escaped_string: String;
                 let res = format!(
@@ -928,6 +1300,105 @@ fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option<Fil
     }
 }
 
+/// Returns the column aliases from the config file by the column each renames, checking that each
+/// is a valid name for a prefix and that each column only has one.
+fn get_column_aliases(
+    column_aliases: &Option<HashMap<String, String>>,
+) -> error::Result<HashMap<String, data_harvester::processes::ProcessSorting>> {
+    let mut aliases_by_column = HashMap::new();
+    let mut lower_case_names = HashSet::new();
+    for (name, column) in column_aliases.iter().flatten() {
+        if name.is_empty() || !name.chars().all(query::is_query_alias_char) {
+            return Err(BottomError::ConfigError(format!(
+                "column alias \"{}\" can only contain letters, numbers, \"_\", and \"-\".",
+                name
+            )));
+        } else if query::is_reserved_word(name) || !lower_case_names.insert(name.to_lowercase()) {
+            return Err(BottomError::ConfigError(format!(
+                "column alias \"{}\" is already used in searches, pick another name.",
+                name
+            )));
+        }
+
+        let column = column.parse::<data_harvester::processes::ProcessSorting>()?;
+        if let Some(other_name) = aliases_by_column.insert(column.clone(), name.clone()) {
+            return Err(BottomError::ConfigError(format!(
+                "column \"{}\" has more than one alias, \"{}\" and \"{}\".",
+                column, other_name, name
+            )));
+        }
+    }
+
+    Ok(aliases_by_column
+        .into_iter()
+        .map(|(column, name)| (name, column))
+        .collect())
+}
+
+/// Returns the prefix each column alias stands for in searches, by the lowercase alias.  Columns
+/// that can't be searched by don't get one.
+fn get_prefix_aliases(
+    column_aliases: &HashMap<String, data_harvester::processes::ProcessSorting>,
+) -> HashMap<String, String> {
+    column_aliases
+        .iter()
+        .filter_map(|(name, column)| {
+            column
+                .get_query_prefix()
+                .map(|prefix| (name.to_lowercase(), prefix.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the query aliases from the config file, checking that each one is a valid query.
+fn get_query_aliases(
+    query_aliases: &Option<HashMap<String, String>>, prefix_aliases: &HashMap<String, String>,
+) -> error::Result<HashMap<String, String>> {
+    let query_aliases = query_aliases.clone().unwrap_or_default();
+    for name in query_aliases.keys() {
+        if name.is_empty() || !name.chars().all(query::is_query_alias_char) {
+            return Err(BottomError::ConfigError(format!(
+                "query alias \"{}\" can only contain letters, numbers, \"_\", and \"-\".",
+                name
+            )));
+        }
+        query::parse(
+            &format!("@{}", name),
+            &QueryOptions {
+                query_aliases: Some(&query_aliases),
+                prefix_aliases: Some(prefix_aliases),
+                ..QueryOptions::default()
+            },
+        )?;
+    }
+
+    Ok(query_aliases)
+}
+
+fn get_process_filter_queries(
+    query_strings: Option<&Vec<String>>, query_aliases: &HashMap<String, String>,
+    prefix_aliases: &HashMap<String, String>,
+) -> error::Result<Vec<query::Query>> {
+    if let Some(query_strings) = query_strings {
+        query_strings
+            .iter()
+            .map(|query_string| {
+                query::parse(
+                    query_string,
+                    &QueryOptions {
+                        query_aliases: Some(query_aliases),
+                        prefix_aliases: Some(prefix_aliases),
+                        ..QueryOptions::default()
+                    },
+                )
+                .map_err(|err| err.error)
+            })
+            .collect()
+    } else {
+        Ok(Vec::new())
+    }
+}
+
 pub fn get_color_scheme(
     matches: &clap::ArgMatches<'static>, config: &Config,
 ) -> error::Result<ColourScheme> {
@@ -977,6 +1448,227 @@ fn get_is_default_tree(matches: &clap::ArgMatches<'static>, config: &Config) ->
     false
 }
 
+fn get_is_default_command(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("process_command") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_command) = flags.process_command {
+            return process_command;
+        }
+    }
+    false
+}
+
+fn get_show_rt_column(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("rt_column") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(rt_column) = flags.rt_column {
+            return rt_column;
+        }
+    }
+    false
+}
+
+fn get_show_security_column(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("security_column") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(security_column) = flags.security_column {
+            return security_column;
+        }
+    }
+    false
+}
+
+fn get_show_namespace_column(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("namespace_column") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(namespace_column) = flags.namespace_column {
+            return namespace_column;
+        }
+    }
+    false
+}
+
+fn get_show_nice_column(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("nice_column") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(nice_column) = flags.nice_column {
+            return nice_column;
+        }
+    }
+    false
+}
+
+fn get_show_app_column(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("app_column") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(app_column) = flags.app_column {
+            return app_column;
+        }
+    }
+    false
+}
+
+/// Returns how numbers should be formatted.  The decimal separator and digit grouping come from
+/// the locale if `locale_number_format` is set, unless they are also set themselves.
+pub fn get_number_format(config: &Config) -> error::Result<NumberFormat> {
+    let flags = match &config.flags {
+        Some(flags) => flags,
+        None => return Ok(NumberFormat::default()),
+    };
+
+    let mut number_format = if flags.locale_number_format.unwrap_or(false) {
+        NumberFormat::from_locale()
+    } else {
+        NumberFormat::default()
+    };
+
+    if let Some(decimal_separator) = &flags.decimal_separator {
+        let mut chars = decimal_separator.chars();
+        number_format.decimal_separator = match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_ascii_digit() && c != '-' => c,
+            _ => {
+                return Err(BottomError::ConfigError(
+                    "set your decimal_separator to a single character that isn't a digit."
+                        .to_string(),
+                ))
+            }
+        };
+    }
+
+    if let Some(digit_grouping) = &flags.digit_grouping {
+        let mut chars = digit_grouping.chars();
+        number_format.digit_grouping = match (chars.next(), chars.next()) {
+            (None, _) => None,
+            (Some(c), None) if !c.is_ascii_digit() && c != '-' => Some(c),
+            _ => {
+                return Err(BottomError::ConfigError(
+                    "set your digit_grouping to a single character that isn't a digit, or to an empty string."
+                        .to_string(),
+                ))
+            }
+        };
+    }
+
+    if number_format.digit_grouping == Some(number_format.decimal_separator) {
+        return Err(BottomError::ConfigError(
+            "set your decimal_separator and digit_grouping to different characters.".to_string(),
+        ));
+    }
+
+    if let Some(decimal_places) = flags.decimal_places {
+        if decimal_places > 6 {
+            return Err(BottomError::ConfigError(
+                "set your decimal_places to be at most 6.".to_string(),
+            ));
+        }
+        number_format.decimal_places = Some(decimal_places as usize);
+    }
+
+    Ok(number_format)
+}
+
+/// Returns which byte units to use everywhere, if `byte_units` is set.  Otherwise, each widget
+/// keeps its own default.
+pub fn get_config_byte_units(config: &Config) -> error::Result<Option<ByteUnits>> {
+    if let Some(flags) = &config.flags {
+        if let Some(byte_units) = &flags.byte_units {
+            return match byte_units.to_lowercase().as_str() {
+                "si" | "decimal" => Ok(Some(ByteUnits::Si)),
+                "binary" | "iec" => Ok(Some(ByteUnits::Binary)),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{}\" is an invalid byte_units, use either \"si\" or \"binary\".",
+                    byte_units
+                ))),
+            };
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns which process columns to show and in what order, if `process_columns` is set.
+fn get_config_process_columns(
+    config: &Config,
+) -> error::Result<Option<Vec<data_harvester::processes::ProcessSorting>>> {
+    if let Some(flags) = &config.flags {
+        if let Some(process_columns) = &flags.process_columns {
+            if process_columns.is_empty() {
+                return Err(BottomError::ConfigError(
+                    "set your process_columns to at least one column.".to_string(),
+                ));
+            }
+
+            return process_columns
+                .iter()
+                .map(|column| column.parse::<data_harvester::processes::ProcessSorting>())
+                .collect::<error::Result<Vec<_>>>()
+                .map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+fn get_enable_gpu_memory(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<bool> {
+    let enable_gpu_memory = if matches.is_present("enable_gpu_memory") {
+        true
+    } else if let Some(flags) = &config.flags {
+        flags.enable_gpu_memory.unwrap_or(false)
+    } else {
+        false
+    };
+
+    if enable_gpu_memory && cfg!(not(any(target_os = "linux", feature = "nvidia"))) {
+        return Err(BottomError::ConfigError(
+            "showing GPU memory usage outside of Linux requires bottom to be built with the \"nvidia\" feature."
+                .to_string(),
+        ));
+    }
+
+    Ok(enable_gpu_memory)
+}
+
+fn get_enable_gpu_usage(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("enable_gpu_usage") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(enable_gpu_usage) = flags.enable_gpu_usage {
+            return enable_gpu_usage;
+        }
+    }
+    false
+}
+
+fn get_gpu_memory_rate_in_milliseconds(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<u64> {
+    let gpu_memory_rate = if let Some(gpu_memory_rate) = matches.value_of("gpu_memory_rate") {
+        gpu_memory_rate.parse::<u64>()?
+    } else if let Some(flags) = &config.flags {
+        flags
+            .gpu_memory_rate
+            .unwrap_or(DEFAULT_GPU_MEMORY_RATE_IN_MILLISECONDS)
+    } else {
+        DEFAULT_GPU_MEMORY_RATE_IN_MILLISECONDS
+    };
+
+    if gpu_memory_rate < 250 {
+        return Err(BottomError::ConfigError(
+            "set your GPU memory rate to be at least 250 milliseconds.".to_string(),
+        ));
+    }
+
+    Ok(gpu_memory_rate)
+}
+
 fn get_show_table_scroll_position(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("show_table_scroll_position") {
         return true;
@@ -987,3 +1679,247 @@ fn get_show_table_scroll_position(matches: &clap::ArgMatches<'static>, config: &
     }
     false
 }
+
+fn get_persist_data(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("persist_data") || get_warm_start(matches, config) {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(persist_data) = flags.persist_data {
+            return persist_data;
+        }
+    }
+    false
+}
+
+fn get_warm_start(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("warm_start") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(warm_start) = flags.warm_start {
+            return warm_start;
+        }
+    }
+    false
+}
+
+fn get_custom_widget_state(
+    name: &str, config: &Config, default_time_value: u64, plugin_host: &mut plugins::PluginHost,
+) -> error::Result<CustomWidgetState> {
+    let custom_widget = config
+        .widget
+        .as_ref()
+        .and_then(|widgets| widgets.iter().find(|widget| widget.name == name))
+        .ok_or_else(|| {
+            BottomError::ConfigError(format!(
+                "no [[widget]] entry was found with the name \"{}\".",
+                name
+            ))
+        })?;
+
+    let kind: CustomWidgetKind = custom_widget.kind.parse()?;
+    let mut custom_widget_state = CustomWidgetState::init(
+        kind,
+        custom_widget
+            .title
+            .clone()
+            .unwrap_or_else(|| custom_widget.name.clone()),
+        custom_widget.max.unwrap_or(100.0),
+        custom_widget.unit.clone().unwrap_or_default(),
+        data_source::create_data_source(&custom_widget.source, plugin_host)?,
+        default_time_value,
+    );
+
+    if let Some(secondary_source) = &custom_widget.secondary_source {
+        if kind != CustomWidgetKind::Graph {
+            return Err(BottomError::ConfigError(format!(
+                "the widget \"{}\" has a secondary_source, which is only supported by graphs.",
+                name
+            )));
+        }
+
+        custom_widget_state.secondary = Some(SecondarySeries::init(
+            custom_widget.secondary_max.unwrap_or(100.0),
+            custom_widget.secondary_unit.clone().unwrap_or_default(),
+            data_source::create_data_source(secondary_source, plugin_host)?,
+        ));
+    }
+
+    Ok(custom_widget_state)
+}
+
+fn get_snapshot_path(matches: &clap::ArgMatches<'static>, config: &Config) -> Option<PathBuf> {
+    if let Some(snapshot_path) = matches.value_of("snapshot_path") {
+        return Some(PathBuf::from(snapshot_path));
+    } else if let Some(flags) = &config.flags {
+        if let Some(snapshot_path) = &flags.snapshot_path {
+            return Some(PathBuf::from(snapshot_path));
+        }
+    }
+    None
+}
+
+fn get_hook_command(matches: &clap::ArgMatches<'static>, config: &Config) -> Option<String> {
+    if let Some(hook_command) = matches.value_of("hook_command") {
+        return Some(hook_command.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(hook_command) = &flags.hook_command {
+            return Some(hook_command.clone());
+        }
+    }
+    None
+}
+
+fn get_process_name_source(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<data_harvester::processes::ProcessNameSource> {
+    if let Some(name_source) = matches.value_of("process_name_source") {
+        return data_harvester::processes::ProcessNameSource::from_str(name_source);
+    } else if let Some(flags) = &config.flags {
+        if let Some(name_source) = &flags.process_name_source {
+            return data_harvester::processes::ProcessNameSource::from_str(name_source);
+        }
+    }
+    Ok(data_harvester::processes::ProcessNameSource::default())
+}
+
+fn get_hook_interval(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<u64> {
+    if let Some(hook_interval) = matches.value_of("hook_interval") {
+        return Ok(hook_interval.parse::<u64>()?);
+    } else if let Some(flags) = &config.flags {
+        if let Some(hook_interval) = flags.hook_interval {
+            return Ok(hook_interval);
+        }
+    }
+    Ok(DEFAULT_HOOK_INTERVAL_IN_MILLISECONDS)
+}
+
+fn get_control_path(matches: &clap::ArgMatches<'static>, config: &Config) -> Option<PathBuf> {
+    if let Some(control_path) = matches.value_of("control_path") {
+        return Some(PathBuf::from(control_path));
+    } else if let Some(flags) = &config.flags {
+        if let Some(control_path) = &flags.control_path {
+            return Some(PathBuf::from(control_path));
+        }
+    }
+    None
+}
+
+fn get_show_process_share(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("show_process_share") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(show_process_share) = flags.show_process_share {
+            return show_process_share;
+        }
+    }
+    false
+}
+
+fn get_max_fps(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<Option<u64>> {
+    let max_fps = if let Some(max_fps) = matches.value_of("max_fps") {
+        Some(max_fps.parse::<u64>()?)
+    } else if let Some(flags) = &config.flags {
+        flags.max_fps
+    } else {
+        None
+    };
+
+    if max_fps == Some(0) {
+        return Err(BottomError::ConfigError(
+            "set your max_fps to be at least 1.".to_string(),
+        ));
+    }
+    Ok(max_fps)
+}
+
+fn get_window_titles(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("window_titles") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(window_titles) = flags.window_titles {
+            return window_titles;
+        }
+    }
+    false
+}
+
+fn get_synchronized_output(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("synchronized_output") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(synchronized_output) = flags.synchronized_output {
+            return synchronized_output;
+        }
+    }
+    false
+}
+
+fn get_hide_removable_disks(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("hide_removable_disks") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(hide_removable_disks) = flags.hide_removable_disks {
+            return hide_removable_disks;
+        }
+    }
+    false
+}
+
+fn get_link_widgets(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("link_widgets") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(link_widgets) = flags.link_widgets {
+            return link_widgets;
+        }
+    }
+    false
+}
+
+fn get_show_graph_stats(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("show_graph_stats") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(show_graph_stats) = flags.show_graph_stats {
+            return show_graph_stats;
+        }
+    }
+    false
+}
+
+fn get_stacked_cpu(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("stacked_cpu") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(stacked_cpu) = flags.stacked_cpu {
+            return stacked_cpu;
+        }
+    }
+    false
+}
+
+fn get_graph_thresholds(config: &Config) -> error::Result<GraphThresholds> {
+    let thresholds = config.thresholds.clone().unwrap_or_default();
+    let cpu = thresholds.cpu.unwrap_or_default();
+    let mem = thresholds.mem.unwrap_or_default();
+    let network = thresholds.network.unwrap_or_default();
+
+    if cpu
+        .iter()
+        .chain(mem.iter())
+        .any(|percent| !(0.0..=100.0).contains(percent))
+    {
+        return Err(BottomError::ConfigError(
+            "set your CPU and memory thresholds to be between 0 and 100.".to_string(),
+        ));
+    } else if network
+        .iter()
+        .any(|bytes| !bytes.is_finite() || *bytes <= 0.0)
+    {
+        return Err(BottomError::ConfigError(
+            "set your network thresholds to be greater than 0.".to_string(),
+        ));
+    }
+
+    Ok(GraphThresholds { cpu, mem, network })
+}
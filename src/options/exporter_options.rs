@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents a destination that metrics are pushed to on every collection tick, defined via
+/// an `[[exporter]]` table.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+pub struct ExporterConfig {
+    /// The type of exporter: one of "statsd", "mqtt", "influxdb", "otlp", or "record".
+    pub kind: String,
+
+    /// Where to send metrics to, as `host:port`.  For InfluxDB, this is a URL starting with
+    /// `file://`, `udp://`, `tcp://`, or `http://`, for OTLP, a URL starting with `http://`, and
+    /// for recordings, the path of the file.
+    pub address: String,
+
+    /// A prefix for metric names (StatsD) or topics (MQTT).  Defaults to "bottom".
+    pub prefix: Option<String>,
+
+    /// Which metrics to send, by name or category (for example, "cpu" or "mem.percent").
+    /// Defaults to all of them.
+    pub metrics: Option<Vec<String>>,
+
+    /// The MQTT client ID.  Defaults to "bottom".
+    pub client_id: Option<String>,
+
+    /// The MQTT username, if the broker requires one.
+    pub username: Option<String>,
+
+    /// The MQTT password, if the broker requires one.
+    pub password: Option<String>,
+
+    /// The InfluxDB or OTLP API token, sent with HTTP writes.
+    pub token: Option<String>,
+
+    /// How many updates are batched into one InfluxDB write.  Defaults to 1.
+    pub batch_size: Option<usize>,
+
+    /// How many times a failed InfluxDB write is retried before the batch is dropped.
+    /// Defaults to 3.
+    pub retries: Option<u32>,
+
+    /// When a recording is rotated, either after a duration like "1h" or once it reaches a size
+    /// like "100mb".  Recordings aren't rotated by default.
+    pub record_rotate: Option<String>,
+
+    /// How many rotated recordings are kept.  Defaults to 5.
+    pub record_keep: Option<usize>,
+}
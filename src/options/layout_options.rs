@@ -1,5 +1,5 @@
 use crate::app::layout_manager::*;
-use crate::error::Result;
+use crate::error::{BottomError, Result};
 use serde::{Deserialize, Serialize};
 
 /// Represents a row.  This has a length of some sort (optional) and a vector
@@ -32,7 +32,7 @@ impl Row {
                         *iter_id += 1;
                         let width_ratio = widget.ratio.unwrap_or(1);
                         total_col_ratio += width_ratio;
-                        let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                        let widget_type = widget.get_widget_type()?;
 
                         if let Some(default_widget_type_val) = default_widget_type {
                             if *default_widget_type_val == widget_type && *default_widget_count > 0
@@ -128,6 +128,7 @@ impl Row {
                                                     .widget_type(BottomWidgetType::Proc)
                                                     .widget_id(proc_id)
                                                     .width_ratio(2)
+                                                    .default_query(widget.default_query.clone())
                                                     .build(),
                                             ])
                                             .total_widget_ratio(3)
@@ -164,7 +165,7 @@ impl Row {
                         let mut col_row_children: Vec<BottomColRow> = Vec::new();
 
                         for widget in child {
-                            let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                            let widget_type = widget.get_widget_type()?;
                             *iter_id += 1;
                             let col_row_height_ratio = widget.ratio.unwrap_or(1);
                             total_col_row_ratio += col_row_height_ratio;
@@ -265,6 +266,7 @@ impl Row {
                                                     .widget_type(BottomWidgetType::Proc)
                                                     .widget_id(proc_id)
                                                     .width_ratio(2)
+                                                    .default_query(widget.default_query.clone())
                                                     .build(),
                                             ])
                                             .col_row_height_ratio(col_row_height_ratio)
@@ -351,4 +353,31 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    pub name: Option<String>,
+    /// The search query a process widget starts with.
+    pub default_query: Option<String>,
+}
+
+impl FinalWidget {
+    /// Parses the widget's type, attaching the name for custom widgets.
+    fn get_widget_type(&self) -> Result<BottomWidgetType> {
+        if self.default_query.is_some()
+            && self.widget_type.parse::<BottomWidgetType>()? != BottomWidgetType::Proc
+        {
+            return Err(BottomError::ConfigError(
+                "\"default_query\" can only be set for widgets of type \"proc\".".to_string(),
+            ));
+        }
+
+        match self.widget_type.parse::<BottomWidgetType>()? {
+            BottomWidgetType::Custom(_) => match &self.name {
+                Some(name) => Ok(BottomWidgetType::Custom(name.clone())),
+                None => Err(BottomError::ConfigError(
+                    "a widget of type \"custom\" requires a \"name\" matching a [[widget]] entry."
+                        .to_string(),
+                )),
+            },
+            widget_type => Ok(widget_type),
+        }
+    }
 }
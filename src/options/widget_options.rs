@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents a generic widget defined entirely in the config file, via a `[[widget]]` table.
+/// These are placed in a layout by using a widget of `type = "custom"` with a matching `name`.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+pub struct CustomWidget {
+    /// The name used to refer to this widget in a layout.
+    pub name: String,
+
+    /// How the widget is drawn: one of "table", "graph", "gauge", or "text".
+    pub kind: String,
+
+    /// What data the widget is bound to, for example "cpu.avg" or "mem.percent".
+    pub source: String,
+
+    /// The title drawn on the widget's border.  Defaults to the name.
+    pub title: Option<String>,
+
+    /// The upper bound for gauges and graphs.  Defaults to 100.
+    pub max: Option<f64>,
+
+    /// A unit appended to displayed values.
+    pub unit: Option<String>,
+
+    /// A second source for graphs, drawn against its own y-axis on the right.
+    pub secondary_source: Option<String>,
+
+    /// The upper bound of the secondary y-axis.  Defaults to 100.
+    pub secondary_max: Option<f64>,
+
+    /// A unit appended to displayed values of the secondary source.
+    pub secondary_unit: Option<String>,
+}
+
+/// Represents an external data source, defined via a `[[plugin]]` table.  Widgets can bind to
+/// it with a source of `plugin.<name>`.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+pub struct PluginConfig {
+    /// The name used to refer to this plugin in a widget's source.
+    pub name: String,
+
+    /// The program to run.
+    pub command: String,
+
+    /// Any arguments passed to the program.
+    pub args: Option<Vec<String>>,
+}
@@ -0,0 +1,2292 @@
+//! The query language used to search processes, disks, and temperature sensors.  Queries are
+//! parsed with [`parse`], and can then be checked against anything that implements
+//! [`Filterable`], so the same syntax can be used outside of bottom's widgets.
+//!
+//! The language is as follows, with case-insensitive prefixes:
+//!
+//! - Process names: No prefix required, can use regex, match word, or case.
+//!   Enclosing anything, including prefixes, in quotes, means we treat it as an entire process
+//!   rather than a prefix.
+//! - PIDs: Use prefix `pid`, can use regex or match word (case is irrelevant).  `pid = 123`
+//!   matches that PID exactly, and can compare, while `pid 123` matches the PID as text, like
+//!   `1234`.  Sets of PIDs can be matched with `pid in (123, 456)` or `pid = 123,456`, which are
+//!   expanded into ORs, and ranges with `pid 1000-2000` as well as the usual range syntax.
+//! - CPU: Use prefix `cpu`, cannot use r/m/c (regex, match word, case).  Can compare.
+//! - MEM: Use prefix `mem`, cannot use r/m/c.  Can compare.
+//! - STATE: Use prefix `state`, can use regex, match word, or case.  Also matches common state
+//!   names like `running` or `zombie`, regardless of how the OS names them.
+//! - Read/s: Use prefix `r`.  Can compare.
+//! - Write/s: Use prefix `w`.  Can compare.
+//! - Total read: Use prefix `read`.  Can compare.
+//! - Total write: Use prefix `write`.  Can compare.
+//! - USER: Use prefix `user` or `uid`, can use regex, match word, or case.  Matches the name or
+//!   UID of the owning user.
+//! - Connections: Use prefix `conns`.  Can compare.  Only collected on Linux, and only while used.
+//! - Threads: Use prefix `threads` or `nth`.  Can compare.  Only collected on Linux.
+//! - Nice value: Use prefix `nice`.  Can compare, including with negative values like `-5`.
+//!   Only collected on Linux.
+//! - Priority: Use prefix `priority` or `prio`.  Can compare, including with negative values.
+//!   Only collected on Linux.
+//! - File descriptors: Use prefix `fds`.  Can compare.  Only collected on Linux, and only while
+//!   used.
+//! - Command: Use prefix `args` or `cmd`, can use regex, match word, or case.  Matches the full
+//!   command line, regardless of whether the command or name is shown.
+//! - GPU: Use prefix `gpu`.  Can compare.  Only collected while used.
+//! - GPU memory: Use prefix `gpumem` or `gmem`.  Can compare, and supports byte units.  Only
+//!   collected while used.
+//! - Run time: Use prefix `time`.  Can compare, in seconds or with a duration unit like `5m`.
+//! - Parent PID: Use prefix `ppid`.  Can compare.
+//! - Suspicious: Use prefix `suspicious`, can use regex, match word, or case.  Matches why a
+//!   process looks suspicious, which is `deleted` if its executable was deleted, or `temp` if
+//!   it runs from a temporary directory.
+//! - Security context: Use prefix `context` or `label`, can use regex, match word, or case.
+//!   Matches the SELinux context or AppArmor profile.  Only collected on Linux, and only while
+//!   used.
+//! - Namespaces: Use prefix `ns` or `namespace`, can use regex, match word, or case.  Matches
+//!   which of the `pid`, `net`, and `mnt` namespaces the process doesn't share with the host,
+//!   like `pid,net`.  Only collected on Linux, and only while used.
+//! - Executable: Use prefix `exe`, can use regex, match word, or case.  Matches the path of the
+//!   executable of the process, which can differ from its name.
+//! - Cgroup: Use prefix `cgroup` or `container`, can use regex, match word, or case.  Matches
+//!   the cgroup path of the process, like `/system.slice/docker-<ID>.scope`, so a container's
+//!   processes can be selected by its ID.  Only collected on Linux, and only while used.
+//! - Environment: Use prefix `env`, can use regex, match word, or case.  Matches any of the
+//!   `KEY=value` entries of the environment, like `env = "DISPLAY=:1"`, or just the value of
+//!   one variable with `env.KEY`, like `env.DISPLAY = :1`.  Only collected on Linux, and only
+//!   while used.
+//! - Media: Use prefix `media`, can use regex, match word, or case.  Matches what media the
+//!   process is using: `cam` for the camera, `mic` for the microphone, and `audio` for playing
+//!   audio.  Only collected on Linux, and only while used.
+//! - Window title: Use prefix `title` or `window`, can use regex, match word, or case.  Matches
+//!   the titles of the windows of the process, like the page a browser is on.  Only collected on
+//!   Linux, from X11 or sway, and only while used.
+//!
+//! Disk and temperature searches, picked with [`QueryOptions::search_target`], have their own
+//! prefixes instead: `mount`, `used`, `free`, and `total` for disks, and `temp` for temperature
+//! sensors.  Any other word, like `cpu` in a disk search or `free` in a process search, is a name.
+//!
+//! Anything that can compare also supports inclusive ranges, written as `cpu 10..50` or
+//! `mem between 100 mb and 1 gb`, which are expanded into an AND of a `>=` and a `<=`.
+//!
+//! Query aliases from the config file, or [`QueryOptions::query_aliases`], can be used with
+//! `@name`, and are replaced by their query in parentheses before anything else is done.
+//! Aliases can use other aliases, but not themselves.
+//!
+//! Prefixes can also be given other names with [`QueryOptions::prefix_aliases`], which is how
+//! columns renamed with `column_aliases` in the config file can be searched by their new name,
+//! like `latency > 100` for a CPU column named `latency`.  These work anywhere a prefix does,
+//! including with search modifiers like `latency~r`.
+//!
+//! For queries, whitespaces are our delimiters.  We will merge together any adjacent non-prefixed
+//! or quoted elements after splitting to treat as process names.
+//! Furthermore, we want to support boolean joiners like AND and OR, negation with NOT, and
+//! brackets.
+
+use crate::utils::{
+    error::{
+        BottomError::{self, QueryError},
+        Result,
+    },
+    gen_util::{get_byte_units, ByteUnits},
+};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::ops::Range;
+
+const DELIMITER_LIST: [char; 7] = ['=', '>', '<', '(', ')', '\"', '!'];
+const COMPARISON_LIST: [&str; 3] = [">", "=", "<"];
+const OR_LIST: [&str; 2] = ["or", "||"];
+const AND_LIST: [&str; 2] = ["and", "&&"];
+const NOT_LIST: [&str; 2] = ["not", "!"];
+
+/// The prefixes and keywords suggested while typing a search, with the preferred ones first if
+/// several could complete what is being typed.
+const COMPLETION_LIST: [&str; 44] = [
+    "cpu",
+    "mem",
+    "memb",
+    "pid",
+    "ppid",
+    "read",
+    "write",
+    "time",
+    "tread",
+    "twrite",
+    "state",
+    "user",
+    "conns",
+    "threads",
+    "nth",
+    "fds",
+    "nice",
+    "priority",
+    "args",
+    "cmd",
+    "gpu",
+    "gpumem",
+    "gmem",
+    "context",
+    "label",
+    "suspicious",
+    "ns",
+    "namespace",
+    "env",
+    "exe",
+    "cgroup",
+    "container",
+    "title",
+    "window",
+    "media",
+    "mount",
+    "used",
+    "free",
+    "total",
+    "temp",
+    "name",
+    "and",
+    "or",
+    "not",
+];
+
+/// Returns what to add to the end of a search query to finish the prefix or keyword being typed,
+/// if any.  If several could, the first in [`COMPLETION_LIST`] is used.  Nothing is suggested
+/// for words that are already finished, or inside quotes.
+pub fn get_query_completion(
+    search_query: &str, search_target: SearchTarget,
+) -> Option<&'static str> {
+    if ends_in_quotes(search_query) {
+        return None;
+    }
+
+    let word_start = tokenize_query(search_query)
+        .pop()
+        .filter(|token| !token.is_quote && token.span.end == search_query.len())
+        .map_or(search_query.len(), |token| token.span.start);
+    let word = &search_query[word_start..];
+    if word.is_empty() {
+        return None;
+    }
+
+    let is_keyword = |word: &str| {
+        let word = word.to_lowercase();
+        OR_LIST.contains(&word.as_str())
+            || AND_LIST.contains(&word.as_str())
+            || NOT_LIST.contains(&word.as_str())
+    };
+
+    // Whatever follows a prefix or a comparison is a value, so don't suggest anything there.
+    // Otherwise, a keyword is more likely than a prefix right after a finished condition.
+    let preceding_text = search_query[..word_start].trim_end();
+    let preceding_word = preceding_text
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default();
+    if (!preceding_word.is_empty() && is_prefix_word(preceding_word, search_target))
+        || preceding_text.ends_with(&['=', '>', '<'][..])
+    {
+        return None;
+    }
+    let is_expecting_keyword = !(preceding_text.is_empty()
+        || preceding_text.ends_with(&['(', '!'][..])
+        || is_keyword(preceding_word));
+
+    let mut candidates = COMPLETION_LIST
+        .iter()
+        .filter(|candidate| {
+            (is_keyword(candidate) || is_prefix_word(candidate, search_target))
+                && candidate.len() >= word.len()
+                && candidate[..word.len()].eq_ignore_ascii_case(word)
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|candidate| is_keyword(candidate) != is_expecting_keyword);
+
+    if candidates
+        .iter()
+        .any(|candidate| candidate.len() == word.len())
+    {
+        None
+    } else {
+        candidates.first().map(|candidate| &candidate[word.len()..])
+    }
+}
+
+/// Returns whether a word of a query is a prefix of the given search, like `cpu` or `name~r`,
+/// rather than a value.
+pub fn is_prefix_word(word: &str, search_target: SearchTarget) -> bool {
+    word.eq_ignore_ascii_case("name")
+        || split_search_modifiers(word, search_target).is_some()
+        || !matches!(search_target.get_prefix_type(word), PrefixType::Name)
+}
+
+/// Replaces a word that is an alias of a prefix, like `latency` or `latency~r`, with the prefix
+/// it stands for, keeping any search modifiers.  Anything else is returned as-is.
+fn resolve_prefix_alias(word: String, prefix_aliases: &HashMap<String, String>) -> String {
+    let (name, modifiers) = match word.split_once('~') {
+        Some((name, modifiers)) => (name, Some(modifiers)),
+        None => (word.as_str(), None),
+    };
+
+    match (prefix_aliases.get(&name.to_lowercase()), modifiers) {
+        (Some(prefix), Some(modifiers)) => format!("{}~{}", prefix, modifiers),
+        (Some(prefix), None) => prefix.clone(),
+        (None, _) => word,
+    }
+}
+
+/// Returns whether a word already means something at the start of a condition, like a prefix or
+/// a keyword such as `and`, so it can't be used as the name of a prefix alias.
+pub fn is_reserved_word(word: &str) -> bool {
+    let lower_case = word.to_lowercase();
+    is_prefix_word(word, SearchTarget::Processes)
+        || OR_LIST.contains(&lower_case.as_str())
+        || AND_LIST.contains(&lower_case.as_str())
+        || NOT_LIST.contains(&lower_case.as_str())
+        || ["in", "between"].contains(&lower_case.as_str())
+}
+
+/// Returns whether a character can be part of the name of a query alias.
+pub fn is_query_alias_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Replaces every `@name` in a query with the query of the alias `name`, in parentheses.  Aliases
+/// inside of these are expanded too, and an alias that ends up using itself is an error.
+/// Anything inside quotes is left alone.
+///
+/// Along with the expanded query, returns where each of its bytes came from in the given query,
+/// so anything from an alias maps back to its `@name`.  Errors also come with where the alias
+/// they are about is.
+fn expand_query_aliases(
+    search_query: &str, query_aliases: &HashMap<String, String>, expanding: &mut Vec<String>,
+) -> std::result::Result<(String, Vec<Range<usize>>), (BottomError, Range<usize>)> {
+    let mut expanded_query = String::with_capacity(search_query.len());
+    let mut origins = Vec::with_capacity(search_query.len());
+    let mut is_in_quotes = false;
+    let mut chars = search_query.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let is_alias_start = c == '@' && !is_in_quotes && is_term_boundary(&search_query[..index]);
+        if !is_alias_start {
+            if c == '"' {
+                is_in_quotes = !is_in_quotes;
+            }
+            expanded_query.push(c);
+            origins.extend(std::iter::repeat_n(
+                index..index + c.len_utf8(),
+                c.len_utf8(),
+            ));
+
+            // Keep an escaped character as-is, so an escaped quote or "@" isn't mistaken for a
+            // real one.
+            if c == '\\' {
+                if let Some((index, next)) =
+                    chars.next_if(|(_, next)| is_escapable(*next, is_in_quotes))
+                {
+                    expanded_query.push(next);
+                    origins.extend(std::iter::repeat_n(
+                        index..index + next.len_utf8(),
+                        next.len_utf8(),
+                    ));
+                }
+            }
+            continue;
+        }
+
+        let mut name = String::new();
+        while let Some((_, next)) = chars.next_if(|(_, next)| is_query_alias_char(*next)) {
+            name.push(next);
+        }
+        let alias_span = index..index + 1 + name.len();
+        if name.is_empty() {
+            return Err((
+                QueryError("Missing query alias name after \"@\"".into()),
+                alias_span,
+            ));
+        }
+
+        let alias_query = match query_aliases.get(&name) {
+            Some(alias_query) => alias_query,
+            None => {
+                return Err((
+                    QueryError(format!("Unknown query alias \"@{}\"", name).into()),
+                    alias_span,
+                ))
+            }
+        };
+        if expanding.contains(&name) {
+            return Err((
+                QueryError(
+                    format!("Query alias \"@{}\" is defined in terms of itself", name).into(),
+                ),
+                alias_span,
+            ));
+        }
+
+        expanding.push(name);
+        let (expanded_alias, _) = expand_query_aliases(alias_query, query_aliases, expanding)
+            .map_err(|(err, _)| (err, alias_span.clone()))?;
+        expanding.pop();
+
+        expanded_query.push('(');
+        expanded_query.push_str(&expanded_alias);
+        expanded_query.push(')');
+        origins.extend(std::iter::repeat_n(alias_span, expanded_alias.len() + 2));
+    }
+
+    Ok((expanded_query, origins))
+}
+
+/// A token of a query, along with where it is in the query.
+struct Token {
+    text: String,
+    span: Range<usize>,
+    /// Whether this is a quote mark around a quoted string, rather than text.
+    is_quote: bool,
+}
+
+/// Returns whether a backslash followed by the given character is an escape, which stands for
+/// just that character.  Inside quotes, only quotes and backslashes can be escaped.
+fn is_escapable(c: char, is_in_quotes: bool) -> bool {
+    c == '"'
+        || c == '\\'
+        || (!is_in_quotes && (c.is_whitespace() || c == '@' || DELIMITER_LIST.contains(&c)))
+}
+
+/// Returns whether a new term starts right after the given text.  A `!` is only an operator at the
+/// start of a term, so one that follows text, like in `foo!`, is part of that text.
+fn is_term_boundary(text_before: &str) -> bool {
+    let is_delimiter = |c: char| c.is_whitespace() || DELIMITER_LIST.contains(&c);
+    let mut chars = text_before.chars().rev();
+    match chars.next() {
+        None => true,
+        Some('!') => chars.next().is_none_or(is_delimiter),
+        Some(prev) => is_delimiter(prev),
+    }
+}
+
+/// Returns whether the end of a query is inside quotes.
+fn ends_in_quotes(search_query: &str) -> bool {
+    let mut is_in_quotes = false;
+    let mut chars = search_query.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && chars
+                .next_if(|next| is_escapable(*next, is_in_quotes))
+                .is_some()
+        {
+            continue;
+        } else if c == '"' {
+            is_in_quotes = !is_in_quotes;
+        }
+    }
+
+    is_in_quotes
+}
+
+/// Splits a query into its tokens, along with where each of them is in the query.  Words are
+/// split on whitespace and delimiters, while a quoted string is kept as one token between two
+/// quote marks, so its spaces and delimiters are preserved.
+///
+/// A backslash escapes the character after it if that is a quote or a backslash, or outside of
+/// quotes, whitespace, a delimiter, or `@`.  A word with an escape in it is treated as if it was
+/// quoted, so something like `Google\ Chrome` is the same as `"Google Chrome"`.  Any other
+/// backslash is kept as-is, so regexes like `\d+` still work.
+fn tokenize_query(search_query: &str) -> Vec<Token> {
+    /// Adds the word being read, if there is one, to the tokens.
+    fn push_word(
+        tokens: &mut Vec<Token>, word: &mut String, word_span: &mut Option<Range<usize>>,
+        is_escaped: &mut bool,
+    ) {
+        if let Some(span) = word_span.take() {
+            let text = std::mem::take(word);
+            if *is_escaped {
+                let first_len = text.chars().next().map_or(0, char::len_utf8);
+                let last_len = text.chars().next_back().map_or(0, char::len_utf8);
+                tokens.push(Token {
+                    text: "\"".to_owned(),
+                    span: span.start..span.start + first_len,
+                    is_quote: true,
+                });
+                tokens.push(Token {
+                    text,
+                    span: span.clone(),
+                    is_quote: false,
+                });
+                tokens.push(Token {
+                    text: "\"".to_owned(),
+                    span: span.end - last_len..span.end,
+                    is_quote: true,
+                });
+            } else {
+                tokens.push(Token {
+                    text,
+                    span,
+                    is_quote: false,
+                });
+            }
+        }
+        *is_escaped = false;
+    }
+
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut word_span: Option<Range<usize>> = None;
+    let mut is_escaped = false;
+    let mut chars = search_query.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c == '\\' {
+            let escaped = chars.next_if(|(_, next)| is_escapable(*next, false));
+            let (end, c) = match escaped {
+                Some((next_index, next)) => {
+                    is_escaped = true;
+                    (next_index + next.len_utf8(), next)
+                }
+                None => (index + 1, c),
+            };
+            word.push(c);
+            word_span.get_or_insert(index..end).end = end;
+        } else if c == '"' {
+            push_word(&mut tokens, &mut word, &mut word_span, &mut is_escaped);
+            tokens.push(Token {
+                text: "\"".to_owned(),
+                span: index..index + 1,
+                is_quote: true,
+            });
+
+            let mut quoted_string = String::new();
+            let quoted_start = index + 1;
+            let mut quoted_end = quoted_start;
+            let mut closing_quote = None;
+            while let Some((index, c)) = chars.next() {
+                if c == '"' {
+                    closing_quote = Some(index);
+                    break;
+                } else if c == '\\' {
+                    if let Some((next_index, next)) =
+                        chars.next_if(|(_, next)| is_escapable(*next, true))
+                    {
+                        quoted_string.push(next);
+                        quoted_end = next_index + next.len_utf8();
+                        continue;
+                    }
+                }
+                quoted_string.push(c);
+                quoted_end = index + c.len_utf8();
+            }
+
+            if !quoted_string.is_empty() {
+                tokens.push(Token {
+                    text: quoted_string,
+                    span: quoted_start..quoted_end,
+                    is_quote: false,
+                });
+            }
+            if let Some(index) = closing_quote {
+                tokens.push(Token {
+                    text: "\"".to_owned(),
+                    span: index..index + 1,
+                    is_quote: true,
+                });
+            }
+        } else if c.is_whitespace() {
+            push_word(&mut tokens, &mut word, &mut word_span, &mut is_escaped);
+        } else if DELIMITER_LIST.contains(&c)
+            && (c != '!'
+                || word_span.is_none()
+                || chars.peek().is_some_and(|(_, next)| *next == '='))
+        {
+            // A "!" inside of a word, like `foo!bar`, is just part of it, unless it starts a "!=".
+            push_word(&mut tokens, &mut word, &mut word_span, &mut is_escaped);
+            tokens.push(Token {
+                text: c.to_string(),
+                span: index..index + c.len_utf8(),
+                is_quote: false,
+            });
+        } else {
+            word.push(c);
+            let end = index + c.len_utf8();
+            word_span.get_or_insert(index..end).end = end;
+        }
+    }
+    push_word(&mut tokens, &mut word, &mut word_span, &mut is_escaped);
+
+    tokens
+}
+
+/// Translates a glob into the equivalent regex, without anchors.  `*` matches anything, `?` matches
+/// any one character, and `[...]` matches any one of the characters inside, or any character
+/// except those if it starts with `!` or `^`.  Everything else is matched literally.
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex_string = String::with_capacity(glob.len() * 2);
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex_string.push_str(".*"),
+            '?' => regex_string.push('.'),
+            '[' if is_glob_class(chars.clone()) => {
+                regex_string.push('[');
+                if chars.next_if(|c| *c == '!' || *c == '^').is_some() {
+                    regex_string.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    } else if ['\\', '[', '^', '&', '~'].contains(&c) {
+                        regex_string.push('\\');
+                    }
+                    regex_string.push(c);
+                }
+                regex_string.push(']');
+            }
+            c => regex_string.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    regex_string
+}
+
+/// Returns whether the rest of a glob after a `[` is a character class, which needs something to
+/// match inside and a closing `]`.  Otherwise, the `[` is matched literally.
+fn is_glob_class(chars: impl Iterator<Item = char>) -> bool {
+    let mut chars = chars.peekable();
+    chars.next_if(|c| *c == '!' || *c == '^');
+    match chars.next() {
+        Some(']') | None => false,
+        Some(_) => chars.any(|c| c == ']'),
+    }
+}
+
+/// What kind of part of a query some text is, for highlighting it in the search bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryHighlight {
+    Prefix,
+    Operator,
+    Number,
+    Quoted,
+}
+
+/// Returns which parts of a query are prefixes, operators, numbers, and quoted strings, so they can
+/// be highlighted while the query is typed.  Anything else, like process names, is left out.
+/// Prefix aliases are highlighted as prefixes.
+pub fn get_query_highlights(
+    search_query: &str, prefix_aliases: &HashMap<String, String>, search_target: SearchTarget,
+) -> Vec<(Range<usize>, QueryHighlight)> {
+    let mut highlights = Vec::new();
+    let mut is_in_quotes = false;
+    let mut is_expecting_value = false;
+
+    for Token {
+        text: token,
+        span,
+        is_quote,
+    } in tokenize_query(search_query)
+    {
+        let lower_case = token.to_lowercase();
+        let highlight = if is_quote {
+            is_in_quotes = !is_in_quotes;
+            is_expecting_value = false;
+            QueryHighlight::Quoted
+        } else if is_in_quotes {
+            QueryHighlight::Quoted
+        } else if COMPARISON_LIST.contains(&lower_case.as_str())
+            || ["!", "(", ")"].contains(&lower_case.as_str())
+            || (is_expecting_value && ["in", "between"].contains(&lower_case.as_str()))
+        {
+            // Whatever follows a comparison is still the value of the prefix before it.
+            QueryHighlight::Operator
+        } else if OR_LIST.contains(&lower_case.as_str())
+            || AND_LIST.contains(&lower_case.as_str())
+            || NOT_LIST.contains(&lower_case.as_str())
+        {
+            is_expecting_value = false;
+            QueryHighlight::Operator
+        } else if token
+            .strip_prefix('-')
+            .unwrap_or(&token)
+            .starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        {
+            is_expecting_value = false;
+            QueryHighlight::Number
+        } else if !is_expecting_value
+            && is_prefix_word(
+                &resolve_prefix_alias(token.clone(), prefix_aliases),
+                search_target,
+            )
+        {
+            is_expecting_value = true;
+            QueryHighlight::Prefix
+        } else {
+            is_expecting_value = false;
+            continue;
+        };
+
+        highlights.push((span, highlight));
+    }
+
+    highlights
+}
+
+/// An error from parsing a query, along with where in the query the problem is, if we know.
+#[derive(Debug)]
+pub struct QueryParseError {
+    pub error: BottomError,
+    /// The bytes of the query the error is about.  This is empty and at the end of the query if
+    /// something is missing from its end.
+    pub span: Option<Range<usize>>,
+}
+
+impl std::fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+/// The tokens of a query that have yet to be parsed, which keeps track of where each of them is
+/// in the query so errors can point at the token they are about.
+struct TokenQueue {
+    tokens: VecDeque<Token>,
+    /// Where the most recently taken token is.
+    last_span: Range<usize>,
+    /// Whether the most recently taken token is a quote mark.
+    last_is_quote: bool,
+    /// Where an error is, if it isn't at the next token.
+    error_span: Option<Range<usize>>,
+    query_len: usize,
+    /// Other names for prefixes, by their lowercase name.
+    prefix_aliases: HashMap<String, String>,
+    search_target: SearchTarget,
+}
+
+impl TokenQueue {
+    fn new(
+        tokens: Vec<Token>, query_len: usize, prefix_aliases: HashMap<String, String>,
+        search_target: SearchTarget,
+    ) -> Self {
+        TokenQueue {
+            tokens: tokens.into(),
+            last_span: 0..0,
+            last_is_quote: false,
+            error_span: None,
+            query_len,
+            prefix_aliases,
+            search_target,
+        }
+    }
+
+    fn front(&self) -> Option<&String> {
+        self.tokens.front().map(|token| &token.text)
+    }
+
+    fn pop_front(&mut self) -> Option<String> {
+        let token = self.tokens.pop_front()?;
+        self.last_span = token.span;
+        self.last_is_quote = token.is_quote;
+        Some(token.text)
+    }
+
+    /// Returns whether the next token is a quote mark, rather than text that happens to be one.
+    fn is_quote_next(&self) -> bool {
+        self.tokens.front().is_some_and(|token| token.is_quote)
+    }
+
+    /// Returns whether the next tokens are a "!=", rather than a "!" negating what follows.
+    fn is_not_equal_next(&self) -> bool {
+        match (self.tokens.front(), self.tokens.get(1)) {
+            (Some(not), Some(equal)) => {
+                not.text == "!" && equal.text == "=" && not.span.end == equal.span.start
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the most recently taken token is a quote mark.
+    fn last_is_quote(&self) -> bool {
+        self.last_is_quote
+    }
+
+    /// Puts back the end of the most recently taken token, like a unit split off of its value.
+    fn push_front(&mut self, token: String) {
+        let start = self
+            .last_span
+            .end
+            .saturating_sub(token.len())
+            .max(self.last_span.start);
+        let span = start..self.last_span.end;
+        self.tokens.push_front(Token {
+            is_quote: self.last_is_quote && token == "\"",
+            text: token,
+            span,
+        });
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Marks an error as being about the most recently taken token, rather than the next one.
+    fn error_at_last_token(&mut self, error: BottomError) -> BottomError {
+        self.error_span = Some(self.last_span.clone());
+        error
+    }
+
+    /// Returns where the last error is, which is the next token unless it was marked otherwise,
+    /// or the end of the query if there are no tokens left.
+    fn get_error_span(&self) -> Range<usize> {
+        self.error_span.clone().unwrap_or_else(|| {
+            self.tokens
+                .front()
+                .map_or(self.query_len..self.query_len, |token| token.span.clone())
+        })
+    }
+}
+
+/// What a query searches, which decides what its prefixes are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchTarget {
+    #[default]
+    Processes,
+    Disks,
+    Temperatures,
+}
+
+impl SearchTarget {
+    /// Returns the prefix a word stands for in this search, which is [`PrefixType::Name`] if it
+    /// isn't one here, even if it is in another search.
+    pub fn get_prefix_type(self, word: &str) -> PrefixType {
+        let prefix_type = word.parse::<PrefixType>().unwrap_or(PrefixType::Name);
+        let is_in_search = match prefix_type {
+            PrefixType::Name => true,
+            PrefixType::Mount
+            | PrefixType::UsedPercent
+            | PrefixType::FreeBytes
+            | PrefixType::TotalBytes => self == SearchTarget::Disks,
+            PrefixType::Temperature => self == SearchTarget::Temperatures,
+            _ => self == SearchTarget::Processes,
+        };
+
+        if is_in_search {
+            prefix_type
+        } else {
+            PrefixType::Name
+        }
+    }
+}
+
+/// Options for how [`parse`] matches text.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryOptions<'a> {
+    /// Whether text has to match whole words.
+    pub is_searching_whole_word: bool,
+    pub is_ignoring_case: bool,
+    /// Whether text is matched as a regex.
+    pub is_searching_with_regex: bool,
+    /// Whether text is matched as a glob, like `fire*`.  Regexes take precedence.
+    pub is_searching_with_glob: bool,
+    /// What any `@name` in the query is replaced by.
+    pub query_aliases: Option<&'a HashMap<String, String>>,
+    /// Other names for prefixes, like `latency` for `cpu`, by their lowercase name.
+    pub prefix_aliases: Option<&'a HashMap<String, String>>,
+    /// What is being searched, which decides what the prefixes are.
+    pub search_target: SearchTarget,
+}
+
+impl Default for QueryOptions<'_> {
+    fn default() -> Self {
+        QueryOptions {
+            is_searching_whole_word: false,
+            is_ignoring_case: true,
+            is_searching_with_regex: false,
+            is_searching_with_glob: false,
+            query_aliases: None,
+            prefix_aliases: None,
+            search_target: SearchTarget::default(),
+        }
+    }
+}
+
+/// Parses a query string as described in the [module documentation](self).  Errors say where in
+/// the query the problem is, if possible.
+pub fn parse(
+    search_query: &str, options: &QueryOptions<'_>,
+) -> std::result::Result<Query, QueryParseError> {
+    let QueryOptions {
+        is_searching_whole_word,
+        is_ignoring_case,
+        is_searching_with_regex,
+        is_searching_with_glob,
+        query_aliases,
+        prefix_aliases,
+        search_target,
+    } = *options;
+    let no_aliases = HashMap::new();
+    let query_aliases = query_aliases.unwrap_or(&no_aliases);
+
+    fn process_string_to_filter(query: &mut TokenQueue) -> Result<Query> {
+        let lhs = process_or(query)?;
+        let mut list_of_ors = vec![lhs];
+
+        while query.front().is_some() {
+            list_of_ors.push(process_or(query)?);
+        }
+
+        Ok(Query { query: list_of_ors })
+    }
+
+    fn process_or(query: &mut TokenQueue) -> Result<Or> {
+        let mut lhs = process_and(query)?;
+        let mut rhs: Option<Box<And>> = None;
+
+        while let Some(queue_top) = query.front() {
+            // debug!("OR QT: {:?}", queue_top);
+            if OR_LIST.contains(&queue_top.to_lowercase().as_str()) {
+                query.pop_front();
+                rhs = Some(Box::new(process_and(query)?));
+
+                if let Some(queue_next) = query.front() {
+                    if OR_LIST.contains(&queue_next.to_lowercase().as_str()) {
+                        // Must merge LHS and RHS
+                        lhs = And {
+                            lhs: Prefix {
+                                or: Some(Box::new(Or { lhs, rhs })),
+                                regex_prefix: None,
+                                compare_prefix: None,
+                                not: None,
+                                modifiers: None,
+                            },
+                            rhs: None,
+                        };
+                        rhs = None;
+                    }
+                } else {
+                    break;
+                }
+            } else if COMPARISON_LIST.contains(&queue_top.to_lowercase().as_str())
+                || query.is_not_equal_next()
+            {
+                return Err(QueryError("Comparison not valid here".into()));
+            } else {
+                break;
+            }
+        }
+
+        Ok(Or { lhs, rhs })
+    }
+
+    fn process_and(query: &mut TokenQueue) -> Result<And> {
+        let mut lhs = process_prefix(query, false)?;
+        let mut rhs: Option<Box<Prefix>> = None;
+
+        while let Some(queue_top) = query.front() {
+            // debug!("AND QT: {:?}", queue_top);
+            if AND_LIST.contains(&queue_top.to_lowercase().as_str()) {
+                query.pop_front();
+
+                rhs = Some(Box::new(process_prefix(query, false)?));
+
+                if let Some(next_queue_top) = query.front() {
+                    if AND_LIST.contains(&next_queue_top.to_lowercase().as_str()) {
+                        // Must merge LHS and RHS
+                        lhs = Prefix {
+                            or: Some(Box::new(Or {
+                                lhs: And { lhs, rhs },
+                                rhs: None,
+                            })),
+                            regex_prefix: None,
+                            compare_prefix: None,
+                            not: None,
+                            modifiers: None,
+                        };
+                        rhs = None;
+                    } else {
+                        break;
+                    }
+                } else {
+                    break;
+                }
+            } else if COMPARISON_LIST.contains(&queue_top.to_lowercase().as_str())
+                || query.is_not_equal_next()
+            {
+                return Err(QueryError("Comparison not valid here".into()));
+            } else {
+                break;
+            }
+        }
+
+        Ok(And { lhs, rhs })
+    }
+
+    fn process_prefix(query: &mut TokenQueue, inside_quotation: bool) -> Result<Prefix> {
+        if let Some(queue_top) = query.pop_front() {
+            // debug!("Prefix QT: {:?}", queue_top);
+
+            if inside_quotation {
+                if query.last_is_quote() {
+                    // This means we hit something like "".  Return an empty prefix, and to deal with
+                    // the close quote checker, add one to the top of the stack.  Ugly fix but whatever.
+                    query.push_front("\"".to_string());
+                    return Ok(Prefix {
+                        or: None,
+                        regex_prefix: Some((
+                            PrefixType::Name,
+                            StringQuery::Value(String::default()),
+                        )),
+                        compare_prefix: None,
+                        not: None,
+                        modifiers: None,
+                    });
+                } else {
+                    let mut quoted_string = queue_top;
+                    while let Some(next_str) = query.front() {
+                        if query.is_quote_next() {
+                            // Stop!
+                            break;
+                        } else {
+                            quoted_string.push_str(next_str);
+                            query.pop_front();
+                        }
+                    }
+                    return Ok(Prefix {
+                        or: None,
+                        regex_prefix: Some((PrefixType::Name, StringQuery::Value(quoted_string))),
+                        compare_prefix: None,
+                        not: None,
+                        modifiers: None,
+                    });
+                }
+            } else if queue_top == "(" {
+                if query.is_empty() {
+                    return Err(QueryError("Missing closing parentheses".into()));
+                }
+
+                let mut list_of_ors = VecDeque::new();
+
+                while let Some(in_paren_query_top) = query.front() {
+                    if in_paren_query_top != ")" {
+                        list_of_ors.push_back(process_or(query)?);
+                    } else {
+                        break;
+                    }
+                }
+
+                // Ensure not empty
+                if list_of_ors.is_empty() {
+                    return Err(QueryError("No values within parentheses group".into()));
+                }
+
+                // Now convert this back to a OR...
+                let initial_or = Or {
+                    lhs: And {
+                        lhs: Prefix {
+                            or: list_of_ors.pop_front().map(Box::new),
+                            compare_prefix: None,
+                            regex_prefix: None,
+                            not: None,
+                            modifiers: None,
+                        },
+                        rhs: None,
+                    },
+                    rhs: None,
+                };
+                let returned_or = list_of_ors.into_iter().fold(initial_or, |lhs, rhs| Or {
+                    lhs: And {
+                        lhs: Prefix {
+                            or: Some(Box::new(lhs)),
+                            compare_prefix: None,
+                            regex_prefix: None,
+                            not: None,
+                            modifiers: None,
+                        },
+                        rhs: Some(Box::new(Prefix {
+                            or: Some(Box::new(rhs)),
+                            compare_prefix: None,
+                            regex_prefix: None,
+                            not: None,
+                            modifiers: None,
+                        })),
+                    },
+                    rhs: None,
+                });
+
+                if let Some(close_paren) = query.pop_front() {
+                    if close_paren == ")" {
+                        return Ok(Prefix {
+                            or: Some(Box::new(returned_or)),
+                            regex_prefix: None,
+                            compare_prefix: None,
+                            not: None,
+                            modifiers: None,
+                        });
+                    } else {
+                        return Err(query.error_at_last_token(QueryError(
+                            "Missing closing parentheses".into(),
+                        )));
+                    }
+                } else {
+                    return Err(QueryError("Missing closing parentheses".into()));
+                }
+            } else if NOT_LIST.contains(&queue_top.to_lowercase().as_str()) {
+                if query.is_empty() {
+                    return Err(QueryError("Missing condition to negate".into()));
+                }
+
+                return Ok(Prefix {
+                    or: None,
+                    regex_prefix: None,
+                    compare_prefix: None,
+                    not: Some(Box::new(process_prefix(query, false)?)),
+                    modifiers: None,
+                });
+            } else if queue_top == ")" {
+                return Err(
+                    query.error_at_last_token(QueryError("Missing opening parentheses".into()))
+                );
+            } else if query.last_is_quote() {
+                // Similar to parentheses, trap and check for missing closing quotes.  Note, however, that we
+                // will DIRECTLY call another process_prefix call...
+
+                let prefix = process_prefix(query, true)?;
+                if query.pop_front().is_some() {
+                    if query.last_is_quote() {
+                        return Ok(prefix);
+                    } else {
+                        return Err(QueryError("Missing closing quotation".into()));
+                    }
+                } else {
+                    return Err(QueryError("Missing closing quotation".into()));
+                }
+            } else {
+                let queue_top = resolve_prefix_alias(queue_top, &query.prefix_aliases);
+
+                // A condition can have its own search options after a "~", like `name~r"^ssh"`.
+                let (queue_top, modifiers) =
+                    match split_search_modifiers(&queue_top, query.search_target) {
+                        Some((prefix, modifiers)) => (
+                            prefix.to_string(),
+                            Some(
+                                modifiers
+                                    .parse::<SearchModifiers>()
+                                    .map_err(|err| query.error_at_last_token(err))?,
+                            ),
+                        ),
+                        None => (queue_top, None),
+                    };
+                let is_name_prefix = queue_top.eq_ignore_ascii_case("name")
+                    || (queue_top.is_empty() && modifiers.is_some());
+
+                //  Get prefix type...
+                let prefix_type = query.search_target.get_prefix_type(&queue_top);
+                let content = if let (PrefixType::Name, false) = (&prefix_type, is_name_prefix) {
+                    Some(queue_top)
+                } else {
+                    query.pop_front()
+                };
+
+                if let Some(content) = content {
+                    match &prefix_type {
+                        PrefixType::Name if !is_name_prefix => {
+                            return Ok(Prefix {
+                                or: None,
+                                regex_prefix: Some((prefix_type, StringQuery::Value(content))),
+                                compare_prefix: None,
+                                not: None,
+                                modifiers: None,
+                            })
+                        }
+                        PrefixType::Name
+                        | PrefixType::Pid
+                        | PrefixType::State
+                        | PrefixType::User
+                        | PrefixType::Command
+                        | PrefixType::SecurityContext
+                        | PrefixType::Suspicious
+                        | PrefixType::Namespace
+                        | PrefixType::Exe
+                        | PrefixType::Cgroup
+                        | PrefixType::WindowTitle
+                        | PrefixType::Media
+                        | PrefixType::Mount
+                        | PrefixType::Env(_)
+                            if !is_numeric_pid_query(&prefix_type, &content, &modifiers, query) =>
+                        {
+                            if let PrefixType::Pid = prefix_type {
+                                if content.to_lowercase() == "in"
+                                    && query.front().is_some_and(|next| next == "(")
+                                {
+                                    query.pop_front();
+                                    let pids = get_pid_set(query)?;
+                                    return Ok(build_pid_or_group(pids));
+                                }
+                            }
+
+                            // A "!=" matches anything the value doesn't, like `user != root`.
+                            let is_not_equal =
+                                content == "!" && query.front().is_some_and(|next| next == "=");
+                            if is_not_equal {
+                                query.pop_front();
+                            }
+
+                            // We have to check if someone put an "="...
+                            let value = if content == "=" || is_not_equal {
+                                // Check next string if possible
+                                query.pop_front()
+                            } else {
+                                Some(content)
+                            };
+
+                            if let Some(value) = value {
+                                let value = if query.last_is_quote() {
+                                    get_quoted_string(query)?
+                                } else {
+                                    value
+                                };
+
+                                let prefix = match prefix_type {
+                                    PrefixType::Pid if value.contains(',') => {
+                                        build_pid_or_group(get_pid_list(value, query)?)
+                                    }
+                                    _ => Prefix {
+                                        or: None,
+                                        regex_prefix: Some((
+                                            prefix_type,
+                                            StringQuery::Value(value),
+                                        )),
+                                        compare_prefix: None,
+                                        not: None,
+                                        modifiers,
+                                    },
+                                };
+
+                                return Ok(if is_not_equal {
+                                    Prefix {
+                                        or: None,
+                                        regex_prefix: None,
+                                        compare_prefix: None,
+                                        not: Some(Box::new(prefix)),
+                                        modifiers: None,
+                                    }
+                                } else {
+                                    prefix
+                                });
+                            } else if is_not_equal {
+                                return Err(QueryError("Missing value".into()));
+                            }
+                        }
+                        _ => {
+                            // Now we gotta parse the content... yay.
+
+                            let mut condition: Option<QueryComparison> = None;
+                            let mut value: Option<f64> = None;
+
+                            if content.to_lowercase() == "between" {
+                                let lower = if let Some(queue_next) = query.pop_front() {
+                                    get_value_and_unit(&queue_next, &prefix_type, query)
+                                } else {
+                                    return Err(QueryError("Missing value".into()));
+                                };
+
+                                if !query.front().is_some_and(|queue_next| {
+                                    AND_LIST.contains(&queue_next.to_lowercase().as_str())
+                                }) {
+                                    return Err(QueryError(
+                                        "Missing \"and\" after lower bound".into(),
+                                    ));
+                                }
+                                query.pop_front();
+
+                                let upper = if let Some(queue_next) = query.pop_front() {
+                                    get_value_and_unit(&queue_next, &prefix_type, query)
+                                } else {
+                                    return Err(QueryError("Missing value".into()));
+                                };
+
+                                if let (Some(lower), Some(upper)) = (lower, upper) {
+                                    return build_range(prefix_type, lower, upper)
+                                        .map_err(|err| query.error_at_last_token(err));
+                                }
+                            } else if let Some((lower, upper)) =
+                                content.split_once("..").or_else(|| match prefix_type {
+                                    PrefixType::Pid => split_pid_range(&content),
+                                    _ => None,
+                                })
+                            {
+                                let mut bound_query = TokenQueue::new(
+                                    Vec::new(),
+                                    0,
+                                    HashMap::new(),
+                                    query.search_target,
+                                );
+                                let lower =
+                                    get_value_and_unit(lower, &prefix_type, &mut bound_query);
+                                let upper =
+                                    get_value_and_unit(upper, &prefix_type, &mut bound_query);
+
+                                // Allow a unit for both bounds after the range, like `1..2 gb`.
+                                let (lower, upper) = if let Some(multiplier) = query
+                                    .front()
+                                    .and_then(|unit| get_unit_multiplier(&prefix_type, unit))
+                                {
+                                    query.pop_front();
+                                    (
+                                        lower.map(|(value, unit_multiplier)| {
+                                            (value, unit_multiplier.or(Some(multiplier)))
+                                        }),
+                                        upper.map(|(value, unit_multiplier)| {
+                                            (value, unit_multiplier.or(Some(multiplier)))
+                                        }),
+                                    )
+                                } else {
+                                    (lower, upper)
+                                };
+
+                                if let (Some(lower), Some(upper)) = (lower, upper) {
+                                    return build_range(prefix_type, lower, upper)
+                                        .map_err(|err| query.error_at_last_token(err));
+                                }
+                            } else if content == "=" {
+                                condition = Some(QueryComparison::Equal);
+                                if let Some(queue_next) = query.pop_front() {
+                                    value = parse_value(&queue_next, &prefix_type, query);
+                                } else {
+                                    return Err(QueryError("Missing value".into()));
+                                }
+                            } else if content == "!" {
+                                // This has to be a "!="...
+                                if query.front().is_some_and(|queue_next| queue_next == "=") {
+                                    query.pop_front();
+                                    condition = Some(QueryComparison::NotEqual);
+                                    if let Some(queue_next) = query.pop_front() {
+                                        value = parse_value(&queue_next, &prefix_type, query);
+                                    } else {
+                                        return Err(QueryError("Missing value".into()));
+                                    }
+                                } else {
+                                    return Err(QueryError("Missing \"=\" after \"!\"".into()));
+                                }
+                            } else if content == ">" || content == "<" {
+                                // We also have to check if the next string is an "=", or a ">"
+                                // for "<>"...
+                                if let Some(queue_next) = query.pop_front() {
+                                    if content == "<" && queue_next == ">" {
+                                        condition = Some(QueryComparison::NotEqual);
+                                        if let Some(queue_next_next) = query.pop_front() {
+                                            value =
+                                                parse_value(&queue_next_next, &prefix_type, query);
+                                        } else {
+                                            return Err(QueryError("Missing value".into()));
+                                        }
+                                    } else if queue_next == "=" {
+                                        condition = Some(if content == ">" {
+                                            QueryComparison::GreaterOrEqual
+                                        } else {
+                                            QueryComparison::LessOrEqual
+                                        });
+                                        if let Some(queue_next_next) = query.pop_front() {
+                                            value =
+                                                parse_value(&queue_next_next, &prefix_type, query);
+                                        } else {
+                                            return Err(QueryError("Missing value".into()));
+                                        }
+                                    } else {
+                                        condition = Some(if content == ">" {
+                                            QueryComparison::Greater
+                                        } else {
+                                            QueryComparison::Less
+                                        });
+                                        value = parse_value(&queue_next, &prefix_type, query);
+                                    }
+                                } else {
+                                    return Err(QueryError("Missing value".into()));
+                                }
+                            }
+
+                            if let Some(condition) = condition {
+                                if let Some(read_value) = value {
+                                    // Now we want to check one last thing - is there a unit?
+                                    // If no unit, assume base.
+                                    // Furthermore, base must be PEEKED at initially, and will
+                                    // require (likely) prefix_type specific checks
+                                    // Lastly, if it *is* a unit, remember to POP!
+
+                                    let mut value = read_value;
+
+                                    let mut prefix_type = prefix_type;
+                                    if let Some(multiplier) = query
+                                        .front()
+                                        .and_then(|unit| get_unit_multiplier(&prefix_type, unit))
+                                    {
+                                        value *= multiplier;
+                                        query.pop_front();
+
+                                        // A unit means we want memory usage in bytes, not as
+                                        // a percentage.
+                                        if let PrefixType::PMem = prefix_type {
+                                            prefix_type = PrefixType::MemBytes;
+                                        }
+                                    }
+
+                                    return Ok(Prefix {
+                                        or: None,
+                                        regex_prefix: None,
+                                        compare_prefix: Some((
+                                            prefix_type,
+                                            NumericalQuery { condition, value },
+                                        )),
+                                        not: None,
+                                        modifiers: None,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    return Err(QueryError("Missing argument for search prefix".into()));
+                }
+            }
+        } else if inside_quotation {
+            // Uh oh, it's empty with quotes!
+            return Err(QueryError("Missing closing quotation".into()));
+        }
+
+        Err(query.error_at_last_token(QueryError("Invalid query".into())))
+    }
+
+    /// Returns what a value needs to be multiplied by to be in the base unit of the prefix type
+    /// (bytes or seconds), if the string is a unit the prefix type supports.  If the byte units
+    /// are set to binary, units like `kb` are treated the same as `kib`.
+    fn get_unit_multiplier(prefix_type: &PrefixType, unit: &str) -> Option<f64> {
+        let unit = match get_byte_units() {
+            Some(ByteUnits::Binary) => match unit.to_lowercase().as_str() {
+                "tb" => "tib",
+                "gb" => "gib",
+                "mb" => "mib",
+                "kb" => "kib",
+                _ => unit,
+            },
+            _ => unit,
+        };
+
+        match prefix_type {
+            PrefixType::PMem
+            | PrefixType::MemBytes
+            | PrefixType::Rps
+            | PrefixType::Wps
+            | PrefixType::TRead
+            | PrefixType::TWrite
+            | PrefixType::GpuMem
+            | PrefixType::FreeBytes
+            | PrefixType::TotalBytes => match unit.to_lowercase().as_str() {
+                "tb" => Some(1_000_000_000_000.0),
+                "tib" => Some(1_099_511_627_776.0),
+                "gb" => Some(1_000_000_000.0),
+                "gib" => Some(1_073_741_824.0),
+                "mb" => Some(1_000_000.0),
+                "mib" => Some(1_048_576.0),
+                "kb" => Some(1000.0),
+                "kib" => Some(1024.0),
+                "b" => Some(1.0),
+                _ => None,
+            },
+            PrefixType::RunTime => match unit.to_lowercase().as_str() {
+                "d" => Some(86400.0),
+                "h" => Some(3600.0),
+                "m" => Some(60.0),
+                "s" => Some(1.0),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Parses a value, which may have a unit attached to it (ie: `500mb`).  The unit is put back
+    /// into the query so it is handled as if it was written separately.
+    fn parse_value(value: &str, prefix_type: &PrefixType, query: &mut TokenQueue) -> Option<f64> {
+        // A minus sign may be written apart from its number, like `nice < - 5`.
+        if value == "-" {
+            let value = query.pop_front()?;
+            return parse_value(&value, prefix_type, query).map(|value| -value);
+        }
+
+        if let Ok(value) = value.parse::<f64>() {
+            Some(value)
+        } else {
+            let unit_index = value.find(|c: char| c.is_alphabetic())?;
+            let (number, unit) = value.split_at(unit_index);
+            get_unit_multiplier(prefix_type, unit)?;
+            let number = number.parse::<f64>().ok()?;
+            query.push_front(unit.to_string());
+
+            Some(number)
+        }
+    }
+
+    /// Parses a value like [`parse_value`], along with its unit if it has one.  Returns the value
+    /// and what the unit needs it to be multiplied by.
+    fn get_value_and_unit(
+        value: &str, prefix_type: &PrefixType, query: &mut TokenQueue,
+    ) -> Option<(f64, Option<f64>)> {
+        let value = parse_value(value, prefix_type, query)?;
+        let multiplier = query
+            .front()
+            .and_then(|unit| get_unit_multiplier(prefix_type, unit));
+        if multiplier.is_some() {
+            query.pop_front();
+        }
+
+        Some((value, multiplier))
+    }
+
+    /// Expands an inclusive range of values into a `>=` and a `<=` condition joined by an AND, so
+    /// it is handled the same as if it was written out by hand.  If only one bound has a unit, the
+    /// other bound uses it too.
+    fn build_range(
+        prefix_type: PrefixType, lower: (f64, Option<f64>), upper: (f64, Option<f64>),
+    ) -> Result<Prefix> {
+        let (lower, lower_multiplier) = lower;
+        let (upper, upper_multiplier) = upper;
+        let lower = lower * lower_multiplier.or(upper_multiplier).unwrap_or(1.0);
+        let upper = upper * upper_multiplier.or(lower_multiplier).unwrap_or(1.0);
+
+        if lower > upper {
+            return Err(QueryError(
+                "Lower bound of range is greater than its upper bound".into(),
+            ));
+        }
+
+        // A unit means we want memory usage in bytes, not as a percentage.
+        let prefix_type = match prefix_type {
+            PrefixType::PMem if lower_multiplier.or(upper_multiplier).is_some() => {
+                PrefixType::MemBytes
+            }
+            prefix_type => prefix_type,
+        };
+
+        let bound_prefix = |condition: QueryComparison, value: f64| Prefix {
+            or: None,
+            regex_prefix: None,
+            compare_prefix: Some((prefix_type.clone(), NumericalQuery { condition, value })),
+            not: None,
+            modifiers: None,
+        };
+
+        Ok(Prefix {
+            or: Some(Box::new(Or {
+                lhs: And {
+                    lhs: bound_prefix(QueryComparison::GreaterOrEqual, lower),
+                    rhs: Some(Box::new(bound_prefix(QueryComparison::LessOrEqual, upper))),
+                },
+                rhs: None,
+            })),
+            regex_prefix: None,
+            compare_prefix: None,
+            not: None,
+            modifiers: None,
+        })
+    }
+
+    /// Returns whether a PID condition compares the PID as a number, like `pid = 123`, `pid > 100`,
+    /// or `pid 1000-2000`, rather than matching it as text.  Lists of PIDs, quoted values, and
+    /// conditions with search modifiers are still matched as text.
+    fn is_numeric_pid_query(
+        prefix_type: &PrefixType, content: &str, modifiers: &Option<SearchModifiers>,
+        query: &TokenQueue,
+    ) -> bool {
+        if !matches!(prefix_type, PrefixType::Pid) || modifiers.is_some() {
+            return false;
+        }
+
+        match content.to_lowercase().as_str() {
+            ">" | "<" | "!" | "between" => true,
+            "=" => {
+                !query.is_quote_next()
+                    && query
+                        .front()
+                        .is_some_and(|value| value.parse::<u32>().is_ok())
+            }
+            content => {
+                content
+                    .split_once("..")
+                    .is_some_and(|(lower, upper)| is_pid_range(lower, upper))
+                    || split_pid_range(content).is_some()
+            }
+        }
+    }
+
+    /// Splits a range of PIDs written with a dash, like `1000-2000`.
+    fn split_pid_range(content: &str) -> Option<(&str, &str)> {
+        content
+            .split_once('-')
+            .filter(|(lower, upper)| is_pid_range(lower, upper))
+    }
+
+    fn is_pid_range(lower: &str, upper: &str) -> bool {
+        lower.parse::<u32>().is_ok() && upper.parse::<u32>().is_ok()
+    }
+
+    /// Reads a comma-separated list of PIDs like `123,456`, which may have spaces after commas.
+    fn get_pid_list(first_value: String, query: &mut TokenQueue) -> Result<Vec<String>> {
+        let mut pid_list = first_value;
+        while pid_list.ends_with(',') {
+            if let Some(next_value) = query.pop_front() {
+                pid_list.push_str(&next_value);
+            } else {
+                break;
+            }
+        }
+
+        split_pid_list(&pid_list).map_err(|err| query.error_at_last_token(err))
+    }
+
+    /// Reads the rest of a PID set like `(123, 456)`, after its opening parenthesis, including
+    /// the closing parenthesis.
+    fn get_pid_set(query: &mut TokenQueue) -> Result<Vec<String>> {
+        let mut pid_list = String::default();
+        while let Some(next_value) = query.pop_front() {
+            if next_value == ")" {
+                return split_pid_list(&pid_list).map_err(|err| query.error_at_last_token(err));
+            }
+            if !pid_list.is_empty() && !pid_list.ends_with(',') && !next_value.starts_with(',') {
+                pid_list.push(',');
+            }
+            pid_list.push_str(&next_value);
+        }
+
+        Err(QueryError("Missing closing parentheses".into()))
+    }
+
+    fn split_pid_list(pid_list: &str) -> Result<Vec<String>> {
+        let pids = pid_list
+            .split(',')
+            .map(|pid| pid.trim())
+            .filter(|pid| !pid.is_empty())
+            .map(|pid| pid.to_string())
+            .collect::<Vec<_>>();
+
+        if pids.is_empty() {
+            Err(QueryError("No values within PID set".into()))
+        } else {
+            Ok(pids)
+        }
+    }
+
+    /// Expands a set of PIDs into a group of `pid = x` conditions joined by ORs, so it is handled
+    /// the same as if it was written out by hand.  Anything that isn't a PID is matched as text.
+    fn build_pid_or_group(pids: Vec<String>) -> Prefix {
+        let pid_prefix = |pid: String| match pid.parse::<u32>() {
+            Ok(value) => Prefix {
+                or: None,
+                regex_prefix: None,
+                compare_prefix: Some((
+                    PrefixType::Pid,
+                    NumericalQuery {
+                        condition: QueryComparison::Equal,
+                        value: f64::from(value),
+                    },
+                )),
+                not: None,
+                modifiers: None,
+            },
+            Err(_) => Prefix {
+                or: None,
+                regex_prefix: Some((PrefixType::Pid, StringQuery::Value(pid))),
+                compare_prefix: None,
+                not: None,
+                modifiers: None,
+            },
+        };
+
+        let mut pids = pids.into_iter();
+        let initial_or = Or {
+            lhs: And {
+                lhs: pid_prefix(pids.next().unwrap_or_default()),
+                rhs: None,
+            },
+            rhs: None,
+        };
+        let pid_or = pids.fold(initial_or, |lhs, pid| Or {
+            lhs: And {
+                lhs: Prefix {
+                    or: Some(Box::new(lhs)),
+                    regex_prefix: None,
+                    compare_prefix: None,
+                    not: None,
+                    modifiers: None,
+                },
+                rhs: None,
+            },
+            rhs: Some(Box::new(And {
+                lhs: pid_prefix(pid),
+                rhs: None,
+            })),
+        });
+
+        Prefix {
+            or: Some(Box::new(pid_or)),
+            regex_prefix: None,
+            compare_prefix: None,
+            not: None,
+            modifiers: None,
+        }
+    }
+
+    /// Reads the rest of a quoted string, after its opening quote, including the closing quote.
+    fn get_quoted_string(query: &mut TokenQueue) -> Result<String> {
+        let mut quoted_string = String::default();
+        while let Some(next_str) = query.pop_front() {
+            if query.last_is_quote() {
+                return Ok(quoted_string);
+            }
+            quoted_string.push_str(&next_str);
+        }
+
+        Err(QueryError("Missing closing quotation".into()))
+    }
+
+    let (expanded_query, origins) =
+        expand_query_aliases(search_query, query_aliases, &mut Vec::new()).map_err(
+            |(error, span)| QueryParseError {
+                error,
+                span: Some(span),
+            },
+        )?;
+
+    // Point the tokens back at the original query, so errors are shown in the right place even if
+    // they came from an alias.
+    let tokens = tokenize_query(&expanded_query)
+        .into_iter()
+        .map(|token| Token {
+            span: origins[token.span.start].start..origins[token.span.end - 1].end,
+            ..token
+        })
+        .collect();
+    let mut split_query = TokenQueue::new(
+        tokens,
+        search_query.len(),
+        prefix_aliases.cloned().unwrap_or_default(),
+        search_target,
+    );
+
+    let mut process_filter =
+        process_string_to_filter(&mut split_query).map_err(|error| QueryParseError {
+            error,
+            span: Some(split_query.get_error_span()),
+        })?;
+    process_filter
+        .process_regexes(
+            is_searching_whole_word,
+            is_ignoring_case,
+            is_searching_with_regex,
+            is_searching_with_glob,
+        )
+        .map_err(|error| QueryParseError { error, span: None })?;
+
+    Ok(process_filter)
+}
+
+pub struct Query {
+    /// Remember, AND > OR, but AND must come after OR when we parse.
+    pub query: Vec<Or>,
+}
+
+impl Query {
+    pub fn process_regexes(
+        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
+        is_searching_with_regex: bool, is_searching_with_glob: bool,
+    ) -> Result<()> {
+        for or in &mut self.query {
+            or.process_regexes(
+                is_searching_whole_word,
+                is_ignoring_case,
+                is_searching_with_regex,
+                is_searching_with_glob,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check<T: Filterable + ?Sized>(&self, target: &T) -> bool {
+        self.query.iter().all(|ok| ok.check(target))
+    }
+
+    /// Returns the items that match the query.
+    pub fn filter<'a, T: Filterable>(&self, items: &'a [T]) -> Vec<&'a T> {
+        items.iter().filter(|item| self.check(*item)).collect()
+    }
+
+    /// Returns whether any part of the query uses the given prefix type.
+    pub fn has_prefix(&self, prefix_type: &PrefixType) -> bool {
+        self.query.iter().any(|or| or.has_prefix(prefix_type))
+    }
+}
+
+/// A set of queries that a process has to satisfy, such as the search query of a widget, its quick
+/// filters, and any excludes from the config file.  The queries are kept separate rather than
+/// being joined into one query string, and checking stops at the first one that fails.
+#[derive(Default)]
+pub struct QuerySet<'a> {
+    required: Vec<&'a Query>,
+    excluded: Vec<&'a Query>,
+}
+
+impl<'a> QuerySet<'a> {
+    /// Only matches processes that also match the given query.
+    pub fn require(&mut self, query: &'a Query) {
+        self.required.push(query);
+    }
+
+    /// Only matches processes that do not match the given query.
+    pub fn exclude(&mut self, query: &'a Query) {
+        self.excluded.push(query);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.required.is_empty() && self.excluded.is_empty()
+    }
+
+    pub fn check<T: Filterable + ?Sized>(&self, target: &T) -> bool {
+        self.excluded.iter().all(|query| !query.check(target))
+            && self.required.iter().all(|query| query.check(target))
+    }
+
+    /// Returns whether any query in the set uses the given prefix type.
+    pub fn has_prefix(&self, prefix_type: &PrefixType) -> bool {
+        self.required
+            .iter()
+            .chain(self.excluded.iter())
+            .any(|query| query.has_prefix(prefix_type))
+    }
+}
+
+impl Debug for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.query))
+    }
+}
+
+#[derive(Default)]
+pub struct Or {
+    pub lhs: And,
+    pub rhs: Option<Box<And>>,
+}
+
+impl Or {
+    pub fn process_regexes(
+        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
+        is_searching_with_regex: bool, is_searching_with_glob: bool,
+    ) -> Result<()> {
+        self.lhs.process_regexes(
+            is_searching_whole_word,
+            is_ignoring_case,
+            is_searching_with_regex,
+            is_searching_with_glob,
+        )?;
+        if let Some(rhs) = &mut self.rhs {
+            rhs.process_regexes(
+                is_searching_whole_word,
+                is_ignoring_case,
+                is_searching_with_regex,
+                is_searching_with_glob,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check<T: Filterable + ?Sized>(&self, target: &T) -> bool {
+        if let Some(rhs) = &self.rhs {
+            self.lhs.check(target) || rhs.check(target)
+        } else {
+            self.lhs.check(target)
+        }
+    }
+
+    pub fn has_prefix(&self, prefix_type: &PrefixType) -> bool {
+        self.lhs.has_prefix(prefix_type)
+            || self
+                .rhs
+                .as_ref()
+                .is_some_and(|rhs| rhs.has_prefix(prefix_type))
+    }
+}
+
+impl Debug for Or {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.rhs {
+            Some(rhs) => f.write_fmt(format_args!("({:?} OR {:?})", self.lhs, rhs)),
+            None => f.write_fmt(format_args!("{:?}", self.lhs)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct And {
+    pub lhs: Prefix,
+    pub rhs: Option<Box<Prefix>>,
+}
+
+impl And {
+    pub fn process_regexes(
+        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
+        is_searching_with_regex: bool, is_searching_with_glob: bool,
+    ) -> Result<()> {
+        self.lhs.process_regexes(
+            is_searching_whole_word,
+            is_ignoring_case,
+            is_searching_with_regex,
+            is_searching_with_glob,
+        )?;
+        if let Some(rhs) = &mut self.rhs {
+            rhs.process_regexes(
+                is_searching_whole_word,
+                is_ignoring_case,
+                is_searching_with_regex,
+                is_searching_with_glob,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check<T: Filterable + ?Sized>(&self, target: &T) -> bool {
+        if let Some(rhs) = &self.rhs {
+            self.lhs.check(target) && rhs.check(target)
+        } else {
+            self.lhs.check(target)
+        }
+    }
+
+    pub fn has_prefix(&self, prefix_type: &PrefixType) -> bool {
+        self.lhs.has_prefix(prefix_type)
+            || self
+                .rhs
+                .as_ref()
+                .is_some_and(|rhs| rhs.has_prefix(prefix_type))
+    }
+}
+
+impl Debug for And {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.rhs {
+            Some(rhs) => f.write_fmt(format_args!("({:?} AND {:?})", self.lhs, rhs)),
+            None => f.write_fmt(format_args!("{:?}", self.lhs)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PrefixType {
+    Pid,
+    PCpu,
+    MemBytes,
+    PMem,
+    Rps,
+    Wps,
+    TRead,
+    TWrite,
+    Name,
+    State,
+    User,
+    Connections,
+    Threads,
+    Fds,
+    Nice,
+    Priority,
+    RunTime,
+    Command,
+    GpuMem,
+    GpuUsage,
+    Ppid,
+    SecurityContext,
+    Suspicious,
+    Namespace,
+    Exe,
+    Cgroup,
+    /// The titles of the windows of a process.
+    WindowTitle,
+    /// What media devices a process is using.
+    Media,
+    /// The mount point of a disk.
+    Mount,
+    /// How much of a disk is used, in percent.
+    UsedPercent,
+    /// The free space of a disk.
+    FreeBytes,
+    /// The total space of a disk.
+    TotalBytes,
+    /// The temperature of a sensor.
+    Temperature,
+    /// The environment of a process, or just the variable with the given name.
+    Env(Option<String>),
+    __Nonexhaustive,
+}
+
+impl std::str::FromStr for PrefixType {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        use PrefixType::*;
+
+        let lower_case = s.to_lowercase();
+        // Didn't add mem_bytes, total_read, and total_write
+        // for now as it causes help to be clogged.
+        match lower_case.as_str() {
+            "cpu" | "cpu%" => Ok(PCpu),
+            "mem" | "mem%" => Ok(PMem),
+            "memb" => Ok(MemBytes),
+            "read" | "r/s" => Ok(Rps),
+            "write" | "w/s" => Ok(Wps),
+            "tread" | "t.read" => Ok(TRead),
+            "twrite" | "t.write" => Ok(TWrite),
+            "pid" => Ok(Pid),
+            "state" => Ok(State),
+            "user" | "uid" => Ok(User),
+            "conns" | "connections" => Ok(Connections),
+            "threads" | "nth" => Ok(Threads),
+            "fds" => Ok(Fds),
+            "nice" => Ok(Nice),
+            "priority" | "prio" => Ok(Priority),
+            "time" | "runtime" => Ok(RunTime),
+            "args" | "cmd" => Ok(Command),
+            "gpumem" | "gmem" => Ok(GpuMem),
+            "gpu" | "gpu%" => Ok(GpuUsage),
+            "ppid" => Ok(Ppid),
+            "context" | "label" => Ok(SecurityContext),
+            "suspicious" => Ok(Suspicious),
+            "ns" | "namespace" => Ok(Namespace),
+            "exe" => Ok(Exe),
+            "cgroup" | "container" => Ok(Cgroup),
+            "title" | "window" => Ok(WindowTitle),
+            "media" => Ok(Media),
+            "mount" => Ok(Mount),
+            "used" | "used%" => Ok(UsedPercent),
+            "free" => Ok(FreeBytes),
+            "total" => Ok(TotalBytes),
+            "temp" => Ok(Temperature),
+            "env" => Ok(Env(None)),
+            // Variable names are case-sensitive, so keep the original case of the key.
+            _ if lower_case.starts_with("env.") && s.len() > "env.".len() => {
+                Ok(Env(Some(s["env.".len()..].to_string())))
+            }
+            _ => Ok(Name),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Prefix {
+    pub or: Option<Box<Or>>,
+    pub regex_prefix: Option<(PrefixType, StringQuery)>,
+    pub compare_prefix: Option<(PrefixType, NumericalQuery)>,
+    /// A negated prefix, which matches if the inner prefix does not.
+    pub not: Option<Box<Prefix>>,
+    /// Search options for just this condition, like `name~r"^ssh"`.
+    pub modifiers: Option<SearchModifiers>,
+}
+
+impl Prefix {
+    pub fn process_regexes(
+        &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
+        is_searching_with_regex: bool, is_searching_with_glob: bool,
+    ) -> Result<()> {
+        if let Some(or) = &mut self.or {
+            return or.process_regexes(
+                is_searching_whole_word,
+                is_ignoring_case,
+                is_searching_with_regex,
+                is_searching_with_glob,
+            );
+        } else if let Some(not) = &mut self.not {
+            return not.process_regexes(
+                is_searching_whole_word,
+                is_ignoring_case,
+                is_searching_with_regex,
+                is_searching_with_glob,
+            );
+        } else if let Some((
+            PrefixType::Pid
+            | PrefixType::Name
+            | PrefixType::State
+            | PrefixType::User
+            | PrefixType::Command
+            | PrefixType::SecurityContext
+            | PrefixType::Suspicious
+            | PrefixType::Namespace
+            | PrefixType::Exe
+            | PrefixType::Cgroup
+            | PrefixType::WindowTitle
+            | PrefixType::Media
+            | PrefixType::Mount
+            | PrefixType::Env(_),
+            StringQuery::Value(regex_string),
+        )) = &mut self.regex_prefix
+        {
+            let modifiers = self.modifiers.unwrap_or_default();
+            let is_searching_whole_word = modifiers
+                .is_searching_whole_word
+                .unwrap_or(is_searching_whole_word);
+            let is_ignoring_case = modifiers.is_ignoring_case.unwrap_or(is_ignoring_case);
+            let is_searching_with_regex = modifiers
+                .is_searching_with_regex
+                .unwrap_or(is_searching_with_regex);
+            let is_searching_with_glob = modifiers
+                .is_searching_with_glob
+                .unwrap_or(is_searching_with_glob);
+
+            // Regex takes priority over globs, and globs always have to match the entire word.
+            let is_searching_with_glob = is_searching_with_glob && !is_searching_with_regex;
+            let is_searching_whole_word = is_searching_whole_word || is_searching_with_glob;
+
+            let escaped_regex: String;
+            let final_regex_string = &format!(
+                "{}{}{}{}",
+                if is_searching_whole_word { "^" } else { "" },
+                if is_ignoring_case { "(?i)" } else { "" },
+                if is_searching_with_regex {
+                    regex_string
+                } else if is_searching_with_glob {
+                    escaped_regex = glob_to_regex(regex_string);
+                    &escaped_regex
+                } else {
+                    escaped_regex = regex::escape(regex_string);
+                    &escaped_regex
+                },
+                if is_searching_whole_word { "$" } else { "" },
+            );
+
+            let taken_pwc = self.regex_prefix.take();
+            if let Some((taken_pt, _)) = taken_pwc {
+                self.regex_prefix = Some((
+                    taken_pt,
+                    StringQuery::Regex(regex::Regex::new(final_regex_string)?),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check<T: Filterable + ?Sized>(&self, target: &T) -> bool {
+        fn matches_condition(condition: &QueryComparison, lhs: f64, rhs: f64) -> bool {
+            match condition {
+                QueryComparison::Equal => (lhs - rhs).abs() < f64::EPSILON,
+                QueryComparison::NotEqual => (lhs - rhs).abs() >= f64::EPSILON,
+                QueryComparison::Less => lhs < rhs,
+                QueryComparison::Greater => lhs > rhs,
+                QueryComparison::LessOrEqual => lhs <= rhs,
+                QueryComparison::GreaterOrEqual => lhs >= rhs,
+            }
+        }
+
+        if let Some(and) = &self.or {
+            and.check(target)
+        } else if let Some(not) = &self.not {
+            !not.check(target)
+        } else if let Some((prefix_type, query_content)) = &self.regex_prefix {
+            if let StringQuery::Regex(r) = query_content {
+                target.is_string_match(prefix_type, r)
+            } else {
+                true
+            }
+        } else if let Some((prefix_type, numerical_query)) = &self.compare_prefix {
+            target.get_number(prefix_type).is_some_and(|number| {
+                matches_condition(&numerical_query.condition, number, numerical_query.value)
+            })
+        } else {
+            // Somehow we have an empty condition... oh well.  Return true.
+            true
+        }
+    }
+
+    pub fn has_prefix(&self, prefix_type: &PrefixType) -> bool {
+        let is_same_type = |other: &PrefixType| {
+            std::mem::discriminant(other) == std::mem::discriminant(prefix_type)
+        };
+
+        if let Some(or) = &self.or {
+            or.has_prefix(prefix_type)
+        } else if let Some(not) = &self.not {
+            not.has_prefix(prefix_type)
+        } else if let Some((regex_prefix_type, _)) = &self.regex_prefix {
+            is_same_type(regex_prefix_type)
+        } else if let Some((compare_prefix_type, _)) = &self.compare_prefix {
+            is_same_type(compare_prefix_type)
+        } else {
+            false
+        }
+    }
+}
+
+/// Something a query can be checked against, like a process or a row of the disk table.  Each
+/// implementation only knows the prefixes that make sense for it, and any other prefix never
+/// matches.
+pub trait Filterable {
+    /// Returns whether the text the prefix refers to matches the regex.
+    fn is_string_match(&self, prefix_type: &PrefixType, regex: &regex::Regex) -> bool;
+
+    /// Returns the number the prefix refers to, or [`None`] if there isn't one.
+    fn get_number(&self, prefix_type: &PrefixType) -> Option<f64>;
+}
+
+/// Canned queries that can be toggled on top of the search query of a process widget.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuickFilter {
+    OwnProcesses,
+    Active,
+    Connected,
+    Suspicious,
+    Media,
+}
+
+impl QuickFilter {
+    /// Returns the query this filter stands for, or [`None`] if it isn't supported on this
+    /// platform.
+    pub fn get_query_string(&self) -> Option<String> {
+        match self {
+            QuickFilter::OwnProcesses => {
+                #[cfg(target_family = "unix")]
+                {
+                    Some(format!("uid = {}", unsafe { libc::getuid() }))
+                }
+                #[cfg(not(target_family = "unix"))]
+                {
+                    None
+                }
+            }
+            QuickFilter::Active => Some("cpu > 0".to_string()),
+            QuickFilter::Connected => Some("conns > 0".to_string()),
+            QuickFilter::Suspicious => {
+                Some("suspicious = deleted or suspicious = temp".to_string())
+            }
+            QuickFilter::Media => Some("media = cam or media = mic or media = audio".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for QuickFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                QuickFilter::OwnProcesses => "mine",
+                QuickFilter::Active => "active",
+                QuickFilter::Connected => "net",
+                QuickFilter::Suspicious => "suspicious",
+                QuickFilter::Media => "media",
+            }
+        )
+    }
+}
+
+impl Debug for Prefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(or) = &self.or {
+            f.write_fmt(format_args!("{:?}", or))
+        } else if let Some(not) = &self.not {
+            f.write_fmt(format_args!("NOT {:?}", not))
+        } else if let Some(regex_prefix) = &self.regex_prefix {
+            f.write_fmt(format_args!("{:?}", regex_prefix))
+        } else if let Some(compare_prefix) = &self.compare_prefix {
+            f.write_fmt(format_args!("{:?}", compare_prefix))
+        } else {
+            f.write_fmt(format_args!(""))
+        }
+    }
+}
+
+/// Search options given to a single condition after a `~`, like the `rc` in `name~rc"^Foo"`,
+/// which take priority over the options of the whole search.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SearchModifiers {
+    pub is_searching_with_regex: Option<bool>,
+    pub is_searching_with_glob: Option<bool>,
+    pub is_ignoring_case: Option<bool>,
+    pub is_searching_whole_word: Option<bool>,
+}
+
+impl std::str::FromStr for SearchModifiers {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut modifiers = SearchModifiers::default();
+        for c in s.chars() {
+            match c.to_ascii_lowercase() {
+                'r' => {
+                    modifiers.is_searching_with_regex = Some(true);
+                    modifiers.is_searching_with_glob = Some(false);
+                }
+                'g' => {
+                    modifiers.is_searching_with_regex = Some(false);
+                    modifiers.is_searching_with_glob = Some(true);
+                }
+                'l' => {
+                    modifiers.is_searching_with_regex = Some(false);
+                    modifiers.is_searching_with_glob = Some(false);
+                }
+                'c' => modifiers.is_ignoring_case = Some(false),
+                'i' => modifiers.is_ignoring_case = Some(true),
+                'w' => modifiers.is_searching_whole_word = Some(true),
+                _ => {
+                    return Err(QueryError(
+                        format!("Unknown search modifier \"{}\"", c).into(),
+                    ))
+                }
+            }
+        }
+
+        Ok(modifiers)
+    }
+}
+
+/// Splits a token like `name~r` into its prefix and search modifiers, if it has any.  Only
+/// prefixes that match text can have modifiers, and no prefix at all means the name.
+fn split_search_modifiers(token: &str, search_target: SearchTarget) -> Option<(&str, &str)> {
+    let (prefix, modifiers) = token.split_once('~')?;
+    let is_string_prefix = prefix.is_empty()
+        || prefix.eq_ignore_ascii_case("name")
+        || matches!(
+            search_target.get_prefix_type(prefix),
+            PrefixType::Pid
+                | PrefixType::State
+                | PrefixType::User
+                | PrefixType::Command
+                | PrefixType::SecurityContext
+                | PrefixType::Suspicious
+                | PrefixType::Namespace
+                | PrefixType::Exe
+                | PrefixType::Cgroup
+                | PrefixType::WindowTitle
+                | PrefixType::Media
+                | PrefixType::Mount
+                | PrefixType::Env(_)
+        );
+
+    if is_string_prefix {
+        Some((prefix, modifiers))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug)]
+pub enum QueryComparison {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+#[derive(Debug)]
+pub enum StringQuery {
+    Value(String),
+    Regex(regex::Regex),
+}
+
+#[derive(Debug)]
+pub struct NumericalQuery {
+    pub condition: QueryComparison,
+    pub value: f64,
+}
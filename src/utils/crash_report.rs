@@ -0,0 +1,104 @@
+//! Writes a crash report when bottom panics, so that users have something
+//! useful to attach to a bug report.  Note that the system information is
+//! anonymized --- we don't include things like the hostname or username.
+
+use std::{fmt::Write, path::PathBuf};
+
+use sysinfo::{System, SystemExt};
+
+use crate::utils::logging::get_data_dir;
+
+/// Writes a crash report with the given panic message, location, and backtrace, returning
+/// the path of the report if it was successfully written.
+pub fn write_crash_report(message: &str, location: &str, backtrace: &str) -> Option<PathBuf> {
+    let now = chrono::Local::now();
+
+    let mut report = String::new();
+    writeln!(report, "bottom crash report").ok()?;
+    writeln!(report, "===================").ok()?;
+    writeln!(report, "Version: {}", env!("CARGO_PKG_VERSION")).ok()?;
+    writeln!(report, "Time: {}", now.format("%Y-%m-%d %H:%M:%S %z")).ok()?;
+    writeln!(
+        report,
+        "Thread: {}",
+        std::thread::current().name().unwrap_or("<unnamed>")
+    )
+    .ok()?;
+    writeln!(report, "Message: {}", anonymize(message)).ok()?;
+    writeln!(report, "Location: {}", location).ok()?;
+    writeln!(report).ok()?;
+    writeln!(report, "System information").ok()?;
+    writeln!(report, "------------------").ok()?;
+    report.push_str(&get_system_info());
+    writeln!(report).ok()?;
+    writeln!(report, "Backtrace").ok()?;
+    writeln!(report, "---------").ok()?;
+    report.push_str(&anonymize(backtrace));
+
+    let mut path = get_data_dir();
+    path.push(format!("crash_{}.log", now.format("%Y%m%d_%H%M%S")));
+    std::fs::write(&path, report).ok()?;
+
+    Some(path)
+}
+
+fn get_system_info() -> String {
+    let mut info = String::new();
+    let _ = writeln!(
+        info,
+        "OS: {} ({}), {}",
+        std::env::consts::OS,
+        std::env::consts::FAMILY,
+        std::env::consts::ARCH
+    );
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(os_release) = std::fs::read_to_string("/etc/os-release") {
+            if let Some(pretty_name) = os_release
+                .lines()
+                .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+            {
+                let _ = writeln!(info, "Distribution: {}", pretty_name.trim_matches('"'));
+            }
+        }
+        if let Ok(kernel) = std::fs::read_to_string("/proc/sys/kernel/osrelease") {
+            let _ = writeln!(info, "Kernel: {}", kernel.trim());
+        }
+    }
+
+    if let Some(hypervisor) = crate::app::data_harvester::guest::detect_hypervisor() {
+        let _ = writeln!(info, "Hypervisor: {}", hypervisor);
+    }
+
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    sys.refresh_memory();
+    let _ = writeln!(info, "CPU cores: {}", sys.get_processors().len());
+    let _ = writeln!(
+        info,
+        "Total memory: {:.1} GiB",
+        sys.get_total_memory() as f64 / (1024.0 * 1024.0)
+    );
+
+    if let Ok(term) = std::env::var("TERM") {
+        let _ = writeln!(info, "Terminal: {}", term);
+    }
+    if let Ok((width, height)) = crossterm::terminal::size() {
+        let _ = writeln!(info, "Terminal size: {}x{}", width, height);
+    }
+
+    info
+}
+
+/// Strips out the home directory (and with it, most likely the username) from the given text.
+fn anonymize(text: &str) -> String {
+    if let Some(home_dir) = dirs_next::home_dir() {
+        let home_dir = home_dir.to_string_lossy();
+        if !home_dir.is_empty() && home_dir != "/" {
+            return text.replace(home_dir.as_ref(), "~");
+        }
+    }
+
+    text.to_string()
+}
@@ -85,6 +85,12 @@ impl From<std::str::Utf8Error> for BottomError {
     }
 }
 
+impl From<crate::query::QueryParseError> for BottomError {
+    fn from(err: crate::query::QueryParseError) -> Self {
+        err.error
+    }
+}
+
 impl From<regex::Error> for BottomError {
     fn from(err: regex::Error) -> Self {
         // We only really want the last part of it... so we'll do it the ugly way:
@@ -1,3 +1,4 @@
+use once_cell::sync::OnceCell;
 use std::cmp::Ordering;
 
 pub const KILO_LIMIT: u64 = 1000;
@@ -89,6 +90,53 @@ pub fn get_simple_byte_values(bytes: u64, spacing: bool) -> (f64, String) {
     }
 }
 
+/// Whether byte values are shown in units of 1000 (KB, MB, ...) or 1024 (KiB, MiB, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteUnits {
+    Si,
+    Binary,
+}
+
+static BYTE_UNITS: OnceCell<ByteUnits> = OnceCell::new();
+
+/// Sets which byte units are used everywhere.  Only the first call does anything.
+pub fn set_byte_units(byte_units: ByteUnits) {
+    if BYTE_UNITS.set(byte_units).is_err() {
+        debug!("The byte units were already set.");
+    }
+}
+
+/// Returns the byte units that were set, if any were.
+pub fn get_byte_units() -> Option<ByteUnits> {
+    BYTE_UNITS.get().copied()
+}
+
+/// Returns a tuple containing the value and the unit, in the byte units that were set.  If none
+/// were set, `default` is used instead.
+pub fn get_byte_values(bytes: u64, spacing: bool, default: ByteUnits) -> (f64, String) {
+    match get_byte_units().unwrap_or(default) {
+        ByteUnits::Si => get_simple_byte_values(bytes, spacing),
+        ByteUnits::Binary => get_exact_byte_values(bytes, spacing),
+    }
+}
+
+/// Returns a duration in seconds as its two largest units, like `3d 4h` or `12m 5s`.
+pub fn get_humanized_duration(secs: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    if secs >= DAY {
+        format!("{}d {}h", secs / DAY, secs % DAY / HOUR)
+    } else if secs >= HOUR {
+        format!("{}h {}m", secs / HOUR, secs % HOUR / MINUTE)
+    } else if secs >= MINUTE {
+        format!("{}m {}s", secs / MINUTE, secs % MINUTE)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 /// Gotta get partial ordering?  No problem, here's something to deal with it~
 pub fn get_ordering<T: std::cmp::PartialOrd>(
     a_val: T, b_val: T, descending_order: bool,
@@ -1,4 +1,47 @@
-use std::ffi::OsStr;
+use std::{
+    ffi::OsStr,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Set to reopen the log file before the next write, ie: after it was rotated.
+static SHOULD_REOPEN_LOG: AtomicBool = AtomicBool::new(false);
+
+/// A log file that can be reopened at the same path, so tools like logrotate can move it.
+struct ReopenableLogFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl ReopenableLogFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(ReopenableLogFile { path, file })
+    }
+}
+
+impl Write for ReopenableLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if SHOULD_REOPEN_LOG.swap(false, Ordering::SeqCst) {
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Reopens the log file before the next message is logged.
+pub fn reopen_log_file() {
+    SHOULD_REOPEN_LOG.store(true, Ordering::SeqCst);
+}
 
 pub fn init_logger(
     min_level: log::LevelFilter, debug_file_name: &OsStr,
@@ -14,8 +57,31 @@ pub fn init_logger(
             ))
         })
         .level(min_level)
-        .chain(fern::log_file(debug_file_name)?)
+        .chain(fern::Output::writer(
+            Box::new(ReopenableLogFile::open(PathBuf::from(debug_file_name))?),
+            "\n",
+        ))
         .apply()?;
 
     Ok(())
 }
+
+/// Returns where bottom stores things like debug logs and crash reports, creating it if needed.
+/// Falls back to the temp directory if the data directory can't be determined or created.
+pub fn get_data_dir() -> PathBuf {
+    if let Some(mut data_dir) = dirs_next::data_dir() {
+        data_dir.push("bottom");
+        if std::fs::create_dir_all(&data_dir).is_ok() {
+            return data_dir;
+        }
+    }
+
+    std::env::temp_dir()
+}
+
+/// Returns where the log written by `--debug_log` is stored.
+pub fn get_debug_log_path() -> PathBuf {
+    let mut path = get_data_dir();
+    path.push("bottom_debug.log");
+    path
+}
@@ -0,0 +1,119 @@
+//! Formats the numbers shown in widgets, so the decimal separator, digit grouping, and number of
+//! decimal places can be changed in one place for all of them.
+
+use once_cell::sync::OnceCell;
+
+static NUMBER_FORMAT: OnceCell<NumberFormat> = OnceCell::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    /// What goes between every three digits before the decimal separator, if anything.
+    pub digit_grouping: Option<char>,
+    /// How many decimal places every number has, instead of what suits each of them.
+    pub decimal_places: Option<usize>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal_separator: '.',
+            digit_grouping: None,
+            decimal_places: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Returns the decimal separator and digit grouping of the user's locale, as set by `LC_ALL`,
+    /// `LC_NUMERIC`, or `LANG`.
+    #[cfg(unix)]
+    pub fn from_locale() -> Self {
+        use std::ffi::CStr;
+
+        fn get_first_char(string: *const libc::c_char) -> Option<char> {
+            if string.is_null() {
+                None
+            } else {
+                unsafe { CStr::from_ptr(string) }
+                    .to_str()
+                    .ok()
+                    .and_then(|string| string.chars().next())
+            }
+        }
+
+        // This has to happen before any other threads start, as the locale is global.  Go back
+        // to the "C" locale afterwards, so nothing else is affected.
+        unsafe {
+            libc::setlocale(libc::LC_NUMERIC, b"\0".as_ptr() as *const libc::c_char);
+            let number_format = libc::localeconv().as_ref().map(|lconv| NumberFormat {
+                decimal_separator: get_first_char(lconv.decimal_point).unwrap_or('.'),
+                digit_grouping: get_first_char(lconv.thousands_sep),
+                decimal_places: None,
+            });
+            libc::setlocale(libc::LC_NUMERIC, b"C\0".as_ptr() as *const libc::c_char);
+
+            number_format.unwrap_or_default()
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_locale() -> Self {
+        NumberFormat::default()
+    }
+
+    /// Formats a number with the given number of decimal places, unless a fixed number of them
+    /// is set.
+    pub fn format(&self, value: f64, decimal_places: usize) -> String {
+        let formatted = format!(
+            "{:.*}",
+            self.decimal_places.unwrap_or(decimal_places),
+            value
+        );
+        if self.decimal_separator == '.' && self.digit_grouping.is_none() {
+            return formatted;
+        }
+
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+        let (sign, digits) = match integer.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", integer),
+        };
+
+        let mut result = String::with_capacity(formatted.len() + digits.len() / 3);
+        result.push_str(sign);
+        for (index, digit) in digits.chars().enumerate() {
+            if let Some(digit_grouping) = self.digit_grouping {
+                if index > 0 && (digits.len() - index) % 3 == 0 {
+                    result.push(digit_grouping);
+                }
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        result
+    }
+}
+
+/// Sets how numbers are formatted everywhere.  Only the first call does anything.
+pub fn set_number_format(number_format: NumberFormat) {
+    if NUMBER_FORMAT.set(number_format).is_err() {
+        debug!("The number format was already set.");
+    }
+}
+
+/// Formats a number with the given number of decimal places, using the number format that was
+/// set.  A fixed number of decimal places in the number format takes priority.
+pub fn format_number(value: f64, decimal_places: usize) -> String {
+    match NUMBER_FORMAT.get() {
+        Some(number_format) => number_format.format(value, decimal_places),
+        None => format!("{:.*}", decimal_places, value),
+    }
+}
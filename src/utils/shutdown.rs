@@ -0,0 +1,105 @@
+//! Handles being asked to stop from outside, like by `SIGTERM`, the terminal hanging up, or the
+//! console window being closed on Windows, so that bottom still saves its state and restores the
+//! terminal before exiting.
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::utils::error;
+
+/// The signal that asked bottom to stop, or 0 if none did.
+static RECEIVED_SIGNAL: once_cell::sync::Lazy<Arc<AtomicUsize>> =
+    once_cell::sync::Lazy::new(|| Arc::new(AtomicUsize::new(0)));
+
+/// Whether bottom is done cleaning up, after which a closing console window may end it.
+#[cfg(target_family = "windows")]
+static IS_CLEANED_UP: AtomicBool = AtomicBool::new(false);
+
+/// Sets `is_terminated` once bottom is asked to stop.  On Unix, this is on `SIGTERM`, `SIGINT`,
+/// `SIGQUIT`, and `SIGHUP`, unless `SIGHUP` is used to reopen the log file.
+#[cfg(target_family = "unix")]
+pub fn register_shutdown_handlers(
+    is_terminated: Arc<AtomicBool>, is_sighup_reserved: bool,
+) -> error::Result<()> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGQUIT, SIGTERM};
+
+    for &signal in &[SIGTERM, SIGINT, SIGQUIT, SIGHUP] {
+        if signal == SIGHUP && is_sighup_reserved {
+            continue;
+        }
+        signal_hook::flag::register_usize(signal, RECEIVED_SIGNAL.clone(), signal as usize)?;
+        signal_hook::flag::register(signal, is_terminated.clone())?;
+    }
+
+    Ok(())
+}
+
+/// Sets `is_terminated` once bottom is asked to stop.  On Windows, this is on Ctrl-C and
+/// Ctrl-Break, as well as when the console window is closed or the user logs off, in which case
+/// Windows is held off from ending bottom until it has cleaned up.
+#[cfg(target_family = "windows")]
+pub fn register_shutdown_handlers(
+    is_terminated: Arc<AtomicBool>, _is_sighup_reserved: bool,
+) -> error::Result<()> {
+    use winapi::{
+        shared::minwindef::{BOOL, DWORD, FALSE, TRUE},
+        um::{
+            consoleapi::SetConsoleCtrlHandler,
+            wincon::{CTRL_CLOSE_EVENT, CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT},
+        },
+    };
+
+    static IS_TERMINATED: once_cell::sync::OnceCell<Arc<AtomicBool>> =
+        once_cell::sync::OnceCell::new();
+
+    unsafe extern "system" fn close_handler(ctrl_type: DWORD) -> BOOL {
+        match ctrl_type {
+            CTRL_CLOSE_EVENT | CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT => {
+                RECEIVED_SIGNAL.store(ctrl_type as usize, Ordering::SeqCst);
+                if let Some(is_terminated) = IS_TERMINATED.get() {
+                    is_terminated.store(true, Ordering::SeqCst);
+                }
+
+                // Windows ends the process once this returns, and gives up waiting after about
+                // five seconds anyway.
+                let wait_start = std::time::Instant::now();
+                while !IS_CLEANED_UP.load(Ordering::SeqCst)
+                    && wait_start.elapsed() < std::time::Duration::from_millis(4500)
+                {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                TRUE
+            }
+            // Leave Ctrl-C and Ctrl-Break to the handler below.
+            _ => FALSE,
+        }
+    }
+
+    let ctrlc_is_terminated = is_terminated.clone();
+    ctrlc::set_handler(move || {
+        ctrlc_is_terminated.store(true, Ordering::SeqCst);
+    })
+    .map_err(|err| error::BottomError::GenericError(err.to_string()))?;
+
+    let _ = IS_TERMINATED.set(is_terminated);
+    if unsafe { SetConsoleCtrlHandler(Some(close_handler), TRUE) } == FALSE {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    Ok(())
+}
+
+/// Marks that bottom is done cleaning up, and returns the exit code to use if it was asked to stop
+/// by a signal, which is 128 plus the signal number like shells use.
+pub fn finish() -> Option<i32> {
+    #[cfg(target_family = "windows")]
+    IS_CLEANED_UP.store(true, Ordering::SeqCst);
+
+    match RECEIVED_SIGNAL.load(Ordering::SeqCst) {
+        0 => None,
+        signal if cfg!(target_family = "unix") => Some(128 + signal as i32),
+        _ => Some(1),
+    }
+}
@@ -23,6 +23,19 @@ fn test_small_rate() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_small_gpu_memory_rate() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--gpu_memory_rate")
+        .arg("249")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your GPU memory rate to be at least 250 milliseconds.",
+        ));
+    Ok(())
+}
+
 #[test]
 fn test_large_default_time() -> Result<(), Box<dyn std::error::Error>> {
     Command::new(get_binary_location())
@@ -170,3 +183,78 @@ fn test_missing_default_widget_type() -> Result<(), Box<dyn std::error::Error>>
 
     Ok(())
 }
+
+#[test]
+fn test_conflicting_debug_logs() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--debug")
+        .arg("--debug_log")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_single_widget() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--widget")
+        .arg("cpu_sparkle")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid widget name"));
+
+    Ok(())
+}
+
+#[test]
+fn test_oneshot() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--oneshot")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^CPU \d+% MEM \d+%")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_filter() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--filter")
+        .arg("cpu >")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing value"));
+
+    Ok(())
+}
+
+#[test]
+fn test_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--capabilities")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"format_version\": 1"))
+        .stdout(predicate::str::contains("\"harvesters\""));
+
+    Ok(())
+}
+
+#[test]
+fn test_compare_missing_session() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--compare")
+        .arg("./tests/does_not_exist_before.bin")
+        .arg("./tests/does_not_exist_after.bin")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unable to compare the given sessions.",
+        ));
+
+    Ok(())
+}
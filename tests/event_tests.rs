@@ -0,0 +1,35 @@
+//! Tests for how the main loop receives events.
+
+use std::time::Duration;
+
+use bottom::{receive_event, BottomEvent};
+
+#[test]
+fn test_input_before_other_events() {
+    let (input_sender, input_receiver) = crossbeam_channel::unbounded();
+    let (sender, receiver) = crossbeam_channel::unbounded();
+
+    sender.send(BottomEvent::<u8, u8>::Clean).unwrap();
+    sender.send(BottomEvent::Resize).unwrap();
+    input_sender.send(BottomEvent::KeyInput(1)).unwrap();
+    input_sender.send(BottomEvent::MouseInput(2)).unwrap();
+
+    let timeout = Duration::from_millis(10);
+    assert!(matches!(
+        receive_event(&input_receiver, &receiver, timeout),
+        Some(BottomEvent::KeyInput(1))
+    ));
+    assert!(matches!(
+        receive_event(&input_receiver, &receiver, timeout),
+        Some(BottomEvent::MouseInput(2))
+    ));
+    assert!(matches!(
+        receive_event(&input_receiver, &receiver, timeout),
+        Some(BottomEvent::Clean)
+    ));
+    assert!(matches!(
+        receive_event(&input_receiver, &receiver, timeout),
+        Some(BottomEvent::Resize)
+    ));
+    assert!(receive_event(&input_receiver, &receiver, timeout).is_none());
+}
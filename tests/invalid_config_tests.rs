@@ -156,3 +156,226 @@ fn test_invalid_default_widget_count() -> Result<(), Box<dyn std::error::Error>>
         .stderr(predicate::str::contains("invalid number"));
     Ok(())
 }
+
+#[test]
+fn test_missing_custom_widget() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/missing_custom_widget.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no [[widget]] entry"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_custom_widget_source() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_custom_widget_source.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid data source"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_secondary_source() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_secondary_source.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only supported by graphs"));
+    Ok(())
+}
+
+#[test]
+fn test_missing_plugin() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/missing_plugin.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no [[plugin]] entry"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_exporter_kind() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_exporter_kind.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid exporter kind"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_influxdb_address() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_influxdb_address.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid InfluxDB address"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_record_rotate() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_record_rotate.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid record_rotate value"));
+    Ok(())
+}
+
+#[cfg(not(feature = "otlp"))]
+#[test]
+fn test_otlp_without_feature() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/otlp_without_feature.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("\"otlp\" feature"));
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", feature = "nvidia")))]
+#[test]
+fn test_gpu_memory_without_feature() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/gpu_memory_without_feature.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("\"nvidia\" feature"));
+    Ok(())
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_invalid_control_path() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_control_path.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a FIFO"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("between 0 and 100"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_process_filter() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_process_filter.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing value"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_process_pin() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_process_pin.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Update 'pin' in 'process_filter'"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_column_alias() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_column_alias.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already used in searches"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_process_name_source() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_process_name_source.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid process name source"));
+    Ok(())
+}
+
+#[test]
+fn test_recursive_query_alias() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/recursive_query_alias.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("defined in terms of itself"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_default_query() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_default_query.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid default_query"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_decimal_separator() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_decimal_separator.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("decimal_separator"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_byte_units() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_byte_units.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid byte_units"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_process_column() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_process_column.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid process column"));
+    Ok(())
+}
@@ -0,0 +1,1136 @@
+//! Tests using the query language the way a crate embedding bottom would.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bottom::app::data_harvester::{media::MediaUsage, processes::SuspiciousReason};
+use bottom::data_conversion::{
+    ConvertedDiskData, ConvertedProcessData, ConvertedTempData, ProcessTarget,
+};
+use bottom::query::{
+    get_query_completion, get_query_highlights, parse, Filterable, PrefixType, QueryHighlight,
+    QueryOptions, QuerySet, SearchTarget,
+};
+use regex::Regex;
+
+struct Service {
+    name: &'static str,
+    pid: u32,
+    cpu: f64,
+}
+
+impl Filterable for Service {
+    fn is_string_match(&self, prefix_type: &PrefixType, regex: &Regex) -> bool {
+        match prefix_type {
+            PrefixType::Name => regex.is_match(self.name),
+            PrefixType::Pid => regex.is_match(&self.pid.to_string()),
+            _ => false,
+        }
+    }
+
+    fn get_number(&self, prefix_type: &PrefixType) -> Option<f64> {
+        match prefix_type {
+            PrefixType::PCpu => Some(self.cpu),
+            PrefixType::Pid => Some(f64::from(self.pid)),
+            _ => None,
+        }
+    }
+}
+
+fn get_services() -> Vec<Service> {
+    vec![
+        Service {
+            name: "nginx",
+            pid: 1,
+            cpu: 12.0,
+        },
+        Service {
+            name: "postgres",
+            pid: 10,
+            cpu: 55.0,
+        },
+        Service {
+            name: "Redis",
+            pid: 1500,
+            cpu: 3.0,
+        },
+    ]
+}
+
+fn get_matching_names(query: &str, options: &QueryOptions<'_>) -> Vec<&'static str> {
+    let services = get_services();
+    parse(query, options)
+        .unwrap()
+        .filter(&services)
+        .into_iter()
+        .map(|service| service.name)
+        .collect()
+}
+
+#[test]
+fn test_filter_custom_type() {
+    let options = QueryOptions::default();
+    assert_eq!(
+        get_matching_names("cpu > 10", &options),
+        ["nginx", "postgres"]
+    );
+    assert_eq!(
+        get_matching_names("redis or cpu > 50", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("not nginx and cpu < 50", &options),
+        ["Redis"]
+    );
+}
+
+#[test]
+fn test_pid_matching() {
+    let options = QueryOptions::default();
+    assert_eq!(
+        get_matching_names("pid 1", &options),
+        ["nginx", "postgres", "Redis"]
+    );
+    assert_eq!(get_matching_names("pid = 1", &options), ["nginx"]);
+    assert_eq!(
+        get_matching_names("pid = 1,10", &options),
+        ["nginx", "postgres"]
+    );
+    assert_eq!(
+        get_matching_names("pid in (1, 1500)", &options),
+        ["nginx", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("pid 5-2000", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("pid 1..10", &options),
+        ["nginx", "postgres"]
+    );
+    assert_eq!(
+        get_matching_names("pid > 1", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("pid != 10", &options),
+        ["nginx", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("pid~r = ^1", &options),
+        ["nginx", "postgres", "Redis"]
+    );
+}
+
+#[test]
+fn test_unknown_prefix_never_matches() {
+    assert!(get_matching_names("mem > 0", &QueryOptions::default()).is_empty());
+}
+
+#[test]
+fn test_options() {
+    let case_sensitive = QueryOptions {
+        is_ignoring_case: false,
+        ..QueryOptions::default()
+    };
+    assert!(get_matching_names("redis", &case_sensitive).is_empty());
+
+    let regex = QueryOptions {
+        is_searching_with_regex: true,
+        ..QueryOptions::default()
+    };
+    assert_eq!(
+        get_matching_names("^n|s$", &regex),
+        ["nginx", "postgres", "Redis"]
+    );
+
+    let mut query_aliases = HashMap::new();
+    query_aliases.insert("busy".to_string(), "cpu > 50".to_string());
+    let aliases = QueryOptions {
+        query_aliases: Some(&query_aliases),
+        ..QueryOptions::default()
+    };
+    assert_eq!(get_matching_names("@busy", &aliases), ["postgres"]);
+
+    let mut prefix_aliases = HashMap::new();
+    prefix_aliases.insert("load".to_string(), "cpu".to_string());
+    prefix_aliases.insert("service".to_string(), "name".to_string());
+    let prefix_aliases = QueryOptions {
+        prefix_aliases: Some(&prefix_aliases),
+        ..QueryOptions::default()
+    };
+    assert_eq!(
+        get_matching_names("LOAD > 50", &prefix_aliases),
+        ["postgres"]
+    );
+    assert_eq!(
+        get_matching_names("service~c nginx", &prefix_aliases),
+        ["nginx"]
+    );
+    assert!(get_matching_names("\"load\"", &prefix_aliases).is_empty());
+}
+
+#[test]
+fn test_parse_error_span() {
+    let err = parse("nginx and cpu >", &QueryOptions::default()).unwrap_err();
+    assert_eq!(err.span, Some(15..15));
+}
+
+#[test]
+fn test_not() {
+    let options = QueryOptions::default();
+    assert_eq!(
+        get_matching_names("not nginx", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("!nginx", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(get_matching_names("!(cpu > 10)", &options), ["Redis"]);
+    assert_eq!(get_matching_names("not not nginx", &options), ["nginx"]);
+    assert_eq!(
+        get_matching_names("not (nginx or redis)", &options),
+        ["postgres"]
+    );
+    assert!(parse("not", &options).is_err());
+}
+
+fn get_process(name: &str) -> ConvertedProcessData {
+    ConvertedProcessData {
+        name: name.to_string(),
+        ..ConvertedProcessData::default()
+    }
+}
+
+fn get_matching_processes<'a>(query: &str, processes: &'a [ConvertedProcessData]) -> Vec<&'a str> {
+    let targets = processes
+        .iter()
+        .map(|process| ProcessTarget {
+            process,
+            is_using_command: false,
+            is_searching_window_titles: false,
+        })
+        .collect::<Vec<_>>();
+    parse(query, &QueryOptions::default())
+        .unwrap()
+        .filter(&targets)
+        .into_iter()
+        .map(|target| target.process.name.as_str())
+        .collect()
+}
+
+#[test]
+fn test_state_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            process_state: "Runnable".to_string(),
+            process_char: 'R',
+            ..get_process("firefox")
+        },
+        ConvertedProcessData {
+            process_state: "Zombie".to_string(),
+            process_char: 'Z',
+            ..get_process("defunct")
+        },
+        ConvertedProcessData {
+            process_state: "Sleeping".to_string(),
+            process_char: 'S',
+            ..get_process("sshd")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("state = zombie", &processes),
+        ["defunct"]
+    );
+    assert_eq!(
+        get_matching_processes("state = running", &processes),
+        ["firefox"]
+    );
+    assert_eq!(
+        get_matching_processes("state runnable", &processes),
+        ["firefox"]
+    );
+    assert_eq!(
+        get_matching_processes("not state = sleeping", &processes),
+        ["firefox", "defunct"]
+    );
+}
+
+#[test]
+fn test_user_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            user: Some("root".to_string()),
+            uid: Some(0),
+            cpu_percent_usage: 20.0,
+            ..get_process("systemd")
+        },
+        ConvertedProcessData {
+            user: Some("root".to_string()),
+            uid: Some(0),
+            cpu_percent_usage: 1.0,
+            ..get_process("cron")
+        },
+        ConvertedProcessData {
+            user: Some("alice".to_string()),
+            uid: Some(1000),
+            cpu_percent_usage: 30.0,
+            ..get_process("firefox")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("user = root and cpu > 10", &processes),
+        ["systemd"]
+    );
+    assert_eq!(
+        get_matching_processes("user alice", &processes),
+        ["firefox"]
+    );
+    assert_eq!(get_matching_processes("uid 1000", &processes), ["firefox"]);
+    assert!(get_matching_processes("user bob", &processes).is_empty());
+}
+
+#[test]
+fn test_memory_units() {
+    let processes = [
+        ConvertedProcessData {
+            mem_usage_bytes: 100_000_000,
+            mem_percent_usage: 1.0,
+            ..get_process("small")
+        },
+        ConvertedProcessData {
+            mem_usage_bytes: 2_000_000_000,
+            mem_percent_usage: 25.0,
+            ..get_process("large")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("memb > 500 mb", &processes),
+        ["large"]
+    );
+    assert_eq!(get_matching_processes("mem > 500mb", &processes), ["large"]);
+    assert_eq!(get_matching_processes("mem < 1 gb", &processes), ["small"]);
+    assert_eq!(get_matching_processes("mem > 10", &processes), ["large"]);
+}
+
+#[test]
+fn test_query_set() {
+    let services = get_services();
+    let options = QueryOptions::default();
+    let search = parse("cpu > 1", &options).unwrap();
+    let quick_filter = parse("cpu < 50", &options).unwrap();
+    let exclude = parse("nginx", &options).unwrap();
+
+    let mut query_set = QuerySet::default();
+    assert!(query_set.is_empty());
+    assert!(services.iter().all(|service| query_set.check(service)));
+
+    query_set.require(&search);
+    query_set.require(&quick_filter);
+    query_set.exclude(&exclude);
+    assert!(!query_set.is_empty());
+    assert!(query_set.has_prefix(&PrefixType::PCpu));
+    assert!(!query_set.has_prefix(&PrefixType::Pid));
+    assert_eq!(
+        services
+            .iter()
+            .filter(|service| query_set.check(*service))
+            .map(|service| service.name)
+            .collect::<Vec<_>>(),
+        ["Redis"]
+    );
+}
+
+#[test]
+fn test_run_time_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            run_time: 30,
+            ..get_process("short")
+        },
+        ConvertedProcessData {
+            run_time: 600,
+            ..get_process("medium")
+        },
+        ConvertedProcessData {
+            run_time: 3 * 60 * 60,
+            ..get_process("long")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("time > 5m", &processes),
+        ["medium", "long"]
+    );
+    assert_eq!(
+        get_matching_processes("time < 2h", &processes),
+        ["short", "medium"]
+    );
+    assert_eq!(
+        get_matching_processes("runtime >= 600", &processes),
+        ["medium", "long"]
+    );
+    assert!(get_matching_processes("time > 1 d", &processes).is_empty());
+}
+
+#[test]
+fn test_command_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            command: "nginx -c /etc/nginx/nginx.conf".to_string(),
+            ..get_process("nginx")
+        },
+        ConvertedProcessData {
+            command: "python3 -m http.server".to_string(),
+            ..get_process("python3")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("args = \"-c /etc/nginx\"", &processes),
+        ["nginx"]
+    );
+    assert_eq!(
+        get_matching_processes("cmd http.server", &processes),
+        ["python3"]
+    );
+    assert!(get_matching_processes("server", &processes).is_empty());
+}
+
+#[test]
+fn test_pid_sets() {
+    let options = QueryOptions::default();
+    assert_eq!(
+        get_matching_names("pid in (1, 10) and cpu > 20", &options),
+        ["postgres"]
+    );
+    assert_eq!(
+        get_matching_names("pid = 10,1500", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("not pid in (1, 10)", &options),
+        ["Redis"]
+    );
+    assert!(parse("pid in (1, 10", &options).is_err());
+}
+
+#[test]
+fn test_not_equal() {
+    let options = QueryOptions::default();
+    assert_eq!(
+        get_matching_names("cpu != 12", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("cpu <> 12", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(get_matching_names("not cpu != 12", &options), ["nginx"]);
+    assert!(parse("cpu !=", &options).is_err());
+
+    assert_eq!(
+        get_matching_names("name != nginx", &options),
+        ["postgres", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("name!=nginx", &options),
+        ["postgres", "Redis"]
+    );
+    assert!(parse("nginx != redis", &options).is_err());
+    assert!(parse("nginx!=redis", &options).is_err());
+    assert!(parse("name !=", &options).is_err());
+}
+
+#[test]
+fn test_bang_inside_words() {
+    let processes = [
+        get_process("foo!bar"),
+        get_process("foo"),
+        get_process("bar"),
+    ];
+    assert_eq!(get_matching_processes("foo!bar", &processes), ["foo!bar"]);
+    assert_eq!(get_matching_processes("foo!", &processes), ["foo!bar"]);
+    assert_eq!(get_matching_processes("foo !bar", &processes), ["foo"]);
+    assert_eq!(get_matching_processes("!foo", &processes), ["bar"]);
+}
+
+#[test]
+fn test_ranges() {
+    let options = QueryOptions::default();
+    assert_eq!(get_matching_names("cpu 10..50", &options), ["nginx"]);
+    assert_eq!(
+        get_matching_names("cpu between 3 and 12", &options),
+        ["nginx", "Redis"]
+    );
+    assert_eq!(
+        get_matching_names("cpu 3..12 or cpu 55..55", &options),
+        ["nginx", "postgres", "Redis"]
+    );
+
+    let processes = [
+        ConvertedProcessData {
+            mem_usage_bytes: 50_000_000,
+            ..get_process("small")
+        },
+        ConvertedProcessData {
+            mem_usage_bytes: 500_000_000,
+            ..get_process("medium")
+        },
+        ConvertedProcessData {
+            mem_usage_bytes: 5_000_000_000,
+            ..get_process("large")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("memb between 100 mb and 1 gb", &processes),
+        ["medium"]
+    );
+    assert!(parse("cpu between 3", &options).is_err());
+}
+
+#[test]
+fn test_ppid_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            ppid: Some(1),
+            ..get_process("orphan")
+        },
+        ConvertedProcessData {
+            ppid: Some(4523),
+            ..get_process("worker")
+        },
+        ConvertedProcessData {
+            ppid: None,
+            ..get_process("init")
+        },
+    ];
+    assert_eq!(get_matching_processes("ppid = 1", &processes), ["orphan"]);
+    assert_eq!(
+        get_matching_processes("ppid = 4523", &processes),
+        ["worker"]
+    );
+    assert_eq!(
+        get_matching_processes("ppid < 10000", &processes),
+        ["orphan", "worker"]
+    );
+}
+
+#[test]
+fn test_query_aliases() {
+    let mut query_aliases = HashMap::new();
+    query_aliases.insert("databases".to_string(), "postgres or redis".to_string());
+    query_aliases.insert("busy".to_string(), "cpu > 10".to_string());
+    query_aliases.insert(
+        "busy_databases".to_string(),
+        "@databases and @busy".to_string(),
+    );
+    query_aliases.insert("loop".to_string(), "nginx or @again".to_string());
+    query_aliases.insert("again".to_string(), "@loop".to_string());
+    let options = QueryOptions {
+        query_aliases: Some(&query_aliases),
+        ..QueryOptions::default()
+    };
+
+    assert_eq!(
+        get_matching_names("@databases and cpu > 5", &options),
+        ["postgres"]
+    );
+    assert_eq!(get_matching_names("not @databases", &options), ["nginx"]);
+    assert_eq!(
+        get_matching_names("@busy_databases", &options),
+        ["postgres"]
+    );
+    assert!(parse("@loop", &options).is_err());
+    assert!(parse("@missing", &options).is_err());
+}
+
+#[test]
+fn test_security_context_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            security_context: Some("system_u:system_r:httpd_t:s0".to_string()),
+            ..get_process("httpd")
+        },
+        ConvertedProcessData {
+            security_context: Some("unconfined".to_string()),
+            ..get_process("bash")
+        },
+        get_process("unknown"),
+    ];
+    assert_eq!(
+        get_matching_processes("context httpd_t", &processes),
+        ["httpd"]
+    );
+    assert_eq!(
+        get_matching_processes("label = unconfined", &processes),
+        ["bash"]
+    );
+    assert_eq!(
+        get_matching_processes("not context unconfined", &processes),
+        ["httpd", "unknown"]
+    );
+}
+
+#[test]
+fn test_suspicious_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            suspicious_reason: Some(SuspiciousReason::DeletedExe),
+            ..get_process("upgraded")
+        },
+        ConvertedProcessData {
+            suspicious_reason: Some(SuspiciousReason::TempExe),
+            ..get_process("dropper")
+        },
+        get_process("sshd"),
+    ];
+    assert_eq!(
+        get_matching_processes("suspicious = deleted", &processes),
+        ["upgraded"]
+    );
+    assert_eq!(
+        get_matching_processes("suspicious temp", &processes),
+        ["dropper"]
+    );
+    assert_eq!(
+        get_matching_processes("suspicious~r .", &processes),
+        ["upgraded", "dropper"]
+    );
+}
+
+#[test]
+fn test_namespace_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            namespaces: Some("pid,net".to_string()),
+            ..get_process("container")
+        },
+        ConvertedProcessData {
+            namespaces: Some("mnt".to_string()),
+            ..get_process("sandbox")
+        },
+        ConvertedProcessData {
+            namespaces: Some(String::new()),
+            ..get_process("host")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("ns = pid", &processes),
+        ["container"]
+    );
+    assert_eq!(
+        get_matching_processes("namespace mnt", &processes),
+        ["sandbox"]
+    );
+    assert_eq!(get_matching_processes("not ns~r .", &processes), ["host"]);
+}
+
+#[test]
+fn test_completion() {
+    assert_eq!(
+        get_query_completion("cp", SearchTarget::Processes),
+        Some("u")
+    );
+    assert_eq!(
+        get_query_completion("CP", SearchTarget::Processes),
+        Some("u")
+    );
+    assert_eq!(
+        get_query_completion("nginx and pp", SearchTarget::Processes),
+        Some("id")
+    );
+    assert_eq!(
+        get_query_completion("cpu > 5 a", SearchTarget::Processes),
+        Some("nd")
+    );
+    assert_eq!(
+        get_query_completion("cpu > 5 o", SearchTarget::Processes),
+        Some("r")
+    );
+    assert_eq!(get_query_completion("cpu", SearchTarget::Processes), None);
+    assert_eq!(get_query_completion("cpu ", SearchTarget::Processes), None);
+    assert_eq!(
+        get_query_completion("state = r", SearchTarget::Processes),
+        None
+    );
+    assert_eq!(get_query_completion("\"cp", SearchTarget::Processes), None);
+    assert_eq!(get_query_completion("xyz", SearchTarget::Processes), None);
+
+    assert_eq!(get_query_completion("mou", SearchTarget::Disks), Some("nt"));
+    assert_eq!(get_query_completion("fr", SearchTarget::Disks), Some("ee"));
+    assert_eq!(get_query_completion("tot", SearchTarget::Disks), Some("al"));
+    assert_eq!(
+        get_query_completion("te", SearchTarget::Temperatures),
+        Some("mp")
+    );
+    assert_eq!(get_query_completion("mou", SearchTarget::Processes), None);
+    assert_eq!(get_query_completion("fr", SearchTarget::Processes), None);
+    assert_eq!(get_query_completion("te", SearchTarget::Processes), None);
+    assert_eq!(get_query_completion("cp", SearchTarget::Disks), None);
+    assert_eq!(
+        get_query_completion("tit", SearchTarget::Processes),
+        Some("le")
+    );
+    assert_eq!(
+        get_query_completion("win", SearchTarget::Processes),
+        Some("dow")
+    );
+    assert_eq!(
+        get_query_completion("med", SearchTarget::Processes),
+        Some("ia")
+    );
+}
+
+#[test]
+fn test_highlights() {
+    assert_eq!(
+        get_query_highlights(
+            "cpu > 5 and \"a b\"",
+            &HashMap::new(),
+            SearchTarget::Processes
+        ),
+        [
+            (0..3, QueryHighlight::Prefix),
+            (4..5, QueryHighlight::Operator),
+            (6..7, QueryHighlight::Number),
+            (8..11, QueryHighlight::Operator),
+            (12..13, QueryHighlight::Quoted),
+            (13..16, QueryHighlight::Quoted),
+            (16..17, QueryHighlight::Quoted),
+        ]
+    );
+    assert_eq!(
+        get_query_highlights("nginx", &HashMap::new(), SearchTarget::Processes),
+        []
+    );
+}
+
+#[test]
+fn test_error_positions() {
+    let options = QueryOptions::default();
+    assert_eq!(parse("cpu > abc", &options).unwrap_err().span, Some(6..9));
+    assert_eq!(parse("(nginx", &options).unwrap_err().span, Some(6..6));
+    assert_eq!(parse("nginx )", &options).unwrap_err().span, Some(6..7));
+}
+
+#[test]
+fn test_glob() {
+    let glob = QueryOptions {
+        is_searching_with_glob: true,
+        ..QueryOptions::default()
+    };
+    assert_eq!(get_matching_names("ngin*", &glob), ["nginx"]);
+    assert_eq!(get_matching_names("*s", &glob), ["postgres", "Redis"]);
+    assert_eq!(get_matching_names("p?stgres", &glob), ["postgres"]);
+    assert!(get_matching_names("gin", &glob).is_empty());
+
+    let regex_and_glob = QueryOptions {
+        is_searching_with_regex: true,
+        ..glob
+    };
+    assert_eq!(get_matching_names("^n", &regex_and_glob), ["nginx"]);
+}
+
+#[test]
+fn test_search_modifiers() {
+    let options = QueryOptions::default();
+    assert_eq!(get_matching_names("name~r ^n", &options), ["nginx"]);
+    assert_eq!(
+        get_matching_names("~r \"s$\"", &options),
+        ["postgres", "Redis"]
+    );
+    assert!(get_matching_names("name~c redis", &options).is_empty());
+    assert_eq!(get_matching_names("name~c Redis", &options), ["Redis"]);
+    assert_eq!(
+        get_matching_names("name~rc ^R or name~g ngin*", &options),
+        ["nginx", "Redis"]
+    );
+    assert!(parse("name~q nginx", &options).is_err());
+}
+
+#[test]
+fn test_quoted_strings() {
+    let processes = [
+        get_process("Google Chrome Helper"),
+        get_process("Google Chrome"),
+        get_process("Chrome Helper"),
+    ];
+    assert_eq!(
+        get_matching_processes("\"Google Chrome Helper\"", &processes),
+        ["Google Chrome Helper"]
+    );
+    assert_eq!(
+        get_matching_processes("\"chrome helper\"", &processes),
+        ["Google Chrome Helper", "Chrome Helper"]
+    );
+    assert_eq!(
+        get_matching_processes("Google\\ Chrome and not helper", &processes),
+        ["Google Chrome"]
+    );
+    assert!(get_matching_processes("\"cpu\" or \"and\"", &processes).is_empty());
+    assert!(parse("\"Google Chrome", &QueryOptions::default()).is_err());
+}
+
+#[test]
+fn test_gpu_prefixes() {
+    let processes = [
+        ConvertedProcessData {
+            gpu_usage_percent: 80.0,
+            gpu_mem_usage_bytes: 4_000_000_000,
+            ..get_process("game")
+        },
+        ConvertedProcessData {
+            gpu_usage_percent: 5.0,
+            gpu_mem_usage_bytes: 200_000_000,
+            ..get_process("browser")
+        },
+    ];
+    assert_eq!(get_matching_processes("gpu > 50", &processes), ["game"]);
+    assert_eq!(get_matching_processes("gmem > 1 gb", &processes), ["game"]);
+    assert_eq!(
+        get_matching_processes("gpumem < 500mb", &processes),
+        ["browser"]
+    );
+}
+
+#[test]
+fn test_threads_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            num_threads: 250,
+            ..get_process("leaky")
+        },
+        ConvertedProcessData {
+            num_threads: 1,
+            ..get_process("simple")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("threads > 100", &processes),
+        ["leaky"]
+    );
+    assert_eq!(get_matching_processes("nth = 1", &processes), ["simple"]);
+}
+
+#[test]
+fn test_fds_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            num_fds: 5000,
+            ..get_process("leaky")
+        },
+        ConvertedProcessData {
+            num_fds: 3,
+            ..get_process("simple")
+        },
+    ];
+    assert_eq!(get_matching_processes("fds > 1000", &processes), ["leaky"]);
+    assert_eq!(get_matching_processes("fds <= 3", &processes), ["simple"]);
+}
+
+#[test]
+fn test_nice_and_priority_prefixes() {
+    let processes = [
+        ConvertedProcessData {
+            nice: -5,
+            priority: 15,
+            ..get_process("boosted")
+        },
+        ConvertedProcessData {
+            nice: 0,
+            priority: 20,
+            ..get_process("normal")
+        },
+        ConvertedProcessData {
+            nice: 10,
+            priority: -51,
+            ..get_process("realtime")
+        },
+    ];
+    assert_eq!(get_matching_processes("nice < 0", &processes), ["boosted"]);
+    assert_eq!(
+        get_matching_processes("nice >= -5 and nice < 5", &processes),
+        ["boosted", "normal"]
+    );
+    assert_eq!(
+        get_matching_processes("priority = -51", &processes),
+        ["realtime"]
+    );
+    assert_eq!(
+        get_matching_processes("prio -100..0", &processes),
+        ["realtime"]
+    );
+}
+
+#[test]
+fn test_env_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            environ: Some(Arc::new(vec![
+                "DISPLAY=:1".to_string(),
+                "HOME=/home/alice".to_string(),
+            ])),
+            ..get_process("xterm")
+        },
+        ConvertedProcessData {
+            environ: Some(Arc::new(vec!["HOME=/root".to_string()])),
+            ..get_process("sshd")
+        },
+        get_process("unknown"),
+    ];
+    assert_eq!(
+        get_matching_processes("env = \"DISPLAY=:1\"", &processes),
+        ["xterm"]
+    );
+    assert_eq!(
+        get_matching_processes("env.DISPLAY = \":1\"", &processes),
+        ["xterm"]
+    );
+    assert_eq!(
+        get_matching_processes("env.HOME = /root", &processes),
+        ["sshd"]
+    );
+    assert!(get_matching_processes("env.home = /root", &processes).is_empty());
+    assert_eq!(
+        get_matching_processes("env home", &processes),
+        ["xterm", "sshd"]
+    );
+}
+
+#[test]
+fn test_exe_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            exe: Some("/usr/lib/firefox/firefox".to_string()),
+            ..get_process("firefox")
+        },
+        ConvertedProcessData {
+            exe: Some("/opt/firefox-nightly/firefox".to_string()),
+            ..get_process("firefox")
+        },
+        get_process("kthreadd"),
+    ];
+    assert_eq!(
+        get_matching_processes("exe = /usr/lib/firefox", &processes).len(),
+        1
+    );
+    assert_eq!(
+        get_matching_processes("exe firefox", &processes),
+        ["firefox", "firefox"]
+    );
+    assert!(get_matching_processes("exe kthreadd", &processes).is_empty());
+}
+
+#[test]
+fn test_cgroup_prefix() {
+    let processes = [
+        ConvertedProcessData {
+            cgroup: Some("/system.slice/docker-abc123.scope".to_string()),
+            ..get_process("app")
+        },
+        ConvertedProcessData {
+            cgroup: Some("/user.slice/user-1000.slice".to_string()),
+            ..get_process("bash")
+        },
+    ];
+    assert_eq!(
+        get_matching_processes("cgroup = docker-abc123", &processes),
+        ["app"]
+    );
+    assert_eq!(
+        get_matching_processes("container abc123", &processes),
+        ["app"]
+    );
+    assert_eq!(
+        get_matching_processes("cgroup user.slice", &processes),
+        ["bash"]
+    );
+}
+
+#[test]
+fn test_window_title_prefix() {
+    let options = QueryOptions::default();
+    for query in ["title = inbox", "window inbox", "title = \"Inbox - Mail\""] {
+        assert!(parse(query, &options)
+            .unwrap()
+            .has_prefix(&PrefixType::WindowTitle));
+    }
+    assert!(parse("title", &options).is_err());
+
+    let processes = [
+        ConvertedProcessData {
+            window_titles: vec!["Inbox - Mail".to_string(), "Calendar".to_string()],
+            ..get_process("thunderbird")
+        },
+        ConvertedProcessData {
+            window_titles: vec!["bottom - Terminal".to_string()],
+            ..get_process("kitty")
+        },
+        get_process("sshd"),
+    ];
+    assert_eq!(
+        get_matching_processes("title = calendar", &processes),
+        ["thunderbird"]
+    );
+    assert_eq!(
+        get_matching_processes("window terminal", &processes),
+        ["kitty"]
+    );
+    assert_eq!(
+        get_matching_processes("title = \"Inbox - Mail\"", &processes),
+        ["thunderbird"]
+    );
+    assert!(get_matching_processes("title \"Mail - Inbox\"", &processes).is_empty());
+    assert!(get_matching_processes("title sshd", &processes).is_empty());
+    assert_eq!(
+        get_matching_processes("not title mail", &processes),
+        ["kitty", "sshd"]
+    );
+}
+
+#[test]
+fn test_media_prefix() {
+    let options = QueryOptions::default();
+    for query in ["media = cam", "media mic", "media~r \"^(cam|mic)$\""] {
+        assert!(parse(query, &options)
+            .unwrap()
+            .has_prefix(&PrefixType::Media));
+    }
+    assert!(parse("media", &options).is_err());
+
+    let processes = [
+        ConvertedProcessData {
+            media_usage: MediaUsage {
+                camera: true,
+                microphone: true,
+                audio: false,
+            },
+            ..get_process("zoom")
+        },
+        ConvertedProcessData {
+            media_usage: MediaUsage {
+                audio: true,
+                ..MediaUsage::default()
+            },
+            ..get_process("spotify")
+        },
+        get_process("sshd"),
+    ];
+    assert_eq!(get_matching_processes("media = cam", &processes), ["zoom"]);
+    assert_eq!(get_matching_processes("media mic", &processes), ["zoom"]);
+    assert_eq!(
+        get_matching_processes("media = audio", &processes),
+        ["spotify"]
+    );
+    assert!(get_matching_processes("media sshd", &processes).is_empty());
+    assert_eq!(
+        get_matching_processes("not media = cam", &processes),
+        ["spotify", "sshd"]
+    );
+
+    assert_eq!(
+        get_query_completion("nginx and med", SearchTarget::Processes),
+        Some("ia")
+    );
+    assert_eq!(
+        get_query_completion("media c", SearchTarget::Processes),
+        None
+    );
+    assert_eq!(get_query_completion("media", SearchTarget::Processes), None);
+}
+
+#[test]
+fn test_disk_queries() {
+    let disks = [
+        ConvertedDiskData {
+            name: "/dev/sda1".to_string(),
+            mount_point: "/".to_string(),
+            used_percent: 90.0,
+            free_bytes: 5_000_000_000,
+            total_bytes: 50_000_000_000,
+            ..ConvertedDiskData::default()
+        },
+        ConvertedDiskData {
+            name: "/dev/sdb1".to_string(),
+            mount_point: "/home".to_string(),
+            used_percent: 20.0,
+            free_bytes: 800_000_000_000,
+            total_bytes: 1_000_000_000_000,
+            ..ConvertedDiskData::default()
+        },
+    ];
+    let options = QueryOptions {
+        search_target: SearchTarget::Disks,
+        ..QueryOptions::default()
+    };
+    let get_matching_disks = |query: &str| {
+        parse(query, &options)
+            .unwrap()
+            .filter(&disks)
+            .into_iter()
+            .map(|disk| disk.name.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(get_matching_disks("mount = /home"), ["/dev/sdb1"]);
+    assert_eq!(get_matching_disks("used > 80"), ["/dev/sda1"]);
+    assert_eq!(get_matching_disks("free < 10 gb"), ["/dev/sda1"]);
+    assert_eq!(get_matching_disks("total >= 1tb"), ["/dev/sdb1"]);
+    assert_eq!(get_matching_disks("home"), ["/dev/sdb1"]);
+    assert!(get_matching_disks("cpu").is_empty());
+    assert!(parse("cpu > 0", &options).is_err());
+}
+
+#[test]
+fn test_temp_queries() {
+    let sensors = [
+        ConvertedTempData {
+            name: "k10temp Tctl".to_string(),
+            temperature: 75.0,
+            ..ConvertedTempData::default()
+        },
+        ConvertedTempData {
+            name: "nvme Composite".to_string(),
+            temperature: 40.0,
+            ..ConvertedTempData::default()
+        },
+    ];
+    let options = QueryOptions {
+        search_target: SearchTarget::Temperatures,
+        ..QueryOptions::default()
+    };
+    let get_matching_sensors = |query: &str| {
+        parse(query, &options)
+            .unwrap()
+            .filter(&sensors)
+            .into_iter()
+            .map(|sensor| sensor.name.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(get_matching_sensors("temp > 70"), ["k10temp Tctl"]);
+    assert_eq!(
+        get_matching_sensors("nvme or temp > 100"),
+        ["nvme Composite"]
+    );
+}
+
+#[test]
+fn test_disk_and_temp_prefixes_are_names_in_processes() {
+    let processes = [
+        get_process("free"),
+        get_process("tempd"),
+        get_process("total-commander"),
+        get_process("mount.ntfs"),
+    ];
+    assert_eq!(get_matching_processes("free", &processes), ["free"]);
+    assert_eq!(get_matching_processes("temp", &processes), ["tempd"]);
+    assert_eq!(
+        get_matching_processes("total", &processes),
+        ["total-commander"]
+    );
+    assert_eq!(get_matching_processes("mount", &processes), ["mount.ntfs"]);
+    assert_eq!(
+        get_matching_processes("free or tempd", &processes),
+        ["free", "tempd"]
+    );
+    assert!(parse("free < 10 gb", &QueryOptions::default()).is_err());
+}
@@ -0,0 +1,86 @@
+//! Tests for the binary format of saved sessions.
+
+use bottom::app::{data_farmer::TimedData, session_cache::SessionSnapshot};
+
+fn test_snapshot() -> SessionSnapshot {
+    SessionSnapshot {
+        saved_at: 1_600_000_000_000,
+        io_counters: vec![
+            ("sda".to_string(), 123_456, 7_890),
+            ("nvme0n1".to_string(), 0, u64::MAX),
+        ],
+        timed_data: vec![
+            (
+                2000,
+                TimedData {
+                    rx_data: 1024.0,
+                    tx_data: 512.5,
+                    cpu_data: vec![12.5, 50.0, 100.0],
+                    mem_data: 42.0,
+                    swap_data: 0.0,
+                },
+            ),
+            (
+                1000,
+                TimedData {
+                    rx_data: 0.0,
+                    tx_data: 0.0,
+                    cpu_data: vec![],
+                    mem_data: 43.25,
+                    swap_data: 1.5,
+                },
+            ),
+        ],
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let snapshot = test_snapshot();
+    let restored = SessionSnapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+
+    assert_eq!(restored.saved_at, snapshot.saved_at);
+    assert_eq!(restored.io_counters, snapshot.io_counters);
+    assert_eq!(restored.timed_data.len(), snapshot.timed_data.len());
+    for ((offset, entry), (expected_offset, expected_entry)) in
+        restored.timed_data.iter().zip(&snapshot.timed_data)
+    {
+        assert_eq!(offset, expected_offset);
+        assert_eq!(entry.rx_data, expected_entry.rx_data);
+        assert_eq!(entry.tx_data, expected_entry.tx_data);
+        assert_eq!(entry.cpu_data, expected_entry.cpu_data);
+        assert_eq!(entry.mem_data, expected_entry.mem_data);
+        assert_eq!(entry.swap_data, expected_entry.swap_data);
+    }
+}
+
+#[test]
+fn test_empty_round_trip() {
+    let restored = SessionSnapshot::from_bytes(&SessionSnapshot::default().to_bytes()).unwrap();
+
+    assert_eq!(restored.saved_at, 0);
+    assert!(restored.io_counters.is_empty());
+    assert!(restored.timed_data.is_empty());
+}
+
+#[test]
+fn test_truncated() {
+    let bytes = test_snapshot().to_bytes();
+    for len in 0..bytes.len() {
+        assert!(SessionSnapshot::from_bytes(&bytes[..len]).is_err());
+    }
+}
+
+#[test]
+fn test_wrong_magic() {
+    let mut bytes = test_snapshot().to_bytes();
+    bytes[0] = b'X';
+    assert!(SessionSnapshot::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_wrong_version() {
+    let mut bytes = test_snapshot().to_bytes();
+    bytes[4] = 2;
+    assert!(SessionSnapshot::from_bytes(&bytes).is_err());
+}